@@ -0,0 +1,548 @@
+//! A language server for PHP that speaks JSON-RPC over stdin and stdout.
+//!
+//! It reports syntax errors as diagnostics and provides document symbols,
+//! folding ranges, selection ranges and semantic tokens. Open documents are
+//! kept in a `Document`, so edits only reparse the statements they touch.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+use std::ops::Range;
+use std::panic::{self, AssertUnwindSafe};
+
+use serde_json::{json, Value};
+
+use php_parser_rs::diagnostics::{Diagnostic, Severity};
+use php_parser_rs::lexer::source::LineIndex;
+use php_parser_rs::lexer::token::Span;
+use php_parser_rs::parser::incremental::{Document, TextEdit};
+use php_parser_rs::ranges::{self, FoldingRange, FoldingRangeKind};
+use php_parser_rs::semantic_tokens::{self, TOKEN_MODIFIERS, TOKEN_TYPES};
+use php_parser_rs::symbols::{self, DocumentSymbol, SymbolKind};
+
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const INTERNAL_ERROR: i64 = -32603;
+
+type Response = Result<Value, (i64, String)>;
+
+#[derive(Default)]
+struct Server {
+    documents: HashMap<String, Document>,
+    shutdown: bool,
+}
+
+fn main() {
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    let stdout = io::stdout();
+    let mut output = stdout.lock();
+
+    let mut server = Server::default();
+    while let Some(message) = read_message(&mut input) {
+        let message = match message.and_then(|message| {
+            serde_json::from_slice::<Value>(&message).map_err(|error| error.to_string())
+        }) {
+            Ok(message) => message,
+            Err(error) => {
+                let response = json!({
+                    "jsonrpc": "2.0",
+                    "id": null,
+                    "error": { "code": PARSE_ERROR, "message": error },
+                });
+                write_message(&mut output, &response);
+                continue;
+            }
+        };
+
+        let method = message["method"].as_str().unwrap_or_default();
+        let params = &message["params"];
+
+        // Requests have an id and get a response, notifications don't.
+        let Some(id) = message.get("id") else {
+            if method == "exit" {
+                std::process::exit(if server.shutdown { 0 } else { 1 });
+            }
+
+            for notification in server.notify(method, params) {
+                write_message(&mut output, &notification);
+            }
+
+            continue;
+        };
+
+        // A bug in the parser shouldn't take down the server for the rest of the session.
+        let response = panic::catch_unwind(AssertUnwindSafe(|| server.request(method, params)))
+            .unwrap_or_else(|panic| Err((INTERNAL_ERROR, crashed(method, panic))));
+
+        let response = match response {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": code, "message": message },
+            }),
+        };
+        write_message(&mut output, &response);
+    }
+}
+
+impl Server {
+    fn request(&mut self, method: &str, params: &Value) -> Response {
+        if self.shutdown && method != "shutdown" {
+            return Err((INVALID_REQUEST, "the server is shutting down".into()));
+        }
+
+        match method {
+            "initialize" => Ok(json!({
+                "capabilities": {
+                    "textDocumentSync": { "openClose": true, "change": 2 },
+                    "documentSymbolProvider": true,
+                    "foldingRangeProvider": true,
+                    "selectionRangeProvider": true,
+                    "semanticTokensProvider": {
+                        "legend": {
+                            "tokenTypes": TOKEN_TYPES,
+                            "tokenModifiers": TOKEN_MODIFIERS,
+                        },
+                        "full": true,
+                    },
+                },
+                "serverInfo": { "name": "php-lsp", "version": env!("CARGO_PKG_VERSION") },
+            })),
+            "shutdown" => {
+                self.shutdown = true;
+
+                Ok(Value::Null)
+            }
+            "textDocument/documentSymbol" => {
+                let document = self.document(params)?;
+                let source = document.source();
+                let index = LineIndex::new(source);
+
+                let symbols = match document.program() {
+                    Ok(program) => symbols::symbols(program),
+                    Err(_) => vec![],
+                };
+
+                Ok(symbols
+                    .iter()
+                    .map(|symbol| document_symbol(source, &index, symbol))
+                    .collect())
+            }
+            "textDocument/foldingRange" => {
+                let document = self.document(params)?;
+                let source = document.source();
+
+                let ranges = match document.tokens() {
+                    Ok(tokens) => {
+                        let program = document.program().map(|p| &p[..]).unwrap_or(&[]);
+
+                        ranges::folding_ranges(source, tokens, program)
+                            .iter()
+                            .map(folding_range)
+                            .collect()
+                    }
+                    Err(_) => vec![],
+                };
+
+                Ok(ranges.into())
+            }
+            "textDocument/selectionRange" => {
+                let document = self.document(params)?;
+                let source = document.source();
+                let index = LineIndex::new(source);
+
+                let positions = params["positions"]
+                    .as_array()
+                    .ok_or((INVALID_PARAMS, "missing positions".to_string()))?;
+
+                let tokens = document.tokens().unwrap_or_default();
+                let program = document.program().map(|p| &p[..]).unwrap_or(&[]);
+
+                positions
+                    .iter()
+                    .map(|position| {
+                        let offset = offset(source, &index, position)?;
+                        let ranges = ranges::selection_ranges(source, tokens, program, offset);
+
+                        Ok(selection_range(source, &index, offset, ranges))
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(Value::from)
+            }
+            "textDocument/semanticTokens/full" => {
+                let document = self.document(params)?;
+                let source = document.source();
+
+                let data = match document.tokens() {
+                    Ok(tokens) => {
+                        let program = document.program().map(|p| &p[..]).unwrap_or(&[]);
+                        let tokens = semantic_tokens::semantic_tokens(source, tokens, program);
+
+                        semantic_tokens::encode(source, &tokens)
+                    }
+                    Err(_) => vec![],
+                };
+
+                Ok(json!({ "data": data }))
+            }
+            _ => Err((METHOD_NOT_FOUND, format!("unknown method `{}`", method))),
+        }
+    }
+
+    fn notify(&mut self, method: &str, params: &Value) -> Vec<Value> {
+        let uri = params["textDocument"]["uri"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+
+        match method {
+            "textDocument/didOpen" => {
+                let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+                match panic::catch_unwind(|| Document::new(text)) {
+                    Ok(document) => {
+                        self.documents.insert(uri.clone(), document);
+                    }
+                    Err(panic) => return self.forget(&uri, crashed(method, panic)),
+                }
+            }
+            "textDocument/didChange" => {
+                let Some(document) = self.documents.get_mut(&uri) else {
+                    return vec![];
+                };
+
+                let changes = params["contentChanges"].as_array().cloned();
+                for change in changes.unwrap_or_default() {
+                    let text = change["text"].as_str().unwrap_or_default();
+                    let range = &change["range"];
+                    if range.is_null() {
+                        match panic::catch_unwind(|| Document::new(text)) {
+                            Ok(new) => *document = new,
+                            Err(panic) => return self.forget(&uri, crashed(method, panic)),
+                        }
+
+                        continue;
+                    }
+
+                    let source = document.source();
+                    let index = LineIndex::new(source);
+                    let (Ok(start), Ok(end)) = (
+                        offset(source, &index, &range["start"]),
+                        offset(source, &index, &range["end"]),
+                    ) else {
+                        continue;
+                    };
+
+                    let edit = TextEdit::new(start..end.max(start), text);
+                    if let Err(panic) =
+                        panic::catch_unwind(AssertUnwindSafe(|| document.edit(edit)))
+                    {
+                        return self.forget(&uri, crashed(method, panic));
+                    }
+                }
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(&uri);
+
+                return vec![publish_diagnostics(&uri, vec![])];
+            }
+            _ => return vec![],
+        }
+
+        let document = &self.documents[&uri];
+        let source = document.source();
+        let index = LineIndex::new(source);
+        let diagnostics = match document.program() {
+            Ok(_) => vec![],
+            Err(error) => vec![diagnostic(source, &index, &Diagnostic::from(error))],
+        };
+
+        vec![publish_diagnostics(&uri, diagnostics)]
+    }
+
+    // Close a document that the parser crashed on, since it may be half-edited,
+    // and tell the user about it. Requests for it fail until it's opened again.
+    fn forget(&mut self, uri: &str, message: String) -> Vec<Value> {
+        self.documents.remove(uri);
+
+        vec![
+            publish_diagnostics(uri, vec![]),
+            json!({
+                "jsonrpc": "2.0",
+                "method": "window/showMessage",
+                "params": { "type": 1, "message": message },
+            }),
+        ]
+    }
+
+    fn document(&self, params: &Value) -> Result<&Document, (i64, String)> {
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+
+        self.documents
+            .get(uri)
+            .ok_or((INVALID_PARAMS, format!("unknown document `{}`", uri)))
+    }
+}
+
+// Log a panic on stderr and describe it for the client.
+fn crashed(method: &str, panic: Box<dyn Any + Send>) -> String {
+    let reason = match panic.downcast::<String>() {
+        Ok(message) => *message,
+        Err(panic) => match panic.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown error".to_string(),
+        },
+    };
+
+    let message = format!("php-lsp crashed while handling `{}`: {}", method, reason);
+    eprintln!("{}", message);
+
+    message
+}
+
+fn publish_diagnostics(uri: &str, diagnostics: Vec<Value>) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": { "uri": uri, "diagnostics": diagnostics },
+    })
+}
+
+fn diagnostic(source: &[u8], index: &LineIndex, diagnostic: &Diagnostic) -> Value {
+    let mut message = diagnostic.message.clone();
+    for note in &diagnostic.notes {
+        message.push('\n');
+        message.push_str(note);
+    }
+
+    json!({
+        "range": point_range(source, index, diagnostic.span),
+        "severity": match diagnostic.severity {
+            Severity::Error => 1,
+            Severity::Warning => 2,
+            Severity::Note => 3,
+        },
+        "code": diagnostic.code,
+        "source": "php-parser",
+        "message": message,
+    })
+}
+
+fn document_symbol(source: &[u8], index: &LineIndex, symbol: &DocumentSymbol) -> Value {
+    let kind = match symbol.kind {
+        SymbolKind::Namespace => 3,
+        SymbolKind::Class | SymbolKind::Trait => 5,
+        SymbolKind::Method => 6,
+        SymbolKind::Property => 7,
+        SymbolKind::Enum => 10,
+        SymbolKind::Interface => 11,
+        SymbolKind::Function => 12,
+        SymbolKind::Constant => 14,
+        SymbolKind::EnumCase => 22,
+    };
+
+    // Property names are spelled with a `$` in the source.
+    let mut name_length = symbol.name.len();
+    if symbol.kind == SymbolKind::Property {
+        name_length += 1;
+    }
+
+    let (start, end) = symbol.span;
+    let name_start = index.offset(symbol.name_span);
+
+    json!({
+        "name": symbol.name,
+        "kind": kind,
+        "range": {
+            "start": position(source, index, index.offset(start)),
+            "end": position(source, index, (index.offset(end) + 1).min(source.len())),
+        },
+        "selectionRange": {
+            "start": position(source, index, name_start),
+            "end": position(source, index, (name_start + name_length).min(source.len())),
+        },
+        "children": symbol
+            .children
+            .iter()
+            .map(|child| document_symbol(source, index, child))
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn folding_range(range: &FoldingRange) -> Value {
+    let mut value = json!({ "startLine": range.start_line - 1, "endLine": range.end_line - 1 });
+
+    match range.kind {
+        Some(FoldingRangeKind::Comment) => value["kind"] = "comment".into(),
+        Some(FoldingRangeKind::Imports) => value["kind"] = "imports".into(),
+        None => {}
+    }
+
+    value
+}
+
+fn selection_range(
+    source: &[u8],
+    index: &LineIndex,
+    offset: usize,
+    mut ranges: Vec<Range<usize>>,
+) -> Value {
+    // Every position needs a range, so fall back to the position itself.
+    if ranges.is_empty() {
+        ranges.push(offset..offset);
+    }
+
+    // Link each range to the one around it, starting from the outermost.
+    let mut selection = Value::Null;
+    for Range { start, end } in ranges.into_iter().rev() {
+        let mut range = json!({
+            "range": {
+                "start": position(source, index, start),
+                "end": position(source, index, end),
+            },
+        });
+        if !selection.is_null() {
+            range["parent"] = selection;
+        }
+
+        selection = range;
+    }
+
+    selection
+}
+
+// LSP positions count characters in UTF-16 code units.
+fn position(source: &[u8], index: &LineIndex, offset: usize) -> Value {
+    let (line, _) = index.span(offset);
+    let line_start = index.line_start(line);
+
+    let character = characters(&source[line_start..offset])
+        .map(|(_, units)| units)
+        .sum::<usize>();
+
+    json!({ "line": line - 1, "character": character })
+}
+
+fn point_range(source: &[u8], index: &LineIndex, span: Span) -> Value {
+    let start = span_offset(source, index, span);
+    let end = match source.get(start) {
+        Some(b'\n') | None => start,
+        Some(_) => start + 1,
+    };
+
+    json!({
+        "start": position(source, index, start),
+        "end": position(source, index, end),
+    })
+}
+
+// Errors at the end of the input don't have a span, and point past the end of the source.
+fn span_offset(source: &[u8], index: &LineIndex, span: Span) -> usize {
+    if span.0 == 0 {
+        return source.len();
+    }
+
+    index.offset(span).min(source.len())
+}
+
+fn offset(source: &[u8], index: &LineIndex, position: &Value) -> Result<usize, (i64, String)> {
+    let (Some(line), Some(character)) = (position["line"].as_u64(), position["character"].as_u64())
+    else {
+        return Err((INVALID_PARAMS, format!("invalid position {}", position)));
+    };
+
+    let lines = index.span(source.len()).0;
+    if line as usize >= lines {
+        return Ok(source.len());
+    }
+
+    let line_start = index.line_start(line as usize + 1);
+    let line_end = source[line_start..]
+        .iter()
+        .position(|byte| *byte == b'\n')
+        .map_or(source.len(), |end| line_start + end);
+
+    let mut offset = line_start;
+    let mut units = 0;
+    for (length, width) in characters(&source[line_start..line_end]) {
+        if units >= character as usize {
+            break;
+        }
+
+        offset += length;
+        units += width;
+    }
+
+    Ok(offset)
+}
+
+// The length in bytes and in UTF-16 code units of each character. Bytes that
+// aren't valid UTF-8 count as one character each.
+fn characters(bytes: &[u8]) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let mut rest = bytes;
+
+    std::iter::from_fn(move || {
+        let first = *rest.first()?;
+        let length = match first {
+            0xF0..=0xF7 => 4,
+            0xE0..=0xEF => 3,
+            0xC0..=0xDF => 2,
+            _ => 1,
+        };
+
+        let (length, units) = match std::str::from_utf8(rest.get(..length)?) {
+            Ok(_) if length == 4 => (4, 2),
+            Ok(_) => (length, 1),
+            Err(_) => (1, 1),
+        };
+        rest = &rest[length..];
+
+        Some((length, units))
+    })
+}
+
+// Read the next message, or `None` once the input is closed. A message without
+// a valid `Content-Length` header is skipped, and returned as an error instead.
+fn read_message(input: &mut impl BufRead) -> Option<Result<Vec<u8>, String>> {
+    let mut length = None;
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header).ok()? == 0 {
+            return None;
+        }
+
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+
+        if let Some(value) = header.strip_prefix("Content-Length:") {
+            length = value.trim().parse::<usize>().ok();
+        }
+    }
+
+    let Some(length) = length else {
+        return Some(Err("missing or invalid Content-Length header".to_string()));
+    };
+
+    let mut message = vec![0; length];
+    input.read_exact(&mut message).ok()?;
+
+    Some(Ok(message))
+}
+
+fn write_message(output: &mut impl Write, message: &Value) {
+    let message = message.to_string();
+
+    // Nothing can be done if the client has gone away.
+    let _ = write!(
+        output,
+        "Content-Length: {}\r\n\r\n{}",
+        message.len(),
+        message
+    );
+    let _ = output.flush();
+}
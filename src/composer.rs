@@ -0,0 +1,486 @@
+//! Checking a project's classes against the autoload rules of its `composer.json`.
+//!
+//! ```ignore
+//! let composer = Composer::load("path/to/project")?;
+//! let result = composer.project().parse()?;
+//! let index = DeclarationIndex::from_project(&result);
+//!
+//! for violation in composer.verify(&index) {
+//!     eprintln!("{}", violation);
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::io;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde_json::Value;
+
+use crate::index::Declaration;
+use crate::index::DeclarationIndex;
+use crate::index::NameTable;
+use crate::lexer::token::Span;
+use crate::project::Glob;
+use crate::project::Project;
+
+/// One of the `autoload` or `autoload-dev` sections of a `composer.json` file.
+///
+/// Paths are relative to the directory of the `composer.json` file, as written.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Autoload {
+    /// Namespace prefixes, e.g. `App\`, and the directories they map to.
+    pub psr4: Vec<(String, Vec<String>)>,
+    /// Namespace or class name prefixes, and the directories that contain them.
+    pub psr0: Vec<(String, Vec<String>)>,
+    /// Directories and files that are scanned for classes, whatever their names.
+    pub classmap: Vec<String>,
+    /// Files that are always included, rather than autoloaded.
+    pub files: Vec<String>,
+    /// Paths left out of the generated classmap, where `*` and `**` are wildcards.
+    pub exclude_from_classmap: Vec<String>,
+}
+
+#[derive(Debug)]
+pub enum ComposerError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    /// The file is valid JSON, but an autoload section isn't what composer expects.
+    Invalid(String),
+}
+
+impl Display for ComposerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "failed to read composer.json: {}", error),
+            Self::Json(error) => write!(f, "failed to parse composer.json: {}", error),
+            Self::Invalid(message) => write!(f, "invalid composer.json: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for ComposerError {}
+
+/// A problem with where a class is declared, given the autoload rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    /// A class in a PSR-4 or PSR-0 directory that the autoloader won't find there.
+    ///
+    /// `expected` is where the autoloader looks for the class, if anywhere.
+    Misplaced {
+        class: String,
+        path: PathBuf,
+        span: Span,
+        expected: Option<PathBuf>,
+    },
+    /// A class that is declared in more than one autoloaded file, so which one
+    /// gets loaded depends on the autoloader.
+    Ambiguous { class: String, paths: Vec<PathBuf> },
+    /// A `files` or `classmap` entry that doesn't exist.
+    MissingPath { path: PathBuf },
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Misplaced {
+                class,
+                path,
+                span,
+                expected: Some(expected),
+            } => write!(
+                f,
+                "class {} in {} on line {} should be in {}",
+                class,
+                path.display(),
+                span.0,
+                expected.display()
+            ),
+            Self::Misplaced {
+                class,
+                path,
+                span,
+                expected: None,
+            } => write!(
+                f,
+                "class {} in {} on line {} isn't in a namespace that is autoloaded from there",
+                class,
+                path.display(),
+                span.0
+            ),
+            Self::Ambiguous { class, paths } => write!(
+                f,
+                "class {} is declared in more than one file: {}",
+                class,
+                paths
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Self::MissingPath { path } => {
+                write!(f, "autoloaded path {} doesn't exist", path.display())
+            }
+        }
+    }
+}
+
+/// The autoload rules of a project, read from its `composer.json`.
+#[derive(Debug, Clone)]
+pub struct Composer {
+    root: PathBuf,
+    pub autoload: Autoload,
+    pub autoload_dev: Autoload,
+    pub vendor_dir: String,
+}
+
+impl Composer {
+    /// Read the `composer.json` file in `root`.
+    pub fn load(root: impl Into<PathBuf>) -> Result<Self, ComposerError> {
+        let root = root.into();
+        let json = std::fs::read(root.join("composer.json")).map_err(ComposerError::Io)?;
+
+        Self::from_json(root, &json)
+    }
+
+    /// Read the contents of a `composer.json` file, whose directory is `root`.
+    pub fn from_json(root: impl Into<PathBuf>, json: &[u8]) -> Result<Self, ComposerError> {
+        let value: Value = serde_json::from_slice(json).map_err(ComposerError::Json)?;
+
+        let vendor_dir = match &value["config"]["vendor-dir"] {
+            Value::String(vendor_dir) => vendor_dir.clone(),
+            _ => "vendor".to_string(),
+        };
+
+        Ok(Self {
+            root: root.into(),
+            autoload: autoload(&value["autoload"], "autoload")?,
+            autoload_dev: autoload(&value["autoload-dev"], "autoload-dev")?,
+            vendor_dir,
+        })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn sections(&self) -> [&Autoload; 2] {
+        [&self.autoload, &self.autoload_dev]
+    }
+
+    /// The files that the autoload rules cover, leaving out the vendor directory.
+    pub fn project(&self) -> Project {
+        let mut project = Project::new(&self.root).with_exclude(&self.vendor_dir);
+
+        for section in self.sections() {
+            let directories = section
+                .psr4
+                .iter()
+                .chain(&section.psr0)
+                .flat_map(|(_, directories)| directories);
+
+            for directory in directories.chain(&section.classmap).chain(&section.files) {
+                let directory = normalise(directory);
+
+                if self.root.join(&directory).is_file() {
+                    project = project.with_include(&directory);
+                } else {
+                    project = project.with_include(&format!("{}/**/*.php", directory));
+                }
+            }
+        }
+
+        project
+    }
+
+    /// Where the autoloader looks for a class, in the order it looks.
+    pub fn expected_paths(&self, class: &str) -> Vec<PathBuf> {
+        let class = class.trim_start_matches('\\');
+        let mut paths = vec![];
+
+        for section in self.sections() {
+            let mut psr4 = section
+                .psr4
+                .iter()
+                .filter(|(prefix, _)| class.starts_with(prefix.as_str()))
+                .collect::<Vec<_>>();
+            // Longer prefixes are tried first.
+            psr4.sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+
+            for (prefix, directories) in psr4 {
+                let relative = class[prefix.len()..].replace('\\', "/");
+                for directory in directories {
+                    paths.push(self.path(directory).join(format!("{}.php", relative)));
+                }
+            }
+        }
+
+        for section in self.sections() {
+            for (prefix, directories) in &section.psr0 {
+                if !class.starts_with(prefix.as_str()) {
+                    continue;
+                }
+
+                for directory in directories {
+                    paths.push(self.path(directory).join(psr0_path(class)));
+                }
+            }
+        }
+
+        paths
+    }
+
+    /// Check every class-like declared in an autoloaded file against the rules.
+    ///
+    /// The index should cover the files of `project`. Classes in files that are
+    /// in the classmap, or in `files`, can live anywhere.
+    pub fn verify(&self, index: &DeclarationIndex) -> Vec<Violation> {
+        let mut violations = vec![];
+
+        for section in self.sections() {
+            for path in section.classmap.iter().chain(&section.files) {
+                let path = self.path(path);
+                if !path.exists() {
+                    violations.push(Violation::MissingPath { path });
+                }
+            }
+        }
+
+        let mut classes: BTreeMap<String, (String, Vec<PathBuf>)> = BTreeMap::new();
+        for (path, declarations) in index.files() {
+            let classmapped = self.is_classmapped(path) || self.is_included(path);
+            let rules = self.rules(path);
+
+            for declaration in classes_in(declarations) {
+                let loaded = classmapped || self.is_expected(&declaration.name, path);
+
+                if !loaded && !rules.is_empty() {
+                    let expected = self
+                        .expected_paths(&declaration.name)
+                        .into_iter()
+                        .find(|expected| rules.iter().any(|rule| expected.starts_with(rule)));
+
+                    violations.push(Violation::Misplaced {
+                        class: declaration.name.clone(),
+                        path: path.to_path_buf(),
+                        span: declaration.name_span,
+                        expected,
+                    });
+                }
+
+                if loaded && !self.is_excluded(path) {
+                    let (_, paths) = classes
+                        .entry(declaration.name.to_ascii_lowercase())
+                        .or_insert_with(|| (declaration.name.clone(), vec![]));
+
+                    paths.push(path.to_path_buf());
+                }
+            }
+        }
+
+        for (_, (class, paths)) in classes {
+            if paths.len() > 1 {
+                violations.push(Violation::Ambiguous { class, paths });
+            }
+        }
+
+        violations
+    }
+
+    /// Build the classmap the autoloader would use once it's optimised: every
+    /// class in the classmap, and every class in a PSR-4 or PSR-0 directory that
+    /// is where the autoloader expects it.
+    ///
+    /// When a class is declared more than once, the first file by path wins.
+    pub fn classmap(&self, index: &DeclarationIndex) -> Classmap {
+        let mut classes = BTreeMap::new();
+
+        for (path, declarations) in index.files() {
+            let classmapped = self.is_classmapped(path);
+
+            for declaration in classes_in(declarations) {
+                let loaded = classmapped || self.is_expected(&declaration.name, path);
+
+                if loaded && !self.is_excluded(path) {
+                    classes
+                        .entry(declaration.name.clone())
+                        .or_insert_with(|| path.to_path_buf());
+                }
+            }
+        }
+
+        Classmap { classes }
+    }
+
+    fn is_expected(&self, class: &str, path: &Path) -> bool {
+        self.expected_paths(class)
+            .iter()
+            .any(|expected| same_path(expected, path))
+    }
+
+    fn path(&self, path: &str) -> PathBuf {
+        match normalise(path).as_str() {
+            "" => self.root.clone(),
+            path => self.root.join(path),
+        }
+    }
+
+    // The PSR-4 and PSR-0 directories that a file is in.
+    fn rules(&self, path: &Path) -> Vec<PathBuf> {
+        self.sections()
+            .into_iter()
+            .flat_map(|section| section.psr4.iter().chain(&section.psr0))
+            .flat_map(|(_, directories)| directories)
+            .map(|directory| self.path(directory))
+            .filter(|directory| path.starts_with(directory))
+            .collect()
+    }
+
+    fn is_classmapped(&self, path: &Path) -> bool {
+        self.sections()
+            .into_iter()
+            .flat_map(|section| &section.classmap)
+            .any(|entry| path.starts_with(self.path(entry)))
+    }
+
+    // Files in `files` are always loaded, but their classes aren't in the classmap.
+    fn is_included(&self, path: &Path) -> bool {
+        self.sections()
+            .into_iter()
+            .flat_map(|section| &section.files)
+            .any(|entry| same_path(&self.path(entry), path))
+    }
+
+    fn is_excluded(&self, path: &Path) -> bool {
+        let Ok(relative) = path.strip_prefix(&self.root) else {
+            return false;
+        };
+        let relative = relative.to_string_lossy().replace('\\', "/");
+
+        // Patterns match the file, or any directory it's in.
+        let paths = relative
+            .match_indices('/')
+            .map(|(separator, _)| &relative[..separator])
+            .chain([relative.as_str()])
+            .collect::<Vec<_>>();
+
+        self.sections()
+            .into_iter()
+            .flat_map(|section| &section.exclude_from_classmap)
+            .map(|pattern| Glob::new(&normalise(pattern)))
+            .any(|glob| paths.iter().any(|path| glob.matches(path)))
+    }
+}
+
+/// The file each class is loaded from, as generated by `Composer::classmap`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Classmap {
+    pub classes: BTreeMap<String, PathBuf>,
+}
+
+impl Classmap {
+    /// Render the classmap as a PHP file that returns it, like composer's
+    /// `autoload_classmap.php`, with paths relative to `base`.
+    pub fn to_php(&self, base: &Path) -> String {
+        let mut php = String::from("<?php\n\nreturn array(\n");
+
+        for (class, path) in &self.classes {
+            let path = path.strip_prefix(base).unwrap_or(path);
+            let path = path.to_string_lossy().replace('\\', "/");
+
+            php.push_str(&format!(
+                "    '{}' => __DIR__ . '/{}',\n",
+                escape(class),
+                escape(&path)
+            ));
+        }
+
+        php.push_str(");\n");
+        php
+    }
+}
+
+fn escape(string: &str) -> String {
+    string.replace('\\', "\\\\").replace('\'', "\\'")
+}
+
+fn classes_in(declarations: &[Declaration]) -> impl Iterator<Item = &Declaration> {
+    declarations
+        .iter()
+        .filter(|declaration| declaration.kind.table() == NameTable::Class)
+}
+
+// PSR-0 maps namespace separators, and underscores in the class name itself, to directories.
+fn psr0_path(class: &str) -> String {
+    let (namespace, name) = match class.rfind('\\') {
+        Some(separator) => (&class[..=separator], &class[separator + 1..]),
+        None => ("", class),
+    };
+
+    format!(
+        "{}{}.php",
+        namespace.replace('\\', "/"),
+        name.replace('_', "/")
+    )
+}
+
+// Strip `./` and trailing slashes, so that paths can be joined and matched.
+fn normalise(path: &str) -> String {
+    Path::new(path)
+        .components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn same_path(a: &Path, b: &Path) -> bool {
+    a.components().eq(b.components())
+}
+
+fn autoload(value: &Value, section: &str) -> Result<Autoload, ComposerError> {
+    let mut autoload = Autoload::default();
+    if value.is_null() {
+        return Ok(autoload);
+    }
+
+    let invalid = |key: &str| ComposerError::Invalid(format!("`{}.{}`", section, key));
+
+    for (key, rules) in [("psr-4", &mut autoload.psr4), ("psr-0", &mut autoload.psr0)] {
+        let Some(map) = value.get(key) else {
+            continue;
+        };
+
+        let map = map.as_object().ok_or_else(|| invalid(key))?;
+        for (prefix, directories) in map {
+            let directories = strings(directories).ok_or_else(|| invalid(key))?;
+            rules.push((prefix.clone(), directories));
+        }
+    }
+
+    for (key, paths) in [
+        ("classmap", &mut autoload.classmap),
+        ("files", &mut autoload.files),
+        ("exclude-from-classmap", &mut autoload.exclude_from_classmap),
+    ] {
+        if let Some(value) = value.get(key) {
+            *paths = strings(value).ok_or_else(|| invalid(key))?;
+        }
+    }
+
+    Ok(autoload)
+}
+
+// A string, or a list of strings.
+fn strings(value: &Value) -> Option<Vec<String>> {
+    match value {
+        Value::String(string) => Some(vec![string.clone()]),
+        Value::Array(values) => values
+            .iter()
+            .map(|value| value.as_str().map(String::from))
+            .collect(),
+        _ => None,
+    }
+}
@@ -0,0 +1,400 @@
+//! The methods a class ends up with once the traits it uses are composed
+//! into it, following the `insteadof` and `as` rules of its `use` clauses.
+
+use std::fmt::Display;
+
+use serde::Serialize;
+
+use crate::hierarchy::Adaptation;
+use crate::hierarchy::ClassLike;
+use crate::hierarchy::Hierarchy;
+use crate::hierarchy::Reference;
+use crate::index::DeclarationKind;
+use crate::lexer::token::Span;
+use crate::parser::ast::modifiers::Visibility;
+
+/// A method of a class, either declared by the class itself or taken from a trait.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ComposedMethod {
+    /// The name of the method in the class, which differs from `method` for aliases.
+    pub name: String,
+    /// The class-like that declares the method, i.e. the class itself or a trait.
+    pub origin: String,
+    /// The name the method is declared with.
+    pub method: String,
+    /// The trait, out of those the class uses directly, that the method comes
+    /// through. Methods the class declares itself don't have one.
+    pub r#trait: Option<String>,
+    pub visibility: Visibility,
+    pub is_static: bool,
+    pub is_abstract: bool,
+}
+
+/// A problem with the traits of a class that PHP refuses to compile.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum Conflict {
+    /// Two traits provide a method with the same name, and no `insteadof`
+    /// rule decides between them.
+    Collision {
+        method: String,
+        first: String,
+        second: String,
+        /// Where the second trait is used.
+        span: Span,
+    },
+    /// An adaptation names a trait that the class doesn't use.
+    UnusedTrait { r#trait: String, span: Span },
+    /// An adaptation names a method that the trait, or none of the traits
+    /// when it doesn't name one, provides.
+    MissingMethod {
+        r#trait: Option<String>,
+        method: String,
+        span: Span,
+    },
+    /// An alias that doesn't name a trait, for a method that more than one
+    /// trait provides.
+    AmbiguousAlias {
+        method: String,
+        traits: Vec<String>,
+        span: Span,
+    },
+}
+
+impl Conflict {
+    pub fn span(&self) -> Span {
+        match self {
+            Self::Collision { span, .. }
+            | Self::UnusedTrait { span, .. }
+            | Self::MissingMethod { span, .. }
+            | Self::AmbiguousAlias { span, .. } => *span,
+        }
+    }
+}
+
+impl Display for Conflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Collision {
+                method,
+                first,
+                second,
+                ..
+            } => write!(
+                f,
+                "method `{}` of trait `{}` collides with the one of trait `{}`",
+                method, second, first
+            ),
+            Self::UnusedTrait { r#trait, .. } => {
+                write!(f, "trait `{}` is not used by this class", r#trait)
+            }
+            Self::MissingMethod {
+                r#trait: Some(r#trait),
+                method,
+                ..
+            } => write!(f, "trait `{}` has no method `{}`", r#trait, method),
+            Self::MissingMethod {
+                r#trait: None,
+                method,
+                ..
+            } => write!(f, "none of the used traits has a method `{}`", method),
+            Self::AmbiguousAlias { method, traits, .. } => write!(
+                f,
+                "alias of method `{}` is ambiguous, since it exists in {}",
+                method,
+                traits
+                    .iter()
+                    .map(|r#trait| format!("`{}`", r#trait))
+                    .collect::<Vec<_>>()
+                    .join(" and ")
+            ),
+        }
+    }
+}
+
+/// The methods of a class-like, along with any conflicts between its traits.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Composition {
+    /// The class-like's own methods in the order they're declared, followed by
+    /// those of its traits in the order the traits are used.
+    pub methods: Vec<ComposedMethod>,
+    pub conflicts: Vec<Conflict>,
+}
+
+impl Composition {
+    /// Find a method by its name, which like in PHP is case insensitive.
+    pub fn method(&self, name: &str) -> Option<&ComposedMethod> {
+        self.methods
+            .iter()
+            .find(|method| method.name.eq_ignore_ascii_case(name))
+    }
+}
+
+/// Compose the traits used by a class-like, and by those traits in turn, into
+/// the class-like's own methods.
+///
+/// Methods of the class-like itself take precedence over those of its traits,
+/// and an abstract trait method is satisfied by a concrete one of another
+/// trait. Traits that the hierarchy doesn't know about contribute no methods.
+/// Only the conflicts of the class-like itself are reported, not those within
+/// the traits it uses.
+pub fn compose(hierarchy: &Hierarchy, class_like: &ClassLike) -> Composition {
+    compose_class_like(hierarchy, class_like, &mut vec![])
+}
+
+fn compose_class_like<'h>(
+    hierarchy: &'h Hierarchy,
+    class_like: &'h ClassLike,
+    composing: &mut Vec<&'h str>,
+) -> Composition {
+    composing.push(&class_like.name);
+
+    let mut composition = Composition {
+        methods: class_like
+            .methods
+            .iter()
+            .map(|method| ComposedMethod {
+                name: method.name.clone(),
+                origin: class_like.name.clone(),
+                method: method.name.clone(),
+                r#trait: None,
+                visibility: method.visibility.clone(),
+                is_static: method.is_static,
+                is_abstract: method.is_abstract,
+            })
+            .collect(),
+        conflicts: vec![],
+    };
+    let own = composition.methods.len();
+
+    // The methods of every trait used, or `None` for those that aren't known,
+    // including traits that end up using themselves.
+    let traits = class_like
+        .traits
+        .iter()
+        .map(|reference| {
+            let methods = hierarchy
+                .get(&reference.name)
+                .map(|(_, r#trait)| r#trait)
+                .filter(|r#trait| {
+                    r#trait.kind == DeclarationKind::Trait
+                        && !composing.iter().any(|name| same_class(name, &r#trait.name))
+                })
+                .map(|r#trait| compose_class_like(hierarchy, r#trait, composing).methods);
+
+            (reference, methods)
+        })
+        .collect::<Vec<_>>();
+
+    let mut excluded = vec![];
+    for adaptation in &class_like.adaptations {
+        match adaptation {
+            Adaptation::Precedence {
+                r#trait,
+                method,
+                span,
+                insteadof,
+            } => {
+                if let Some(r#trait) = r#trait {
+                    check(&traits, r#trait, method, *span, &mut composition.conflicts);
+                }
+
+                for other in insteadof {
+                    match used(&traits, &other.name) {
+                        Some(_) => excluded.push((other.name.as_str(), method.as_str())),
+                        None => composition.conflicts.push(Conflict::UnusedTrait {
+                            r#trait: other.name.clone(),
+                            span: other.span,
+                        }),
+                    }
+                }
+            }
+            Adaptation::Alias {
+                r#trait: Some(r#trait),
+                method,
+                span,
+                ..
+            } => check(&traits, r#trait, method, *span, &mut composition.conflicts),
+            Adaptation::Alias {
+                r#trait: None,
+                method,
+                span,
+                alias,
+                ..
+            } => {
+                let providers = traits
+                    .iter()
+                    .filter(|(_, methods)| {
+                        methods
+                            .as_ref()
+                            .is_some_and(|methods| has_method(methods, method))
+                    })
+                    .map(|(reference, _)| reference.name.clone())
+                    .collect::<Vec<_>>();
+
+                if providers.len() > 1 && alias.is_some() {
+                    composition.conflicts.push(Conflict::AmbiguousAlias {
+                        method: method.clone(),
+                        traits: providers,
+                        span: *span,
+                    });
+                } else if providers.is_empty()
+                    && traits.iter().all(|(_, methods)| methods.is_some())
+                {
+                    composition.conflicts.push(Conflict::MissingMethod {
+                        r#trait: None,
+                        method: method.clone(),
+                        span: *span,
+                    });
+                }
+            }
+        }
+    }
+
+    for (reference, methods) in &traits {
+        let Some(methods) = methods else {
+            continue;
+        };
+
+        for method in methods {
+            // Methods excluded by `insteadof` can still be added under an alias.
+            let is_excluded = excluded.iter().any(|(r#trait, name)| {
+                same_class(r#trait, &reference.name) && name.eq_ignore_ascii_case(&method.name)
+            });
+
+            // The `as` rules that apply to this method of this trait.
+            let rules = class_like
+                .adaptations
+                .iter()
+                .filter_map(|adaptation| match adaptation {
+                    Adaptation::Alias {
+                        r#trait,
+                        method: name,
+                        alias,
+                        visibility,
+                        ..
+                    } if name.eq_ignore_ascii_case(&method.name)
+                        && r#trait
+                            .as_ref()
+                            .is_none_or(|r#trait| same_class(&r#trait.name, &reference.name)) =>
+                    {
+                        Some((alias, visibility))
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+
+            let visibility = rules
+                .iter()
+                .rev()
+                .find(|(alias, visibility)| alias.is_none() && visibility.is_some())
+                .and_then(|(_, visibility)| (*visibility).clone())
+                .unwrap_or_else(|| method.visibility.clone());
+
+            if !is_excluded {
+                let composed = ComposedMethod {
+                    r#trait: Some(reference.name.clone()),
+                    visibility,
+                    ..method.clone()
+                };
+                add(&mut composition, own, composed, reference.span);
+            }
+
+            for (alias, visibility) in rules {
+                if let Some(alias) = alias {
+                    let aliased = ComposedMethod {
+                        name: alias.clone(),
+                        r#trait: Some(reference.name.clone()),
+                        visibility: visibility.clone().unwrap_or(method.visibility.clone()),
+                        ..method.clone()
+                    };
+                    add(&mut composition, own, aliased, reference.span);
+                }
+            }
+        }
+    }
+
+    composing.pop();
+
+    composition
+}
+
+type Traits<'h> = [(&'h Reference, Option<Vec<ComposedMethod>>)];
+
+fn used<'t, 'h>(
+    traits: &'t Traits<'h>,
+    name: &str,
+) -> Option<&'t (&'h Reference, Option<Vec<ComposedMethod>>)> {
+    traits
+        .iter()
+        .find(|(reference, _)| same_class(&reference.name, name))
+}
+
+// Check that an adaptation names a trait that's used, and a method it has.
+fn check(
+    traits: &Traits,
+    r#trait: &Reference,
+    method: &str,
+    span: Span,
+    conflicts: &mut Vec<Conflict>,
+) {
+    match used(traits, &r#trait.name) {
+        None => conflicts.push(Conflict::UnusedTrait {
+            r#trait: r#trait.name.clone(),
+            span: r#trait.span,
+        }),
+        Some((_, Some(methods))) if !has_method(methods, method) => {
+            conflicts.push(Conflict::MissingMethod {
+                r#trait: Some(r#trait.name.clone()),
+                method: method.to_string(),
+                span,
+            })
+        }
+        _ => {}
+    }
+}
+
+// Add a method taken from a trait, unless it clashes with one that's already there.
+fn add(composition: &mut Composition, own: usize, method: ComposedMethod, span: Span) {
+    let Some(index) = composition
+        .methods
+        .iter()
+        .position(|other| other.name.eq_ignore_ascii_case(&method.name))
+    else {
+        composition.methods.push(method);
+
+        return;
+    };
+
+    let existing = &composition.methods[index];
+    if index < own
+        || method.is_abstract
+        || (same_class(&existing.origin, &method.origin)
+            && existing.method.eq_ignore_ascii_case(&method.method))
+    {
+        return;
+    }
+
+    if existing.is_abstract {
+        composition.methods[index] = method;
+
+        return;
+    }
+
+    composition.conflicts.push(Conflict::Collision {
+        method: method.name,
+        first: existing.r#trait.clone().unwrap_or_default(),
+        second: method.r#trait.unwrap_or_default(),
+        span,
+    });
+}
+
+fn has_method(methods: &[ComposedMethod], name: &str) -> bool {
+    methods
+        .iter()
+        .any(|method| method.name.eq_ignore_ascii_case(name))
+}
+
+fn same_class(a: &str, b: &str) -> bool {
+    a.trim_start_matches('\\')
+        .eq_ignore_ascii_case(b.trim_start_matches('\\'))
+}
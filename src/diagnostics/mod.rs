@@ -0,0 +1,186 @@
+pub mod render;
+
+use serde::Serialize;
+
+use crate::lexer::error::SyntaxError;
+use crate::lexer::token::Span;
+use crate::parser::error::ParseError;
+
+pub use self::render::render;
+pub use self::render::RenderFormat;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Error => write!(f, "error"),
+            Self::Warning => write!(f, "warning"),
+            Self::Note => write!(f, "note"),
+        }
+    }
+}
+
+/// A secondary location attached to a diagnostic, rendered with its own message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Label {
+    pub span: Span,
+    pub message: String,
+}
+
+/// A problem found in a piece of source code, along with everything
+/// needed to present it to a user.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    pub code: &'static str,
+    pub severity: Severity,
+    pub message: String,
+    pub span: Span,
+    pub labels: Vec<Label>,
+    pub notes: Vec<String>,
+}
+
+impl Diagnostic {
+    pub fn error<M: Into<String>>(code: &'static str, message: M, span: Span) -> Self {
+        Self {
+            code,
+            severity: Severity::Error,
+            message: message.into(),
+            span,
+            labels: vec![],
+            notes: vec![],
+        }
+    }
+
+    pub fn warning<M: Into<String>>(code: &'static str, message: M, span: Span) -> Self {
+        Self {
+            severity: Severity::Warning,
+            ..Self::error(code, message, span)
+        }
+    }
+
+    pub fn with_label<M: Into<String>>(mut self, span: Span, message: M) -> Self {
+        self.labels.push(Label {
+            span,
+            message: message.into(),
+        });
+
+        self
+    }
+
+    pub fn with_note<M: Into<String>>(mut self, note: M) -> Self {
+        self.notes.push(note.into());
+
+        self
+    }
+}
+
+impl From<&SyntaxError> for Diagnostic {
+    fn from(error: &SyntaxError) -> Self {
+        let diagnostic = Diagnostic::error(error.code(), message(error), error.span());
+
+        match error {
+            SyntaxError::InvalidDocIndentation { .. } => diagnostic
+                .with_note("the body of a heredoc or nowdoc must be indented with either tabs or spaces, not both"),
+            SyntaxError::InvalidDocBodyIndentationLevel { .. } => diagnostic.with_note(
+                "every line of the body must be indented at least as much as the closing label",
+            ),
+            _ => diagnostic,
+        }
+    }
+}
+
+impl From<SyntaxError> for Diagnostic {
+    fn from(error: SyntaxError) -> Self {
+        Self::from(&error)
+    }
+}
+
+impl From<&ParseError> for Diagnostic {
+    fn from(error: &ParseError) -> Self {
+        if let ParseError::SyntaxError(error) = error {
+            return error.into();
+        }
+
+        let mut diagnostic = Diagnostic::error(error.code(), message(error), error.span());
+        for related in error.related_spans() {
+            diagnostic = diagnostic.with_label(related.span, related.message);
+        }
+
+        match help(error) {
+            Some(help) => diagnostic.with_note(help),
+            None => diagnostic,
+        }
+    }
+}
+
+impl From<ParseError> for Diagnostic {
+    fn from(error: ParseError) -> Self {
+        Self::from(&error)
+    }
+}
+
+// Error messages are written for single-line output, e.g. "Parse Error: ... on line 1 column 5".
+// Diagnostics show the location separately, so we only keep the description itself.
+fn message<E: ToString>(error: &E) -> String {
+    let message = error.to_string();
+
+    let message = ["Syntax Error: ", "Parse Error: ", "Parse error: "]
+        .iter()
+        .find_map(|prefix| message.strip_prefix(prefix))
+        .unwrap_or(&message);
+
+    let message = match message.rfind(" on line ") {
+        Some(index) => &message[..index],
+        None => message,
+    };
+
+    message.trim_end_matches('.').to_string()
+}
+
+fn help(error: &ParseError) -> Option<&'static str> {
+    Some(match error {
+        ParseError::MultipleModifiers { .. } => "remove the duplicated modifier",
+        ParseError::MultipleVisibilityModifiers { .. } => {
+            "a member can only have one of `public`, `protected` or `private`"
+        }
+        ParseError::TryWithoutCatchOrFinally { .. } => "add a `catch` or `finally` block",
+        ParseError::MissingTypeForReadonlyProperty { .. } => {
+            "add a type to the property, e.g. `mixed`"
+        }
+        ParseError::ReadonlyPropertyHasDefaultValue { .. } => {
+            "readonly properties can only be initialized once, from inside of the class"
+        }
+        ParseError::StaticPropertyUsingReadonlyModifier { .. } => {
+            "remove either the `static` or the `readonly` modifier"
+        }
+        ParseError::MissingCaseValueForBackedEnum { .. } => {
+            "cases of a backed enum must be assigned a value, e.g. `case Foo = 'foo';`"
+        }
+        ParseError::CaseValueForUnitEnum { .. } => {
+            "give the enum a backing type, e.g. `enum Foo: string`, or remove the value"
+        }
+        ParseError::MatchExpressionWithMultipleDefaultArms { .. } => {
+            "remove one of the `default` arms"
+        }
+        ParseError::MixingBracedAndUnBracedNamespaceDeclarations { .. } => {
+            "use either braced or unbraced namespace declarations throughout the file"
+        }
+        ParseError::PromotedPropertyOutsideConstructor { .. } => {
+            "properties can only be promoted in `__construct`"
+        }
+        ParseError::CannotMixKeyedAndUnkeyedEntries { .. } => {
+            "either give every entry a key, or none of them"
+        }
+        ParseError::CannotUsePositionalArgumentAfterNamedArgument { .. } => {
+            "move positional arguments before any named arguments"
+        }
+        _ => return None,
+    })
+}
@@ -0,0 +1,279 @@
+use std::fmt::Write;
+
+use crate::diagnostics::Diagnostic;
+use crate::diagnostics::Severity;
+use crate::lexer::token::Span;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RenderFormat {
+    #[default]
+    Plain,
+    Ansi,
+    Json,
+}
+
+/// Render a diagnostic against the source code it was produced from.
+///
+/// The plain and ANSI formats produce an annotated snippet of the source, e.g:
+///
+/// ```text
+/// error[E0045]: Match expressions may only contain one default arm
+///  --> index.php:3:5
+///   |
+/// 2 |     default => 1,
+///   |     ------- first default arm
+/// 3 |     default => 2,
+///   |     ^^^^^^^
+///   |
+///   = help: remove one of the `default` arms
+/// ```
+///
+/// The JSON format produces a single-line object containing the diagnostic.
+pub fn render(
+    diagnostic: &Diagnostic,
+    source: &[u8],
+    path: Option<&str>,
+    format: RenderFormat,
+) -> String {
+    match format {
+        RenderFormat::Plain => Snippet::new(source, Style { ansi: false }).render(diagnostic, path),
+        RenderFormat::Ansi => Snippet::new(source, Style { ansi: true }).render(diagnostic, path),
+        RenderFormat::Json => json(diagnostic, path),
+    }
+}
+
+fn json(diagnostic: &Diagnostic, path: Option<&str>) -> String {
+    let mut value = serde_json::to_value(diagnostic).unwrap();
+    if let (Some(path), Some(object)) = (path, value.as_object_mut()) {
+        object.insert("path".to_string(), path.into());
+    }
+
+    value.to_string()
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Style {
+    ansi: bool,
+}
+
+impl Style {
+    const BOLD: &'static str = "1";
+    const RED: &'static str = "1;31";
+    const YELLOW: &'static str = "1;33";
+    const BLUE: &'static str = "1;34";
+    const CYAN: &'static str = "1;36";
+
+    fn paint(&self, text: &str, colour: &str) -> String {
+        if self.ansi && !text.is_empty() {
+            format!("\x1b[{}m{}\x1b[0m", colour, text)
+        } else {
+            text.to_string()
+        }
+    }
+
+    fn severity(&self, severity: Severity) -> &'static str {
+        match severity {
+            Severity::Error => Self::RED,
+            Severity::Warning => Self::YELLOW,
+            Severity::Note => Self::CYAN,
+        }
+    }
+}
+
+struct Annotation<'a> {
+    line: usize,
+    column: usize,
+    primary: bool,
+    message: Option<&'a str>,
+}
+
+struct Snippet<'a> {
+    lines: Vec<&'a [u8]>,
+    style: Style,
+}
+
+impl<'a> Snippet<'a> {
+    fn new(source: &'a [u8], style: Style) -> Self {
+        let lines = source
+            .split(|byte| *byte == b'\n')
+            .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+            .collect();
+
+        Self { lines, style }
+    }
+
+    fn render(&self, diagnostic: &Diagnostic, path: Option<&str>) -> String {
+        let style = self.style;
+        let colour = style.severity(diagnostic.severity);
+
+        let mut annotations = vec![self.annotation(diagnostic.span, true, None)];
+        for label in &diagnostic.labels {
+            annotations.push(self.annotation(label.span, false, Some(&label.message)));
+        }
+        annotations.sort_by_key(|annotation| (annotation.line, annotation.column));
+
+        let (line, column) = (annotations.iter())
+            .find(|annotation| annotation.primary)
+            .map(|annotation| (annotation.line, annotation.column))
+            .unwrap();
+
+        let last = annotations.iter().map(|a| a.line).max().unwrap_or(line);
+        let width = last.to_string().len();
+        let gutter = style.paint(&format!("{} |", " ".repeat(width)), Style::BLUE);
+
+        let mut output = String::new();
+        let _ = writeln!(
+            output,
+            "{}{}",
+            style.paint(
+                &format!("{}[{}]", diagnostic.severity, diagnostic.code),
+                colour
+            ),
+            style.paint(&format!(": {}", diagnostic.message), Style::BOLD),
+        );
+        let _ = writeln!(
+            output,
+            "{}{}:{}:{}",
+            style.paint(&format!("{}--> ", " ".repeat(width)), Style::BLUE),
+            path.unwrap_or("<input>"),
+            line,
+            column
+        );
+        let _ = writeln!(output, "{}", gutter);
+
+        let mut previous: Option<usize> = None;
+        for annotation in &annotations {
+            if previous != Some(annotation.line) {
+                match previous {
+                    Some(previous) if annotation.line == previous + 2 => {
+                        self.write_line(&mut output, previous + 1, width);
+                    }
+                    Some(previous) if annotation.line > previous + 2 => {
+                        let _ = writeln!(output, "{}", style.paint("...", Style::BLUE));
+                    }
+                    _ => {}
+                }
+
+                self.write_line(&mut output, annotation.line, width);
+                previous = Some(annotation.line);
+            }
+
+            let text = self.text(annotation.line);
+            let marker = if annotation.primary { "^" } else { "-" };
+            let marker = marker.repeat(caret_width(text, annotation.column));
+            let marker = match annotation.message {
+                Some(message) => format!("{} {}", marker, message),
+                None => marker,
+            };
+
+            let _ = writeln!(
+                output,
+                "{} {}{}",
+                gutter,
+                indentation(text, annotation.column),
+                style.paint(
+                    &marker,
+                    if annotation.primary {
+                        colour
+                    } else {
+                        Style::BLUE
+                    }
+                )
+            );
+        }
+
+        if !diagnostic.notes.is_empty() {
+            let _ = writeln!(output, "{}", gutter);
+        }
+
+        for note in &diagnostic.notes {
+            let _ = writeln!(
+                output,
+                "{} {} {}",
+                style.paint(&format!("{} =", " ".repeat(width)), Style::BLUE),
+                style.paint("help:", Style::BOLD),
+                note
+            );
+        }
+
+        output
+    }
+
+    // The parser uses an empty span for the end of file, so point at the
+    // very end of the source instead.
+    fn annotation(&self, span: Span, primary: bool, message: Option<&'a str>) -> Annotation<'a> {
+        let (line, column) = if span.0 == 0 || span.0 > self.lines.len() {
+            let line = self.lines.len();
+
+            (line, self.text(line).len() + 1)
+        } else {
+            span
+        };
+
+        Annotation {
+            line,
+            column: column.max(1),
+            primary,
+            message,
+        }
+    }
+
+    fn text(&self, line: usize) -> &'a [u8] {
+        self.lines.get(line.wrapping_sub(1)).copied().unwrap_or(b"")
+    }
+
+    fn write_line(&self, output: &mut String, line: usize, width: usize) {
+        let text = String::from_utf8_lossy(self.text(line));
+        let number = self
+            .style
+            .paint(&format!("{:>width$} |", line, width = width), Style::BLUE);
+
+        if text.trim().is_empty() {
+            let _ = writeln!(output, "{}", number);
+        } else {
+            let _ = writeln!(output, "{} {}", number, text.trim_end());
+        }
+    }
+}
+
+// Spans only record where a token starts, so we guess how long it is by looking
+// at the source: identifiers, variables and numbers are underlined in full.
+fn caret_width(text: &[u8], column: usize) -> usize {
+    let rest = text.get(column - 1..).unwrap_or(b"");
+
+    let start = match rest {
+        [b'$', next, ..] if is_identifier_part(*next) => 1,
+        [first, ..] if is_identifier_part(*first) || *first == b'\\' => 0,
+        _ => return 1,
+    };
+
+    let length = rest[start..]
+        .iter()
+        .take_while(|byte| is_identifier_part(**byte) || **byte == b'\\')
+        .count();
+
+    let length = start + length;
+    let characters = String::from_utf8_lossy(&rest[..length]).chars().count();
+
+    characters.max(1)
+}
+
+fn is_identifier_part(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || byte >= 0x80
+}
+
+// Keep tabs from the original line so that markers line up with the code above them.
+fn indentation(text: &[u8], column: usize) -> String {
+    let prefix = text.get(..column - 1).unwrap_or(text);
+
+    let mut indentation: String = String::from_utf8_lossy(prefix)
+        .chars()
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+
+    if column - 1 > prefix.len() {
+        indentation.push_str(&" ".repeat(column - 1 - prefix.len()));
+    }
+
+    indentation
+}
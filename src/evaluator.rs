@@ -0,0 +1,1540 @@
+//! Evaluating the constant expressions of a program, i.e. the values of
+//! constants and enum cases, the defaults of properties and parameters, and
+//! the arguments of attributes, the way PHP does when it compiles them.
+
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use crate::diagnostics::Diagnostic;
+use crate::index::define;
+use crate::index::key;
+use crate::index::NameTable;
+use crate::lexer::byte_string::ByteString;
+use crate::lexer::token::Span;
+use crate::names;
+use crate::names::Scope;
+use crate::parser::ast::identifiers::Identifier;
+use crate::parser::ast::node::Node;
+use crate::parser::ast::operators::ArithmeticOperation;
+use crate::parser::ast::operators::BitwiseOperation;
+use crate::parser::ast::operators::ComparisonOperation;
+use crate::parser::ast::operators::LogicalOperation;
+use crate::parser::ast::ArrayItem;
+use crate::parser::ast::Expression;
+use crate::parser::ast::MagicConst;
+use crate::parser::ast::Program;
+use crate::parser::ast::Statement;
+use crate::parser::ast::StringPart;
+use crate::parser::stack::ensure_sufficient_stack;
+
+/// A PHP value that a constant expression can evaluate to.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(ByteString<'static>),
+    Array(Array),
+}
+
+impl Value {
+    /// The name of the value's type, as PHP puts it in error messages.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::Null => "null",
+            Self::Bool(_) => "bool",
+            Self::Int(_) => "int",
+            Self::Float(_) => "float",
+            Self::String(_) => "string",
+            Self::Array(_) => "array",
+        }
+    }
+
+    /// Whether the value is truthy, e.g. in the condition of a ternary.
+    pub fn to_bool(&self) -> bool {
+        match self {
+            Self::Null => false,
+            Self::Bool(value) => *value,
+            Self::Int(value) => *value != 0,
+            Self::Float(value) => *value != 0.0,
+            Self::String(value) => !(value.is_empty() || **value == *b"0"),
+            Self::Array(array) => !array.is_empty(),
+        }
+    }
+
+    fn string(value: impl Into<Vec<u8>>) -> Self {
+        Self::String(ByteString::new(value.into()))
+    }
+
+    // The value converted to a string. PHP warns when it converts an array.
+    fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Self::Null | Self::Bool(false) => vec![],
+            Self::Bool(true) => b"1".to_vec(),
+            Self::Int(value) => value.to_string().into_bytes(),
+            Self::Float(value) => float_to_string(*value).into_bytes(),
+            Self::String(value) => value.to_vec(),
+            Self::Array(_) => b"Array".to_vec(),
+        }
+    }
+
+    // The value converted to a number for arithmetic, which fails for arrays
+    // and strings that don't start with a number.
+    fn to_number(&self) -> Option<Number> {
+        match self {
+            Self::Null => Some(Number::Int(0)),
+            Self::Bool(value) => Some(Number::Int(*value as i64)),
+            Self::Int(value) => Some(Number::Int(*value)),
+            Self::Float(value) => Some(Number::Float(*value)),
+            Self::String(value) => numeric(value).map(|(number, _)| number),
+            Self::Array(_) => None,
+        }
+    }
+
+    fn to_int(&self) -> Option<i64> {
+        self.to_number().map(Number::to_int)
+    }
+}
+
+/// The key of an array element. Like in PHP, strings that are canonical
+/// integers and other scalars are cast to integer keys.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub enum ArrayKey {
+    Int(i64),
+    String(ByteString<'static>),
+}
+
+impl TryFrom<&Value> for ArrayKey {
+    type Error = String;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Null => Ok(Self::String(ByteString::new(vec![]))),
+            Value::Bool(value) => Ok(Self::Int(*value as i64)),
+            Value::Int(value) => Ok(Self::Int(*value)),
+            Value::Float(value) => Ok(Self::Int(float_to_int(*value))),
+            Value::String(value) => Ok(match canonical_int(value) {
+                Some(value) => Self::Int(value),
+                None => Self::String(value.clone()),
+            }),
+            Value::Array(_) => Err("illegal offset type".to_string()),
+        }
+    }
+}
+
+impl Display for ArrayKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Int(key) => write!(f, "{}", key),
+            Self::String(key) => write!(f, "\"{}\"", key),
+        }
+    }
+}
+
+/// An ordered PHP array.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Array {
+    entries: Vec<(ArrayKey, Value)>,
+    // Where the element with each key is in `entries`.
+    #[serde(skip)]
+    positions: HashMap<ArrayKey, usize>,
+    // The key of the next element that's appended, which follows the largest
+    // integer key so far. Once an element has the key `i64::MAX`, it's out of
+    // range and nothing more can be appended.
+    next_index: Option<i128>,
+}
+
+impl Array {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, key: &ArrayKey) -> Option<&Value> {
+        self.positions
+            .get(key)
+            .map(|position| &self.entries[*position].1)
+    }
+
+    /// The elements of the array, in order.
+    pub fn iter(&self) -> impl Iterator<Item = (&ArrayKey, &Value)> {
+        self.entries.iter().map(|(key, value)| (key, value))
+    }
+
+    /// Set the element with the given key, keeping its position when it
+    /// already exists.
+    pub fn insert(&mut self, key: ArrayKey, value: Value) {
+        if let ArrayKey::Int(index) = key {
+            let next = i128::from(index) + 1;
+            self.next_index = Some(self.next_index.map_or(next, |current| current.max(next)));
+        }
+
+        match self.positions.get(&key) {
+            Some(position) => self.entries[*position].1 = value,
+            None => {
+                self.positions.insert(key.clone(), self.entries.len());
+                self.entries.push((key, value));
+            }
+        }
+    }
+
+    /// Append an element, as `$array[] = $value` does. This fails once an
+    /// element has the key `i64::MAX`, like it does in PHP.
+    pub fn push(&mut self, value: Value) -> Result<(), String> {
+        let Ok(index) = i64::try_from(self.next_index.unwrap_or(0)) else {
+            return Err(
+                "cannot add element to the array as the next element is already occupied"
+                    .to_string(),
+            );
+        };
+
+        self.insert(ArrayKey::Int(index), value);
+
+        Ok(())
+    }
+}
+
+/// Why an expression couldn't be evaluated.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum EvaluationError {
+    /// The expression contains something that isn't allowed in a constant
+    /// expression, such as a variable or a function call.
+    NotConstant { range: Option<(Span, Span)> },
+    /// The expression is constant, but its value depends on something that
+    /// isn't known, such as a constant defined elsewhere or an object.
+    Unknown {
+        name: String,
+        range: Option<(Span, Span)>,
+    },
+    /// Evaluating the expression fails, e.g. because of a division by zero.
+    Invalid {
+        message: String,
+        range: Option<(Span, Span)>,
+    },
+}
+
+impl EvaluationError {
+    /// Where the part of the expression that caused the error starts and ends.
+    pub fn range(&self) -> Option<(Span, Span)> {
+        match self {
+            Self::NotConstant { range }
+            | Self::Unknown { range, .. }
+            | Self::Invalid { range, .. } => *range,
+        }
+    }
+
+    fn not_constant(expression: &Expression) -> Self {
+        Self::NotConstant {
+            range: Node::Expression(expression).range(),
+        }
+    }
+
+    fn unknown(name: impl Into<String>, expression: &Expression) -> Self {
+        Self::Unknown {
+            name: name.into(),
+            range: Node::Expression(expression).range(),
+        }
+    }
+
+    fn invalid(message: impl Into<String>, expression: &Expression) -> Self {
+        Self::Invalid {
+            message: message.into(),
+            range: Node::Expression(expression).range(),
+        }
+    }
+}
+
+impl Display for EvaluationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotConstant { .. } => write!(f, "not a constant expression"),
+            Self::Unknown { name, .. } => write!(f, "the value of `{}` is not known", name),
+            Self::Invalid { message, .. } => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for EvaluationError {}
+
+/// Something PHP warns about while evaluating an expression, which still has a
+/// value, such as converting an array to a string.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EvaluationWarning {
+    pub message: String,
+    pub range: Option<(Span, Span)>,
+}
+
+/// Evaluates constant expressions, given the constants that are already known
+/// and the context the expressions appear in.
+#[derive(Debug, Clone, Default)]
+pub struct Evaluator {
+    // Warnings raised while evaluating, see `take_warnings`.
+    warnings: RefCell<Vec<EvaluationWarning>>,
+    scope: Scope,
+    file: Option<PathBuf>,
+    class: Option<String>,
+    parent: Option<String>,
+    r#trait: Option<String>,
+    function: Option<String>,
+    constants: HashMap<(NameTable, String), Value>,
+    class_constants: HashMap<(String, String), Value>,
+}
+
+impl Evaluator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The namespace and imports that names are resolved with.
+    pub fn with_scope(mut self, scope: Scope) -> Self {
+        self.scope = scope;
+
+        self
+    }
+
+    /// The file that `__FILE__` and `__DIR__` refer to.
+    pub fn with_file(mut self, file: impl Into<PathBuf>) -> Self {
+        self.file = Some(file.into());
+
+        self
+    }
+
+    /// The fully qualified name of the enclosing class, for `self` and `__CLASS__`.
+    pub fn with_class(mut self, class: impl Into<String>) -> Self {
+        self.class = Some(class.into());
+
+        self
+    }
+
+    /// The fully qualified name of the enclosing class's parent, for `parent`.
+    pub fn with_parent(mut self, parent: impl Into<String>) -> Self {
+        self.parent = Some(parent.into());
+
+        self
+    }
+
+    /// The fully qualified name of the enclosing trait, for `__TRAIT__`.
+    pub fn with_trait(mut self, r#trait: impl Into<String>) -> Self {
+        self.r#trait = Some(r#trait.into());
+
+        self
+    }
+
+    /// The name of the enclosing function or method, for `__FUNCTION__` and `__METHOD__`.
+    pub fn with_function(mut self, function: impl Into<String>) -> Self {
+        self.function = Some(function.into());
+
+        self
+    }
+
+    /// Make the value of a constant known, by its fully qualified name.
+    pub fn define_constant(&mut self, name: &str, value: Value) {
+        self.constants.insert(
+            key(NameTable::Constant, name.trim_start_matches('\\')),
+            value,
+        );
+    }
+
+    /// Make the value of a class constant, or of an enum case, known.
+    pub fn define_class_constant(&mut self, class: &str, name: &str, value: Value) {
+        self.class_constants.insert(
+            (
+                class.trim_start_matches('\\').to_ascii_lowercase(),
+                name.to_string(),
+            ),
+            value,
+        );
+    }
+
+    /// Take the warnings raised by the expressions evaluated since the last call.
+    pub fn take_warnings(&self) -> Vec<EvaluationWarning> {
+        self.warnings.take()
+    }
+
+    /// Work out the value of a constant expression, with PHP's type juggling.
+    ///
+    /// Operands that are skipped, like the right-hand side of `false && ...`,
+    /// must still be constant, but aren't otherwise evaluated.
+    pub fn evaluate(&self, expression: &Expression) -> Result<Value, EvaluationError> {
+        // Long chains of operators nest very deeply, see `parser::ast::drop`.
+        ensure_sufficient_stack(|| match expression {
+            Expression::LiteralInteger { i } => integer_literal(&i.to_string())
+                .ok_or_else(|| EvaluationError::invalid("invalid integer literal", expression)),
+            Expression::LiteralFloat { f } => f
+                .to_string()
+                .replace('_', "")
+                .parse()
+                .map(Value::Float)
+                .map_err(|_| EvaluationError::invalid("invalid float literal", expression)),
+            Expression::LiteralString { value } | Expression::Nowdoc { value } => {
+                Ok(Value::string(value.to_vec()))
+            }
+            Expression::InterpolatedString { parts } | Expression::Heredoc { parts } => {
+                let mut string = vec![];
+                for part in parts {
+                    match part {
+                        StringPart::Const(value) => string.extend_from_slice(value),
+                        StringPart::Expr(part) => return Err(EvaluationError::not_constant(part)),
+                    }
+                }
+
+                Ok(Value::string(string))
+            }
+            Expression::Bool { value } => Ok(Value::Bool(*value)),
+            Expression::Null => Ok(Value::Null),
+            Expression::Parenthesized { expr, .. } => self.evaluate(expr),
+            Expression::Array { items } => self.array(items).map_err(|error| {
+                self.prefer(
+                    error,
+                    items
+                        .iter()
+                        .flat_map(|item| item.key.iter().chain([&item.value])),
+                )
+            }),
+            Expression::ArrayIndex { .. } => self.index(expression, false),
+            Expression::ArithmeticOperation(operation) => self.arithmetic(expression, operation),
+            Expression::BitwiseOperation(BitwiseOperation::Not { right, .. })
+            | Expression::BitwiseNot { value: right, .. } => match self.evaluate(right)? {
+                Value::Int(value) => Ok(Value::Int(!value)),
+                Value::Float(value) => Ok(Value::Int(!float_to_int(value))),
+                Value::String(value) => Ok(Value::string(
+                    value.iter().map(|byte| !byte).collect::<Vec<_>>(),
+                )),
+                value => Err(EvaluationError::invalid(
+                    format!("cannot perform bitwise not on {}", value.type_name()),
+                    expression,
+                )),
+            },
+            Expression::BitwiseOperation(operation) => {
+                let (operator, left, right) = match operation {
+                    BitwiseOperation::And { left, right, .. } => (Operator::And, left, right),
+                    BitwiseOperation::Or { left, right, .. } => (Operator::Or, left, right),
+                    BitwiseOperation::Xor { left, right, .. } => (Operator::Xor, left, right),
+                    BitwiseOperation::LeftShift { left, right, .. } => {
+                        (Operator::LeftShift, left, right)
+                    }
+                    BitwiseOperation::RightShift { left, right, .. } => {
+                        (Operator::RightShift, left, right)
+                    }
+                    BitwiseOperation::Not { .. } => unreachable!(),
+                };
+
+                let (left, right) = self.operands(left, right)?;
+
+                bitwise(operator, &left, &right)
+                    .map_err(|message| EvaluationError::invalid(message, expression))
+            }
+            Expression::Concat { left, right, .. } => {
+                let (left, right) = self.operands(left, right)?;
+
+                if matches!(left, Value::Array(_)) || matches!(right, Value::Array(_)) {
+                    self.warn("array to string conversion", expression);
+                }
+
+                let mut string = left.to_bytes();
+                string.extend(right.to_bytes());
+
+                Ok(Value::string(string))
+            }
+            Expression::ComparisonOperation(operation) => {
+                let (left, right) = match operation {
+                    ComparisonOperation::Equal { left, right, .. }
+                    | ComparisonOperation::Identical { left, right, .. }
+                    | ComparisonOperation::NotEqual { left, right, .. }
+                    | ComparisonOperation::AngledNotEqual { left, right, .. }
+                    | ComparisonOperation::NotIdentical { left, right, .. }
+                    | ComparisonOperation::LessThan { left, right, .. }
+                    | ComparisonOperation::GreaterThan { left, right, .. }
+                    | ComparisonOperation::LessThanOrEqual { left, right, .. }
+                    | ComparisonOperation::GreaterThanOrEqual { left, right, .. }
+                    | ComparisonOperation::Spaceship { left, right, .. } => (left, right),
+                };
+
+                let (left, right) = self.operands(left, right)?;
+
+                // Like PHP, `a > b` is evaluated as `b < a`, which matters for
+                // values that can't be ordered, such as NAN.
+                Ok(match operation {
+                    ComparisonOperation::Equal { .. } => {
+                        Value::Bool(compare(&left, &right) == Ordering::Equal)
+                    }
+                    ComparisonOperation::NotEqual { .. }
+                    | ComparisonOperation::AngledNotEqual { .. } => {
+                        Value::Bool(compare(&left, &right) != Ordering::Equal)
+                    }
+                    ComparisonOperation::Identical { .. } => Value::Bool(identical(&left, &right)),
+                    ComparisonOperation::NotIdentical { .. } => {
+                        Value::Bool(!identical(&left, &right))
+                    }
+                    ComparisonOperation::LessThan { .. } => {
+                        Value::Bool(compare(&left, &right) == Ordering::Less)
+                    }
+                    ComparisonOperation::LessThanOrEqual { .. } => {
+                        Value::Bool(compare(&left, &right) != Ordering::Greater)
+                    }
+                    ComparisonOperation::GreaterThan { .. } => {
+                        Value::Bool(compare(&right, &left) == Ordering::Less)
+                    }
+                    ComparisonOperation::GreaterThanOrEqual { .. } => {
+                        Value::Bool(compare(&right, &left) != Ordering::Greater)
+                    }
+                    ComparisonOperation::Spaceship { .. } => {
+                        Value::Int(compare(&left, &right) as i64)
+                    }
+                })
+            }
+            Expression::LogicalOperation(operation) => match operation {
+                LogicalOperation::Not { right, .. } => {
+                    Ok(Value::Bool(!self.evaluate(right)?.to_bool()))
+                }
+                LogicalOperation::And { left, right, .. }
+                | LogicalOperation::LogicalAnd { left, right, .. } => {
+                    if self
+                        .evaluate(left)
+                        .map_err(|error| self.prefer(error, [right.as_ref()]))?
+                        .to_bool()
+                    {
+                        Ok(Value::Bool(self.evaluate(right)?.to_bool()))
+                    } else {
+                        self.skip(right)?;
+
+                        Ok(Value::Bool(false))
+                    }
+                }
+                LogicalOperation::Or { left, right, .. }
+                | LogicalOperation::LogicalOr { left, right, .. } => {
+                    if self
+                        .evaluate(left)
+                        .map_err(|error| self.prefer(error, [right.as_ref()]))?
+                        .to_bool()
+                    {
+                        self.skip(right)?;
+
+                        Ok(Value::Bool(true))
+                    } else {
+                        Ok(Value::Bool(self.evaluate(right)?.to_bool()))
+                    }
+                }
+                LogicalOperation::LogicalXor { left, right, .. } => {
+                    let (left, right) = self.operands(left, right)?;
+
+                    Ok(Value::Bool(left.to_bool() != right.to_bool()))
+                }
+            },
+            Expression::Ternary {
+                condition,
+                then,
+                r#else,
+            } => {
+                let condition_value = self.evaluate(condition).map_err(|error| {
+                    self.prefer(error, then.as_deref().into_iter().chain([r#else.as_ref()]))
+                })?;
+                if condition_value.to_bool() {
+                    self.skip(r#else)?;
+
+                    match then {
+                        Some(then) => self.evaluate(then),
+                        None => Ok(condition_value),
+                    }
+                } else {
+                    if let Some(then) = then {
+                        self.skip(then)?;
+                    }
+
+                    self.evaluate(r#else)
+                }
+            }
+            Expression::Coalesce { lhs, rhs } => {
+                let left = match lhs.as_ref() {
+                    Expression::ArrayIndex { .. } => self.index(lhs, true),
+                    lhs => self.evaluate(lhs),
+                }
+                .map_err(|error| self.prefer(error, [rhs.as_ref()]))?;
+
+                if left == Value::Null {
+                    self.evaluate(rhs)
+                } else {
+                    self.skip(rhs)?;
+
+                    Ok(left)
+                }
+            }
+            Expression::Identifier(Identifier::SimpleIdentifier(identifier)) => {
+                self.constant(expression, &identifier.name.to_string())
+            }
+            Expression::ConstFetch { target, constant } => {
+                let class = match target.as_ref() {
+                    Expression::Self_ => self.special_class("self", target)?,
+                    Expression::Parent => self.special_class("parent", target)?,
+                    Expression::Identifier(Identifier::SimpleIdentifier(identifier)) => {
+                        let name = identifier.name.to_string();
+                        if name.eq_ignore_ascii_case("static") {
+                            return Err(EvaluationError::not_constant(target));
+                        } else if name.eq_ignore_ascii_case("self")
+                            || name.eq_ignore_ascii_case("parent")
+                        {
+                            self.special_class(&name, target)?
+                        } else {
+                            self.scope.resolve_class(&name)
+                        }
+                    }
+                    _ => return Err(EvaluationError::not_constant(target)),
+                };
+
+                let constant = constant.name.to_string();
+                if constant.eq_ignore_ascii_case("class") {
+                    return Ok(Value::string(class));
+                }
+
+                self.class_constants
+                    .get(&(class.to_ascii_lowercase(), constant.clone()))
+                    .cloned()
+                    .ok_or_else(|| {
+                        EvaluationError::unknown(format!("{}::{}", class, constant), expression)
+                    })
+            }
+            Expression::MagicConst { span, constant } => {
+                let value = match constant {
+                    MagicConst::Line => return Ok(Value::Int(span.0 as i64)),
+                    MagicConst::Namespace => Some(self.scope.namespace().to_string()),
+                    MagicConst::File => self
+                        .file
+                        .as_ref()
+                        .map(|file| file.to_string_lossy().into_owned()),
+                    MagicConst::Directory => self.file.as_ref().map(|file| {
+                        file.parent()
+                            .map(|directory| directory.to_string_lossy().into_owned())
+                            .unwrap_or_default()
+                    }),
+                    MagicConst::Class => self.class.clone(),
+                    MagicConst::Trait => self.r#trait.clone(),
+                    MagicConst::Function => self.function.clone(),
+                    MagicConst::Method => {
+                        self.function.as_ref().map(|function| match &self.class {
+                            Some(class) => format!("{}::{}", class, function),
+                            None => function.clone(),
+                        })
+                    }
+                };
+
+                value.map(Value::string).ok_or_else(|| {
+                    EvaluationError::unknown(magic_constant_name(constant), expression)
+                })
+            }
+            Expression::New { target, .. } => Err(EvaluationError::unknown(
+                match target.as_ref() {
+                    Expression::Identifier(Identifier::SimpleIdentifier(identifier)) => {
+                        format!("new {}", identifier.name)
+                    }
+                    _ => "new".to_string(),
+                },
+                expression,
+            )),
+            _ => Err(EvaluationError::not_constant(expression)),
+        })
+    }
+
+    fn array(&self, items: &[ArrayItem]) -> Result<Value, EvaluationError> {
+        let mut array = Array::new();
+        for item in items {
+            if item.by_ref {
+                return Err(EvaluationError::not_constant(&item.value));
+            }
+
+            let value = self.evaluate(&item.value)?;
+            if item.unpack {
+                let Value::Array(unpacked) = value else {
+                    return Err(EvaluationError::invalid(
+                        format!("cannot unpack a value of type {}", value.type_name()),
+                        &item.value,
+                    ));
+                };
+
+                for (key, value) in unpacked.entries {
+                    match key {
+                        ArrayKey::Int(_) => array
+                            .push(value)
+                            .map_err(|message| EvaluationError::invalid(message, &item.value))?,
+                        key => array.insert(key, value),
+                    }
+                }
+
+                continue;
+            }
+
+            match &item.key {
+                Some(key) => {
+                    let key = ArrayKey::try_from(&self.evaluate(key)?)
+                        .map_err(|message| EvaluationError::invalid(message, key))?;
+                    array.insert(key, value);
+                }
+                None => array
+                    .push(value)
+                    .map_err(|message| EvaluationError::invalid(message, &item.value))?,
+            }
+        }
+
+        Ok(Value::Array(array))
+    }
+
+    // Check that an operand that's skipped is constant, without caring
+    // whether it can be evaluated.
+    fn skip(&self, expression: &Expression) -> Result<(), EvaluationError> {
+        // PHP doesn't evaluate the operand, so it doesn't warn about it either.
+        let warnings = self.warnings.borrow().len();
+        let result = self.evaluate(expression);
+        self.warnings.borrow_mut().truncate(warnings);
+
+        match result {
+            Err(error @ EvaluationError::NotConstant { .. }) => Err(error),
+            _ => Ok(()),
+        }
+    }
+
+    fn warn(&self, message: impl Into<String>, expression: &Expression) {
+        self.warnings.borrow_mut().push(EvaluationWarning {
+            message: message.into(),
+            range: Node::Expression(expression).range(),
+        });
+    }
+
+    // Evaluate both operands of a binary operation.
+    fn operands(
+        &self,
+        left: &Expression,
+        right: &Expression,
+    ) -> Result<(Value, Value), EvaluationError> {
+        let left = self
+            .evaluate(left)
+            .map_err(|error| self.prefer(error, [right]))?;
+
+        Ok((left, self.evaluate(right)?))
+    }
+
+    // When part of an expression can't be evaluated, the rest of it may still
+    // not be constant, which takes precedence over the error.
+    fn prefer<'e, 'a: 'e>(
+        &self,
+        error: EvaluationError,
+        others: impl IntoIterator<Item = &'e Expression<'a>>,
+    ) -> EvaluationError {
+        if let EvaluationError::NotConstant { .. } = error {
+            return error;
+        }
+
+        others
+            .into_iter()
+            .find_map(|other| self.skip(other).err())
+            .unwrap_or(error)
+    }
+
+    // Look up an element of an array or a character of a string. When `quiet`,
+    // as on the left-hand side of `??`, missing elements are null.
+    fn index(&self, expression: &Expression, quiet: bool) -> Result<Value, EvaluationError> {
+        let Expression::ArrayIndex { array, index } = expression else {
+            return self.evaluate(expression);
+        };
+        let Some(index) = index else {
+            return Err(EvaluationError::not_constant(expression));
+        };
+
+        let value = match array.as_ref() {
+            Expression::ArrayIndex { .. } if quiet => self.index(array, true),
+            array => self.evaluate(array),
+        }
+        .map_err(|error| self.prefer(error, [index.as_ref()]))?;
+        let offset = self.evaluate(index)?;
+
+        match value {
+            Value::Array(elements) => {
+                let key = ArrayKey::try_from(&offset)
+                    .map_err(|message| EvaluationError::invalid(message, index))?;
+
+                match elements.get(&key) {
+                    Some(element) => Ok(element.clone()),
+                    None if quiet => Ok(Value::Null),
+                    None => Err(EvaluationError::invalid(
+                        format!("undefined array key {}", key),
+                        expression,
+                    )),
+                }
+            }
+            Value::String(string) => {
+                let position = match &offset {
+                    Value::Int(position) => Some(*position),
+                    Value::String(position) => canonical_int(position),
+                    _ => None,
+                };
+                let Some(position) = position else {
+                    return Err(EvaluationError::invalid(
+                        format!(
+                            "cannot access offset of type {} on string",
+                            offset.type_name()
+                        ),
+                        index,
+                    ));
+                };
+
+                let length = string.len() as i64;
+                let absolute = if position < 0 {
+                    position + length
+                } else {
+                    position
+                };
+
+                match usize::try_from(absolute).ok().and_then(|i| string.get(i)) {
+                    Some(byte) => Ok(Value::string(vec![*byte])),
+                    None if quiet => Ok(Value::Null),
+                    None => Err(EvaluationError::invalid(
+                        format!("uninitialized string offset {}", position),
+                        expression,
+                    )),
+                }
+            }
+            Value::Null if quiet => Ok(Value::Null),
+            value => Err(EvaluationError::invalid(
+                format!(
+                    "trying to access array offset on value of type {}",
+                    value.type_name()
+                ),
+                array,
+            )),
+        }
+    }
+
+    fn arithmetic(
+        &self,
+        expression: &Expression,
+        operation: &ArithmeticOperation,
+    ) -> Result<Value, EvaluationError> {
+        let (operator, left, right) = match operation {
+            ArithmeticOperation::Addition { left, right, .. } => {
+                (Operator::Add, left, right.as_ref())
+            }
+            ArithmeticOperation::Subtraction { left, right, .. } => {
+                (Operator::Sub, left, right.as_ref())
+            }
+            ArithmeticOperation::Multiplication { left, right, .. } => {
+                (Operator::Mul, left, right.as_ref())
+            }
+            ArithmeticOperation::Division { left, right, .. } => {
+                (Operator::Div, left, right.as_ref())
+            }
+            ArithmeticOperation::Modulo { left, right, .. } => {
+                (Operator::Mod, left, right.as_ref())
+            }
+            ArithmeticOperation::Exponentiation { left, right, .. } => {
+                (Operator::Pow, left, right.as_ref())
+            }
+            // PHP negates a value by multiplying it with -1, and so on.
+            ArithmeticOperation::Negation { right, .. } => {
+                let right = self.evaluate(right)?;
+
+                return arithmetic(Operator::Mul, &right, &Value::Int(-1))
+                    .map_err(|message| EvaluationError::invalid(message, expression));
+            }
+            ArithmeticOperation::Identity { right, .. } => {
+                let right = self.evaluate(right)?;
+
+                return arithmetic(Operator::Mul, &right, &Value::Int(1))
+                    .map_err(|message| EvaluationError::invalid(message, expression));
+            }
+            ArithmeticOperation::PreIncrement { .. }
+            | ArithmeticOperation::PostIncrement { .. }
+            | ArithmeticOperation::PreDecrement { .. }
+            | ArithmeticOperation::PostDecrement { .. } => {
+                return Err(EvaluationError::not_constant(expression))
+            }
+        };
+
+        let (left, right) = self.operands(left, right)?;
+
+        arithmetic(operator, &left, &right)
+            .map_err(|message| EvaluationError::invalid(message, expression))
+    }
+
+    fn constant(&self, expression: &Expression, name: &str) -> Result<Value, EvaluationError> {
+        let unqualified = name.trim_start_matches('\\');
+        if !unqualified.contains('\\') {
+            if unqualified.eq_ignore_ascii_case("true") {
+                return Ok(Value::Bool(true));
+            } else if unqualified.eq_ignore_ascii_case("false") {
+                return Ok(Value::Bool(false));
+            } else if unqualified.eq_ignore_ascii_case("null") {
+                return Ok(Value::Null);
+            }
+        }
+
+        let resolved = self.scope.resolve(NameTable::Constant, name);
+        let candidates =
+            std::iter::once(resolved.clone()).chain(self.scope.fallback(NameTable::Constant, name));
+
+        for candidate in candidates {
+            if let Some(value) = self.constants.get(&key(NameTable::Constant, &candidate)) {
+                return Ok(value.clone());
+            }
+
+            if let Some(value) = builtin(&candidate) {
+                return Ok(value);
+            }
+        }
+
+        Err(EvaluationError::unknown(resolved, expression))
+    }
+
+    fn special_class(&self, name: &str, target: &Expression) -> Result<String, EvaluationError> {
+        let class = if name.eq_ignore_ascii_case("self") {
+            &self.class
+        } else {
+            &self.parent
+        };
+
+        class
+            .clone()
+            .ok_or_else(|| EvaluationError::unknown(name.to_ascii_lowercase(), target))
+    }
+}
+
+/// Report the constant expressions of a program that contain something that
+/// isn't allowed in them, or whose evaluation fails.
+///
+/// Array to string conversions are reported as warnings, like PHP does.
+///
+/// Constants that the program defines with `const` or `define()` are known to
+/// the expressions that follow them. Expressions that depend on anything else
+/// that isn't known, such as class constants, aren't reported.
+pub fn diagnostics(path: &Path, program: &Program) -> Vec<Diagnostic> {
+    let mut evaluator = Evaluator::new().with_file(path);
+    let mut diagnostics = vec![];
+
+    names::walk(program, |scope, node| {
+        // PHP allows `new` in global constants, parameter defaults and
+        // attribute arguments, but not in the rest.
+        let (what, allows_new, expressions): (&str, bool, Vec<(&Expression, Span)>) = match node {
+            Node::ClassishConstant(constant) => (
+                "the value of a class constant",
+                false,
+                constant
+                    .entries
+                    .iter()
+                    .map(|entry| (&entry.value, entry.name.span))
+                    .collect(),
+            ),
+            Node::BackedEnumCase(case) => (
+                "the value of an enum case",
+                false,
+                vec![(&case.value, case.name.span)],
+            ),
+            Node::PropertyEntry(entry) => (
+                "the default value of a property",
+                false,
+                entry
+                    .value
+                    .iter()
+                    .map(|value| (value, entry.variable.span))
+                    .collect(),
+            ),
+            Node::VariablePropertyEntry(entry) => (
+                "the default value of a property",
+                false,
+                entry
+                    .value
+                    .iter()
+                    .map(|value| (value, entry.variable.span))
+                    .collect(),
+            ),
+            Node::FunctionParameter(parameter) => (
+                "the default value of a parameter",
+                true,
+                parameter
+                    .default
+                    .iter()
+                    .map(|default| (default, parameter.name.span))
+                    .collect(),
+            ),
+            Node::MethodParameter(parameter) => (
+                "the default value of a parameter",
+                true,
+                parameter
+                    .default
+                    .iter()
+                    .map(|default| (default, parameter.name.span))
+                    .collect(),
+            ),
+            Node::Attribute(attribute) => match &attribute.expression {
+                Expression::Call { args, .. } => (
+                    "an attribute argument",
+                    true,
+                    args.iter()
+                        .map(|arg| (&arg.value, attribute.start))
+                        .collect(),
+                ),
+                _ => return,
+            },
+            Node::Statement(Statement::Constant(constant)) => {
+                evaluator.scope = scope.clone();
+                for entry in &constant.entries {
+                    if let Ok(value) = evaluator.evaluate(&entry.value) {
+                        let name = match scope.namespace() {
+                            "" => entry.name.name.to_string(),
+                            namespace => format!("{}\\{}", namespace, entry.name.name),
+                        };
+                        evaluator.define_constant(&name, value);
+                    }
+                }
+                evaluator.take_warnings();
+
+                (
+                    "the value of a constant",
+                    true,
+                    constant
+                        .entries
+                        .iter()
+                        .map(|entry| (&entry.value, entry.name.span))
+                        .collect(),
+                )
+            }
+            Node::Expression(expression) => {
+                if let Some(define) = define(expression) {
+                    evaluator.scope = scope.clone();
+                    if let Some(Ok(value)) = define.value.map(|value| evaluator.evaluate(value)) {
+                        evaluator.define_constant(&define.name, value);
+                    }
+                    evaluator.take_warnings();
+                }
+
+                return;
+            }
+            _ => return,
+        };
+
+        if expressions.is_empty() {
+            return;
+        }
+
+        evaluator.scope = scope.clone();
+        for (expression, span) in expressions {
+            let (error, message) = match evaluator.evaluate(expression) {
+                Err(error @ EvaluationError::NotConstant { .. }) => {
+                    (error, format!("{} must be a constant expression", what))
+                }
+                Err(error @ EvaluationError::Invalid { .. }) => {
+                    let message = error.to_string();
+                    (error, message)
+                }
+                Ok(_) | Err(EvaluationError::Unknown { .. }) => {
+                    match find_new(Node::Expression(expression)).filter(|_| !allows_new) {
+                        Some(new) => (
+                            EvaluationError::not_constant(new),
+                            format!("new expressions are not supported in {}", what),
+                        ),
+                        None => {
+                            for warning in evaluator.take_warnings() {
+                                let span = warning.range.map_or(span, |(start, _)| start);
+                                diagnostics.push(Diagnostic::warning(
+                                    "E0067",
+                                    warning.message,
+                                    span,
+                                ));
+                            }
+
+                            continue;
+                        }
+                    }
+                }
+            };
+            evaluator.take_warnings();
+
+            let code = match error {
+                EvaluationError::NotConstant { .. } => "E0063",
+                _ => "E0064",
+            };
+            let span = error.range().map_or(span, |(start, _)| start);
+
+            diagnostics.push(Diagnostic::error(code, message, span));
+        }
+    });
+
+    diagnostics
+}
+
+// The first `new` expression in a node, if there's one.
+fn find_new<'n, 'a>(node: Node<'n, 'a>) -> Option<&'n Expression<'a>> {
+    if let Node::Expression(expression @ Expression::New { .. }) = node {
+        return Some(expression);
+    }
+
+    ensure_sufficient_stack(|| node.children().into_iter().find_map(find_new))
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Operator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+    Pow,
+    And,
+    Or,
+    Xor,
+    LeftShift,
+    RightShift,
+}
+
+impl Display for Operator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let symbol = match self {
+            Self::Add => "+",
+            Self::Sub => "-",
+            Self::Mul => "*",
+            Self::Div => "/",
+            Self::Mod => "%",
+            Self::Pow => "**",
+            Self::And => "&",
+            Self::Or => "|",
+            Self::Xor => "^",
+            Self::LeftShift => "<<",
+            Self::RightShift => ">>",
+        };
+
+        write!(f, "{}", symbol)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Number {
+    Int(i64),
+    Float(f64),
+}
+
+impl Number {
+    fn to_float(self) -> f64 {
+        match self {
+            Self::Int(value) => value as f64,
+            Self::Float(value) => value,
+        }
+    }
+
+    fn to_int(self) -> i64 {
+        match self {
+            Self::Int(value) => value,
+            Self::Float(value) => float_to_int(value),
+        }
+    }
+}
+
+fn unsupported(operator: Operator, left: &Value, right: &Value) -> String {
+    format!(
+        "unsupported operand types: {} {} {}",
+        left.type_name(),
+        operator,
+        right.type_name()
+    )
+}
+
+fn arithmetic(operator: Operator, left: &Value, right: &Value) -> Result<Value, String> {
+    // Adding arrays keeps the elements of the left one, and adds those of the
+    // right one whose keys are missing.
+    if let (Operator::Add, Value::Array(left), Value::Array(right)) = (operator, left, right) {
+        let mut union = left.clone();
+        for (key, value) in right.iter() {
+            if union.get(key).is_none() {
+                union.insert(key.clone(), value.clone());
+            }
+        }
+
+        return Ok(Value::Array(union));
+    }
+
+    let (Some(a), Some(b)) = (left.to_number(), right.to_number()) else {
+        return Err(unsupported(operator, left, right));
+    };
+
+    // Integer results that overflow become floats.
+    let integer = |result: Option<i64>, float: fn(f64, f64) -> f64| {
+        result
+            .map(Value::Int)
+            .unwrap_or_else(|| Value::Float(float(a.to_float(), b.to_float())))
+    };
+
+    Ok(match (operator, a, b) {
+        (Operator::Add, Number::Int(a), Number::Int(b)) => integer(a.checked_add(b), |a, b| a + b),
+        (Operator::Sub, Number::Int(a), Number::Int(b)) => integer(a.checked_sub(b), |a, b| a - b),
+        (Operator::Mul, Number::Int(a), Number::Int(b)) => integer(a.checked_mul(b), |a, b| a * b),
+        (Operator::Add, ..) => Value::Float(a.to_float() + b.to_float()),
+        (Operator::Sub, ..) => Value::Float(a.to_float() - b.to_float()),
+        (Operator::Mul, ..) => Value::Float(a.to_float() * b.to_float()),
+        (Operator::Div, ..) if b.to_float() == 0.0 => return Err("division by zero".to_string()),
+        (Operator::Div, Number::Int(a), Number::Int(b))
+            if a.checked_rem(b) == Some(0) && a.checked_div(b).is_some() =>
+        {
+            Value::Int(a / b)
+        }
+        (Operator::Div, ..) => Value::Float(a.to_float() / b.to_float()),
+        (Operator::Mod, ..) => match b.to_int() {
+            0 => return Err("modulo by zero".to_string()),
+            // `PHP_INT_MIN % -1` would overflow.
+            -1 => Value::Int(0),
+            divisor => Value::Int(a.to_int() % divisor),
+        },
+        (Operator::Pow, Number::Int(a), Number::Int(b)) if b >= 0 => integer(
+            u32::try_from(b).ok().and_then(|b| a.checked_pow(b)),
+            f64::powf,
+        ),
+        (Operator::Pow, ..) => Value::Float(a.to_float().powf(b.to_float())),
+        _ => unreachable!(),
+    })
+}
+
+fn bitwise(operator: Operator, left: &Value, right: &Value) -> Result<Value, String> {
+    // Bitwise operations on two strings work on their bytes.
+    if let (Value::String(a), Value::String(b)) = (left, right) {
+        let bytes = match operator {
+            Operator::And => a.iter().zip(b.iter()).map(|(a, b)| a & b).collect(),
+            Operator::Xor => a.iter().zip(b.iter()).map(|(a, b)| a ^ b).collect(),
+            Operator::Or => {
+                let (longer, shorter) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+                longer
+                    .iter()
+                    .enumerate()
+                    .map(|(i, byte)| byte | shorter.get(i).copied().unwrap_or(0))
+                    .collect()
+            }
+            _ => vec![],
+        };
+
+        if matches!(operator, Operator::And | Operator::Or | Operator::Xor) {
+            return Ok(Value::string(bytes));
+        }
+    }
+
+    let (Some(a), Some(b)) = (left.to_int(), right.to_int()) else {
+        return Err(unsupported(operator, left, right));
+    };
+
+    Ok(Value::Int(match operator {
+        Operator::And => a & b,
+        Operator::Or => a | b,
+        Operator::Xor => a ^ b,
+        Operator::LeftShift | Operator::RightShift if b < 0 => {
+            return Err("bit shift by negative number".to_string())
+        }
+        Operator::LeftShift if b >= 64 => 0,
+        Operator::LeftShift => a << b,
+        Operator::RightShift if b >= 64 => {
+            if a < 0 {
+                -1
+            } else {
+                0
+            }
+        }
+        Operator::RightShift => a >> b,
+        _ => unreachable!(),
+    }))
+}
+
+// PHP 8's loose comparison, as used by `==`, `<` and `<=>`. Values that can't
+// be ordered compare as greater.
+fn compare(left: &Value, right: &Value) -> Ordering {
+    match (left, right) {
+        (Value::Null, Value::Null) => Ordering::Equal,
+        (Value::Null, Value::String(string)) => {
+            if string.is_empty() {
+                Ordering::Equal
+            } else {
+                Ordering::Less
+            }
+        }
+        (Value::String(string), Value::Null) => {
+            if string.is_empty() {
+                Ordering::Equal
+            } else {
+                Ordering::Greater
+            }
+        }
+        (Value::Null | Value::Bool(_), _) | (_, Value::Null | Value::Bool(_)) => {
+            left.to_bool().cmp(&right.to_bool())
+        }
+        (Value::String(a), Value::String(b)) => match (numeric_string(a), numeric_string(b)) {
+            (Some(a), Some(b)) => compare_numbers(a, b),
+            _ => (**a).cmp(&**b),
+        },
+        (Value::Array(a), Value::Array(b)) => {
+            if a.len() != b.len() {
+                return a.len().cmp(&b.len());
+            }
+
+            for (key, value) in a.iter() {
+                match b.get(key) {
+                    Some(other) => match compare(value, other) {
+                        Ordering::Equal => {}
+                        ordering => return ordering,
+                    },
+                    None => return Ordering::Greater,
+                }
+            }
+
+            Ordering::Equal
+        }
+        (Value::Array(_), _) => Ordering::Greater,
+        (_, Value::Array(_)) => Ordering::Less,
+        (Value::String(string), number) => match numeric_string(string) {
+            Some(a) => compare_numbers(a, number.to_number().unwrap_or(Number::Int(0))),
+            None => (**string).cmp(number.to_bytes().as_slice()),
+        },
+        (number, Value::String(_)) => compare(right, number).reverse(),
+        (a, b) => compare_numbers(
+            a.to_number().unwrap_or(Number::Int(0)),
+            b.to_number().unwrap_or(Number::Int(0)),
+        ),
+    }
+}
+
+fn compare_numbers(a: Number, b: Number) -> Ordering {
+    match (a, b) {
+        (Number::Int(a), Number::Int(b)) => a.cmp(&b),
+        (a, b) => {
+            let (a, b) = (a.to_float(), b.to_float());
+            if a == b {
+                Ordering::Equal
+            } else if a < b {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            }
+        }
+    }
+}
+
+// `===`, where values must have the same type, and arrays the same elements in
+// the same order.
+fn identical(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .zip(b.iter())
+                    .all(|((k, v), (l, w))| k == l && identical(v, w))
+        }
+        _ => left == right,
+    }
+}
+
+// The number a string starts with, and whether that's all the string is,
+// ignoring whitespace around it.
+fn numeric(bytes: &[u8]) -> Option<(Number, bool)> {
+    let is_whitespace = |byte: &u8| matches!(byte, b' ' | b'\t' | b'\n' | b'\r' | 0x0b | 0x0c);
+    let digits = |from: usize| {
+        bytes[from.min(bytes.len())..]
+            .iter()
+            .take_while(|byte| byte.is_ascii_digit())
+            .count()
+    };
+
+    let start = bytes
+        .iter()
+        .position(|byte| !is_whitespace(byte))
+        .unwrap_or(bytes.len());
+    let mut end = start;
+    if matches!(bytes.get(end), Some(b'+' | b'-')) {
+        end += 1;
+    }
+
+    let integer = digits(end);
+    end += integer;
+
+    let mut is_float = false;
+    if bytes.get(end) == Some(&b'.') {
+        let fraction = digits(end + 1);
+        if integer == 0 && fraction == 0 {
+            return None;
+        }
+
+        is_float = true;
+        end += 1 + fraction;
+    } else if integer == 0 {
+        return None;
+    }
+
+    if matches!(bytes.get(end), Some(b'e' | b'E')) {
+        let mut exponent = end + 1;
+        if matches!(bytes.get(exponent), Some(b'+' | b'-')) {
+            exponent += 1;
+        }
+
+        let count = digits(exponent);
+        if count > 0 {
+            is_float = true;
+            end = exponent + count;
+        }
+    }
+
+    let text = std::str::from_utf8(&bytes[start..end]).ok()?;
+    let number = match text.parse() {
+        Ok(value) if !is_float => Number::Int(value),
+        _ => Number::Float(text.parse().ok()?),
+    };
+
+    Some((number, bytes[end..].iter().all(is_whitespace)))
+}
+
+fn numeric_string(bytes: &[u8]) -> Option<Number> {
+    numeric(bytes)
+        .filter(|(_, whole)| *whole)
+        .map(|(number, _)| number)
+}
+
+// The integer a string is the canonical form of, like "12" but not "012" or "1.0".
+fn canonical_int(bytes: &[u8]) -> Option<i64> {
+    let text = std::str::from_utf8(bytes).ok()?;
+    let value: i64 = text.parse().ok()?;
+
+    (value.to_string() == text).then_some(value)
+}
+
+fn integer_literal(text: &str) -> Option<Value> {
+    let text = text.replace('_', "");
+    let (digits, radix) = match text.get(..2) {
+        Some("0x" | "0X") => (&text[2..], 16),
+        Some("0b" | "0B") => (&text[2..], 2),
+        Some("0o" | "0O") => (&text[2..], 8),
+        _ if text.len() > 1 && text.starts_with('0') => (&text[1..], 8),
+        _ => (text.as_str(), 10),
+    };
+
+    if let Ok(value) = i64::from_str_radix(digits, radix) {
+        return Some(Value::Int(value));
+    }
+
+    // Literals that don't fit in an integer are floats. Decimal ones are parsed
+    // as a whole, so that they're rounded only once.
+    if radix == 10 {
+        return digits.parse().ok().map(Value::Float);
+    }
+
+    digits
+        .chars()
+        .try_fold(0.0, |value: f64, digit| {
+            digit
+                .to_digit(radix)
+                .map(|digit| value * radix as f64 + digit as f64)
+        })
+        .map(Value::Float)
+}
+
+// PHP's conversion of floats to integers, which wraps around for floats that
+// are out of range.
+fn float_to_int(value: f64) -> i64 {
+    if !value.is_finite() {
+        0
+    } else if (-9.223372036854776e18..9.223372036854776e18).contains(&value) {
+        value as i64
+    } else {
+        value.rem_euclid(18446744073709551616.0) as u64 as i64
+    }
+}
+
+// PHP's conversion of floats to strings, which uses 14 significant digits.
+fn float_to_string(value: f64) -> String {
+    if value.is_nan() {
+        return "NAN".to_string();
+    } else if value.is_infinite() {
+        return if value > 0.0 { "INF" } else { "-INF" }.to_string();
+    } else if value == 0.0 {
+        return if value.is_sign_negative() { "-0" } else { "0" }.to_string();
+    }
+
+    let scientific = format!("{:.13e}", value);
+    let (mantissa, exponent) = scientific.split_once('e').unwrap();
+    let exponent: i32 = exponent.parse().unwrap();
+
+    if (-4..14).contains(&exponent) {
+        let fixed = format!("{:.*}", (13 - exponent) as usize, value);
+        let fixed = if fixed.contains('.') {
+            fixed.trim_end_matches('0').trim_end_matches('.')
+        } else {
+            &fixed
+        };
+
+        return fixed.to_string();
+    }
+
+    let mantissa = mantissa.trim_end_matches('0');
+    let mantissa = match mantissa.strip_suffix('.') {
+        Some(integer) => format!("{}.0", integer),
+        None => mantissa.to_string(),
+    };
+
+    format!(
+        "{}E{}{}",
+        mantissa,
+        if exponent < 0 { '-' } else { '+' },
+        exponent.abs()
+    )
+}
+
+fn magic_constant_name(constant: &MagicConst) -> &'static str {
+    match constant {
+        MagicConst::Directory => "__DIR__",
+        MagicConst::File => "__FILE__",
+        MagicConst::Line => "__LINE__",
+        MagicConst::Class => "__CLASS__",
+        MagicConst::Function => "__FUNCTION__",
+        MagicConst::Method => "__METHOD__",
+        MagicConst::Namespace => "__NAMESPACE__",
+        MagicConst::Trait => "__TRAIT__",
+    }
+}
+
+// The constants that PHP and its core extensions always define, as they are on
+// a 64-bit platform that isn't Windows.
+fn builtin(name: &str) -> Option<Value> {
+    Some(match name {
+        "PHP_EOL" => Value::string("\n"),
+        "PHP_INT_MAX" => Value::Int(i64::MAX),
+        "PHP_INT_MIN" => Value::Int(i64::MIN),
+        "PHP_INT_SIZE" => Value::Int(8),
+        "PHP_FLOAT_EPSILON" => Value::Float(f64::EPSILON),
+        "PHP_FLOAT_MAX" => Value::Float(f64::MAX),
+        "PHP_FLOAT_MIN" => Value::Float(f64::MIN_POSITIVE),
+        "PHP_FLOAT_DIG" => Value::Int(15),
+        "NAN" => Value::Float(f64::NAN),
+        "INF" => Value::Float(f64::INFINITY),
+        "M_PI" => Value::Float(std::f64::consts::PI),
+        "M_E" => Value::Float(std::f64::consts::E),
+        "DIRECTORY_SEPARATOR" => Value::string("/"),
+        "PATH_SEPARATOR" => Value::string(":"),
+        "E_ERROR" => Value::Int(1),
+        "E_WARNING" => Value::Int(2),
+        "E_PARSE" => Value::Int(4),
+        "E_NOTICE" => Value::Int(8),
+        "E_CORE_ERROR" => Value::Int(16),
+        "E_CORE_WARNING" => Value::Int(32),
+        "E_COMPILE_ERROR" => Value::Int(64),
+        "E_COMPILE_WARNING" => Value::Int(128),
+        "E_USER_ERROR" => Value::Int(256),
+        "E_USER_WARNING" => Value::Int(512),
+        "E_USER_NOTICE" => Value::Int(1024),
+        "E_STRICT" => Value::Int(2048),
+        "E_RECOVERABLE_ERROR" => Value::Int(4096),
+        "E_DEPRECATED" => Value::Int(8192),
+        "E_USER_DEPRECATED" => Value::Int(16384),
+        "E_ALL" => Value::Int(32767),
+        _ => return None,
+    })
+}
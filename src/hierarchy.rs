@@ -0,0 +1,964 @@
+//! The inheritance hierarchy of the classes, interfaces, traits and enums
+//! declared across the files of a project.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use serde::Serialize;
+
+use crate::diagnostics::Diagnostic;
+use crate::index::key;
+use crate::index::DeclarationKind;
+use crate::index::NameTable;
+use crate::lexer::token::Span;
+use crate::names;
+use crate::names::Scope;
+use crate::parser::ast::classes::ClassExtends;
+use crate::parser::ast::classes::ClassImplements;
+use crate::parser::ast::classes::ClassMember;
+use crate::parser::ast::enums::BackedEnumMember;
+use crate::parser::ast::enums::UnitEnumMember;
+use crate::parser::ast::functions::Method;
+use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::interfaces::InterfaceMember;
+use crate::parser::ast::modifiers::Visibility;
+use crate::parser::ast::modifiers::VisibilityModifier;
+use crate::parser::ast::node::Node;
+use crate::parser::ast::traits::TraitMember;
+use crate::parser::ast::traits::TraitUsage;
+use crate::parser::ast::traits::TraitUsageAdaptation;
+use crate::parser::ast::Program;
+use crate::project::ProjectResult;
+
+/// The classes, interfaces and traits declared by PHP itself and by the
+/// extensions that are bundled with it. References to them aren't reported as
+/// missing, even though no file of the project declares them. Names from other
+/// extensions or libraries can be added with `Hierarchy::with_external`.
+const BUILTIN: &[&str] = &[
+    "__PHP_Incomplete_Class",
+    "AddressInfo",
+    "AllowDynamicProperties",
+    "AppendIterator",
+    "ArgumentCountError",
+    "ArithmeticError",
+    "ArrayAccess",
+    "ArrayIterator",
+    "ArrayObject",
+    "AssertionError",
+    "Attribute",
+    "BackedEnum",
+    "BadFunctionCallException",
+    "BadMethodCallException",
+    "CachingIterator",
+    "CallbackFilterIterator",
+    "ClosedGeneratorException",
+    "Closure",
+    "Collator",
+    "CompileError",
+    "Countable",
+    "CURLFile",
+    "CurlHandle",
+    "CurlMultiHandle",
+    "CurlShareHandle",
+    "CURLStringFile",
+    "DateError",
+    "DateException",
+    "DateInterval",
+    "DateInvalidOperationException",
+    "DateInvalidTimeZoneException",
+    "DateMalformedIntervalStringException",
+    "DateMalformedPeriodStringException",
+    "DateMalformedStringException",
+    "DateObjectError",
+    "DatePeriod",
+    "DateRangeError",
+    "DateTime",
+    "DateTimeImmutable",
+    "DateTimeInterface",
+    "DateTimeZone",
+    "DeflateContext",
+    "Directory",
+    "DirectoryIterator",
+    "DivisionByZeroError",
+    "DomainException",
+    "DOMAttr",
+    "DOMCdataSection",
+    "DOMCharacterData",
+    "DOMChildNode",
+    "DOMComment",
+    "DOMDocument",
+    "DOMDocumentFragment",
+    "DOMDocumentType",
+    "DOMElement",
+    "DOMEntity",
+    "DOMEntityReference",
+    "DOMException",
+    "DOMImplementation",
+    "DOMNamedNodeMap",
+    "DOMNameSpaceNode",
+    "DOMNode",
+    "DOMNodeList",
+    "DOMNotation",
+    "DOMParentNode",
+    "DOMProcessingInstruction",
+    "DOMText",
+    "DOMXPath",
+    "EmptyIterator",
+    "Error",
+    "ErrorException",
+    "Exception",
+    "Fiber",
+    "FiberError",
+    "FilesystemIterator",
+    "FilterIterator",
+    "finfo",
+    "GdFont",
+    "GdImage",
+    "Generator",
+    "GlobIterator",
+    "GMP",
+    "HashContext",
+    "InfiniteIterator",
+    "InflateContext",
+    "InternalIterator",
+    "IntlBreakIterator",
+    "IntlCalendar",
+    "IntlChar",
+    "IntlCodePointBreakIterator",
+    "IntlDateFormatter",
+    "IntlDatePatternGenerator",
+    "IntlException",
+    "IntlGregorianCalendar",
+    "IntlIterator",
+    "IntlPartsIterator",
+    "IntlRuleBasedBreakIterator",
+    "IntlTimeZone",
+    "InvalidArgumentException",
+    "Iterator",
+    "IteratorAggregate",
+    "IteratorIterator",
+    "JsonException",
+    "JsonSerializable",
+    "LengthException",
+    "LibXMLError",
+    "LimitIterator",
+    "Locale",
+    "LogicException",
+    "MessageFormatter",
+    "MultipleIterator",
+    "mysqli",
+    "mysqli_driver",
+    "mysqli_result",
+    "mysqli_sql_exception",
+    "mysqli_stmt",
+    "mysqli_warning",
+    "NoRewindIterator",
+    "Normalizer",
+    "NumberFormatter",
+    "OpenSSLAsymmetricKey",
+    "OpenSSLCertificate",
+    "OpenSSLCertificateSigningRequest",
+    "OuterIterator",
+    "OutOfBoundsException",
+    "OutOfRangeException",
+    "OverflowException",
+    "Override",
+    "ParentIterator",
+    "ParseError",
+    "PDO",
+    "PDOException",
+    "PDORow",
+    "PDOStatement",
+    "Phar",
+    "PharData",
+    "PharException",
+    "PharFileInfo",
+    "php_user_filter",
+    "Random\\BrokenRandomEngineError",
+    "Random\\CryptoSafeEngine",
+    "Random\\Engine",
+    "Random\\Engine\\Mt19937",
+    "Random\\Engine\\PcgOneseq128XslRr64",
+    "Random\\Engine\\Secure",
+    "Random\\Engine\\Xoshiro256StarStar",
+    "Random\\RandomError",
+    "Random\\RandomException",
+    "Random\\Randomizer",
+    "RangeException",
+    "RecursiveArrayIterator",
+    "RecursiveCachingIterator",
+    "RecursiveCallbackFilterIterator",
+    "RecursiveDirectoryIterator",
+    "RecursiveFilterIterator",
+    "RecursiveIterator",
+    "RecursiveIteratorIterator",
+    "RecursiveRegexIterator",
+    "RecursiveTreeIterator",
+    "Reflection",
+    "ReflectionAttribute",
+    "ReflectionClass",
+    "ReflectionClassConstant",
+    "ReflectionEnum",
+    "ReflectionEnumBackedCase",
+    "ReflectionEnumUnitCase",
+    "ReflectionException",
+    "ReflectionExtension",
+    "ReflectionFiber",
+    "ReflectionFunction",
+    "ReflectionFunctionAbstract",
+    "ReflectionGenerator",
+    "ReflectionIntersectionType",
+    "ReflectionMethod",
+    "ReflectionNamedType",
+    "ReflectionObject",
+    "ReflectionParameter",
+    "ReflectionProperty",
+    "ReflectionReference",
+    "ReflectionType",
+    "ReflectionUnionType",
+    "ReflectionZendExtension",
+    "Reflector",
+    "RegexIterator",
+    "ResourceBundle",
+    "ReturnTypeWillChange",
+    "RuntimeException",
+    "SeekableIterator",
+    "SensitiveParameter",
+    "SensitiveParameterValue",
+    "Serializable",
+    "SessionHandler",
+    "SessionHandlerInterface",
+    "SessionIdInterface",
+    "SessionUpdateTimestampHandlerInterface",
+    "SimpleXMLElement",
+    "SimpleXMLIterator",
+    "SoapClient",
+    "SoapFault",
+    "SoapHeader",
+    "SoapParam",
+    "SoapServer",
+    "SoapVar",
+    "Socket",
+    "SodiumException",
+    "SplDoublyLinkedList",
+    "SplFileInfo",
+    "SplFileObject",
+    "SplFixedArray",
+    "SplHeap",
+    "SplMaxHeap",
+    "SplMinHeap",
+    "SplObjectStorage",
+    "SplObserver",
+    "SplPriorityQueue",
+    "SplQueue",
+    "SplStack",
+    "SplSubject",
+    "SplTempFileObject",
+    "Spoofchecker",
+    "SQLite3",
+    "SQLite3Result",
+    "SQLite3Stmt",
+    "stdClass",
+    "Stringable",
+    "Throwable",
+    "Transliterator",
+    "Traversable",
+    "TypeError",
+    "UConverter",
+    "UnderflowException",
+    "UnexpectedValueException",
+    "UnhandledMatchError",
+    "UnitEnum",
+    "ValueError",
+    "WeakMap",
+    "WeakReference",
+    "XMLParser",
+    "XMLReader",
+    "XMLWriter",
+    "XSLTProcessor",
+    "ZipArchive",
+];
+
+/// A class-like and the names it inherits from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ClassLike {
+    pub kind: DeclarationKind,
+    /// The fully qualified name, without a leading `\`. Anonymous classes are
+    /// named after where they're declared, e.g. `class@anonymous/app/index.php:3:9`.
+    pub name: String,
+    /// Where the name is written, or where `class` is for anonymous classes.
+    pub span: Span,
+    /// The parent class, or the interfaces that an interface extends.
+    pub extends: Vec<Reference>,
+    /// The interfaces that a class or enum implements.
+    pub implements: Vec<Reference>,
+    /// The traits used by a class or trait.
+    pub traits: Vec<Reference>,
+    /// The `insteadof` and `as` rules of the trait `use` clauses.
+    pub adaptations: Vec<Adaptation>,
+    /// The methods that the class-like declares itself.
+    pub methods: Vec<MethodDeclaration>,
+}
+
+impl ClassLike {
+    fn new(kind: DeclarationKind, name: String, span: Span) -> Self {
+        Self {
+            kind,
+            name,
+            span,
+            extends: vec![],
+            implements: vec![],
+            traits: vec![],
+            adaptations: vec![],
+            methods: vec![],
+        }
+    }
+
+    /// Everything the class-like inherits from directly, i.e. its parents,
+    /// interfaces and traits.
+    pub fn supertypes(&self) -> impl Iterator<Item = &Reference> {
+        self.extends
+            .iter()
+            .chain(&self.implements)
+            .chain(&self.traits)
+    }
+
+    fn with_members<'n, 'a: 'n>(
+        mut self,
+        scope: &Scope,
+        usages: impl Iterator<Item = &'n TraitUsage<'a>>,
+        methods: impl Iterator<Item = &'n Method<'a>>,
+    ) -> Self {
+        for usage in usages {
+            self.traits.extend(references(scope, &usage.traits));
+            self.adaptations.extend(
+                usage
+                    .adaptations
+                    .iter()
+                    .map(|adaptation| self::adaptation(scope, adaptation)),
+            );
+        }
+
+        self.methods = methods
+            .map(|method| MethodDeclaration {
+                name: method.name.name.to_string(),
+                span: method.name.span,
+                visibility: method.modifiers.visibility(),
+                is_static: method.modifiers.has_static(),
+                is_abstract: method.modifiers.has_abstract() || method.body.is_none(),
+            })
+            .collect();
+
+        self
+    }
+}
+
+/// A name written in an `extends`, `implements` or `use` clause.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Reference {
+    /// The fully qualified name, resolved with the imports in scope.
+    pub name: String,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MethodDeclaration {
+    pub name: String,
+    pub span: Span,
+    pub visibility: Visibility,
+    pub is_static: bool,
+    /// Whether the method is declared `abstract`, or has no body as in interfaces.
+    pub is_abstract: bool,
+}
+
+/// A rule from the block of a trait `use` clause.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum Adaptation {
+    /// `Trait::method as visibility alias`, where either the visibility or the
+    /// alias can be left out, and so can the trait.
+    Alias {
+        r#trait: Option<Reference>,
+        method: String,
+        /// Where the method's name is written.
+        span: Span,
+        alias: Option<String>,
+        visibility: Option<Visibility>,
+    },
+    /// `Trait::method insteadof Other`.
+    Precedence {
+        r#trait: Option<Reference>,
+        method: String,
+        /// Where the method's name is written.
+        span: Span,
+        insteadof: Vec<Reference>,
+    },
+}
+
+/// Find every class-like declared by a program, including anonymous classes
+/// and those nested in blocks or function bodies.
+pub fn class_likes(path: &Path, program: &Program) -> Vec<ClassLike> {
+    let mut class_likes = vec![];
+
+    names::walk(program, |scope, node| {
+        let class_like = match node {
+            Node::Class(class) => ClassLike {
+                extends: extends(scope, &class.extends),
+                implements: implements(scope, &class.implements),
+                ..ClassLike::new(
+                    DeclarationKind::Class,
+                    qualify(scope, &class.name),
+                    class.name.span,
+                )
+            }
+            .with_members(
+                scope,
+                class.members.iter().filter_map(class_usage),
+                class.members.iter().filter_map(class_method),
+            ),
+            Node::AnonymousClass(class) => ClassLike {
+                extends: extends(scope, &class.extends),
+                implements: implements(scope, &class.implements),
+                ..ClassLike::new(
+                    DeclarationKind::Class,
+                    format!(
+                        "class@anonymous{}:{}:{}",
+                        path.display(),
+                        class.start.0,
+                        class.start.1
+                    ),
+                    class.start,
+                )
+            }
+            .with_members(
+                scope,
+                class.members.iter().filter_map(class_usage),
+                class.members.iter().filter_map(class_method),
+            ),
+            Node::Interface(interface) => ClassLike {
+                extends: references(
+                    scope,
+                    interface
+                        .extends
+                        .iter()
+                        .flat_map(|extends| &extends.parents),
+                ),
+                ..ClassLike::new(
+                    DeclarationKind::Interface,
+                    qualify(scope, &interface.name),
+                    interface.name.span,
+                )
+            }
+            .with_members(
+                scope,
+                std::iter::empty(),
+                interface.members.iter().filter_map(|member| match member {
+                    InterfaceMember::Method(method) => Some(method),
+                    _ => None,
+                }),
+            ),
+            Node::Trait(r#trait) => ClassLike::new(
+                DeclarationKind::Trait,
+                qualify(scope, &r#trait.name),
+                r#trait.name.span,
+            )
+            .with_members(
+                scope,
+                r#trait.members.iter().filter_map(|member| match member {
+                    TraitMember::TraitUsage(usage) => Some(usage),
+                    _ => None,
+                }),
+                r#trait.members.iter().filter_map(|member| match member {
+                    TraitMember::Method(method) => Some(method),
+                    _ => None,
+                }),
+            ),
+            Node::UnitEnum(r#enum) => ClassLike {
+                implements: references(scope, &r#enum.implements),
+                ..ClassLike::new(
+                    DeclarationKind::Enum,
+                    qualify(scope, &r#enum.name),
+                    r#enum.name.span,
+                )
+            }
+            .with_members(
+                scope,
+                std::iter::empty(),
+                r#enum.members.iter().filter_map(|member| match member {
+                    UnitEnumMember::Method(method) => Some(method),
+                    _ => None,
+                }),
+            ),
+            Node::BackedEnum(r#enum) => ClassLike {
+                implements: references(scope, &r#enum.implements),
+                ..ClassLike::new(
+                    DeclarationKind::Enum,
+                    qualify(scope, &r#enum.name),
+                    r#enum.name.span,
+                )
+            }
+            .with_members(
+                scope,
+                std::iter::empty(),
+                r#enum.members.iter().filter_map(|member| match member {
+                    BackedEnumMember::Method(method) => Some(method),
+                    _ => None,
+                }),
+            ),
+            _ => return,
+        };
+
+        class_likes.push(class_like);
+    });
+
+    class_likes
+}
+
+fn class_usage<'n, 'a>(member: &'n ClassMember<'a>) -> Option<&'n TraitUsage<'a>> {
+    match member {
+        ClassMember::TraitUsage(usage) => Some(usage),
+        _ => None,
+    }
+}
+
+fn class_method<'n, 'a>(member: &'n ClassMember<'a>) -> Option<&'n Method<'a>> {
+    match member {
+        ClassMember::Method(method) => Some(method),
+        _ => None,
+    }
+}
+
+fn qualify(scope: &Scope, name: &SimpleIdentifier) -> String {
+    match scope.namespace() {
+        "" => name.name.to_string(),
+        namespace => format!("{}\\{}", namespace, name.name),
+    }
+}
+
+fn references<'n, 'a: 'n>(
+    scope: &Scope,
+    names: impl IntoIterator<Item = &'n SimpleIdentifier<'a>>,
+) -> Vec<Reference> {
+    names
+        .into_iter()
+        .map(|name| reference(scope, name))
+        .collect()
+}
+
+fn reference(scope: &Scope, name: &SimpleIdentifier) -> Reference {
+    Reference {
+        name: scope.resolve_class(&name.name.to_string()),
+        span: name.span,
+    }
+}
+
+fn extends(scope: &Scope, extends: &Option<ClassExtends>) -> Vec<Reference> {
+    references(scope, extends.iter().map(|extends| &extends.parent))
+}
+
+fn implements(scope: &Scope, implements: &Option<ClassImplements>) -> Vec<Reference> {
+    references(
+        scope,
+        implements
+            .iter()
+            .flat_map(|implements| &implements.interfaces),
+    )
+}
+
+fn adaptation(scope: &Scope, adaptation: &TraitUsageAdaptation) -> Adaptation {
+    match adaptation {
+        TraitUsageAdaptation::Alias {
+            r#trait,
+            method,
+            alias,
+            visibility,
+        } => Adaptation::Alias {
+            r#trait: r#trait.as_ref().map(|name| reference(scope, name)),
+            method: method.name.to_string(),
+            span: method.span,
+            alias: Some(alias.name.to_string()),
+            visibility: visibility.as_ref().map(self::visibility),
+        },
+        TraitUsageAdaptation::Visibility {
+            r#trait,
+            method,
+            visibility,
+        } => Adaptation::Alias {
+            r#trait: r#trait.as_ref().map(|name| reference(scope, name)),
+            method: method.name.to_string(),
+            span: method.span,
+            alias: None,
+            visibility: Some(self::visibility(visibility)),
+        },
+        TraitUsageAdaptation::Precedence {
+            r#trait,
+            method,
+            insteadof,
+        } => Adaptation::Precedence {
+            r#trait: r#trait.as_ref().map(|name| reference(scope, name)),
+            method: method.name.to_string(),
+            span: method.span,
+            insteadof: references(scope, insteadof),
+        },
+    }
+}
+
+fn visibility(modifier: &VisibilityModifier) -> Visibility {
+    match modifier {
+        VisibilityModifier::Public { .. } => Visibility::Public,
+        VisibilityModifier::Protected { .. } => Visibility::Protected,
+        VisibilityModifier::Private { .. } => Visibility::Private,
+    }
+}
+
+/// Identifies a class-like by the file it's declared in and its position
+/// among the class-likes of that file.
+type Id = (PathBuf, usize);
+
+/// The class-likes of every file in a project and how they relate to each
+/// other, kept up to date one file at a time.
+///
+/// Names are compared case insensitively, like PHP does. When a name is
+/// declared more than once, the declaration in the first file, by path, is
+/// the one whose parents are followed.
+#[derive(Debug, Clone, Default)]
+pub struct Hierarchy {
+    files: BTreeMap<PathBuf, Vec<ClassLike>>,
+    names: HashMap<String, Vec<Id>>,
+    // Class-likes by the name of a class or interface they extend or implement.
+    subtypes: HashMap<String, Vec<Id>>,
+    // Class-likes by the name of a trait they use.
+    users: HashMap<String, Vec<Id>>,
+    // Names declared outside of the project, besides the builtin ones.
+    external: HashSet<String>,
+}
+
+impl Hierarchy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Treat the given names as declared, like PHP's own classes, e.g. because
+    /// they come from an extension or a library that isn't part of the project.
+    pub fn with_external<I>(mut self, names: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.external
+            .extend(names.into_iter().map(|name| normalise(name.as_ref())));
+
+        self
+    }
+
+    /// Build the hierarchy of every file of a project that could be parsed.
+    pub fn from_project(result: &ProjectResult) -> Self {
+        let mut hierarchy = Self::new();
+        for file in &result.files {
+            if let Ok(program) = &file.result {
+                hierarchy.update(&file.path, class_likes(&file.path, program));
+            }
+        }
+
+        hierarchy
+    }
+
+    /// Replace the class-likes of a file.
+    pub fn update(&mut self, path: impl Into<PathBuf>, class_likes: Vec<ClassLike>) {
+        let path = path.into();
+        self.remove(&path);
+
+        for (index, class_like) in class_likes.iter().enumerate() {
+            let id = (path.clone(), index);
+
+            insert(&mut self.names, &class_like.name, &id);
+            for reference in class_like.extends.iter().chain(&class_like.implements) {
+                insert(&mut self.subtypes, &reference.name, &id);
+            }
+            for reference in &class_like.traits {
+                insert(&mut self.users, &reference.name, &id);
+            }
+        }
+
+        self.files.insert(path, class_likes);
+    }
+
+    /// Forget the class-likes of a file, e.g. when it's deleted.
+    pub fn remove(&mut self, path: &Path) -> Option<Vec<ClassLike>> {
+        let class_likes = self.files.remove(path)?;
+
+        for class_like in &class_likes {
+            retain(&mut self.names, &class_like.name, path);
+            for reference in class_like.supertypes() {
+                retain(&mut self.subtypes, &reference.name, path);
+                retain(&mut self.users, &reference.name, path);
+            }
+        }
+
+        Some(class_likes)
+    }
+
+    /// The class-likes of a file, in the order they appear.
+    pub fn file(&self, path: &Path) -> Option<&[ClassLike]> {
+        self.files.get(path).map(Vec::as_slice)
+    }
+
+    pub fn class_likes(&self) -> impl Iterator<Item = (&Path, &ClassLike)> {
+        self.files.iter().flat_map(|(path, class_likes)| {
+            class_likes
+                .iter()
+                .map(move |class_like| (path.as_path(), class_like))
+        })
+    }
+
+    /// Find a class-like by its fully qualified name, with or without a leading `\`.
+    pub fn get(&self, name: &str) -> Option<(&Path, &ClassLike)> {
+        self.names
+            .get(&normalise(name))
+            .and_then(|ids| ids.first())
+            .map(|id| self.resolve(id))
+    }
+
+    /// The names of the classes that a class extends, nearest first, or of
+    /// every interface that an interface extends.
+    ///
+    /// Names that aren't declared by the project are included, but their own
+    /// parents can't be known.
+    pub fn ancestors(&self, name: &str) -> Vec<&str> {
+        self.search(name, |class_like| class_like.extends.iter())
+    }
+
+    /// The names of every interface that a class-like implements, including
+    /// those implemented by its parents and the parents of those interfaces.
+    /// For an interface, these are the interfaces that it extends.
+    pub fn interfaces(&self, name: &str) -> Vec<&str> {
+        let Some((_, class_like)) = self.get(name) else {
+            return vec![];
+        };
+
+        let mut interfaces = vec![];
+        let classes = std::iter::once(class_like.name.as_str()).chain(self.ancestors(name));
+        for class in classes {
+            let Some((_, class_like)) = self.get(class) else {
+                continue;
+            };
+
+            let direct = match class_like.kind {
+                DeclarationKind::Interface => &class_like.extends,
+                _ => &class_like.implements,
+            };
+
+            for interface in direct {
+                let names =
+                    std::iter::once(interface.name.as_str()).chain(self.ancestors(&interface.name));
+
+                for name in names {
+                    if !interfaces
+                        .iter()
+                        .any(|other: &&str| normalise(other) == normalise(name))
+                    {
+                        interfaces.push(name);
+                    }
+                }
+            }
+        }
+
+        interfaces
+    }
+
+    /// Every class-like that extends or implements the given class or
+    /// interface, directly or through others, in breadth first order.
+    pub fn descendants(&self, name: &str) -> Vec<(&Path, &ClassLike)> {
+        self.reverse(&self.subtypes, name)
+    }
+
+    /// Every class-like that uses a trait, either directly or through
+    /// another trait. Subclasses of those, which inherit the trait's members
+    /// too, aren't included.
+    pub fn trait_users(&self, name: &str) -> Vec<(&Path, &ClassLike)> {
+        self.reverse(&self.users, name)
+    }
+
+    /// Report class-likes that inherit from themselves, along with references
+    /// to class-likes that aren't declared by the project or PHP itself.
+    pub fn diagnostics(&self) -> Vec<(&Path, Diagnostic)> {
+        let mut diagnostics = vec![];
+
+        for (path, class_like) in self.class_likes() {
+            if let Some(cycle) = self.cycle(class_like) {
+                let names = std::iter::once(class_like.name.as_str())
+                    .chain(cycle.iter().map(|reference| reference.name.as_str()))
+                    .map(|name| format!("`{}`", name))
+                    .collect::<Vec<_>>();
+
+                diagnostics.push((
+                    path,
+                    Diagnostic::error(
+                        "E0058",
+                        format!("`{}` inherits from itself", class_like.name),
+                        cycle[0].span,
+                    )
+                    .with_note(format!("the cycle is {}", names.join(" -> "))),
+                ));
+            }
+
+            let kinds = [
+                (&class_like.extends, self::parent(class_like.kind)),
+                (&class_like.implements, "interface"),
+                (&class_like.traits, "trait"),
+            ];
+
+            for (references, kind) in kinds {
+                for reference in references {
+                    if self.get(&reference.name).is_none() && !self.is_external(&reference.name) {
+                        diagnostics.push((
+                            path,
+                            Diagnostic::warning(
+                                "E0059",
+                                format!("unknown {} `{}`", kind, reference.name),
+                                reference.span,
+                            ),
+                        ));
+                    }
+                }
+            }
+        }
+
+        diagnostics
+    }
+
+    fn is_external(&self, name: &str) -> bool {
+        static BUILTIN_NAMES: OnceLock<HashSet<String>> = OnceLock::new();
+
+        let name = normalise(name);
+        let builtin =
+            BUILTIN_NAMES.get_or_init(|| BUILTIN.iter().map(|name| normalise(name)).collect());
+
+        builtin.contains(&name) || self.external.contains(&name)
+    }
+
+    fn resolve<'h>(&'h self, (path, index): &'h Id) -> (&'h Path, &'h ClassLike) {
+        (path.as_path(), &self.files[path][*index])
+    }
+
+    // The names reachable from a class-like by following `next`, breadth first.
+    fn search<'h, I>(&'h self, name: &str, next: impl Fn(&'h ClassLike) -> I) -> Vec<&'h str>
+    where
+        I: Iterator<Item = &'h Reference>,
+    {
+        let mut found = vec![];
+        let mut seen = HashSet::from([normalise(name)]);
+        let mut queue = VecDeque::from([name.to_string()]);
+
+        while let Some(name) = queue.pop_front() {
+            let Some((_, class_like)) = self.get(&name) else {
+                continue;
+            };
+
+            for reference in next(class_like) {
+                if seen.insert(normalise(&reference.name)) {
+                    found.push(reference.name.as_str());
+                    queue.push_back(reference.name.clone());
+                }
+            }
+        }
+
+        found
+    }
+
+    fn reverse<'h>(
+        &'h self,
+        edges: &'h HashMap<String, Vec<Id>>,
+        name: &str,
+    ) -> Vec<(&'h Path, &'h ClassLike)> {
+        let mut found = vec![];
+        let mut seen = HashSet::new();
+        let mut queue = VecDeque::from([normalise(name)]);
+
+        while let Some(name) = queue.pop_front() {
+            for id in edges.get(&name).into_iter().flatten() {
+                if seen.insert(id) {
+                    let (path, class_like) = self.resolve(id);
+                    found.push((path, class_like));
+                    queue.push_back(normalise(&class_like.name));
+                }
+            }
+        }
+
+        found
+    }
+
+    // The shortest path of references that leads from a class-like back to itself.
+    fn cycle<'h>(&'h self, class_like: &'h ClassLike) -> Option<Vec<&'h Reference>> {
+        let start = normalise(&class_like.name);
+        let mut previous: HashMap<String, (String, &Reference)> = HashMap::new();
+        let mut queue = VecDeque::from([(start.clone(), class_like)]);
+
+        while let Some((name, class_like)) = queue.pop_front() {
+            for reference in class_like.supertypes() {
+                let next = normalise(&reference.name);
+                if previous.contains_key(&next) {
+                    continue;
+                }
+
+                previous.insert(next.clone(), (name.clone(), reference));
+
+                if next == start {
+                    let mut cycle = vec![];
+                    let mut current = next;
+                    loop {
+                        let (from, reference) = &previous[&current];
+                        cycle.push(*reference);
+                        if *from == start {
+                            break;
+                        }
+                        current = from.clone();
+                    }
+                    cycle.reverse();
+
+                    return Some(cycle);
+                }
+
+                if let Some((_, class_like)) = self.get(&reference.name) {
+                    queue.push_back((next, class_like));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+fn parent(kind: DeclarationKind) -> &'static str {
+    match kind {
+        DeclarationKind::Interface => "parent interface",
+        _ => "parent class",
+    }
+}
+
+fn normalise(name: &str) -> String {
+    key(NameTable::Class, name.trim_start_matches('\\')).1
+}
+
+fn insert(map: &mut HashMap<String, Vec<Id>>, name: &str, id: &Id) {
+    let ids = map.entry(normalise(name)).or_default();
+    if !ids.contains(id) {
+        ids.push(id.clone());
+        ids.sort();
+    }
+}
+
+fn retain(map: &mut HashMap<String, Vec<Id>>, name: &str, path: &Path) {
+    let name = normalise(name);
+    if let Some(ids) = map.get_mut(&name) {
+        ids.retain(|(other, _)| other != path);
+
+        if ids.is_empty() {
+            map.remove(&name);
+        }
+    }
+}
@@ -0,0 +1,554 @@
+//! Which files include or require which others, as far as that can be told
+//! without running any code.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use crate::diagnostics::Diagnostic;
+use crate::index::define;
+use crate::index::key;
+use crate::index::NameTable;
+use crate::lexer::token::Span;
+use crate::names;
+use crate::names::Scope;
+use crate::parser::ast::identifiers::Identifier;
+use crate::parser::ast::node::Node;
+use crate::parser::ast::Expression;
+use crate::parser::ast::IncludeKind;
+use crate::parser::ast::MagicConst;
+use crate::parser::ast::Program;
+use crate::parser::ast::Statement;
+use crate::parser::ast::StringPart;
+use crate::parser::stack::ensure_sufficient_stack;
+use crate::project::ProjectResult;
+
+/// An `include`, `include_once`, `require` or `require_once` expression.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Include {
+    pub kind: IncludeKind,
+    /// Where the `include` or `require` keyword is.
+    pub span: Span,
+    /// The path of the included file, or `None` when it depends on something
+    /// that's only known at runtime.
+    pub path: Option<PathBuf>,
+}
+
+/// Find every include in a program, and work out which files they include.
+///
+/// Paths are made of string literals, `__DIR__`, `__FILE__`, `dirname()`,
+/// `DIRECTORY_SEPARATOR` and constants that the file defines itself, joined
+/// with `.`. Relative included paths are taken to be relative to the including
+/// file, rather than to PHP's `include_path` or working directory, and a
+/// relative `path` is taken to be relative to the current directory, so the
+/// included paths are always absolute.
+pub fn includes(path: &Path, program: &Program) -> Vec<Include> {
+    let path = absolute(path);
+    let mut constants = HashMap::new();
+    names::walk(program, |scope, node| match node {
+        Node::Statement(Statement::Constant(constant)) => {
+            for entry in &constant.entries {
+                let name = match scope.namespace() {
+                    "" => entry.name.name.to_string(),
+                    namespace => format!("{}\\{}", namespace, entry.name.name),
+                };
+
+                constants.insert(
+                    key(NameTable::Constant, &name),
+                    (scope.clone(), &entry.value),
+                );
+            }
+        }
+        Node::Expression(expression) => {
+            if let Some(define) = define(expression) {
+                if let Some(value) = define.value {
+                    constants.insert(
+                        key(NameTable::Constant, &define.name),
+                        (scope.clone(), value),
+                    );
+                }
+            }
+        }
+        _ => {}
+    });
+
+    let context = Context {
+        path: &path,
+        constants,
+    };
+
+    let mut includes = vec![];
+    names::walk(program, |scope, node| {
+        if let Node::Expression(Expression::Include { span, kind, path }) = node {
+            // Paths built from `__DIR__` or `__FILE__` are already absolute.
+            let path = context.evaluate(scope, path, &[]).map(|included| {
+                let included = PathBuf::from(included);
+                match context.path.parent() {
+                    Some(directory) if included.is_relative() => {
+                        normalise(&directory.join(included))
+                    }
+                    _ => normalise(&included),
+                }
+            });
+
+            includes.push(Include {
+                kind: kind.clone(),
+                span: *span,
+                path,
+            });
+        }
+    });
+
+    includes
+}
+
+struct Context<'n, 'a> {
+    path: &'n Path,
+    // The constants defined by the file, along with the scope they're defined in.
+    constants: HashMap<(NameTable, String), (Scope, &'n Expression<'a>)>,
+}
+
+impl<'n, 'a> Context<'n, 'a> {
+    // Work out the string an expression evaluates to. `evaluating` are the
+    // constants whose values are being worked out, so that constants defined
+    // in terms of themselves don't recurse forever.
+    fn evaluate(
+        &self,
+        scope: &Scope,
+        expression: &Expression,
+        evaluating: &[(NameTable, String)],
+    ) -> Option<String> {
+        ensure_sufficient_stack(|| match expression {
+            Expression::LiteralString { value } | Expression::Nowdoc { value } => {
+                Some(value.to_string())
+            }
+            Expression::InterpolatedString { parts } | Expression::Heredoc { parts } => parts
+                .iter()
+                .map(|part| match part {
+                    StringPart::Const(value) => Some(value.to_string()),
+                    StringPart::Expr(_) => None,
+                })
+                .collect(),
+            Expression::Concat { left, right, .. } => Some(format!(
+                "{}{}",
+                self.evaluate(scope, left, evaluating)?,
+                self.evaluate(scope, right, evaluating)?
+            )),
+            Expression::Parenthesized { expr, .. } => self.evaluate(scope, expr, evaluating),
+            Expression::MagicConst {
+                constant: MagicConst::File,
+                ..
+            } => Some(self.path.to_string_lossy().into_owned()),
+            Expression::MagicConst {
+                constant: MagicConst::Directory,
+                ..
+            } => Some(dirname(&self.path.to_string_lossy(), 1)),
+            Expression::Call { target, args } => {
+                let Expression::Identifier(Identifier::SimpleIdentifier(function)) =
+                    target.as_ref()
+                else {
+                    return None;
+                };
+
+                if !function
+                    .name
+                    .to_string()
+                    .trim_start_matches('\\')
+                    .eq_ignore_ascii_case("dirname")
+                    || args.iter().any(|arg| arg.name.is_some() || arg.unpack)
+                {
+                    return None;
+                }
+
+                let levels = match args.get(1).map(|arg| &arg.value) {
+                    None => 1,
+                    Some(Expression::LiteralInteger { i }) => i.to_string().parse().ok()?,
+                    Some(_) => return None,
+                };
+
+                Some(dirname(
+                    &self.evaluate(scope, &args.first()?.value, evaluating)?,
+                    levels,
+                ))
+            }
+            Expression::Identifier(Identifier::SimpleIdentifier(name)) => {
+                let name = name.name.to_string();
+                if name.trim_start_matches('\\') == "DIRECTORY_SEPARATOR" {
+                    return Some("/".to_string());
+                }
+
+                let candidates = std::iter::once(scope.resolve(NameTable::Constant, &name))
+                    .chain(scope.fallback(NameTable::Constant, &name));
+
+                for candidate in candidates {
+                    let key = key(NameTable::Constant, &candidate);
+                    if evaluating.contains(&key) {
+                        return None;
+                    }
+
+                    if let Some((scope, value)) = self.constants.get(&key) {
+                        let mut evaluating = evaluating.to_vec();
+                        evaluating.push(key);
+
+                        return self.evaluate(scope, value, &evaluating);
+                    }
+                }
+
+                None
+            }
+            _ => None,
+        })
+    }
+}
+
+// PHP's `dirname()` for `/` separated paths.
+fn dirname(path: &str, levels: usize) -> String {
+    let mut path = Path::new(path);
+    for _ in 0..levels {
+        path = match path.parent() {
+            Some(parent) if parent.as_os_str().is_empty() => Path::new("."),
+            Some(parent) => parent,
+            None => break,
+        };
+    }
+
+    path.to_string_lossy().into_owned()
+}
+
+// Make a path absolute, using the current directory, and normalise it.
+fn absolute(path: &Path) -> PathBuf {
+    if path.is_relative() {
+        if let Ok(directory) = std::env::current_dir() {
+            return normalise(&directory.join(path));
+        }
+    }
+
+    normalise(path)
+}
+
+// Remove `.` and `..` from a path, without looking at the filesystem.
+fn normalise(path: &Path) -> PathBuf {
+    let mut normalised = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if normalised.file_name().is_some() => {
+                normalised.pop();
+            }
+            component => normalised.push(component),
+        }
+    }
+
+    normalised
+}
+
+/// The includes of every file in a project, kept up to date one file at a time.
+///
+/// Files are identified by their absolute path, see `includes`. Relative paths
+/// passed to any of the methods are made absolute the same way.
+#[derive(Debug, Clone, Default)]
+pub struct IncludeGraph {
+    files: BTreeMap<PathBuf, Vec<Include>>,
+}
+
+impl IncludeGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Find the includes of every file of a project that could be parsed.
+    pub fn from_project(result: &ProjectResult) -> Self {
+        let mut graph = Self::new();
+        for file in &result.files {
+            if let Ok(program) = &file.result {
+                graph.update(&file.path, includes(&file.path, program));
+            }
+        }
+
+        graph
+    }
+
+    /// Replace the includes of a file.
+    pub fn update(&mut self, path: impl Into<PathBuf>, includes: Vec<Include>) {
+        self.files.insert(absolute(&path.into()), includes);
+    }
+
+    /// Forget the includes of a file, e.g. when it's deleted.
+    pub fn remove(&mut self, path: &Path) -> Option<Vec<Include>> {
+        self.files.remove(&absolute(path))
+    }
+
+    /// The includes of a file, in the order they appear.
+    pub fn file(&self, path: &Path) -> Option<&[Include]> {
+        self.files.get(&absolute(path)).map(Vec::as_slice)
+    }
+
+    pub fn files(&self) -> impl Iterator<Item = (&Path, &[Include])> {
+        self.files
+            .iter()
+            .map(|(path, includes)| (path.as_path(), includes.as_slice()))
+    }
+
+    /// The files that a file includes directly, in the order they're included.
+    pub fn dependencies(&self, path: &Path) -> Vec<&Path> {
+        let mut dependencies: Vec<&Path> = vec![];
+        for include in self.file(path).unwrap_or_default() {
+            if let Some(path) = &include.path {
+                if !dependencies.contains(&path.as_path()) {
+                    dependencies.push(path);
+                }
+            }
+        }
+
+        dependencies
+    }
+
+    /// The files that include a file directly, sorted by their path.
+    pub fn dependents(&self, path: &Path) -> Vec<&Path> {
+        let path = absolute(path);
+
+        self.files()
+            .filter(|(_, includes)| {
+                includes
+                    .iter()
+                    .any(|include| include.path.as_deref() == Some(path.as_path()))
+            })
+            .map(|(path, _)| path)
+            .collect()
+    }
+
+    /// Every file that a file ends up including, directly or through the
+    /// files it includes, in breadth first order. The file itself isn't
+    /// included, even when it's part of a cycle.
+    pub fn transitive_dependencies(&self, path: &Path) -> Vec<&Path> {
+        let path = absolute(path);
+
+        let mut found = vec![];
+        let mut seen = HashSet::from([path.as_path()]);
+        let mut queue = VecDeque::from([path.as_path()]);
+
+        while let Some(path) = queue.pop_front() {
+            for dependency in self.dependencies(path) {
+                if seen.insert(dependency) {
+                    found.push(dependency);
+                    queue.push_back(dependency);
+                }
+            }
+        }
+
+        found
+    }
+
+    /// Report includes whose path can't be worked out, or whose file doesn't
+    /// exist, along with files that end up including themselves.
+    ///
+    /// Each cycle is reported once, on the first of its files by path.
+    /// Included files that aren't part of the graph are looked for on disk.
+    pub fn diagnostics(&self) -> Vec<(&Path, Diagnostic)> {
+        let mut diagnostics = vec![];
+
+        let cycles = self
+            .components()
+            .into_iter()
+            .filter_map(|component| {
+                let start = *component.iter().min()?;
+                let cycle = self.cycle(start, &component)?;
+
+                Some((start, cycle))
+            })
+            .collect::<HashMap<_, _>>();
+
+        for (path, includes) in self.files() {
+            if let Some(cycle) = cycles.get(path) {
+                let names = std::iter::once(path)
+                    .chain(cycle.iter().filter_map(|include| include.path.as_deref()))
+                    .map(|path| format!("`{}`", path.display()))
+                    .collect::<Vec<_>>();
+
+                diagnostics.push((
+                    path,
+                    Diagnostic::warning("E0062", "file includes itself", cycle[0].span)
+                        .with_note(format!("the cycle is {}", names.join(" -> "))),
+                ));
+            }
+
+            for include in includes {
+                let diagnostic = match &include.path {
+                    None => Diagnostic::warning(
+                        "E0060",
+                        format!(
+                            "the path of this {} can't be determined statically",
+                            keyword(&include.kind)
+                        ),
+                        include.span,
+                    ),
+                    Some(included) if !self.files.contains_key(included) && !included.is_file() => {
+                        let message =
+                            format!("included file `{}` does not exist", included.display());
+                        match include.kind {
+                            IncludeKind::Require | IncludeKind::RequireOnce => {
+                                Diagnostic::error("E0061", message, include.span)
+                            }
+                            IncludeKind::Include | IncludeKind::IncludeOnce => {
+                                Diagnostic::warning("E0061", message, include.span)
+                            }
+                        }
+                    }
+                    Some(_) => continue,
+                };
+
+                diagnostics.push((path, diagnostic));
+            }
+        }
+
+        diagnostics
+    }
+
+    // The strongly connected components of the graph, found with Tarjan's
+    // algorithm. A file is part of a cycle when its component has more than one
+    // file, or it includes itself directly.
+    fn components(&self) -> Vec<HashSet<&Path>> {
+        let paths = self.files.keys().map(PathBuf::as_path).collect::<Vec<_>>();
+        let ids = paths
+            .iter()
+            .enumerate()
+            .map(|(id, path)| (*path, id))
+            .collect::<HashMap<_, _>>();
+        let edges = self
+            .files
+            .values()
+            .map(|includes| {
+                includes
+                    .iter()
+                    .filter_map(|include| ids.get(include.path.as_deref()?).copied())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let mut order = vec![None; paths.len()];
+        let mut low = vec![0; paths.len()];
+        let mut on_stack = vec![false; paths.len()];
+        let mut stack = vec![];
+        let mut components = vec![];
+        let mut next = 0;
+
+        for root in 0..paths.len() {
+            if order[root].is_some() {
+                continue;
+            }
+
+            // The files being visited, with how many of their edges have been followed.
+            let mut visiting = vec![(root, 0)];
+            order[root] = Some(next);
+            low[root] = next;
+            next += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            while let Some(&(file, edge)) = visiting.last() {
+                if let Some(&included) = edges[file].get(edge) {
+                    visiting.last_mut().unwrap().1 += 1;
+
+                    match order[included] {
+                        None => {
+                            order[included] = Some(next);
+                            low[included] = next;
+                            next += 1;
+                            stack.push(included);
+                            on_stack[included] = true;
+                            visiting.push((included, 0));
+                        }
+                        Some(index) if on_stack[included] => {
+                            low[file] = low[file].min(index);
+                        }
+                        Some(_) => {}
+                    }
+
+                    continue;
+                }
+
+                visiting.pop();
+                if let Some(&(parent, _)) = visiting.last() {
+                    low[parent] = low[parent].min(low[file]);
+                }
+
+                if Some(low[file]) == order[file] {
+                    let mut component = HashSet::new();
+                    while let Some(member) = stack.pop() {
+                        on_stack[member] = false;
+                        component.insert(paths[member]);
+                        if member == file {
+                            break;
+                        }
+                    }
+
+                    if component.len() > 1 || edges[file].contains(&file) {
+                        components.push(component);
+                    }
+                }
+            }
+        }
+
+        components
+    }
+
+    // The shortest chain of includes that leads from a file back to itself,
+    // through the files of its component.
+    fn cycle<'g>(
+        &'g self,
+        start: &'g Path,
+        component: &HashSet<&Path>,
+    ) -> Option<Vec<&'g Include>> {
+        let mut previous: HashMap<&Path, (&Path, &Include)> = HashMap::new();
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(path) = queue.pop_front() {
+            for include in self.file(path).unwrap_or_default() {
+                let Some(next) = include.path.as_deref() else {
+                    continue;
+                };
+
+                if previous.contains_key(next) || !component.contains(next) {
+                    continue;
+                }
+
+                previous.insert(next, (path, include));
+
+                if next == start {
+                    let mut cycle = vec![];
+                    let mut current = next;
+                    loop {
+                        let (from, include) = previous[current];
+                        cycle.push(include);
+                        if from == start {
+                            break;
+                        }
+                        current = from;
+                    }
+                    cycle.reverse();
+
+                    return Some(cycle);
+                }
+
+                queue.push_back(next);
+            }
+        }
+
+        None
+    }
+}
+
+fn keyword(kind: &IncludeKind) -> &'static str {
+    match kind {
+        IncludeKind::Include => "include",
+        IncludeKind::IncludeOnce => "include_once",
+        IncludeKind::Require => "require",
+        IncludeKind::RequireOnce => "require_once",
+    }
+}
@@ -0,0 +1,357 @@
+//! An index of the classes, functions and constants declared across the files
+//! of a project, keyed by their fully qualified names.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use crate::lexer::token::Span;
+use crate::parser::ast::identifiers::Identifier;
+use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::node::Node;
+use crate::parser::ast::Expression;
+use crate::parser::ast::Program;
+use crate::parser::ast::Statement;
+use crate::project::ProjectResult;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeclarationKind {
+    Class,
+    Interface,
+    Trait,
+    Enum,
+    Function,
+    Constant,
+}
+
+impl DeclarationKind {
+    /// Classes, interfaces, traits and enums share one table of names in PHP,
+    /// while functions and constants each have their own.
+    pub fn table(&self) -> NameTable {
+        match self {
+            Self::Class | Self::Interface | Self::Trait | Self::Enum => NameTable::Class,
+            Self::Function => NameTable::Function,
+            Self::Constant => NameTable::Constant,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NameTable {
+    Class,
+    Function,
+    Constant,
+}
+
+/// A class-like, function or constant declared by a file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Declaration {
+    pub kind: DeclarationKind,
+    /// The fully qualified name, without a leading `\`, e.g. `App\Models\User`.
+    pub name: String,
+    /// The whole declaration, see `Node::range`. For `define()` this is the call.
+    pub span: (Span, Span),
+    /// Where the name is written. String literals don't record where they are,
+    /// so for `define()` this is where `define` is.
+    pub name_span: Span,
+}
+
+/// Find every class, interface, trait, enum, function and constant declared by a program.
+///
+/// Constants are declared either with `const` or by calling `define()` with a
+/// literal name. Declarations nested in blocks or function bodies, which PHP
+/// only makes when that code runs, are included as well.
+pub fn declarations(program: &Program) -> Vec<Declaration> {
+    let mut declarations = vec![];
+
+    for statement in program {
+        match statement {
+            Statement::Namespace { name, body }
+            | Statement::BracedNamespace {
+                name: Some(name),
+                body,
+            } => {
+                let namespace = name.name.to_string();
+                for statement in body {
+                    collect(Node::Statement(statement), &namespace, &mut declarations);
+                }
+            }
+            Statement::BracedNamespace { name: None, body } => {
+                for statement in body {
+                    collect(Node::Statement(statement), "", &mut declarations);
+                }
+            }
+            _ => collect(Node::Statement(statement), "", &mut declarations),
+        }
+    }
+
+    declarations
+}
+
+fn collect(node: Node, namespace: &str, declarations: &mut Vec<Declaration>) {
+    let mut stack = vec![node];
+
+    while let Some(node) = stack.pop() {
+        let declaration = match node {
+            Node::Statement(statement) => match statement {
+                Statement::Class(class) => Some((DeclarationKind::Class, &class.name)),
+                Statement::Interface(interface) => {
+                    Some((DeclarationKind::Interface, &interface.name))
+                }
+                Statement::Trait(r#trait) => Some((DeclarationKind::Trait, &r#trait.name)),
+                Statement::UnitEnum(r#enum) => Some((DeclarationKind::Enum, &r#enum.name)),
+                Statement::BackedEnum(r#enum) => Some((DeclarationKind::Enum, &r#enum.name)),
+                Statement::Function(function) => Some((DeclarationKind::Function, &function.name)),
+                Statement::Constant(constant) => {
+                    for entry in &constant.entries {
+                        declarations.push(declaration(
+                            DeclarationKind::Constant,
+                            qualify(namespace, &entry.name),
+                            Node::ConstantEntry(entry),
+                            entry.name.span,
+                        ));
+                    }
+
+                    None
+                }
+                _ => None,
+            },
+            Node::Expression(expression) => {
+                if let Some(define) = define(expression) {
+                    declarations.push(declaration(
+                        DeclarationKind::Constant,
+                        define.name,
+                        node,
+                        define.function.span,
+                    ));
+                }
+
+                None
+            }
+            _ => None,
+        };
+
+        if let Some((kind, name)) = declaration {
+            declarations.push(self::declaration(
+                kind,
+                qualify(namespace, name),
+                node,
+                name.span,
+            ));
+        }
+
+        let mut children = node.children();
+        children.reverse();
+        stack.extend(children);
+    }
+}
+
+fn declaration(kind: DeclarationKind, name: String, node: Node, name_span: Span) -> Declaration {
+    Declaration {
+        kind,
+        name,
+        span: node.range().unwrap_or((name_span, name_span)),
+        name_span,
+    }
+}
+
+fn qualify(namespace: &str, name: &SimpleIdentifier) -> String {
+    match namespace {
+        "" => name.name.to_string(),
+        _ => format!("{}\\{}", namespace, name.name),
+    }
+}
+
+/// A call to `define()` with a literal name, which is always fully qualified.
+pub(crate) struct Define<'n, 'a> {
+    pub(crate) name: String,
+    pub(crate) function: &'n SimpleIdentifier<'a>,
+    pub(crate) value: Option<&'n Expression<'a>>,
+}
+
+pub(crate) fn define<'n, 'a>(expression: &'n Expression<'a>) -> Option<Define<'n, 'a>> {
+    let Expression::Call { target, args } = expression else {
+        return None;
+    };
+
+    let Expression::Identifier(Identifier::SimpleIdentifier(function)) = target.as_ref() else {
+        return None;
+    };
+
+    let function_name = function.name.to_string();
+    if !function_name
+        .trim_start_matches('\\')
+        .eq_ignore_ascii_case("define")
+    {
+        return None;
+    }
+
+    let argument = |name: &str, position: usize| {
+        args.iter()
+            .find(|arg| {
+                arg.name
+                    .as_ref()
+                    .is_some_and(|arg| arg.name.bytes == name.as_bytes())
+            })
+            .or_else(|| {
+                args.get(position)
+                    .filter(|arg| arg.name.is_none() && !arg.unpack)
+            })
+            .map(|arg| &arg.value)
+    };
+
+    match argument("constant_name", 0)? {
+        Expression::LiteralString { value } => Some(Define {
+            name: value.to_string().trim_start_matches('\\').to_string(),
+            function,
+            value: argument("value", 1),
+        }),
+        _ => None,
+    }
+}
+
+/// The declarations of every file in a project, kept up to date one file at a time.
+///
+/// Lookups follow PHP's rules for names: class-like and function names are case
+/// insensitive, and so are the namespaces of constants, but not their own names.
+#[derive(Debug, Clone, Default)]
+pub struct DeclarationIndex {
+    files: BTreeMap<PathBuf, Vec<Declaration>>,
+    names: HashMap<(NameTable, String), Vec<PathBuf>>,
+}
+
+impl DeclarationIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Index the declarations of every file of a project that could be parsed.
+    pub fn from_project(result: &ProjectResult) -> Self {
+        let mut index = Self::new();
+        for file in &result.files {
+            if let Ok(program) = &file.result {
+                index.update(&file.path, declarations(program));
+            }
+        }
+
+        index
+    }
+
+    /// Replace the declarations of a file.
+    pub fn update(&mut self, path: impl Into<PathBuf>, declarations: Vec<Declaration>) {
+        let path = path.into();
+        self.remove(&path);
+
+        for declaration in &declarations {
+            let paths = self
+                .names
+                .entry(key(declaration.kind.table(), &declaration.name))
+                .or_default();
+
+            if !paths.contains(&path) {
+                paths.push(path.clone());
+                paths.sort();
+            }
+        }
+
+        self.files.insert(path, declarations);
+    }
+
+    /// Forget the declarations of a file, e.g. when it's deleted.
+    pub fn remove(&mut self, path: &Path) -> Option<Vec<Declaration>> {
+        let declarations = self.files.remove(path)?;
+
+        for declaration in &declarations {
+            let key = key(declaration.kind.table(), &declaration.name);
+            if let Some(paths) = self.names.get_mut(&key) {
+                paths.retain(|other| other != path);
+
+                if paths.is_empty() {
+                    self.names.remove(&key);
+                }
+            }
+        }
+
+        Some(declarations)
+    }
+
+    /// The declarations of a file, in the order they appear.
+    pub fn file(&self, path: &Path) -> Option<&[Declaration]> {
+        self.files.get(path).map(Vec::as_slice)
+    }
+
+    pub fn files(&self) -> impl Iterator<Item = (&Path, &[Declaration])> {
+        self.files
+            .iter()
+            .map(|(path, declarations)| (path.as_path(), declarations.as_slice()))
+    }
+
+    /// Find the declarations of a fully qualified name, with or without a leading `\`.
+    ///
+    /// There's usually at most one, but a name can be declared more than once,
+    /// e.g. in different branches of an `if` or by mistake.
+    pub fn get(&self, table: NameTable, name: &str) -> Vec<(&Path, &Declaration)> {
+        let key = key(table, name.trim_start_matches('\\'));
+
+        let Some(paths) = self.names.get(&key) else {
+            return vec![];
+        };
+
+        paths
+            .iter()
+            .flat_map(|path| {
+                self.files[path]
+                    .iter()
+                    .filter(|declaration| {
+                        declaration.kind.table() == table
+                            && self::key(table, &declaration.name) == key
+                    })
+                    .map(move |declaration| (path.as_path(), declaration))
+            })
+            .collect()
+    }
+
+    pub fn class(&self, name: &str) -> Vec<(&Path, &Declaration)> {
+        self.get(NameTable::Class, name)
+    }
+
+    pub fn function(&self, name: &str) -> Vec<(&Path, &Declaration)> {
+        self.get(NameTable::Function, name)
+    }
+
+    pub fn constant(&self, name: &str) -> Vec<(&Path, &Declaration)> {
+        self.get(NameTable::Constant, name)
+    }
+
+    /// The names that are declared more than once, with all of their declarations,
+    /// sorted by name.
+    pub fn duplicates(&self) -> Vec<Vec<(&Path, &Declaration)>> {
+        let mut keys = self.names.keys().collect::<Vec<_>>();
+        keys.sort();
+
+        keys.into_iter()
+            .map(|(table, name)| self.get(*table, name))
+            .filter(|declarations| declarations.len() > 1)
+            .collect()
+    }
+}
+
+// Normalise a name to how PHP compares it.
+pub(crate) fn key(table: NameTable, name: &str) -> (NameTable, String) {
+    let name = match (table, name.rfind('\\')) {
+        (NameTable::Constant, Some(separator)) => format!(
+            "{}{}",
+            name[..separator].to_ascii_lowercase(),
+            &name[separator..]
+        ),
+        (NameTable::Constant, None) => name.to_string(),
+        _ => name.to_ascii_lowercase(),
+    };
+
+    (table, name)
+}
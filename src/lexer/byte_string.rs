@@ -1,30 +1,63 @@
-use serde::{Deserialize, Serialize};
+use serde::de::{Error, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
 use std::cmp::{Eq, PartialEq};
 use std::ops::{Deref, DerefMut};
 use std::str::from_utf8;
 
-/// A wrapper for Vec<u8> that provides a human-readable Debug impl and
+/// A wrapper for a byte string that provides a human-readable Debug impl and
 /// a few other conveniences.
 ///
 /// The Trunk lexer and parser work mainly with byte strings because
 /// valid PHP code is not required to be valid UTF-8.
-#[derive(PartialEq, Eq, PartialOrd, Clone, Serialize, Deserialize)]
-pub struct ByteString {
-    pub bytes: Vec<u8>,
-    pub length: usize,
+///
+/// Byte strings borrow from the source code whenever the bytes appear in it
+/// verbatim, and only own their bytes when they have to be built up, e.g. when
+/// a string literal contains escape sequences. Use `into_owned` to detach a
+/// byte string from the source code.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+pub struct ByteString<'a> {
+    pub bytes: Cow<'a, [u8]>,
 }
 
-impl ByteString {
+impl<'a> ByteString<'a> {
     pub fn new(bytes: Vec<u8>) -> Self {
-        let length = bytes.len();
+        ByteString {
+            bytes: Cow::Owned(bytes),
+        }
+    }
+
+    pub const fn borrowed(bytes: &'a [u8]) -> Self {
+        ByteString {
+            bytes: Cow::Borrowed(bytes),
+        }
+    }
+
+    /// Whether the bytes are borrowed from the source code.
+    pub const fn is_borrowed(&self) -> bool {
+        matches!(self.bytes, Cow::Borrowed(_))
+    }
 
-        ByteString { bytes, length }
+    /// Remove the first `length` bytes, without copying borrowed bytes.
+    pub fn remove_prefix(&mut self, length: usize) {
+        match &mut self.bytes {
+            Cow::Borrowed(bytes) => *bytes = &bytes[length..],
+            Cow::Owned(bytes) => {
+                bytes.drain(..length);
+            }
+        }
+    }
+
+    pub fn into_owned(self) -> ByteString<'static> {
+        ByteString {
+            bytes: Cow::Owned(self.bytes.into_owned()),
+        }
     }
 }
 
-impl std::fmt::Display for ByteString {
+impl<'a> std::fmt::Display for ByteString<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for &b in &self.bytes {
+        for &b in self.bytes.iter() {
             match b {
                 0 => write!(f, "\\0")?,
                 b'\n' | b'\r' | b'\t' => write!(f, "{}", b.escape_ascii())?,
@@ -37,10 +70,10 @@ impl std::fmt::Display for ByteString {
     }
 }
 
-impl std::fmt::Debug for ByteString {
+impl<'a> std::fmt::Debug for ByteString<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "\"")?;
-        for &b in &self.bytes {
+        for &b in self.bytes.iter() {
             match b {
                 0 => write!(f, "\\0")?,
                 b'\n' | b'\r' | b'\t' => write!(f, "{}", b.escape_ascii())?,
@@ -53,65 +86,111 @@ impl std::fmt::Debug for ByteString {
     }
 }
 
-impl<const N: usize> PartialEq<&[u8; N]> for ByteString {
+impl<'a, const N: usize> PartialEq<&[u8; N]> for ByteString<'a> {
     fn eq(&self, other: &&[u8; N]) -> bool {
-        &self.bytes == other
+        self.bytes.as_ref() == other.as_slice()
     }
 }
 
-impl<const N: usize> PartialEq<&[u8; N]> for &ByteString {
+impl<'a, const N: usize> PartialEq<&[u8; N]> for &ByteString<'a> {
     fn eq(&self, other: &&[u8; N]) -> bool {
-        &self.bytes == other
+        self.bytes.as_ref() == other.as_slice()
     }
 }
 
-impl From<Vec<u8>> for ByteString {
+// Byte strings are serialized as strings when they're valid UTF-8, which
+// they almost always are, and as raw bytes otherwise.
+impl<'a> Serialize for ByteString<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match from_utf8(&self.bytes) {
+            Ok(string) => serializer.serialize_str(string),
+            Err(_) => serializer.serialize_bytes(&self.bytes),
+        }
+    }
+}
+
+impl<'de, 'a> Deserialize<'de> for ByteString<'a> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ByteStringVisitor;
+
+        impl<'de> Visitor<'de> for ByteStringVisitor {
+            type Value = Vec<u8>;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "a string or a sequence of bytes")
+            }
+
+            fn visit_str<E: Error>(self, value: &str) -> Result<Self::Value, E> {
+                Ok(value.as_bytes().to_vec())
+            }
+
+            fn visit_bytes<E: Error>(self, value: &[u8]) -> Result<Self::Value, E> {
+                Ok(value.to_vec())
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut bytes = vec![];
+                while let Some(byte) = seq.next_element()? {
+                    bytes.push(byte);
+                }
+
+                Ok(bytes)
+            }
+        }
+
+        deserializer
+            .deserialize_any(ByteStringVisitor)
+            .map(ByteString::new)
+    }
+}
+
+impl<'a> From<Vec<u8>> for ByteString<'a> {
     fn from(bytes: Vec<u8>) -> Self {
         ByteString::new(bytes)
     }
 }
 
-impl From<&[u8]> for ByteString {
-    fn from(bytes: &[u8]) -> Self {
-        ByteString::new(bytes.to_vec())
+impl<'a> From<&'a [u8]> for ByteString<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        ByteString::borrowed(bytes)
     }
 }
 
-impl<const N: usize> From<&[u8; N]> for ByteString {
-    fn from(bytes: &[u8; N]) -> Self {
-        ByteString::new(bytes.to_vec())
+impl<'a, const N: usize> From<&'a [u8; N]> for ByteString<'a> {
+    fn from(bytes: &'a [u8; N]) -> Self {
+        ByteString::borrowed(bytes)
     }
 }
 
-impl From<&str> for ByteString {
-    fn from(bytes: &str) -> Self {
-        ByteString::new(bytes.as_bytes().to_vec())
+impl<'a> From<&'a str> for ByteString<'a> {
+    fn from(bytes: &'a str) -> Self {
+        ByteString::borrowed(bytes.as_bytes())
     }
 }
 
-impl From<String> for ByteString {
+impl<'a> From<String> for ByteString<'a> {
     fn from(bytes: String) -> Self {
         ByteString::new(bytes.into_bytes())
     }
 }
 
-impl From<ByteString> for String {
-    fn from(bytes: ByteString) -> Self {
+impl<'a> From<ByteString<'a>> for String {
+    fn from(bytes: ByteString<'a>) -> Self {
         String::from(from_utf8(&bytes.bytes).unwrap())
     }
 }
 
-impl Deref for ByteString {
-    type Target = Vec<u8>;
+impl<'a> Deref for ByteString<'a> {
+    type Target = [u8];
 
-    fn deref(&self) -> &Vec<u8> {
+    fn deref(&self) -> &[u8] {
         &self.bytes
     }
 }
 
-impl DerefMut for ByteString {
+impl<'a> DerefMut for ByteString<'a> {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.bytes
+        self.bytes.to_mut()
     }
 }
 
@@ -131,4 +210,17 @@ mod tests {
             r#""\x01\x10\x7f\xff""#
         );
     }
+
+    #[test]
+    fn test_byte_string_into_owned() {
+        let source = b"foo".to_vec();
+        let borrowed = ByteString::from(source.as_slice());
+        assert!(borrowed.is_borrowed());
+
+        let owned = borrowed.into_owned();
+        drop(source);
+
+        assert!(!owned.is_borrowed());
+        assert_eq!(owned, b"foo");
+    }
 }
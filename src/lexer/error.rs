@@ -1,79 +1,122 @@
 use std::fmt::Display;
 
+use serde::Serialize;
+
 use crate::lexer::token::Span;
 
 pub type SyntaxResult<T> = Result<T, SyntaxError>;
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Eq, PartialEq, Clone, Serialize)]
 pub enum SyntaxError {
-    UnexpectedEndOfFile(Span),
-    UnexpectedError(Span),
-    UnexpectedCharacter(u8, Span),
-    InvalidHaltCompiler(Span),
-    InvalidOctalEscape(Span),
-    InvalidOctalLiteral(Span),
-    InvalidUnicodeEscape(Span),
-    UnpredictableState(Span),
-    InvalidDocIndentation(Span),
-    InvalidDocBodyIndentationLevel(usize, Span),
-    UnrecognisedToken(u8, Span),
+    UnexpectedEndOfFile { span: Span },
+    UnexpectedError { span: Span },
+    UnexpectedCharacter { character: u8, span: Span },
+    InvalidHaltCompiler { span: Span },
+    InvalidOctalEscape { span: Span },
+    InvalidOctalLiteral { span: Span },
+    InvalidUnicodeEscape { span: Span },
+    UnpredictableState { span: Span },
+    InvalidDocIndentation { span: Span },
+    InvalidDocBodyIndentationLevel { expected: usize, span: Span },
+    UnrecognisedToken { token: u8, span: Span },
 }
 
+impl SyntaxError {
+    /// A stable, machine-readable code identifying the kind of error.
+    ///
+    /// Codes are never reused, so they can be relied upon by tooling
+    /// even when the error message changes.
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Self::UnexpectedEndOfFile { .. } => "E0001",
+            Self::UnexpectedError { .. } => "E0002",
+            Self::UnexpectedCharacter { .. } => "E0003",
+            Self::InvalidHaltCompiler { .. } => "E0004",
+            Self::InvalidOctalEscape { .. } => "E0005",
+            Self::InvalidOctalLiteral { .. } => "E0006",
+            Self::InvalidUnicodeEscape { .. } => "E0007",
+            Self::UnpredictableState { .. } => "E0008",
+            Self::InvalidDocIndentation { .. } => "E0009",
+            Self::InvalidDocBodyIndentationLevel { .. } => "E0010",
+            Self::UnrecognisedToken { .. } => "E0011",
+        }
+    }
+
+    /// The location in the source code where the error occurred.
+    pub const fn span(&self) -> Span {
+        match self {
+            Self::UnexpectedEndOfFile { span }
+            | Self::UnexpectedError { span }
+            | Self::UnexpectedCharacter { span, .. }
+            | Self::InvalidHaltCompiler { span }
+            | Self::InvalidOctalEscape { span }
+            | Self::InvalidOctalLiteral { span }
+            | Self::InvalidUnicodeEscape { span }
+            | Self::UnpredictableState { span }
+            | Self::InvalidDocIndentation { span }
+            | Self::InvalidDocBodyIndentationLevel { span, .. }
+            | Self::UnrecognisedToken { span, .. } => *span,
+        }
+    }
+}
+
+impl std::error::Error for SyntaxError {}
+
 impl Display for SyntaxError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::UnexpectedEndOfFile(span) => write!(
+            Self::UnexpectedEndOfFile { span } => write!(
                 f,
                 "Syntax Error: unexpected end of file on line {} column {}",
                 span.0, span.1
             ),
-            Self::UnexpectedError(span) => write!(
+            Self::UnexpectedError { span } => write!(
                 f,
                 "Syntax Error: unexpected error on line {} column {}",
                 span.0, span.1
             ),
-            Self::UnexpectedCharacter(char, span) => write!(
+            Self::UnexpectedCharacter { character, span } => write!(
                 f,
                 "Syntax Error: unexpected character `{:?}` on line {} column {}",
-                *char as char, span.0, span.1
+                *character as char, span.0, span.1
             ),
-            Self::InvalidHaltCompiler(span) => write!(
+            Self::InvalidHaltCompiler { span } => write!(
                 f,
                 "Syntax Error: invalid halt compiler on line {} column {}",
                 span.0, span.1
             ),
-            Self::InvalidOctalEscape(span) => write!(
+            Self::InvalidOctalEscape { span } => write!(
                 f,
                 "Syntax Error: invalid octal escape on line {} column {}",
                 span.0, span.1
             ),
-            Self::InvalidOctalLiteral(span) => write!(
+            Self::InvalidOctalLiteral { span } => write!(
                 f,
                 "Syntax Error: invalid octal literal on line {} column {}",
                 span.0, span.1
             ),
-            Self::InvalidUnicodeEscape(span) => write!(
+            Self::InvalidUnicodeEscape { span } => write!(
                 f,
                 "Syntax Error: invalid unicode escape on line {} column {}",
                 span.0, span.1
             ),
-            Self::UnpredictableState(span) => write!(
+            Self::UnpredictableState { span } => write!(
                 f,
                 "Syntax Error: Reached an unpredictable state on line {} column {}",
                 span.0, span.1
             ),
-            Self::InvalidDocIndentation(span) => write!(
+            Self::InvalidDocIndentation { span } => write!(
                 f,
                 "Syntax Error: Invalid indentation - cannot use tabs and spaces on line {}",
                 span.0
             ),
-            Self::InvalidDocBodyIndentationLevel(expected, span) => write!(
+            Self::InvalidDocBodyIndentationLevel { expected, span } => write!(
                 f,
                 "Syntax Error: Invalid body indentation level - expecting an indentation level of at least {} on line {}",
                 expected,
                 span.0
             ),
-            Self::UnrecognisedToken(token, span) => write!(
+            Self::UnrecognisedToken { token, span } => write!(
                 f,
                 "Syntax Error: Unrecognised token {} on line {} column {}",
                 token,
@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+use std::sync::atomic::AtomicU32;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::RwLock;
+
+static NEXT_INTERNER: AtomicU32 = AtomicU32::new(0);
+
+/// A handle to a name stored in an `Interner`.
+///
+/// Symbols are cheap to copy, compare and hash, which makes them a better fit
+/// than byte strings for keys in large, project-wide indexes. Symbols created
+/// by different interners are never equal, even for the same name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol {
+    interner: u32,
+    index: u32,
+}
+
+impl Symbol {
+    /// The position of the name in the interner that created this symbol.
+    pub const fn as_u32(self) -> u32 {
+        self.index
+    }
+
+    /// Whether both symbols were created by the same interner, in which case
+    /// they are equal exactly when the names they stand for are.
+    pub const fn is_comparable_to(self, other: Symbol) -> bool {
+        self.interner == other.interner
+    }
+}
+
+/// A thread-safe table of interned names.
+///
+/// Interning the same bytes twice always returns the same symbol, and each
+/// name is only stored once no matter how often it occurs. Names are interned
+/// exactly as they are written: PHP treats class and function names as
+/// case-insensitive, so callers that need that behaviour should lowercase
+/// names before interning them.
+#[derive(Debug)]
+pub struct Interner {
+    id: u32,
+    names: RwLock<Names>,
+}
+
+#[derive(Debug, Default)]
+struct Names {
+    symbols: HashMap<Arc<[u8]>, Symbol>,
+    names: Vec<Arc<[u8]>>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self {
+            id: NEXT_INTERNER.fetch_add(1, Ordering::Relaxed),
+            names: RwLock::default(),
+        }
+    }
+
+    /// Get the symbol for `name`, adding it to the table if it isn't there yet.
+    pub fn intern(&self, name: &[u8]) -> Symbol {
+        if let Some(symbol) = self.get(name) {
+            return symbol;
+        }
+
+        let mut names = self.names.write().unwrap();
+        // Another thread may have interned the name while we were waiting for the lock.
+        if let Some(symbol) = names.symbols.get(name) {
+            return *symbol;
+        }
+
+        let symbol = Symbol {
+            interner: self.id,
+            index: u32::try_from(names.names.len()).expect("too many interned names"),
+        };
+        let name: Arc<[u8]> = Arc::from(name);
+        names.names.push(name.clone());
+        names.symbols.insert(name, symbol);
+
+        symbol
+    }
+
+    /// Get the symbol for `name` without adding it to the table.
+    pub fn get(&self, name: &[u8]) -> Option<Symbol> {
+        self.names.read().unwrap().symbols.get(name).copied()
+    }
+
+    /// Get the name a symbol was created from.
+    ///
+    /// Returns `None` if the symbol was created by a different interner.
+    pub fn resolve(&self, symbol: Symbol) -> Option<Arc<[u8]>> {
+        if symbol.interner != self.id {
+            return None;
+        }
+
+        self.names
+            .read()
+            .unwrap()
+            .names
+            .get(symbol.index as usize)
+            .cloned()
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.read().unwrap().names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for Interner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
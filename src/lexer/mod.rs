@@ -1,14 +1,19 @@
 pub mod byte_string;
 pub mod error;
+pub mod interner;
 pub mod source;
 pub mod token;
 
 mod macros;
 mod state;
 
+use std::sync::Arc;
+
 use crate::lexer::byte_string::ByteString;
 use crate::lexer::error::SyntaxError;
 use crate::lexer::error::SyntaxResult;
+use crate::lexer::interner::Interner;
+use crate::lexer::interner::Symbol;
 use crate::lexer::source::Source;
 use crate::lexer::state::StackFrame;
 use crate::lexer::state::State;
@@ -22,83 +27,186 @@ use crate::ident_start;
 pub use self::state::DocStringKind;
 use self::token::DocStringIndentationKind;
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
-pub struct Lexer;
+/// An iterator over the tokens of a piece of source code, see `Lexer::stream`.
+#[derive(Debug)]
+pub struct TokenStream<'a> {
+    lexer: Lexer,
+    state: State<'a>,
+    // Tokens that have been lexed but not yet yielded, in reverse order.
+    tokens: Vec<Token<'a>>,
+    finished: bool,
+}
+
+impl<'a> Iterator for TokenStream<'a> {
+    type Item = SyntaxResult<Token<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(token) = self.tokens.pop() {
+                return Some(Ok(token));
+            }
+
+            if self.finished {
+                return None;
+            }
+
+            match self.lexer.step(&mut self.state, &mut self.tokens) {
+                Ok(more) => {
+                    self.finished = !more;
+                    self.tokens.reverse();
+
+                    if let Some(interner) = &self.lexer.interner {
+                        for token in self.tokens.iter_mut() {
+                            token.symbol = symbol(interner, &token.kind);
+                        }
+                    }
+                }
+                Err(error) => {
+                    self.finished = true;
+                    self.tokens.clear();
+
+                    return Some(Err(error));
+                }
+            }
+        }
+    }
+}
+
+impl<'a> std::iter::FusedIterator for TokenStream<'a> {}
+
+#[derive(Debug, Clone, Default)]
+pub struct Lexer {
+    scripting: bool,
+    interner: Option<Arc<Interner>>,
+}
 
 impl Lexer {
     pub const fn new() -> Self {
-        Self {}
+        Self {
+            scripting: false,
+            interner: None,
+        }
     }
 
-    pub fn tokenize<B: ?Sized + AsRef<[u8]>>(&self, input: &B) -> SyntaxResult<Vec<Token>> {
-        let mut state = State::new(Source::new(input.as_ref()));
-        let mut tokens = Vec::new();
-
-        while !state.source.eof() {
-            match state.frame()? {
-                // The "Initial" state is used to parse inline HTML. It is essentially a catch-all
-                // state that will build up a single token buffer until it encounters an open tag
-                // of some description.
-                StackFrame::Initial => self.initial(&mut state, &mut tokens)?,
-                // The scripting state is entered when an open tag is encountered in the source code.
-                // This tells the lexer to start analysing characters at PHP tokens instead of inline HTML.
-                StackFrame::Scripting => {
-                    self.skip_whitespace(&mut state);
-
-                    // If we have consumed whitespace and then reached the end of the file, we should break.
-                    if state.source.eof() {
-                        break;
-                    }
+    /// Start lexing in scripting mode, as if the input was preceded by an open tag.
+    ///
+    /// This is useful for code that is stored without an open tag, such as
+    /// `eval()`'d snippets or code kept in a database.
+    pub const fn in_scripting_mode(mut self) -> Self {
+        self.scripting = true;
 
-                    tokens.push(self.scripting(&mut state)?);
-                }
-                // The "Halted" state is entered when the `__halt_compiler` token is encountered.
-                // In this state, all the text that follows is no longer parsed as PHP as is collected
-                // into a single "InlineHtml" token (kind of cheating, oh well).
-                StackFrame::Halted => {
-                    tokens.push(Token {
-                        kind: TokenKind::InlineHtml(state.source.read_remaining().into()),
-                        span: state.source.span(),
-                    });
-                    break;
+        self
+    }
+
+    /// Intern identifiers, variables and keywords as they are lexed.
+    ///
+    /// Each of those tokens will carry the symbol for its name, so that the same
+    /// interner can be shared by every file in a project.
+    pub fn with_interner(mut self, interner: Arc<Interner>) -> Self {
+        self.interner = Some(interner);
+
+        self
+    }
+
+    pub fn tokenize<'a, B: ?Sized + AsRef<[u8]>>(
+        &self,
+        input: &'a B,
+    ) -> SyntaxResult<Vec<Token<'a>>> {
+        self.stream(input).collect()
+    }
+
+    /// Lex the input on demand, one token at a time.
+    ///
+    /// Unlike `tokenize`, the tokens are never all held in memory at once, which
+    /// makes this a better fit for very large files. Iteration stops after the
+    /// first error.
+    pub fn stream<'a, B: ?Sized + AsRef<[u8]>>(&self, input: &'a B) -> TokenStream<'a> {
+        let frame = if self.scripting {
+            StackFrame::Scripting
+        } else {
+            StackFrame::Initial
+        };
+
+        TokenStream {
+            lexer: self.clone(),
+            state: State::new(Source::new(input.as_ref()), frame),
+            tokens: Vec::new(),
+            finished: false,
+        }
+    }
+
+    // Lex the next chunk of the input, which may produce zero or more tokens.
+    //
+    // Returns `false` once the end of the input has been reached.
+    fn step<'a>(&self, state: &mut State<'a>, tokens: &mut Vec<Token<'a>>) -> SyntaxResult<bool> {
+        if state.source.eof() {
+            return Ok(false);
+        }
+
+        match state.frame()? {
+            // The "Initial" state is used to parse inline HTML. It is essentially a catch-all
+            // state that will build up a single token buffer until it encounters an open tag
+            // of some description.
+            StackFrame::Initial => self.initial(state, tokens)?,
+            // The scripting state is entered when an open tag is encountered in the source code.
+            // This tells the lexer to start analysing characters at PHP tokens instead of inline HTML.
+            StackFrame::Scripting => {
+                self.skip_whitespace(state);
+
+                // If we have consumed whitespace and then reached the end of the file, we should break.
+                if state.source.eof() {
+                    return Ok(false);
                 }
-                // The double quote state is entered when inside a double-quoted string that
-                // contains variables.
-                StackFrame::DoubleQuote => self.double_quote(&mut state, &mut tokens)?,
-                // The shell exec state is entered when inside of a execution string (`).
-                StackFrame::ShellExec => self.shell_exec(&mut state, &mut tokens)?,
-                // The doc string state is entered when tokenizing heredocs and nowdocs.
-                StackFrame::DocString(kind, label, ..) => {
-                    let kind = *kind;
-                    let label = label.clone();
-
-                    self.docstring(&mut state, &mut tokens, kind, label)?;
-                }
-                // LookingForProperty is entered inside double quotes,
-                // backticks, or a heredoc, expecting a variable name.
-                // If one isn't found, it switches to scripting.
-                StackFrame::LookingForVarname => {
-                    if let Some(token) = self.looking_for_varname(&mut state)? {
-                        tokens.push(token);
-                    }
+
+                tokens.push(self.scripting(state)?);
+            }
+            // The "Halted" state is entered when the `__halt_compiler` token is encountered.
+            // In this state, all the text that follows is no longer parsed as PHP as is collected
+            // into a single "InlineHtml" token (kind of cheating, oh well).
+            StackFrame::Halted => {
+                tokens.push(Token {
+                    kind: TokenKind::InlineHtml(state.source.read_remaining().into()),
+                    span: state.source.span(),
+                    symbol: None,
+                });
+                return Ok(false);
+            }
+            // The double quote state is entered when inside a double-quoted string that
+            // contains variables.
+            StackFrame::DoubleQuote => self.double_quote(state, tokens)?,
+            // The shell exec state is entered when inside of a execution string (`).
+            StackFrame::ShellExec => self.shell_exec(state, tokens)?,
+            // The doc string state is entered when tokenizing heredocs and nowdocs.
+            StackFrame::DocString(kind, label) => {
+                let kind = *kind;
+                let label = label.clone();
+
+                self.docstring(state, tokens, kind, label)?;
+            }
+            // LookingForProperty is entered inside double quotes,
+            // backticks, or a heredoc, expecting a variable name.
+            // If one isn't found, it switches to scripting.
+            StackFrame::LookingForVarname => {
+                if let Some(token) = self.looking_for_varname(state)? {
+                    tokens.push(token);
                 }
-                // LookingForProperty is entered inside double quotes,
-                // backticks, or a heredoc, expecting an arrow followed by a
-                // property name.
-                StackFrame::LookingForProperty => {
-                    tokens.push(self.looking_for_property(&mut state)?);
+            }
+            // LookingForProperty is entered inside double quotes,
+            // backticks, or a heredoc, expecting an arrow followed by a
+            // property name.
+            StackFrame::LookingForProperty => {
+                tokens.push(self.looking_for_property(state)?);
+            }
+            StackFrame::VarOffset => {
+                if state.source.eof() {
+                    return Ok(false);
                 }
-                StackFrame::VarOffset => {
-                    if state.source.eof() {
-                        break;
-                    }
 
-                    tokens.push(self.var_offset(&mut state)?);
-                }
+                tokens.push(self.var_offset(state)?);
             }
         }
 
-        Ok(tokens)
+        Ok(true)
     }
 
     fn skip_whitespace(&self, state: &mut State) {
@@ -107,12 +215,13 @@ impl Lexer {
         }
     }
 
-    fn initial(&self, state: &mut State, tokens: &mut Vec<Token>) -> SyntaxResult<()> {
+    fn initial<'a>(&self, state: &mut State<'a>, tokens: &mut Vec<Token<'a>>) -> SyntaxResult<()> {
         let inline_span = state.source.span();
-        let mut buffer = Vec::new();
-        while let Some(char) = state.source.current() {
+        let start = state.source.cursor();
+        while state.source.current().is_some() {
             if state.source.at(b"<?php", 5) {
                 let tag_span = state.source.span();
+                let buffer = state.source.read_since(start);
 
                 state.source.skip(5);
                 state.replace(StackFrame::Scripting);
@@ -121,30 +230,32 @@ impl Lexer {
                     tokens.push(Token {
                         kind: TokenKind::InlineHtml(buffer.into()),
                         span: inline_span,
+                        symbol: None,
                     });
                 }
 
                 tokens.push(Token {
                     kind: TokenKind::OpenTag(OpenTagKind::Full),
                     span: tag_span,
+                    symbol: None,
                 });
 
                 return Ok(());
             }
 
             state.source.next();
-            buffer.push(*char);
         }
 
         tokens.push(Token {
-            kind: TokenKind::InlineHtml(buffer.into()),
+            kind: TokenKind::InlineHtml(state.source.read_since(start).into()),
             span: inline_span,
+            symbol: None,
         });
 
         Ok(())
     }
 
-    fn scripting(&self, state: &mut State) -> SyntaxResult<Token> {
+    fn scripting<'a>(&self, state: &mut State<'a>) -> SyntaxResult<Token<'a>> {
         let span = state.source.span();
         let kind = match state.source.read(3) {
             [b'!', b'=', b'='] => {
@@ -265,22 +376,14 @@ impl Lexer {
                 TokenKind::Dot
             }
             [b'\\', ident_start!(), ..] => {
+                let start = state.source.cursor();
                 state.source.next();
 
                 match self.scripting(state)? {
                     Token {
-                        kind:
-                            TokenKind::Identifier(ByteString { mut bytes, length })
-                            | TokenKind::QualifiedIdentifier(ByteString { mut bytes, length }),
+                        kind: TokenKind::Identifier(_) | TokenKind::QualifiedIdentifier(_),
                         ..
-                    } => {
-                        bytes.insert(0, b'\\');
-
-                        TokenKind::FullyQualifiedIdentifier(ByteString {
-                            bytes,
-                            length: length + 1,
-                        })
-                    }
+                    } => TokenKind::FullyQualifiedIdentifier(state.source.read_since(start).into()),
                     s => unreachable!("{:?}", s),
                 }
             }
@@ -288,15 +391,14 @@ impl Lexer {
                 state.source.next();
                 TokenKind::NamespaceSeparator
             }
-            [b @ ident_start!(), ..] => {
+            [ident_start!(), ..] => {
+                let start = state.source.cursor();
                 state.source.next();
                 let mut qualified = false;
                 let mut last_was_slash = false;
 
-                let mut buffer = vec![*b];
                 while let Some(next @ ident!() | next @ b'\\') = state.source.current() {
                     if matches!(next, ident!()) {
-                        buffer.push(*next);
                         state.source.next();
                         last_was_slash = false;
                         continue;
@@ -305,7 +407,6 @@ impl Lexer {
                     if *next == b'\\' && !last_was_slash {
                         qualified = true;
                         last_was_slash = true;
-                        buffer.push(*next);
                         state.source.next();
                         continue;
                     }
@@ -313,10 +414,12 @@ impl Lexer {
                     break;
                 }
 
+                let buffer = state.source.read_since(start);
+
                 if qualified {
                     TokenKind::QualifiedIdentifier(buffer.into())
                 } else {
-                    let kind = identifier_to_keyword(&buffer)
+                    let kind = identifier_to_keyword(buffer)
                         .unwrap_or_else(|| TokenKind::Identifier(buffer.into()));
 
                     if kind == TokenKind::HaltCompiler {
@@ -325,7 +428,11 @@ impl Lexer {
                                 state.source.skip(3);
                                 state.replace(StackFrame::Halted);
                             }
-                            _ => return Err(SyntaxError::InvalidHaltCompiler(state.source.span())),
+                            _ => {
+                                return Err(SyntaxError::InvalidHaltCompiler {
+                                    span: state.source.span(),
+                                })
+                            }
                         }
                     }
 
@@ -333,19 +440,17 @@ impl Lexer {
                 }
             }
             [b'/', b'*', ..] => {
+                let start = state.source.cursor();
                 state.source.next();
-                let mut buffer = vec![b'/'];
 
                 loop {
                     match state.source.read(2) {
                         [b'*', b'/'] => {
                             state.source.skip(2);
-                            buffer.extend_from_slice(b"*/");
                             break;
                         }
-                        &[t, ..] => {
+                        [_, ..] => {
                             state.source.next();
-                            buffer.push(t);
                         }
                         _ => {
                             break;
@@ -353,6 +458,8 @@ impl Lexer {
                     }
                 }
 
+                let buffer = state.source.read_since(start);
+
                 if buffer.starts_with(b"/**") {
                     TokenKind::DocumentComment(buffer.into())
                 } else {
@@ -364,17 +471,15 @@ impl Lexer {
                 TokenKind::Attribute
             }
             [ch @ b'/', b'/', ..] | [ch @ b'#', ..] => {
-                let mut buffer = if *ch == b'/' {
+                let start = state.source.cursor();
+                if *ch == b'/' {
                     state.source.skip(2);
-                    b"//".to_vec()
                 } else {
                     state.source.next();
-                    b"#".to_vec()
-                };
+                }
 
                 while let Some(c) = state.source.current() {
                     if *c == b'\n' {
-                        state.source.next();
                         break;
                     }
 
@@ -382,7 +487,11 @@ impl Lexer {
                         break;
                     }
 
-                    buffer.push(*c);
+                    state.source.next();
+                }
+
+                let buffer = state.source.read_since(start);
+                if let Some(b'\n') = state.source.current() {
                     state.source.next();
                 }
 
@@ -433,7 +542,9 @@ impl Lexer {
                     }
                     [_, ..] => DocStringKind::Heredoc,
                     [] => {
-                        return Err(SyntaxError::UnexpectedEndOfFile(state.source.span()));
+                        return Err(SyntaxError::UnexpectedEndOfFile {
+                            span: state.source.span(),
+                        });
                     }
                 };
 
@@ -441,10 +552,15 @@ impl Lexer {
                     Some(_) => self.consume_identifier(state).into(),
                     None => match state.source.current() {
                         Some(c) => {
-                            return Err(SyntaxError::UnexpectedCharacter(*c, state.source.span()))
+                            return Err(SyntaxError::UnexpectedCharacter {
+                                character: *c,
+                                span: state.source.span(),
+                            })
                         }
                         None => {
-                            return Err(SyntaxError::UnexpectedEndOfFile(state.source.span()));
+                            return Err(SyntaxError::UnexpectedEndOfFile {
+                                span: state.source.span(),
+                            });
                         }
                     },
                 };
@@ -452,32 +568,38 @@ impl Lexer {
                 if doc_string_kind == DocStringKind::Nowdoc {
                     match state.source.current() {
                         Some(b'\'') => state.source.next(),
-                        _ => {
-                            // TODO(azjezz) this is most likely a bug, what if current is none?
-                            return Err(SyntaxError::UnexpectedCharacter(
-                                *state.source.current().unwrap(),
-                                state.source.span(),
-                            ));
+                        Some(character) => {
+                            return Err(SyntaxError::UnexpectedCharacter {
+                                character: *character,
+                                span: state.source.span(),
+                            });
+                        }
+                        None => {
+                            return Err(SyntaxError::UnexpectedEndOfFile {
+                                span: state.source.span(),
+                            });
                         }
                     };
                 } else if let Some(b'"') = state.source.current() {
                     state.source.next();
                 }
 
-                if !matches!(state.source.current(), Some(b'\n')) {
-                    return Err(SyntaxError::UnexpectedCharacter(
-                        *state.source.current().unwrap(),
-                        state.source.span(),
-                    ));
+                match state.source.current() {
+                    Some(b'\n') => state.source.next(),
+                    Some(character) => {
+                        return Err(SyntaxError::UnexpectedCharacter {
+                            character: *character,
+                            span: state.source.span(),
+                        });
+                    }
+                    None => {
+                        return Err(SyntaxError::UnexpectedEndOfFile {
+                            span: state.source.span(),
+                        });
+                    }
                 }
 
-                state.source.next();
-                state.replace(StackFrame::DocString(
-                    doc_string_kind,
-                    label.clone(),
-                    DocStringIndentationKind::None,
-                    0,
-                ));
+                state.replace(StackFrame::DocString(doc_string_kind, label.clone()));
 
                 TokenKind::StartDocString(label, doc_string_kind)
             }
@@ -670,13 +792,25 @@ impl Lexer {
             ),
             // We should never reach this point since we have the empty checks surrounding
             // the call to this function, but it's better to be safe than sorry.
-            [] => return Err(SyntaxError::UnexpectedEndOfFile(state.source.span())),
+            [] => {
+                return Err(SyntaxError::UnexpectedEndOfFile {
+                    span: state.source.span(),
+                })
+            }
         };
 
-        Ok(Token { kind, span })
+        Ok(Token {
+            kind,
+            span,
+            symbol: None,
+        })
     }
 
-    fn double_quote(&self, state: &mut State, tokens: &mut Vec<Token>) -> SyntaxResult<()> {
+    fn double_quote<'a>(
+        &self,
+        state: &mut State<'a>,
+        tokens: &mut Vec<Token<'a>>,
+    ) -> SyntaxResult<()> {
         let span = state.source.span();
         let mut buffer = Vec::new();
         let kind = loop {
@@ -751,14 +885,18 @@ impl Lexer {
                     }
 
                     if code_point.is_empty() || state.source.current() != Some(&b'}') {
-                        return Err(SyntaxError::InvalidUnicodeEscape(state.source.span()));
+                        return Err(SyntaxError::InvalidUnicodeEscape {
+                            span: state.source.span(),
+                        });
                     }
                     state.source.next();
 
                     let c = if let Ok(c) = u32::from_str_radix(&code_point, 16) {
                         c
                     } else {
-                        return Err(SyntaxError::InvalidUnicodeEscape(state.source.span()));
+                        return Err(SyntaxError::InvalidUnicodeEscape {
+                            span: state.source.span(),
+                        });
                     };
 
                     if let Some(c) = char::from_u32(c) {
@@ -766,7 +904,9 @@ impl Lexer {
                         let bytes = c.encode_utf8(&mut tmp);
                         buffer.extend(bytes.as_bytes());
                     } else {
-                        return Err(SyntaxError::InvalidUnicodeEscape(state.source.span()));
+                        return Err(SyntaxError::InvalidUnicodeEscape {
+                            span: state.source.span(),
+                        });
                     }
                 }
                 &[b'\\', b @ b'0'..=b'7', ..] => {
@@ -785,7 +925,9 @@ impl Lexer {
                     if let Ok(b) = u8::from_str_radix(&octal, 8) {
                         buffer.push(b);
                     } else {
-                        return Err(SyntaxError::InvalidOctalEscape(state.source.span()));
+                        return Err(SyntaxError::InvalidOctalEscape {
+                            span: state.source.span(),
+                        });
                     }
                 }
                 [b'$', ident_start!(), ..] => {
@@ -806,7 +948,11 @@ impl Lexer {
                     state.source.next();
                     buffer.push(b);
                 }
-                [] => return Err(SyntaxError::UnexpectedEndOfFile(state.source.span())),
+                [] => {
+                    return Err(SyntaxError::UnexpectedEndOfFile {
+                        span: state.source.span(),
+                    })
+                }
             }
         };
 
@@ -814,14 +960,23 @@ impl Lexer {
             tokens.push(Token {
                 kind: TokenKind::StringPart(buffer.into()),
                 span,
+                symbol: None,
             })
         }
 
-        tokens.push(Token { kind, span });
+        tokens.push(Token {
+            kind,
+            span,
+            symbol: None,
+        });
         Ok(())
     }
 
-    fn shell_exec(&self, state: &mut State, tokens: &mut Vec<Token>) -> SyntaxResult<()> {
+    fn shell_exec<'a>(
+        &self,
+        state: &mut State<'a>,
+        tokens: &mut Vec<Token<'a>>,
+    ) -> SyntaxResult<()> {
         let span = state.source.span();
         let mut buffer = Vec::new();
         let kind = loop {
@@ -860,7 +1015,11 @@ impl Lexer {
                     state.source.next();
                     buffer.push(b);
                 }
-                [] => return Err(SyntaxError::UnexpectedEndOfFile(state.source.span())),
+                [] => {
+                    return Err(SyntaxError::UnexpectedEndOfFile {
+                        span: state.source.span(),
+                    })
+                }
             }
         };
 
@@ -868,20 +1027,25 @@ impl Lexer {
             tokens.push(Token {
                 kind: TokenKind::StringPart(buffer.into()),
                 span,
+                symbol: None,
             })
         }
 
-        tokens.push(Token { kind, span });
+        tokens.push(Token {
+            kind,
+            span,
+            symbol: None,
+        });
 
         Ok(())
     }
 
-    fn docstring(
+    fn docstring<'a>(
         &self,
-        state: &mut State,
-        tokens: &mut Vec<Token>,
+        state: &mut State<'a>,
+        tokens: &mut Vec<Token<'a>>,
         kind: DocStringKind,
-        label: ByteString,
+        label: ByteString<'a>,
     ) -> SyntaxResult<()> {
         match kind {
             DocStringKind::Heredoc => self.heredoc(state, tokens, label)?,
@@ -891,11 +1055,11 @@ impl Lexer {
         Ok(())
     }
 
-    fn heredoc(
+    fn heredoc<'a>(
         &self,
-        state: &mut State,
-        tokens: &mut Vec<Token>,
-        label: ByteString,
+        state: &mut State<'a>,
+        tokens: &mut Vec<Token<'a>>,
+        label: ByteString<'a>,
     ) -> SyntaxResult<()> {
         let span = state.source.span();
         let mut buffer: Vec<u8> = Vec::new();
@@ -967,14 +1131,18 @@ impl Lexer {
                     }
 
                     if code_point.is_empty() || state.source.current() != Some(&b'}') {
-                        return Err(SyntaxError::InvalidUnicodeEscape(state.source.span()));
+                        return Err(SyntaxError::InvalidUnicodeEscape {
+                            span: state.source.span(),
+                        });
                     }
                     state.source.next();
 
                     let c = if let Ok(c) = u32::from_str_radix(&code_point, 16) {
                         c
                     } else {
-                        return Err(SyntaxError::InvalidUnicodeEscape(state.source.span()));
+                        return Err(SyntaxError::InvalidUnicodeEscape {
+                            span: state.source.span(),
+                        });
                     };
 
                     if let Some(c) = char::from_u32(c) {
@@ -982,7 +1150,9 @@ impl Lexer {
                         let bytes = c.encode_utf8(&mut tmp);
                         buffer.extend(bytes.as_bytes());
                     } else {
-                        return Err(SyntaxError::InvalidUnicodeEscape(state.source.span()));
+                        return Err(SyntaxError::InvalidUnicodeEscape {
+                            span: state.source.span(),
+                        });
                     }
                 }
                 &[b'\\', b @ b'0'..=b'7', ..] => {
@@ -1001,7 +1171,9 @@ impl Lexer {
                     if let Ok(b) = u8::from_str_radix(&octal, 8) {
                         buffer.push(b);
                     } else {
-                        return Err(SyntaxError::InvalidOctalEscape(state.source.span()));
+                        return Err(SyntaxError::InvalidOctalEscape {
+                            span: state.source.span(),
+                        });
                     }
                 }
                 [b'$', ident_start!(), ..] => {
@@ -1068,7 +1240,9 @@ impl Lexer {
                         if whitespace_kind != DocStringIndentationKind::None
                             && !extra_whitespace_buffer.is_empty()
                         {
-                            return Err(SyntaxError::InvalidDocIndentation(state.source.span()));
+                            return Err(SyntaxError::InvalidDocIndentation {
+                                span: state.source.span(),
+                            });
                         }
 
                         // If we get here, only 1 type of indentation was found. We can move
@@ -1095,7 +1269,11 @@ impl Lexer {
                     state.source.next();
                     buffer.push(b);
                 }
-                [] => return Err(SyntaxError::UnexpectedEndOfFile(state.source.span())),
+                [] => {
+                    return Err(SyntaxError::UnexpectedEndOfFile {
+                        span: state.source.span(),
+                    })
+                }
             }
         };
 
@@ -1108,19 +1286,24 @@ impl Lexer {
             tokens.push(Token {
                 kind: TokenKind::StringPart(buffer.into()),
                 span,
+                symbol: None,
             })
         }
 
-        tokens.push(Token { kind, span });
+        tokens.push(Token {
+            kind,
+            span,
+            symbol: None,
+        });
 
         Ok(())
     }
 
-    fn nowdoc(
+    fn nowdoc<'a>(
         &self,
-        state: &mut State,
-        tokens: &mut Vec<Token>,
-        label: ByteString,
+        state: &mut State<'a>,
+        tokens: &mut Vec<Token<'a>>,
+        label: ByteString<'a>,
     ) -> SyntaxResult<()> {
         let span = state.source.span();
         let mut buffer: Vec<u8> = Vec::new();
@@ -1177,7 +1360,9 @@ impl Lexer {
                         if whitespace_kind != DocStringIndentationKind::None
                             && !extra_whitespace_buffer.is_empty()
                         {
-                            return Err(SyntaxError::InvalidDocIndentation(state.source.span()));
+                            return Err(SyntaxError::InvalidDocIndentation {
+                                span: state.source.span(),
+                            });
                         }
 
                         // If we get here, only 1 type of indentation was found. We can move
@@ -1204,7 +1389,11 @@ impl Lexer {
                     state.source.next();
                     buffer.push(b);
                 }
-                [] => return Err(SyntaxError::UnexpectedEndOfFile(state.source.span())),
+                [] => {
+                    return Err(SyntaxError::UnexpectedEndOfFile {
+                        span: state.source.span(),
+                    })
+                }
             }
         };
 
@@ -1217,26 +1406,31 @@ impl Lexer {
             tokens.push(Token {
                 kind: TokenKind::StringPart(buffer.into()),
                 span,
+                symbol: None,
             })
         }
 
-        tokens.push(Token { kind, span });
+        tokens.push(Token {
+            kind,
+            span,
+            symbol: None,
+        });
 
         Ok(())
     }
 
-    fn looking_for_varname(&self, state: &mut State) -> SyntaxResult<Option<Token>> {
+    fn looking_for_varname<'a>(&self, state: &mut State<'a>) -> SyntaxResult<Option<Token<'a>>> {
         let identifier = self.peek_identifier(state);
 
         if let Some(ident) = identifier {
             if let [b'[' | b'}'] = state.source.peek(ident.len(), 1) {
-                let ident = ident.to_vec();
                 let span = state.source.span();
                 state.source.skip(ident.len());
                 state.replace(StackFrame::Scripting);
                 return Ok(Some(Token {
                     kind: TokenKind::Identifier(ident.into()),
                     span,
+                    symbol: None,
                 }));
             }
         }
@@ -1246,7 +1440,7 @@ impl Lexer {
         Ok(None)
     }
 
-    fn looking_for_property(&self, state: &mut State) -> SyntaxResult<Token> {
+    fn looking_for_property<'a>(&self, state: &mut State<'a>) -> SyntaxResult<Token<'a>> {
         let span = state.source.span();
         let kind = match state.source.read(3) {
             [b'?', b'-', b'>'] => {
@@ -1266,10 +1460,14 @@ impl Lexer {
             _ => unreachable!(),
         };
 
-        Ok(Token { kind, span })
+        Ok(Token {
+            kind,
+            span,
+            symbol: None,
+        })
     }
 
-    fn var_offset(&self, state: &mut State) -> SyntaxResult<Token> {
+    fn var_offset<'a>(&self, state: &mut State<'a>) -> SyntaxResult<Token<'a>> {
         let span = state.source.span();
         let kind = match state.source.read(2) {
             [b'$', ident_start!()] => {
@@ -1299,13 +1497,39 @@ impl Lexer {
                 let label = self.consume_identifier(state);
                 TokenKind::Identifier(label.into())
             }
-            &[b, ..] => return Err(SyntaxError::UnrecognisedToken(b, state.source.span())),
-            [] => return Err(SyntaxError::UnexpectedEndOfFile(state.source.span())),
+            &[b, ..] => {
+                return Err(SyntaxError::UnrecognisedToken {
+                    token: b,
+                    span: state.source.span(),
+                })
+            }
+            [] => {
+                return Err(SyntaxError::UnexpectedEndOfFile {
+                    span: state.source.span(),
+                })
+            }
         };
-        Ok(Token { kind, span })
+        Ok(Token {
+            kind,
+            span,
+            symbol: None,
+        })
     }
 
-    fn tokenize_single_quote_string(&self, state: &mut State) -> SyntaxResult<TokenKind> {
+    fn tokenize_single_quote_string<'a>(
+        &self,
+        state: &mut State<'a>,
+    ) -> SyntaxResult<TokenKind<'a>> {
+        // Most strings don't contain any escape sequences, so they can be borrowed as-is.
+        let rest = state.source.read_remaining();
+        if let Some(end) = rest.iter().position(|b| matches!(b, b'\'' | b'\\')) {
+            if rest[end] == b'\'' {
+                state.source.skip(end + 1);
+
+                return Ok(TokenKind::LiteralString(rest[..end].into()));
+            }
+        }
+
         let mut buffer = Vec::new();
 
         loop {
@@ -1322,14 +1546,34 @@ impl Lexer {
                     state.source.next();
                     buffer.push(b);
                 }
-                [] => return Err(SyntaxError::UnexpectedEndOfFile(state.source.span())),
+                [] => {
+                    return Err(SyntaxError::UnexpectedEndOfFile {
+                        span: state.source.span(),
+                    })
+                }
             }
         }
 
         Ok(TokenKind::LiteralString(buffer.into()))
     }
 
-    fn tokenize_double_quote_string(&self, state: &mut State) -> SyntaxResult<TokenKind> {
+    fn tokenize_double_quote_string<'a>(
+        &self,
+        state: &mut State<'a>,
+    ) -> SyntaxResult<TokenKind<'a>> {
+        // Most strings don't contain any escape sequences or interpolation, so they can be borrowed as-is.
+        let rest = state.source.read_remaining();
+        if let Some(end) = rest
+            .iter()
+            .position(|b| matches!(b, b'"' | b'\\' | b'$' | b'{'))
+        {
+            if rest[end] == b'"' {
+                state.source.skip(end + 1);
+
+                return Ok(TokenKind::LiteralString(rest[..end].into()));
+            }
+        }
+
         let mut buffer = Vec::new();
 
         let constant = loop {
@@ -1392,14 +1636,18 @@ impl Lexer {
                     }
 
                     if code_point.is_empty() || state.source.current() != Some(&b'}') {
-                        return Err(SyntaxError::InvalidUnicodeEscape(state.source.span()));
+                        return Err(SyntaxError::InvalidUnicodeEscape {
+                            span: state.source.span(),
+                        });
                     }
                     state.source.next();
 
                     let c = if let Ok(c) = u32::from_str_radix(&code_point, 16) {
                         c
                     } else {
-                        return Err(SyntaxError::InvalidUnicodeEscape(state.source.span()));
+                        return Err(SyntaxError::InvalidUnicodeEscape {
+                            span: state.source.span(),
+                        });
                     };
 
                     if let Some(c) = char::from_u32(c) {
@@ -1407,7 +1655,9 @@ impl Lexer {
                         let bytes = c.encode_utf8(&mut tmp);
                         buffer.extend(bytes.as_bytes());
                     } else {
-                        return Err(SyntaxError::InvalidUnicodeEscape(state.source.span()));
+                        return Err(SyntaxError::InvalidUnicodeEscape {
+                            span: state.source.span(),
+                        });
                     }
                 }
                 &[b'\\', b @ b'0'..=b'7', ..] => {
@@ -1427,7 +1677,9 @@ impl Lexer {
                     if let Ok(b) = u8::from_str_radix(&octal, 8) {
                         buffer.push(b);
                     } else {
-                        return Err(SyntaxError::InvalidOctalEscape(state.source.span()));
+                        return Err(SyntaxError::InvalidOctalEscape {
+                            span: state.source.span(),
+                        });
                     }
                 }
                 [b'$', ident_start!(), ..] | [b'{', b'$', ..] | [b'$', b'{', ..] => {
@@ -1437,7 +1689,11 @@ impl Lexer {
                     state.source.next();
                     buffer.push(b);
                 }
-                [] => return Err(SyntaxError::UnexpectedEndOfFile(state.source.span())),
+                [] => {
+                    return Err(SyntaxError::UnexpectedEndOfFile {
+                        span: state.source.span(),
+                    })
+                }
             }
         };
 
@@ -1449,7 +1705,7 @@ impl Lexer {
         })
     }
 
-    fn peek_identifier<'a>(&'a self, state: &'a State) -> Option<&'a [u8]> {
+    fn peek_identifier<'a>(&self, state: &State<'a>) -> Option<&'a [u8]> {
         let mut size = 0;
 
         if let [ident_start!()] = state.source.read(1) {
@@ -1464,18 +1720,19 @@ impl Lexer {
         }
     }
 
-    fn consume_identifier(&self, state: &mut State) -> Vec<u8> {
-        let ident = self.peek_identifier(state).unwrap().to_vec();
+    fn consume_identifier<'a>(&self, state: &mut State<'a>) -> &'a [u8] {
+        let ident = self.peek_identifier(state).unwrap();
         state.source.skip(ident.len());
 
         ident
     }
 
-    fn tokenize_variable(&self, state: &mut State) -> TokenKind {
+    fn tokenize_variable<'a>(&self, state: &mut State<'a>) -> TokenKind<'a> {
         TokenKind::Variable(self.consume_identifier(state).into())
     }
 
-    fn tokenize_number(&self, state: &mut State) -> SyntaxResult<TokenKind> {
+    fn tokenize_number<'a>(&self, state: &mut State<'a>) -> SyntaxResult<TokenKind<'a>> {
+        let start = state.source.cursor();
         let mut buffer = Vec::new();
 
         let (base, kind) = match state.source.read(2) {
@@ -1505,7 +1762,7 @@ impl Lexer {
         if kind != NumberKind::Float {
             self.read_digits(state, &mut buffer, base);
             if kind == NumberKind::Int {
-                return parse_int(&buffer);
+                return parse_int(number(state, start, buffer));
             }
         }
 
@@ -1516,7 +1773,7 @@ impl Lexer {
         );
 
         if !is_float {
-            return parse_int(&buffer);
+            return parse_int(number(state, start, buffer));
         }
 
         if let Some(b'.') = state.source.current() {
@@ -1535,7 +1792,7 @@ impl Lexer {
             self.read_digits(state, &mut buffer, 10);
         }
 
-        Ok(TokenKind::LiteralFloat(buffer.into()))
+        Ok(TokenKind::LiteralFloat(number(state, start, buffer)))
     }
 
     fn read_digits(&self, state: &mut State, buffer: &mut Vec<u8>, base: usize) {
@@ -1583,11 +1840,37 @@ impl Lexer {
 
 // Parses an integer literal in the given base and converts errors to SyntaxError.
 // It returns a float token instead on overflow.
-fn parse_int(buffer: &[u8]) -> SyntaxResult<TokenKind> {
-    Ok(TokenKind::LiteralInteger(buffer.into()))
+fn parse_int(buffer: ByteString) -> SyntaxResult<TokenKind> {
+    Ok(TokenKind::LiteralInteger(buffer))
+}
+
+// Numbers are normalised while they are read, e.g. `1_000` becomes `1000`,
+// so we can only borrow them when nothing had to be changed.
+fn number<'a>(state: &State<'a>, start: usize, buffer: Vec<u8>) -> ByteString<'a> {
+    let text = state.source.read_since(start);
+
+    if text == buffer.as_slice() {
+        text.into()
+    } else {
+        buffer.into()
+    }
+}
+
+fn symbol(interner: &Interner, kind: &TokenKind) -> Option<Symbol> {
+    match kind {
+        TokenKind::Identifier(name)
+        | TokenKind::QualifiedIdentifier(name)
+        | TokenKind::FullyQualifiedIdentifier(name)
+        | TokenKind::Variable(name) => Some(interner.intern(name)),
+        // Only keywords are interned, not punctuation or literals.
+        _ => kind
+            .as_static_str()
+            .filter(|text| identifier_to_keyword(text.as_bytes()).is_some())
+            .map(|text| interner.intern(text.as_bytes())),
+    }
 }
 
-fn identifier_to_keyword(ident: &[u8]) -> Option<TokenKind> {
+fn identifier_to_keyword(ident: &[u8]) -> Option<TokenKind<'static>> {
     Some(match ident {
         b"enddeclare" => TokenKind::EndDeclare,
         b"endswitch" => TokenKind::EndSwitch,
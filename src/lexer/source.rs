@@ -1,4 +1,8 @@
+use std::ops::Range;
+
 use crate::lexer::token::Span;
+use crate::lexer::token::Token;
+use crate::lexer::token::TokenKind;
 
 #[derive(Debug)]
 pub struct Source<'a> {
@@ -10,7 +14,6 @@ pub struct Source<'a> {
 
 impl<'a> Source<'a> {
     pub fn new(input: &'a [u8]) -> Self {
-        let input = input;
         let length = input.len();
 
         Self {
@@ -71,8 +74,15 @@ impl<'a> Source<'a> {
         &self.input[from..until]
     }
 
+    /// Read everything between the given position and the cursor.
+    pub fn read_since(&self, from: usize) -> &'a [u8] {
+        let until = self.remaining_bound();
+
+        &self.input[from.min(until)..until]
+    }
+
     pub fn read_remaining(&self) -> &'a [u8] {
-        let from = self.from_bound();
+        let from = self.remaining_bound();
 
         &self.input[from..]
     }
@@ -116,7 +126,7 @@ impl<'a> Source<'a> {
         (self.cursor, until)
     }
 
-    const fn from_bound(&self) -> usize {
+    const fn remaining_bound(&self) -> usize {
         if self.cursor >= self.length {
             self.length
         } else {
@@ -124,3 +134,81 @@ impl<'a> Source<'a> {
         }
     }
 }
+
+/// Converts between byte offsets into a source and the line and column spans used by tokens.
+#[derive(Debug, Clone)]
+pub struct LineIndex {
+    // The offset at which each line starts.
+    lines: Vec<usize>,
+}
+
+impl LineIndex {
+    pub fn new(source: &[u8]) -> Self {
+        let mut lines = vec![0];
+        lines.extend(
+            source
+                .iter()
+                .enumerate()
+                .filter(|(_, byte)| **byte == b'\n')
+                .map(|(offset, _)| offset + 1),
+        );
+
+        Self { lines }
+    }
+
+    /// The byte offset of a span.
+    pub fn offset(&self, span: Span) -> usize {
+        self.lines[span.0 - 1] + span.1 - 1
+    }
+
+    /// The span of a byte offset.
+    pub fn span(&self, offset: usize) -> Span {
+        let line = self.lines.partition_point(|start| *start <= offset);
+
+        (line, offset - self.lines[line - 1] + 1)
+    }
+
+    /// The byte offset at which a line starts.
+    pub fn line_start(&self, line: usize) -> usize {
+        self.lines[line - 1]
+    }
+}
+
+/// The byte range of each token in `source`, or `None` for tokens without a span.
+///
+/// Tokens only record where they start, so each one is taken to run up to the
+/// start of the next, without the whitespace in between.
+pub fn token_ranges(
+    source: &[u8],
+    index: &LineIndex,
+    tokens: &[Token],
+) -> Vec<Option<Range<usize>>> {
+    let starts = tokens
+        .iter()
+        .map(|token| (token.span.0 > 0).then(|| index.offset(token.span)))
+        .collect::<Vec<_>>();
+
+    starts
+        .iter()
+        .enumerate()
+        .map(|(i, start)| {
+            let start = (*start)?;
+            let mut end = starts[i + 1..]
+                .iter()
+                .flatten()
+                .next()
+                .copied()
+                .unwrap_or(source.len())
+                .min(source.len());
+
+            // Whitespace at the end of a string is part of it.
+            if !matches!(tokens[i].kind, TokenKind::StringPart(_)) {
+                while end > start && source[end - 1].is_ascii_whitespace() {
+                    end -= 1;
+                }
+            }
+
+            Some(start..end)
+        })
+        .collect()
+}
@@ -5,9 +5,6 @@ use crate::lexer::error::SyntaxError;
 use crate::lexer::error::SyntaxResult;
 use crate::lexer::source::Source;
 
-use super::token::DocStringIndentationAmount;
-use super::token::DocStringIndentationKind;
-
 #[derive(Debug, PartialEq, Eq, PartialOrd, Clone, Copy)]
 pub enum DocStringKind {
     Heredoc,
@@ -15,18 +12,13 @@ pub enum DocStringKind {
 }
 
 #[derive(Debug)]
-pub enum StackFrame {
+pub enum StackFrame<'a> {
     Initial,
     Scripting,
     Halted,
     DoubleQuote,
     ShellExec,
-    DocString(
-        DocStringKind,
-        ByteString,
-        DocStringIndentationKind,
-        DocStringIndentationAmount,
-    ),
+    DocString(DocStringKind, ByteString<'a>),
     LookingForVarname,
     LookingForProperty,
     VarOffset,
@@ -35,30 +27,32 @@ pub enum StackFrame {
 #[derive(Debug)]
 pub struct State<'a> {
     pub source: Source<'a>,
-    pub stack: VecDeque<StackFrame>,
+    pub stack: VecDeque<StackFrame<'a>>,
 }
 
 impl<'a> State<'a> {
-    pub fn new(source: Source<'a>) -> Self {
+    pub fn new(source: Source<'a>, frame: StackFrame<'a>) -> Self {
         Self {
             source,
-            stack: VecDeque::from([StackFrame::Initial]),
+            stack: VecDeque::from([frame]),
         }
     }
 
-    pub fn frame(&self) -> SyntaxResult<&StackFrame> {
+    pub fn frame(&self) -> SyntaxResult<&StackFrame<'a>> {
         self.stack
             .back()
-            .ok_or_else(|| SyntaxError::UnpredictableState(self.source.span()))
+            .ok_or_else(|| SyntaxError::UnpredictableState {
+                span: self.source.span(),
+            })
     }
 
-    pub fn replace(&mut self, state: StackFrame) {
+    pub fn replace(&mut self, state: StackFrame<'a>) {
         let i = self.stack.len() - 1;
 
         self.stack[i] = state;
     }
 
-    pub fn enter(&mut self, state: StackFrame) {
+    pub fn enter(&mut self, state: StackFrame<'a>) {
         self.stack.push_back(state);
     }
 
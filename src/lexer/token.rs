@@ -1,6 +1,7 @@
 use std::fmt::Display;
 
 use crate::lexer::byte_string::ByteString;
+use crate::lexer::interner::Symbol;
 
 use super::state::DocStringKind;
 
@@ -42,13 +43,13 @@ impl From<DocStringIndentationKind> for u8 {
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Clone)]
-pub enum TokenKind {
+pub enum TokenKind<'a> {
     // Can't use `Self` as a name here, so suffixing with an underscore.
     Self_,
     Parent,
     Backtick,
-    StartDocString(ByteString, DocStringKind),
-    EndDocString(ByteString, DocStringIndentationKind, usize),
+    StartDocString(ByteString<'a>, DocStringKind),
+    EndDocString(ByteString<'a>, DocStringIndentationKind, usize),
     From,
     Print,
     Dollar,
@@ -98,13 +99,13 @@ pub enum TokenKind {
     AsteriskEqual,
     Colon,
     Comma,
-    SingleLineComment(ByteString),
-    HashMarkComment(ByteString),
-    MultiLineComment(ByteString),
-    DocumentComment(ByteString),
+    SingleLineComment(ByteString<'a>),
+    HashMarkComment(ByteString<'a>),
+    MultiLineComment(ByteString<'a>),
+    DocumentComment(ByteString<'a>),
     ConcatEqual,
     Const,
-    LiteralString(ByteString),
+    LiteralString(ByteString<'a>),
     Continue,
     CurlyOpen,
     Declare,
@@ -113,7 +114,7 @@ pub enum TokenKind {
     DirConstant,
     DivEqual,
     Do,
-    DocOpen(ByteString),
+    DocOpen(ByteString<'a>),
     DollarLeftBrace,
     Dot,
     DotEquals,
@@ -142,22 +143,22 @@ pub enum TokenKind {
     False,
     Final,
     Finally,
-    LiteralFloat(ByteString),
+    LiteralFloat(ByteString<'a>),
     Fn,
     For,
     Foreach,
-    FullyQualifiedIdentifier(ByteString),
+    FullyQualifiedIdentifier(ByteString<'a>),
     Function,
     Goto,
     GreaterThan,
     GreaterThanEquals,
-    Identifier(ByteString),
+    Identifier(ByteString<'a>),
     If,
     Implements,
     Include,
     IncludeOnce,
     Increment,
-    InlineHtml(ByteString),
+    InlineHtml(ByteString<'a>),
     Instanceof,
     Insteadof,
     Eval,
@@ -165,7 +166,7 @@ pub enum TokenKind {
     Unset,
     Isset,
     List,
-    LiteralInteger(ByteString),
+    LiteralInteger(ByteString<'a>),
     IntCast,
     IntegerCast,
     Interface,
@@ -199,7 +200,7 @@ pub enum TokenKind {
     Private,
     Protected,
     Public,
-    QualifiedIdentifier(ByteString),
+    QualifiedIdentifier(ByteString<'a>),
     Question,
     QuestionColon,
     Require,
@@ -214,7 +215,7 @@ pub enum TokenKind {
     Static,
     StringCast,
     BinaryCast,
-    StringPart(ByteString),
+    StringPart(ByteString<'a>),
     Switch,
     Throw,
     Trait,
@@ -223,7 +224,7 @@ pub enum TokenKind {
     Try,
     Use,
     Var,
-    Variable(ByteString),
+    Variable(ByteString<'a>),
     Yield,
     While,
     BitwiseNot,
@@ -232,35 +233,62 @@ pub enum TokenKind {
     LogicalXor,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Token {
-    pub kind: TokenKind,
+#[derive(Clone)]
+pub struct Token<'a> {
+    pub kind: TokenKind<'a>,
     pub span: Span,
+    /// The interned name of an identifier, variable or keyword, when the lexer has an interner.
+    pub symbol: Option<Symbol>,
 }
 
-impl Default for Token {
+// Tokens, and the `SimpleIdentifier` and `SimpleVariable` nodes their symbols
+// end up in, compare names by symbol when both sides were lexed with the same
+// interner, and by bytes otherwise. The symbol is never printed, so code prints
+// the same whether or not it was lexed with an interner.
+impl<'a> PartialEq for Token<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        if self.span != other.span {
+            return false;
+        }
+
+        match (self.symbol, other.symbol) {
+            (Some(a), Some(b)) if a.is_comparable_to(b) => {
+                a == b && std::mem::discriminant(&self.kind) == std::mem::discriminant(&other.kind)
+            }
+            _ => self.kind == other.kind,
+        }
+    }
+}
+
+impl<'a> Eq for Token<'a> {}
+
+impl<'a> std::fmt::Debug for Token<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Token")
+            .field("kind", &self.kind)
+            .field("span", &self.span)
+            .finish()
+    }
+}
+
+impl<'a> Default for Token<'a> {
     fn default() -> Self {
         Self {
             kind: TokenKind::Eof,
             span: (0, 0),
+            symbol: None,
         }
     }
 }
 
-impl Display for TokenKind {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let s = match self {
+impl<'a> TokenKind<'a> {
+    /// The text of a token that's always written the same way, such as a
+    /// keyword or an operator, or `None` for tokens that carry their own text.
+    pub fn as_static_str(&self) -> Option<&'static str> {
+        Some(match self {
             Self::Self_ => "self",
             Self::Parent => "parent",
             Self::Backtick => "`",
-            Self::StartDocString(label, kind) => {
-                if kind == &DocStringKind::Nowdoc {
-                    return write!(f, "<<<'{}'", label);
-                } else {
-                    return write!(f, "<<<{}", label);
-                }
-            }
-            Self::EndDocString(label, ..) => return write!(f, "{}", label),
             Self::BangEquals => "!=",
             Self::From => "from",
             Self::Print => "print",
@@ -339,7 +367,6 @@ impl Display for TokenKind {
             Self::False => "false",
             Self::Final => "final",
             Self::Finally => "finally",
-            Self::LiteralFloat(bytes) => return write!(f, "{}", bytes),
             Self::Fn => "fn",
             Self::For => "for",
             Self::Function => "function",
@@ -350,7 +377,6 @@ impl Display for TokenKind {
             Self::Implements => "implements",
             Self::Increment => "++",
             Self::InlineHtml(_) => "InlineHtml",
-            Self::LiteralInteger(bytes) => return write!(f, "{}", bytes),
             Self::LeftBrace => "{",
             Self::LeftBracket => "[",
             Self::LeftParen => "(",
@@ -433,9 +459,38 @@ impl Display for TokenKind {
             Self::Interface => "interface",
             Self::NamespaceConstant => "__NAMESPACE__",
             Self::PowEquals => "**=",
-            Self::Variable(v) => {
-                return write!(f, "${}", v);
+            Self::StartDocString(..)
+            | Self::EndDocString(..)
+            | Self::LiteralFloat(_)
+            | Self::LiteralInteger(_)
+            | Self::Variable(_)
+            | Self::StringPart(_)
+            | Self::QualifiedIdentifier(_)
+            | Self::Identifier(_)
+            | Self::FullyQualifiedIdentifier(_)
+            | Self::DocOpen(_)
+            | Self::LiteralString(_)
+            | Self::SingleLineComment(_)
+            | Self::MultiLineComment(_)
+            | Self::HashMarkComment(_)
+            | Self::DocumentComment(_) => return None,
+        })
+    }
+}
+
+impl<'a> Display for TokenKind<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::StartDocString(label, kind) => {
+                if kind == &DocStringKind::Nowdoc {
+                    write!(f, "<<<'{}'", label)
+                } else {
+                    write!(f, "<<<{}", label)
+                }
             }
+            Self::EndDocString(label, ..) => write!(f, "{}", label),
+            Self::LiteralFloat(bytes) | Self::LiteralInteger(bytes) => write!(f, "{}", bytes),
+            Self::Variable(v) => write!(f, "${}", v),
             Self::StringPart(v)
             | Self::QualifiedIdentifier(v)
             | Self::Identifier(v)
@@ -445,11 +500,8 @@ impl Display for TokenKind {
             | Self::SingleLineComment(v)
             | Self::MultiLineComment(v)
             | Self::HashMarkComment(v)
-            | Self::DocumentComment(v) => {
-                return write!(f, "{}", v);
-            }
-        };
-
-        write!(f, "{}", s)
+            | Self::DocumentComment(v) => write!(f, "{}", v),
+            _ => f.write_str(self.as_static_str().unwrap_or_default()),
+        }
     }
 }
@@ -1,4 +1,23 @@
+pub mod composer;
+pub mod composition;
+pub mod diagnostics;
+pub mod evaluator;
+pub mod hierarchy;
+pub mod includes;
+pub mod index;
 pub mod lexer;
+pub mod names;
 pub mod parser;
+pub mod project;
+pub mod ranges;
+pub mod semantic_tokens;
+pub mod symbols;
 
+pub use parser::options::CancellationToken;
+pub use parser::options::ParserOptions;
 pub use parser::parse;
+pub use parser::parse_class_member;
+pub use parser::parse_expression;
+pub use parser::parse_statement;
+pub use parser::parse_stream;
+pub use parser::parse_type;
@@ -1,33 +1,288 @@
+use std::io::{IsTerminal, Read, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use php_parser_rs::diagnostics::{render, Diagnostic, RenderFormat};
 use php_parser_rs::lexer::Lexer;
-use php_parser_rs::parser::error::ParseResult;
+use php_parser_rs::project::{FileError, Project};
+
+const USAGE: &str = "Usage: php-parser-rs <command> [options]
+
+Commands:
+    tokens <file>                    Print the tokens of a file, one per line
+    ast <file> [--format <format>]   Print the AST of a file, as `debug` (default) or `json`
+    check <paths...> [--format <format>]
+                                     Check files, and `.php` files in directories, for
+                                     syntax errors, printed as `plain` (default) or `json`
+
+Pass `--stdin` instead of a file or paths to read the code from standard input.";
+
+// Exit codes, where 1 means the code has errors and 2 that the command couldn't run.
+const FAILURE: u8 = 1;
+const USAGE_ERROR: u8 = 2;
+
+static LEXER: Lexer = Lexer::new();
+
+enum Input {
+    Stdin,
+    Path(PathBuf),
+}
+
+impl Input {
+    fn name(&self) -> String {
+        match self {
+            Self::Stdin => "<stdin>".to_string(),
+            Self::Path(path) => path.display().to_string(),
+        }
+    }
+
+    fn read(&self) -> Result<Vec<u8>, String> {
+        let result = match self {
+            Self::Stdin => {
+                let mut contents = vec![];
+                std::io::stdin()
+                    .read_to_end(&mut contents)
+                    .map(|_| contents)
+            }
+            Self::Path(path) => std::fs::read(path),
+        };
+
+        result.map_err(|error| format!("failed to read {}: {}", self.name(), error))
+    }
+}
+
+struct Arguments {
+    command: String,
+    inputs: Vec<Input>,
+    format: Option<String>,
+}
 
-fn main() -> ParseResult<()> {
-    let file = match std::env::args().nth(1) {
-        Some(file) => file,
-        None => {
-            println!("Usage: php-parser [file]");
+fn main() -> ExitCode {
+    let arguments = match arguments(std::env::args().skip(1)) {
+        Ok(Some(arguments)) => arguments,
+        Ok(None) => {
+            println!("{}", USAGE);
 
-            ::std::process::exit(0);
+            return ExitCode::SUCCESS;
         }
+        Err(error) => {
+            eprintln!("error: {}\n\n{}", error, USAGE);
+
+            return ExitCode::from(USAGE_ERROR);
+        }
+    };
+
+    let result = match arguments.command.as_str() {
+        "tokens" => single(&arguments).and_then(tokens),
+        "ast" => single(&arguments).and_then(|input| {
+            let format = arguments.format.as_deref().unwrap_or("debug");
+
+            match format {
+                "debug" | "json" => ast(input, format),
+                _ => Err(format!("unknown format `{}` for `ast`", format)),
+            }
+        }),
+        "check" => {
+            let format = match arguments.format.as_deref() {
+                None if std::io::stdout().is_terminal() => Ok(RenderFormat::Ansi),
+                None | Some("plain") => Ok(RenderFormat::Plain),
+                Some("json") => Ok(RenderFormat::Json),
+                Some(format) => Err(format!("unknown format `{}` for `check`", format)),
+            };
+
+            format.and_then(|format| check(&arguments.inputs, format))
+        }
+        command => Err(format!("unknown command `{}`", command)),
     };
 
-    let contents = match std::fs::read(&file) {
-        Ok(contents) => contents,
+    match result {
+        Ok(code) => code,
         Err(error) => {
-            println!("Failed to read file: {}", error);
+            eprintln!("error: {}", error);
+
+            ExitCode::from(USAGE_ERROR)
+        }
+    }
+}
+
+fn arguments(mut args: impl Iterator<Item = String>) -> Result<Option<Arguments>, String> {
+    let command = match args.next() {
+        Some(command) if command != "help" && command != "--help" && command != "-h" => command,
+        _ => return Ok(None),
+    };
+
+    let mut inputs = vec![];
+    let mut format = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--stdin" => inputs.push(Input::Stdin),
+            "--format" => match args.next() {
+                Some(value) => format = Some(value),
+                None => return Err("`--format` needs a value".to_string()),
+            },
+            "--help" | "-h" => return Ok(None),
+            _ if arg.starts_with("--") => return Err(format!("unknown option `{}`", arg)),
+            _ => inputs.push(Input::Path(PathBuf::from(arg))),
+        }
+    }
+
+    Ok(Some(Arguments {
+        command,
+        inputs,
+        format,
+    }))
+}
+
+fn single(arguments: &Arguments) -> Result<&Input, String> {
+    match &arguments.inputs[..] {
+        [input] => Ok(input),
+        _ => Err(format!(
+            "`{}` takes exactly one file, or `--stdin`",
+            arguments.command
+        )),
+    }
+}
+
+fn tokens(input: &Input) -> Result<ExitCode, String> {
+    let contents = input.read()?;
 
-            ::std::process::exit(1);
+    match LEXER.tokenize(&contents) {
+        Ok(tokens) => {
+            let mut stdout = std::io::stdout().lock();
+            for token in tokens {
+                // Stop quietly when the output is piped to something like `head`.
+                let line = writeln!(
+                    stdout,
+                    "{}:{}\t{:?}",
+                    token.span.0, token.span.1, token.kind
+                );
+                if line.is_err() {
+                    break;
+                }
+            }
+
+            Ok(ExitCode::SUCCESS)
         }
+        Err(error) => Ok(report(input, &contents, &Diagnostic::from(error))),
+    }
+}
+
+fn ast(input: &Input, format: &str) -> Result<ExitCode, String> {
+    let contents = input.read()?;
+
+    let program = LEXER
+        .tokenize(&contents)
+        .map_err(Diagnostic::from)
+        .and_then(|tokens| php_parser_rs::parse(tokens).map_err(Diagnostic::from));
+
+    match program {
+        Ok(program) => {
+            let output = if format == "json" {
+                serde_json::to_string_pretty(&program).unwrap()
+            } else {
+                format!("{:#?}", program)
+            };
+
+            let _ = writeln!(std::io::stdout(), "{}", output);
+
+            Ok(ExitCode::SUCCESS)
+        }
+        Err(diagnostic) => Ok(report(input, &contents, &diagnostic)),
+    }
+}
+
+fn report(input: &Input, contents: &[u8], diagnostic: &Diagnostic) -> ExitCode {
+    let format = if std::io::stderr().is_terminal() {
+        RenderFormat::Ansi
+    } else {
+        RenderFormat::Plain
     };
 
-    let lexer = Lexer::new();
+    eprint!(
+        "{}",
+        render(diagnostic, contents, Some(&input.name()), format)
+    );
+
+    ExitCode::from(FAILURE)
+}
+
+fn check(inputs: &[Input], format: RenderFormat) -> Result<ExitCode, String> {
+    if inputs.is_empty() {
+        return Err("`check` needs at least one path, or `--stdin`".to_string());
+    }
+
+    let mut checked = 0;
+    let mut failed = 0;
+    for input in inputs {
+        match input {
+            Input::Path(path) if path.is_dir() => {
+                let result = Project::new(path)
+                    .parse()
+                    .map_err(|error| format!("failed to read {}: {}", path.display(), error))?;
+
+                for file in result.files {
+                    checked += 1;
+
+                    match file.result {
+                        Ok(_) => {}
+                        Err(FileError::Read(error)) => {
+                            return Err(format!(
+                                "failed to read {}: {}",
+                                file.path.display(),
+                                error
+                            ))
+                        }
+                        Err(FileError::Parse(error)) => {
+                            failed += 1;
 
-    let tokens = lexer.tokenize(&contents)?;
-    dbg!(&tokens);
+                            let name = file.path.display().to_string();
+                            print_diagnostic(&Diagnostic::from(error), &file.source, &name, format);
+                        }
+                        Err(error @ FileError::Panic(_)) => {
+                            failed += 1;
 
-    let ast = php_parser_rs::parse(tokens)?;
+                            eprintln!("error: {}: {}", file.path.display(), error);
+                        }
+                    }
+                }
+            }
+            _ => {
+                let contents = input.read()?;
+                checked += 1;
+
+                let result = LEXER
+                    .tokenize(&contents)
+                    .map_err(Diagnostic::from)
+                    .and_then(|tokens| php_parser_rs::parse(tokens).map_err(Diagnostic::from));
+
+                if let Err(diagnostic) = result {
+                    failed += 1;
+
+                    print_diagnostic(&diagnostic, &contents, &input.name(), format);
+                }
+            }
+        }
+    }
+
+    eprintln!(
+        "checked {} {}: {} with errors",
+        checked,
+        if checked == 1 { "file" } else { "files" },
+        failed
+    );
+
+    if failed > 0 {
+        Ok(ExitCode::from(FAILURE))
+    } else {
+        Ok(ExitCode::SUCCESS)
+    }
+}
 
-    dbg!(ast);
+fn print_diagnostic(diagnostic: &Diagnostic, contents: &[u8], name: &str, format: RenderFormat) {
+    let rendered = render(diagnostic, contents, Some(name), format);
 
-    Ok(())
+    match format {
+        RenderFormat::Json => println!("{}", rendered),
+        _ => print!("{}", rendered),
+    }
 }
@@ -0,0 +1,185 @@
+//! Resolving the names written in a program to the fully qualified names they
+//! refer to, following the namespace and `use` imports in scope.
+
+use std::collections::HashMap;
+
+use crate::index::key;
+use crate::index::NameTable;
+use crate::parser::ast::node::Node;
+use crate::parser::ast::Program;
+use crate::parser::ast::Statement;
+use crate::parser::ast::Use;
+use crate::parser::ast::UseKind;
+
+/// The namespace and imports in effect at some point of a program.
+#[derive(Debug, Clone, Default)]
+pub struct Scope {
+    namespace: String,
+    imports: HashMap<(NameTable, String), String>,
+}
+
+impl Scope {
+    /// A scope without any imports, e.g. `Scope::new("App\\Models")`.
+    pub fn new(namespace: &str) -> Self {
+        Self {
+            namespace: namespace.trim_matches('\\').to_string(),
+            imports: HashMap::new(),
+        }
+    }
+
+    /// The current namespace, or an empty string for the global namespace.
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// Import `name` into the scope as `alias`, or as its last segment.
+    pub fn import(&mut self, table: NameTable, name: &str, alias: Option<&str>) {
+        let name = name.trim_start_matches('\\');
+        let alias = alias.unwrap_or_else(|| last_segment(name));
+
+        self.imports.insert(key(table, alias), name.to_string());
+    }
+
+    /// The fully qualified name, without a leading `\`, that `name` refers to.
+    ///
+    /// Unqualified function and constant names that aren't imported may also
+    /// refer to a global one at runtime, see `Scope::fallback`. The special class
+    /// names `self`, `static` and `parent` depend on the enclosing class rather
+    /// than the scope, and are returned as they are.
+    pub fn resolve(&self, table: NameTable, name: &str) -> String {
+        if let Some(name) = name.strip_prefix('\\') {
+            return name.to_string();
+        }
+
+        if table == NameTable::Class && is_special_class(name) {
+            return name.to_string();
+        }
+
+        if let Some(name) = name
+            .get(..10)
+            .filter(|prefix| prefix.eq_ignore_ascii_case("namespace\\"))
+            .map(|_| &name[10..])
+        {
+            return self.qualify(name);
+        }
+
+        match name.split_once('\\') {
+            // Qualified names are always resolved through the class imports, even
+            // for functions and constants, e.g. `Foo\bar()` with `use App\Foo`.
+            Some((first, rest)) => match self.imports.get(&key(NameTable::Class, first)) {
+                Some(import) => format!("{}\\{}", import, rest),
+                None => self.qualify(name),
+            },
+            None => match self.imports.get(&key(table, name)) {
+                Some(import) => import.clone(),
+                None => self.qualify(name),
+            },
+        }
+    }
+
+    pub fn resolve_class(&self, name: &str) -> String {
+        self.resolve(NameTable::Class, name)
+    }
+
+    /// The global name PHP falls back to when an unqualified function or
+    /// constant isn't declared in the current namespace.
+    pub fn fallback(&self, table: NameTable, name: &str) -> Option<String> {
+        if table == NameTable::Class
+            || self.namespace.is_empty()
+            || name.contains('\\')
+            || self.imports.contains_key(&key(table, name))
+        {
+            return None;
+        }
+
+        Some(name.to_string())
+    }
+
+    fn qualify(&self, name: &str) -> String {
+        match self.namespace.as_str() {
+            "" => name.to_string(),
+            namespace => format!("{}\\{}", namespace, name),
+        }
+    }
+
+    fn import_all(&mut self, kind: &UseKind, prefix: &str, uses: &[Use]) {
+        let table = match kind {
+            UseKind::Normal => NameTable::Class,
+            UseKind::Function => NameTable::Function,
+            UseKind::Const => NameTable::Constant,
+        };
+
+        for r#use in uses {
+            let name = format!("{}{}", prefix, r#use.name.name);
+            let alias = r#use.alias.as_ref().map(|alias| alias.name.to_string());
+
+            self.import(table, &name, alias.as_deref());
+        }
+    }
+}
+
+/// Call `visit` with every node of a program, along with the scope it's in.
+///
+/// Nodes are visited in the order they appear, parents before their children.
+/// Namespace and `use` statements themselves aren't visited, only their effect
+/// on the scope of the statements that follow them.
+pub fn walk<'n, 'a>(program: &'n Program<'a>, mut visit: impl FnMut(&Scope, Node<'n, 'a>)) {
+    let mut scope = Scope::default();
+
+    for statement in program {
+        match statement {
+            Statement::Namespace { name, body } => {
+                scope = Scope::new(&name.name.to_string());
+                walk_statements(&mut scope, body, &mut visit);
+            }
+            Statement::BracedNamespace { name, body } => {
+                scope = Scope::new(
+                    &name
+                        .as_ref()
+                        .map(|name| name.name.to_string())
+                        .unwrap_or_default(),
+                );
+                walk_statements(&mut scope, body, &mut visit);
+            }
+            _ => walk_statements(&mut scope, std::slice::from_ref(statement), &mut visit),
+        }
+    }
+}
+
+fn walk_statements<'n, 'a>(
+    scope: &mut Scope,
+    statements: &'n [Statement<'a>],
+    visit: &mut impl FnMut(&Scope, Node<'n, 'a>),
+) {
+    for statement in statements {
+        match statement {
+            Statement::Use { uses, kind } => scope.import_all(kind, "", uses),
+            Statement::GroupUse { prefix, kind, uses } => {
+                scope.import_all(kind, &prefix.name.to_string(), uses)
+            }
+            _ => {
+                let mut stack = vec![Node::Statement(statement)];
+                while let Some(node) = stack.pop() {
+                    visit(scope, node);
+
+                    let mut children = node.children();
+                    children.reverse();
+                    stack.extend(children);
+                }
+            }
+        }
+    }
+}
+
+fn is_special_class(name: &str) -> bool {
+    ["self", "static", "parent"]
+        .iter()
+        .any(|special| name.eq_ignore_ascii_case(special))
+}
+
+fn last_segment(name: &str) -> &str {
+    match name.rfind('\\') {
+        Some(separator) => &name[separator + 1..],
+        None => name,
+    }
+}
@@ -1,16 +1,18 @@
+use serde::Serialize;
+
 use crate::lexer::token::Span;
 use crate::parser::ast::Expression;
 
-#[derive(Debug, PartialEq, Clone)]
-pub struct Attribute {
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct Attribute<'a> {
     pub start: Span,
     pub end: Span,
-    pub expression: Expression,
+    pub expression: Expression<'a>,
 }
 
-#[derive(Debug, PartialEq, Clone)]
-pub struct AttributeGroup {
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct AttributeGroup<'a> {
     pub start: Span,
     pub end: Span,
-    pub members: Vec<Attribute>,
+    pub members: Vec<Attribute<'a>>,
 }
@@ -1,50 +1,54 @@
+use serde::Serialize;
+
 use crate::lexer::token::Span;
 use crate::parser::ast::attributes::AttributeGroup;
 use crate::parser::ast::constant::ClassishConstant;
 use crate::parser::ast::functions::Method;
 use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::modifiers::ClassModifierGroup;
 use crate::parser::ast::properties::Property;
 use crate::parser::ast::properties::VariableProperty;
 use crate::parser::ast::traits::TraitUsage;
 
-#[derive(Debug, PartialEq, Clone)]
-pub struct Class {
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct Class<'a> {
     pub start: Span,
     pub end: Span,
-    pub name: SimpleIdentifier,
-    pub extends: Option<ClassExtends>,
-    pub implements: Option<ClassImplements>,
-    pub attributes: Vec<AttributeGroup>,
-    pub members: Vec<ClassMember>,
+    pub name: SimpleIdentifier<'a>,
+    pub extends: Option<ClassExtends<'a>>,
+    pub implements: Option<ClassImplements<'a>>,
+    pub attributes: Vec<AttributeGroup<'a>>,
+    pub modifiers: ClassModifierGroup,
+    pub members: Vec<ClassMember<'a>>,
 }
 
-#[derive(Debug, PartialEq, Clone)]
-pub struct AnonymousClass {
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct AnonymousClass<'a> {
     pub start: Span,
     pub end: Span,
-    pub extends: Option<ClassExtends>,
-    pub implements: Option<ClassImplements>,
-    pub attributes: Vec<AttributeGroup>,
-    pub members: Vec<ClassMember>,
+    pub extends: Option<ClassExtends<'a>>,
+    pub implements: Option<ClassImplements<'a>>,
+    pub attributes: Vec<AttributeGroup<'a>>,
+    pub members: Vec<ClassMember<'a>>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct ClassExtends {
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ClassExtends<'a> {
     pub span: Span,
-    pub parent: SimpleIdentifier,
+    pub parent: SimpleIdentifier<'a>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct ClassImplements {
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ClassImplements<'a> {
     pub span: Span,
-    pub interfaces: Vec<SimpleIdentifier>,
+    pub interfaces: Vec<SimpleIdentifier<'a>>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub enum ClassMember {
-    Constant(ClassishConstant),
-    TraitUsage(TraitUsage),
-    Property(Property),
-    VariableProperty(VariableProperty),
-    Method(Method),
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum ClassMember<'a> {
+    Constant(ClassishConstant<'a>),
+    TraitUsage(TraitUsage<'a>),
+    Property(Property<'a>),
+    VariableProperty(VariableProperty<'a>),
+    Method(Method<'a>),
 }
@@ -12,9 +12,9 @@ pub enum CommentFormat {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
-pub struct Comment {
+pub struct Comment<'a> {
     pub start: Span,
     pub end: Span,
     pub format: CommentFormat,
-    pub content: ByteString,
+    pub content: ByteString<'a>,
 }
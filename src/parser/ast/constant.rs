@@ -1,27 +1,29 @@
+use serde::Serialize;
+
 use crate::lexer::token::Span;
 use crate::parser::ast::attributes::AttributeGroup;
 use crate::parser::ast::identifiers::SimpleIdentifier;
 use crate::parser::ast::modifiers::ConstantModifierGroup;
 use crate::parser::ast::Expression;
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct ConstantEntry {
-    pub name: SimpleIdentifier,
-    pub value: Expression,
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConstantEntry<'a> {
+    pub name: SimpleIdentifier<'a>,
+    pub value: Expression<'a>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct Constant {
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Constant<'a> {
     pub start: Span,
     pub end: Span,
-    pub entries: Vec<ConstantEntry>,
+    pub entries: Vec<ConstantEntry<'a>>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct ClassishConstant {
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ClassishConstant<'a> {
     pub start: Span,
     pub end: Span,
-    pub attributes: Vec<AttributeGroup>,
+    pub attributes: Vec<AttributeGroup<'a>>,
     pub modifiers: ConstantModifierGroup,
-    pub entries: Vec<ConstantEntry>,
+    pub entries: Vec<ConstantEntry<'a>>,
 }
@@ -0,0 +1,414 @@
+use crate::lexer::byte_string::ByteString;
+use crate::lexer::token::Span;
+use crate::parser::ast::classes::AnonymousClass;
+use crate::parser::ast::functions::ArrowFunction;
+use crate::parser::ast::functions::Closure;
+use crate::parser::ast::functions::FunctionParameterList;
+use crate::parser::ast::identifiers::Identifier;
+use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::operators::ArithmeticOperation;
+use crate::parser::ast::operators::AssignmentOperation;
+use crate::parser::ast::operators::BitwiseOperation;
+use crate::parser::ast::operators::ComparisonOperation;
+use crate::parser::ast::operators::LogicalOperation;
+use crate::parser::ast::variables::SimpleVariable;
+use crate::parser::ast::variables::Variable;
+use crate::parser::ast::Arg;
+use crate::parser::ast::Expression;
+use crate::parser::ast::StringPart;
+
+// Long chains of operators, method calls or property fetches, and deeply nested
+// arrays or calls, produce very deep trees, e.g. a generated string
+// concatenation with thousands of terms. Dropping
+// those recursively would overflow the stack, so the nested expressions are moved
+// onto a heap-allocated stack and dropped one at a time instead.
+impl<'a> Drop for Expression<'a> {
+    fn drop(&mut self) {
+        let mut stack = Vec::new();
+        self.take_children(&mut stack);
+
+        while let Some(mut expression) = stack.pop() {
+            expression.take_children(&mut stack);
+        }
+    }
+}
+
+impl<'a> Expression<'a> {
+    // Move the sub-expressions out of this expression, leaving empty expressions
+    // and lists behind.
+    fn take_children(&mut self, stack: &mut Vec<Expression<'a>>) {
+        fn take<'a>(expression: &mut Expression<'a>) -> Expression<'a> {
+            std::mem::replace(expression, Expression::Empty)
+        }
+
+        fn take_args<'a>(args: &mut Vec<Arg<'a>>, stack: &mut Vec<Expression<'a>>) {
+            stack.extend(args.drain(..).map(|arg| arg.value));
+        }
+
+        match self {
+            Expression::ArithmeticOperation(operation) => match operation {
+                ArithmeticOperation::Addition { left, right, .. }
+                | ArithmeticOperation::Subtraction { left, right, .. }
+                | ArithmeticOperation::Multiplication { left, right, .. }
+                | ArithmeticOperation::Division { left, right, .. }
+                | ArithmeticOperation::Modulo { left, right, .. }
+                | ArithmeticOperation::Exponentiation { left, right, .. } => {
+                    stack.push(take(left));
+                    stack.push(take(right));
+                }
+                ArithmeticOperation::Negation { right, .. }
+                | ArithmeticOperation::Identity { right, .. }
+                | ArithmeticOperation::PreIncrement { right, .. }
+                | ArithmeticOperation::PreDecrement { right, .. } => {
+                    stack.push(take(right));
+                }
+                ArithmeticOperation::PostIncrement { left, .. }
+                | ArithmeticOperation::PostDecrement { left, .. } => {
+                    stack.push(take(left));
+                }
+            },
+            Expression::AssignmentOperation(operation) => match operation {
+                AssignmentOperation::Assign { left, right, .. }
+                | AssignmentOperation::Addition { left, right, .. }
+                | AssignmentOperation::Subtraction { left, right, .. }
+                | AssignmentOperation::Multiplication { left, right, .. }
+                | AssignmentOperation::Division { left, right, .. }
+                | AssignmentOperation::Modulo { left, right, .. }
+                | AssignmentOperation::Exponentiation { left, right, .. }
+                | AssignmentOperation::Concat { left, right, .. }
+                | AssignmentOperation::BitwiseAnd { left, right, .. }
+                | AssignmentOperation::BitwiseOr { left, right, .. }
+                | AssignmentOperation::BitwiseXor { left, right, .. }
+                | AssignmentOperation::LeftShift { left, right, .. }
+                | AssignmentOperation::RightShift { left, right, .. }
+                | AssignmentOperation::Coalesce { left, right, .. } => {
+                    stack.push(take(left));
+                    stack.push(take(right));
+                }
+            },
+            Expression::BitwiseOperation(operation) => match operation {
+                BitwiseOperation::And { left, right, .. }
+                | BitwiseOperation::Or { left, right, .. }
+                | BitwiseOperation::Xor { left, right, .. }
+                | BitwiseOperation::LeftShift { left, right, .. }
+                | BitwiseOperation::RightShift { left, right, .. } => {
+                    stack.push(take(left));
+                    stack.push(take(right));
+                }
+                BitwiseOperation::Not { right, .. } => {
+                    stack.push(take(right));
+                }
+            },
+            Expression::ComparisonOperation(operation) => match operation {
+                ComparisonOperation::Equal { left, right, .. }
+                | ComparisonOperation::Identical { left, right, .. }
+                | ComparisonOperation::NotEqual { left, right, .. }
+                | ComparisonOperation::AngledNotEqual { left, right, .. }
+                | ComparisonOperation::NotIdentical { left, right, .. }
+                | ComparisonOperation::LessThan { left, right, .. }
+                | ComparisonOperation::GreaterThan { left, right, .. }
+                | ComparisonOperation::LessThanOrEqual { left, right, .. }
+                | ComparisonOperation::GreaterThanOrEqual { left, right, .. }
+                | ComparisonOperation::Spaceship { left, right, .. } => {
+                    stack.push(take(left));
+                    stack.push(take(right));
+                }
+            },
+            Expression::LogicalOperation(operation) => match operation {
+                LogicalOperation::And { left, right, .. }
+                | LogicalOperation::Or { left, right, .. }
+                | LogicalOperation::LogicalAnd { left, right, .. }
+                | LogicalOperation::LogicalOr { left, right, .. }
+                | LogicalOperation::LogicalXor { left, right, .. } => {
+                    stack.push(take(left));
+                    stack.push(take(right));
+                }
+                LogicalOperation::Not { right, .. } => {
+                    stack.push(take(right));
+                }
+            },
+            Expression::Concat { left, right, .. } | Expression::Instanceof { left, right, .. } => {
+                stack.push(take(left));
+                stack.push(take(right));
+            }
+            Expression::Reference { right, .. } => {
+                stack.push(take(right));
+            }
+            Expression::Parenthesized { expr, .. } | Expression::ErrorSuppress { expr, .. } => {
+                stack.push(take(expr));
+            }
+            Expression::Include { path, .. } => {
+                stack.push(take(path));
+            }
+            Expression::Call { target, args } | Expression::New { target, args, .. } => {
+                stack.push(take(target));
+                take_args(args, stack);
+            }
+            Expression::ConstFetch { target, .. } | Expression::Clone { target } => {
+                stack.push(take(target));
+            }
+            Expression::Array { items } => {
+                for item in items.drain(..) {
+                    stack.extend(item.key);
+                    stack.push(item.value);
+                }
+            }
+            Expression::List { items } => {
+                for item in items.drain(..) {
+                    stack.extend(item.key);
+                    stack.push(item.value);
+                }
+            }
+            Expression::InterpolatedString { parts }
+            | Expression::Heredoc { parts }
+            | Expression::ShellExec { parts } => {
+                for part in parts.drain(..) {
+                    if let StringPart::Expr(expression) = part {
+                        stack.push(*expression);
+                    }
+                }
+            }
+            Expression::PropertyFetch { target, property }
+            | Expression::NullsafePropertyFetch { target, property }
+            | Expression::StaticPropertyFetch { target, property } => {
+                stack.push(take(target));
+                stack.push(take(property));
+            }
+            Expression::NullsafeMethodCall {
+                target,
+                method,
+                args,
+            }
+            | Expression::MethodCall {
+                target,
+                method,
+                args,
+            }
+            | Expression::StaticMethodCall {
+                target,
+                method,
+                args,
+            } => {
+                stack.push(take(target));
+                stack.push(take(method));
+                take_args(args, stack);
+            }
+            Expression::ArrayIndex { array, index } => {
+                stack.push(take(array));
+                if let Some(index) = index {
+                    stack.push(take(index));
+                }
+            }
+            Expression::Ternary {
+                condition,
+                then,
+                r#else,
+            } => {
+                stack.push(take(condition));
+                if let Some(then) = then {
+                    stack.push(take(then));
+                }
+                stack.push(take(r#else));
+            }
+            Expression::Coalesce { lhs, rhs } => {
+                stack.push(take(lhs));
+                stack.push(take(rhs));
+            }
+            Expression::Match {
+                condition,
+                default,
+                arms,
+            } => {
+                stack.push(take(condition));
+                if let Some(default) = default.take() {
+                    stack.push(default.body);
+                }
+                for arm in arms.drain(..) {
+                    stack.extend(arm.conditions);
+                    stack.push(arm.body);
+                }
+            }
+            Expression::Throw { value }
+            | Expression::YieldFrom { value }
+            | Expression::BitwiseNot { value, .. }
+            | Expression::Print { value, .. }
+            | Expression::Cast { value, .. } => {
+                stack.push(take(value));
+            }
+            Expression::Yield { key, value } => {
+                if let Some(key) = key {
+                    stack.push(take(key));
+                }
+                if let Some(value) = value {
+                    stack.push(take(value));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// Because `Expression` implements `Drop`, the fields of its variants can't be moved
+// out with a plain `match`. Conversions such as `into_owned` take
+// them out one by one instead, leaving placeholders behind for the (now empty)
+// expression to drop.
+pub(super) trait Placeholder {
+    fn placeholder() -> Self;
+}
+
+pub(super) fn take<T: Placeholder>(value: &mut T) -> T {
+    std::mem::replace(value, T::placeholder())
+}
+
+impl<'a> Placeholder for Expression<'a> {
+    fn placeholder() -> Self {
+        Expression::Empty
+    }
+}
+
+impl<T> Placeholder for Vec<T> {
+    fn placeholder() -> Self {
+        Vec::new()
+    }
+}
+
+impl<T> Placeholder for Option<T> {
+    fn placeholder() -> Self {
+        None
+    }
+}
+
+impl<'a> Placeholder for ByteString<'a> {
+    fn placeholder() -> Self {
+        ByteString::borrowed(b"")
+    }
+}
+
+impl<'a> Placeholder for SimpleIdentifier<'a> {
+    fn placeholder() -> Self {
+        SimpleIdentifier {
+            span: Span::default(),
+            name: ByteString::placeholder(),
+            symbol: None,
+        }
+    }
+}
+
+impl<'a> Placeholder for Identifier<'a> {
+    fn placeholder() -> Self {
+        Identifier::SimpleIdentifier(SimpleIdentifier::placeholder())
+    }
+}
+
+impl<'a> Placeholder for Variable<'a> {
+    fn placeholder() -> Self {
+        Variable::SimpleVariable(SimpleVariable {
+            span: Span::default(),
+            name: ByteString::placeholder(),
+            symbol: None,
+        })
+    }
+}
+
+impl<'a> Placeholder for FunctionParameterList<'a> {
+    fn placeholder() -> Self {
+        FunctionParameterList {
+            start: Span::default(),
+            end: Span::default(),
+            members: Vec::new(),
+        }
+    }
+}
+
+impl<'a> Placeholder for Closure<'a> {
+    fn placeholder() -> Self {
+        Closure {
+            start: Span::default(),
+            end: Span::default(),
+            attributes: Vec::new(),
+            parameters: FunctionParameterList::placeholder(),
+            return_ty: None,
+            uses: Vec::new(),
+            by_ref: false,
+            body: Vec::new(),
+            r#static: false,
+        }
+    }
+}
+
+impl<'a> Placeholder for AnonymousClass<'a> {
+    fn placeholder() -> Self {
+        AnonymousClass {
+            start: Span::default(),
+            end: Span::default(),
+            extends: None,
+            implements: None,
+            attributes: Vec::new(),
+            members: Vec::new(),
+        }
+    }
+}
+
+// The placeholders below allocate an empty box, which is only freed again when
+// the expression they were taken from is dropped.
+impl<'a> Placeholder for ArrowFunction<'a> {
+    fn placeholder() -> Self {
+        ArrowFunction {
+            start: Span::default(),
+            end: Span::default(),
+            attributes: Vec::new(),
+            parameters: FunctionParameterList::placeholder(),
+            return_type: None,
+            by_ref: false,
+            body: Box::new(Expression::Empty),
+            r#static: false,
+        }
+    }
+}
+
+impl<'a> Placeholder for ArithmeticOperation<'a> {
+    fn placeholder() -> Self {
+        ArithmeticOperation::Negation {
+            span: Span::default(),
+            right: Box::new(Expression::Empty),
+        }
+    }
+}
+
+impl<'a> Placeholder for AssignmentOperation<'a> {
+    fn placeholder() -> Self {
+        AssignmentOperation::Assign {
+            left: Box::new(Expression::Empty),
+            span: Span::default(),
+            right: Box::new(Expression::Empty),
+        }
+    }
+}
+
+impl<'a> Placeholder for BitwiseOperation<'a> {
+    fn placeholder() -> Self {
+        BitwiseOperation::Not {
+            span: Span::default(),
+            right: Box::new(Expression::Empty),
+        }
+    }
+}
+
+impl<'a> Placeholder for ComparisonOperation<'a> {
+    fn placeholder() -> Self {
+        ComparisonOperation::Equal {
+            left: Box::new(Expression::Empty),
+            span: Span::default(),
+            right: Box::new(Expression::Empty),
+        }
+    }
+}
+
+impl<'a> Placeholder for LogicalOperation<'a> {
+    fn placeholder() -> Self {
+        LogicalOperation::Not {
+            span: Span::default(),
+            right: Box::new(Expression::Empty),
+        }
+    }
+}
@@ -7,29 +7,29 @@ use crate::parser::ast::functions::Method;
 use crate::parser::ast::identifiers::SimpleIdentifier;
 use crate::parser::ast::Expression;
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct UnitEnumCase {
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UnitEnumCase<'a> {
     pub start: Span,
     pub end: Span,
-    pub attributes: Vec<AttributeGroup>,
-    pub name: SimpleIdentifier,
+    pub attributes: Vec<AttributeGroup<'a>>,
+    pub name: SimpleIdentifier<'a>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub enum UnitEnumMember {
-    Case(UnitEnumCase),
-    Method(Method),
-    Constant(ClassishConstant),
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum UnitEnumMember<'a> {
+    Case(UnitEnumCase<'a>),
+    Method(Method<'a>),
+    Constant(ClassishConstant<'a>),
 }
 
-#[derive(Debug, PartialEq, Clone)]
-pub struct UnitEnum {
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct UnitEnum<'a> {
     pub start: Span,
     pub end: Span,
-    pub name: SimpleIdentifier,
-    pub attributes: Vec<AttributeGroup>,
-    pub implements: Vec<SimpleIdentifier>,
-    pub members: Vec<UnitEnumMember>,
+    pub name: SimpleIdentifier<'a>,
+    pub attributes: Vec<AttributeGroup<'a>>,
+    pub implements: Vec<SimpleIdentifier<'a>>,
+    pub members: Vec<UnitEnumMember<'a>>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
@@ -38,29 +38,29 @@ pub enum BackedEnumType {
     Int(Span),
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct BackedEnumCase {
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BackedEnumCase<'a> {
     pub start: Span,
     pub end: Span,
-    pub name: SimpleIdentifier,
-    pub attributes: Vec<AttributeGroup>,
-    pub value: Expression,
+    pub name: SimpleIdentifier<'a>,
+    pub attributes: Vec<AttributeGroup<'a>>,
+    pub value: Expression<'a>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub enum BackedEnumMember {
-    Case(BackedEnumCase),
-    Method(Method),
-    Constant(ClassishConstant),
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum BackedEnumMember<'a> {
+    Case(BackedEnumCase<'a>),
+    Method(Method<'a>),
+    Constant(ClassishConstant<'a>),
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct BackedEnum {
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BackedEnum<'a> {
     pub start: Span,
     pub end: Span,
-    pub name: SimpleIdentifier,
-    pub attributes: Vec<AttributeGroup>,
-    pub implements: Vec<SimpleIdentifier>,
+    pub name: SimpleIdentifier<'a>,
+    pub attributes: Vec<AttributeGroup<'a>>,
+    pub implements: Vec<SimpleIdentifier<'a>>,
     pub backed_type: BackedEnumType,
-    pub members: Vec<BackedEnumMember>,
+    pub members: Vec<BackedEnumMember<'a>>,
 }
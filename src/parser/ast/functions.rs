@@ -1,3 +1,5 @@
+use serde::Serialize;
+
 use crate::lexer::token::Span;
 use crate::parser::ast::attributes::AttributeGroup;
 use crate::parser::ast::identifiers::SimpleIdentifier;
@@ -8,97 +10,97 @@ use crate::parser::ast::Block;
 use crate::parser::ast::Expression;
 use crate::parser::ast::Type;
 
-#[derive(Debug, PartialEq, Clone)]
-pub struct FunctionParameter {
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct FunctionParameter<'a> {
     pub start: Span,
     pub end: Span,
-    pub name: SimpleVariable,
-    pub attributes: Vec<AttributeGroup>,
-    pub r#type: Option<Type>,
+    pub name: SimpleVariable<'a>,
+    pub attributes: Vec<AttributeGroup<'a>>,
+    pub r#type: Option<Type<'a>>,
     pub variadic: bool,
-    pub default: Option<Expression>,
+    pub default: Option<Expression<'a>>,
     pub by_ref: bool,
 }
 
-#[derive(Debug, PartialEq, Clone)]
-pub struct FunctionParameterList {
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct FunctionParameterList<'a> {
     pub start: Span,
     pub end: Span,
-    pub members: Vec<FunctionParameter>,
+    pub members: Vec<FunctionParameter<'a>>,
 }
 
-#[derive(Debug, PartialEq, Clone)]
-pub struct Function {
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct Function<'a> {
     pub start: Span,
     pub end: Span,
-    pub name: SimpleIdentifier,
-    pub attributes: Vec<AttributeGroup>,
-    pub parameters: FunctionParameterList,
-    pub return_type: Option<Type>,
+    pub name: SimpleIdentifier<'a>,
+    pub attributes: Vec<AttributeGroup<'a>>,
+    pub parameters: FunctionParameterList<'a>,
+    pub return_type: Option<Type<'a>>,
     pub by_ref: bool,
-    pub body: Block,
+    pub body: Block<'a>,
 }
 
-#[derive(Debug, PartialEq, Clone)]
-pub struct ClosureUse {
-    pub var: Expression,
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct ClosureUse<'a> {
+    pub var: Expression<'a>,
     pub by_ref: bool,
 }
 
-#[derive(Debug, PartialEq, Clone)]
-pub struct Closure {
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct Closure<'a> {
     pub start: Span,
     pub end: Span,
-    pub attributes: Vec<AttributeGroup>,
-    pub parameters: FunctionParameterList,
-    pub return_ty: Option<Type>,
-    pub uses: Vec<ClosureUse>,
+    pub attributes: Vec<AttributeGroup<'a>>,
+    pub parameters: FunctionParameterList<'a>,
+    pub return_ty: Option<Type<'a>>,
+    pub uses: Vec<ClosureUse<'a>>,
     pub by_ref: bool,
-    pub body: Block,
+    pub body: Block<'a>,
     pub r#static: bool,
 }
 
-#[derive(Debug, PartialEq, Clone)]
-pub struct ArrowFunction {
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct ArrowFunction<'a> {
     pub start: Span,
     pub end: Span,
-    pub attributes: Vec<AttributeGroup>,
-    pub parameters: FunctionParameterList,
-    pub return_type: Option<Type>,
+    pub attributes: Vec<AttributeGroup<'a>>,
+    pub parameters: FunctionParameterList<'a>,
+    pub return_type: Option<Type<'a>>,
     pub by_ref: bool,
-    pub body: Box<Expression>,
+    pub body: Box<Expression<'a>>,
     pub r#static: bool,
 }
 
-#[derive(Debug, PartialEq, Clone)]
-pub struct MethodParameter {
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct MethodParameter<'a> {
     pub start: Span,
     pub end: Span,
-    pub name: SimpleVariable,
-    pub attributes: Vec<AttributeGroup>,
-    pub r#type: Option<Type>,
+    pub name: SimpleVariable<'a>,
+    pub attributes: Vec<AttributeGroup<'a>>,
+    pub r#type: Option<Type<'a>>,
     pub variadic: bool,
-    pub default: Option<Expression>,
+    pub default: Option<Expression<'a>>,
     pub modifiers: PromotedPropertyModifierGroup,
     pub by_ref: bool,
 }
 
-#[derive(Debug, PartialEq, Clone)]
-pub struct MethodParameterList {
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct MethodParameterList<'a> {
     pub start: Span,
     pub end: Span,
-    pub members: Vec<MethodParameter>,
+    pub members: Vec<MethodParameter<'a>>,
 }
 
-#[derive(Debug, PartialEq, Clone)]
-pub struct Method {
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct Method<'a> {
     pub start: Span,
     pub end: Span,
-    pub name: SimpleIdentifier,
-    pub attributes: Vec<AttributeGroup>,
-    pub parameters: MethodParameterList,
-    pub body: Option<Block>,
+    pub name: SimpleIdentifier<'a>,
+    pub attributes: Vec<AttributeGroup<'a>>,
+    pub parameters: MethodParameterList<'a>,
+    pub body: Option<Block<'a>>,
     pub modifiers: MethodModifierGroup,
-    pub return_type: Option<Type>,
+    pub return_type: Option<Type<'a>>,
     pub by_ref: bool,
 }
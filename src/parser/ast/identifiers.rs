@@ -3,30 +3,54 @@ use std::fmt::Display;
 use serde::{Deserialize, Serialize};
 
 use crate::lexer::byte_string::ByteString;
+use crate::lexer::interner::Symbol;
 use crate::lexer::token::Span;
 use crate::parser::ast::Expression;
 
-#[derive(Debug, PartialEq, Clone)]
-pub enum Identifier {
-    SimpleIdentifier(SimpleIdentifier),
-    DynamicIdentifier(DynamicIdentifier),
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub enum Identifier<'a> {
+    SimpleIdentifier(SimpleIdentifier<'a>),
+    DynamicIdentifier(DynamicIdentifier<'a>),
 }
 
-#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
-pub struct SimpleIdentifier {
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SimpleIdentifier<'a> {
     pub span: Span,
-    pub name: ByteString,
+    pub name: ByteString<'a>,
+    #[serde(skip)]
+    pub symbol: Option<Symbol>,
 }
 
-impl Display for SimpleIdentifier {
+impl<'a> PartialEq for SimpleIdentifier<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.span == other.span
+            && match (self.symbol, other.symbol) {
+                (Some(a), Some(b)) if a.is_comparable_to(b) => a == b,
+                _ => self.name == other.name,
+            }
+    }
+}
+
+impl<'a> Eq for SimpleIdentifier<'a> {}
+
+impl<'a> std::fmt::Debug for SimpleIdentifier<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SimpleIdentifier")
+            .field("span", &self.span)
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+impl<'a> Display for SimpleIdentifier<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.name)
     }
 }
 
-#[derive(Debug, PartialEq, Clone)]
-pub struct DynamicIdentifier {
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct DynamicIdentifier<'a> {
     pub start: Span,
-    pub expr: Box<Expression>,
+    pub expr: Box<Expression<'a>>,
     pub end: Span,
 }
@@ -1,27 +1,29 @@
+use serde::Serialize;
+
 use crate::lexer::token::Span;
 use crate::parser::ast::attributes::AttributeGroup;
 use crate::parser::ast::constant::ClassishConstant;
 use crate::parser::ast::functions::Method;
 use crate::parser::ast::identifiers::SimpleIdentifier;
 
-#[derive(Debug, Clone, PartialEq)]
-pub enum InterfaceMember {
-    Constant(ClassishConstant),
-    Method(Method),
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum InterfaceMember<'a> {
+    Constant(ClassishConstant<'a>),
+    Method(Method<'a>),
 }
 
-#[derive(Debug, PartialEq, Clone)]
-pub struct Interface {
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct Interface<'a> {
     pub start: Span,
     pub end: Span,
-    pub attributes: Vec<AttributeGroup>,
-    pub name: SimpleIdentifier,
-    pub extends: Option<InterfaceExtends>,
-    pub members: Vec<InterfaceMember>,
+    pub attributes: Vec<AttributeGroup<'a>>,
+    pub name: SimpleIdentifier<'a>,
+    pub extends: Option<InterfaceExtends<'a>>,
+    pub members: Vec<InterfaceMember<'a>>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
-pub struct InterfaceExtends {
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub struct InterfaceExtends<'a> {
     pub span: Span,
-    pub parents: Vec<SimpleIdentifier>,
+    pub parents: Vec<SimpleIdentifier<'a>>,
 }
@@ -7,14 +7,22 @@ pub mod functions;
 pub mod identifiers;
 pub mod interfaces;
 pub mod modifiers;
+pub mod node;
 pub mod operators;
+pub mod owned;
 pub mod properties;
 pub mod traits;
 pub mod try_block;
 pub mod variables;
 
+pub(crate) mod spans;
+
+mod drop;
+
 use std::fmt::Display;
 
+use serde::{Deserialize, Serialize};
+
 use crate::lexer::byte_string::ByteString;
 use crate::lexer::token::Span;
 use crate::lexer::token::TokenKind;
@@ -40,16 +48,16 @@ use self::operators::BitwiseOperation;
 use self::operators::ComparisonOperation;
 use self::operators::LogicalOperation;
 
-pub type Block = Vec<Statement>;
-pub type Program = Block;
+pub type Block<'a> = Vec<Statement<'a>>;
+pub type Program<'a> = Block<'a>;
 
-#[derive(Debug, Eq, PartialEq, Clone)]
-pub enum Type {
-    Identifier(SimpleIdentifier),
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+pub enum Type<'a> {
+    Identifier(SimpleIdentifier<'a>),
     // TODO: add `start` and `end` for all types.
-    Nullable(Box<Type>),
-    Union(Vec<Type>),
-    Intersection(Vec<Type>),
+    Nullable(Box<Type<'a>>),
+    Union(Vec<Type<'a>>),
+    Intersection(Vec<Type<'a>>),
     Void,
     Null,
     True,
@@ -69,7 +77,7 @@ pub enum Type {
     ParentReference,
 }
 
-impl Type {
+impl<'a> Type<'a> {
     pub fn standalone(&self) -> bool {
         matches!(self, Type::Mixed | Type::Never | Type::Void)
     }
@@ -103,7 +111,7 @@ impl Type {
     }
 }
 
-impl Display for Type {
+impl<'a> Display for Type<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match &self {
             Type::Identifier(inner) => write!(f, "{}", inner),
@@ -147,20 +155,20 @@ impl Display for Type {
     }
 }
 
-#[derive(Debug, Eq, PartialEq, Clone)]
+#[derive(Debug, Eq, PartialEq, Clone, Serialize)]
 pub enum UseKind {
     Normal,
     Function,
     Const,
 }
 
-#[derive(Debug, PartialEq, Clone)]
-pub struct StaticVar {
-    pub var: Variable,
-    pub default: Option<Expression>,
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct StaticVar<'a> {
+    pub var: Variable<'a>,
+    pub default: Option<Expression<'a>>,
 }
 
-#[derive(Debug, Eq, PartialEq, Clone)]
+#[derive(Debug, Eq, PartialEq, Clone, Serialize)]
 pub enum IncludeKind {
     Include,
     IncludeOnce,
@@ -168,7 +176,7 @@ pub enum IncludeKind {
     RequireOnce,
 }
 
-impl From<&TokenKind> for IncludeKind {
+impl From<&TokenKind<'_>> for IncludeKind {
     fn from(k: &TokenKind) -> Self {
         match k {
             TokenKind::Include => IncludeKind::Include,
@@ -180,115 +188,116 @@ impl From<&TokenKind> for IncludeKind {
     }
 }
 
-#[derive(Debug, PartialEq, Clone)]
-pub enum Statement {
-    InlineHtml(ByteString),
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub enum Statement<'a> {
+    InlineHtml(ByteString<'a>),
     Goto {
-        label: SimpleIdentifier,
+        label: SimpleIdentifier<'a>,
     },
     Label {
-        label: SimpleIdentifier,
+        label: SimpleIdentifier<'a>,
     },
     HaltCompiler {
-        content: Option<ByteString>,
+        content: Option<ByteString<'a>>,
     },
     Static {
-        vars: Vec<StaticVar>,
+        vars: Vec<StaticVar<'a>>,
     },
     DoWhile {
-        condition: Expression,
-        body: Block,
+        condition: Expression<'a>,
+        body: Block<'a>,
     },
     While {
-        condition: Expression,
-        body: Block,
+        condition: Expression<'a>,
+        body: Block<'a>,
     },
     For {
-        init: Vec<Expression>,
-        condition: Vec<Expression>,
-        r#loop: Vec<Expression>,
-        then: Block,
+        init: Vec<Expression<'a>>,
+        condition: Vec<Expression<'a>>,
+        r#loop: Vec<Expression<'a>>,
+        then: Block<'a>,
     },
     Foreach {
-        expr: Expression,
+        expr: Expression<'a>,
         by_ref: bool,
-        key_var: Option<Expression>,
-        value_var: Expression,
-        body: Block,
-    },
-    Constant(Constant),
-    Function(Function),
-    Class(Class),
-    Trait(Trait),
-    Interface(Interface),
+        key_var: Option<Expression<'a>>,
+        value_var: Expression<'a>,
+        body: Block<'a>,
+    },
+    Constant(Constant<'a>),
+    Function(Function<'a>),
+    Class(Class<'a>),
+    Trait(Trait<'a>),
+    Interface(Interface<'a>),
     If {
-        condition: Expression,
-        then: Block,
-        else_ifs: Vec<ElseIf>,
-        r#else: Option<Block>,
+        condition: Expression<'a>,
+        then: Block<'a>,
+        else_ifs: Vec<ElseIf<'a>>,
+        r#else: Option<Block<'a>>,
     },
     Return {
-        value: Option<Expression>,
+        value: Option<Expression<'a>>,
     },
     Switch {
-        condition: Expression,
-        cases: Vec<Case>,
+        condition: Expression<'a>,
+        cases: Vec<Case<'a>>,
     },
     Break {
-        num: Option<Expression>,
+        num: Option<Expression<'a>>,
     },
     Continue {
-        num: Option<Expression>,
+        num: Option<Expression<'a>>,
     },
     Echo {
-        values: Vec<Expression>,
+        values: Vec<Expression<'a>>,
     },
     Expression {
-        expr: Expression,
+        expr: Expression<'a>,
     },
     Namespace {
-        name: SimpleIdentifier,
-        body: Block,
+        name: SimpleIdentifier<'a>,
+        body: Block<'a>,
     },
     BracedNamespace {
-        name: Option<SimpleIdentifier>,
-        body: Block,
+        name: Option<SimpleIdentifier<'a>>,
+        body: Block<'a>,
     },
     Use {
-        uses: Vec<Use>,
+        uses: Vec<Use<'a>>,
         kind: UseKind,
     },
     GroupUse {
-        prefix: SimpleIdentifier,
+        prefix: SimpleIdentifier<'a>,
         kind: UseKind,
-        uses: Vec<Use>,
+        uses: Vec<Use<'a>>,
     },
-    Comment(Comment),
-    Try(TryBlock),
-    UnitEnum(UnitEnum),
-    BackedEnum(BackedEnum),
+    Comment(Comment<'a>),
+    Try(TryBlock<'a>),
+    UnitEnum(UnitEnum<'a>),
+    BackedEnum(BackedEnum<'a>),
     Block {
-        body: Block,
+        body: Block<'a>,
     },
     Global {
         span: Span,
-        variables: Vec<Variable>,
+        variables: Vec<Variable<'a>>,
     },
     Declare {
-        declares: Vec<DeclareItem>,
-        body: Block,
+        declares: Vec<DeclareItem<'a>>,
+        body: Block<'a>,
     },
     Noop(Span),
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct DeclareItem {
-    pub key: SimpleIdentifier,
-    pub value: Expression,
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DeclareItem<'a> {
+    pub key: SimpleIdentifier<'a>,
+    pub value: Expression<'a>,
 }
 
 // See https://www.php.net/manual/en/language.types.type-juggling.php#language.types.typecasting for more info.
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
 pub enum CastKind {
     Int,
     Bool,
@@ -299,7 +308,7 @@ pub enum CastKind {
     Unset,
 }
 
-impl From<TokenKind> for CastKind {
+impl From<TokenKind<'_>> for CastKind {
     fn from(kind: TokenKind) -> Self {
         match kind {
             TokenKind::StringCast | TokenKind::BinaryCast => Self::String,
@@ -314,58 +323,62 @@ impl From<TokenKind> for CastKind {
     }
 }
 
-impl From<&TokenKind> for CastKind {
+impl From<&TokenKind<'_>> for CastKind {
     fn from(kind: &TokenKind) -> Self {
         kind.clone().into()
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
 pub enum BackedEnumType {
     String,
     Int,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct Case {
-    pub condition: Option<Expression>,
-    pub body: Block,
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Case<'a> {
+    pub condition: Option<Expression<'a>>,
+    pub body: Block<'a>,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
-pub struct Use {
-    pub name: SimpleIdentifier,
-    pub alias: Option<SimpleIdentifier>,
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct Use<'a> {
+    pub name: SimpleIdentifier<'a>,
+    pub alias: Option<SimpleIdentifier<'a>>,
 }
 
-#[derive(Debug, PartialEq, Clone)]
-pub enum Expression {
-    ArithmeticOperation(ArithmeticOperation),
-    AssignmentOperation(AssignmentOperation),
-    BitwiseOperation(BitwiseOperation),
-    ComparisonOperation(ComparisonOperation),
-    LogicalOperation(LogicalOperation),
+/// Expressions implement `Drop`, so that deeply nested ones don't overflow the
+/// stack when they are dropped. This means their fields can't be moved out by
+/// destructuring: match on a reference instead, and use `std::mem::replace` to
+/// take a nested expression out, leaving `Expression::Empty` behind.
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub enum Expression<'a> {
+    ArithmeticOperation(ArithmeticOperation<'a>),
+    AssignmentOperation(AssignmentOperation<'a>),
+    BitwiseOperation(BitwiseOperation<'a>),
+    ComparisonOperation(ComparisonOperation<'a>),
+    LogicalOperation(LogicalOperation<'a>),
     Concat {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     Instanceof {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     Reference {
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     Parenthesized {
         start: Span,
-        expr: Box<Expression>,
+        expr: Box<Expression<'a>>,
         end: Span,
     },
     List {
-        items: Vec<ListItem>,
+        items: Vec<ListItem<'a>>,
     },
     Empty,
     VariadicPlaceholder,
@@ -374,49 +387,49 @@ pub enum Expression {
         expr: Box<Self>,
     },
     LiteralInteger {
-        i: ByteString,
+        i: ByteString<'a>,
     },
     LiteralFloat {
-        f: ByteString,
+        f: ByteString<'a>,
     },
-    Identifier(Identifier),
-    Variable(Variable),
+    Identifier(Identifier<'a>),
+    Variable(Variable<'a>),
     Include {
         span: Span,
         kind: IncludeKind,
-        path: Box<Expression>,
+        path: Box<Expression<'a>>,
     },
     Call {
         target: Box<Self>,
-        args: Vec<Arg>,
+        args: Vec<Arg<'a>>,
     },
     Static,
     Self_,
     Parent,
     Array {
-        items: Vec<ArrayItem>,
+        items: Vec<ArrayItem<'a>>,
     },
-    Closure(Closure),
-    ArrowFunction(ArrowFunction),
+    Closure(Closure<'a>),
+    ArrowFunction(ArrowFunction<'a>),
     New {
         target: Box<Self>,
         span: Span,
-        args: Vec<Arg>,
+        args: Vec<Arg<'a>>,
     },
     LiteralString {
-        value: ByteString,
+        value: ByteString<'a>,
     },
     InterpolatedString {
-        parts: Vec<StringPart>,
+        parts: Vec<StringPart<'a>>,
     },
     Heredoc {
-        parts: Vec<StringPart>,
+        parts: Vec<StringPart<'a>>,
     },
     Nowdoc {
-        value: ByteString,
+        value: ByteString<'a>,
     },
     ShellExec {
-        parts: Vec<StringPart>,
+        parts: Vec<StringPart<'a>>,
     },
     PropertyFetch {
         target: Box<Self>,
@@ -429,7 +442,7 @@ pub enum Expression {
     NullsafeMethodCall {
         target: Box<Self>,
         method: Box<Self>,
-        args: Vec<Arg>,
+        args: Vec<Arg<'a>>,
     },
     StaticPropertyFetch {
         target: Box<Self>,
@@ -437,19 +450,19 @@ pub enum Expression {
     },
     ConstFetch {
         target: Box<Self>,
-        constant: SimpleIdentifier,
+        constant: SimpleIdentifier<'a>,
     },
     MethodCall {
         target: Box<Self>,
         method: Box<Self>,
-        args: Vec<Arg>,
+        args: Vec<Arg<'a>>,
     },
     StaticMethodCall {
         target: Box<Self>,
         method: Box<Self>,
-        args: Vec<Arg>,
+        args: Vec<Arg<'a>>,
     },
-    AnonymousClass(AnonymousClass),
+    AnonymousClass(AnonymousClass<'a>),
     Bool {
         value: bool,
     },
@@ -476,11 +489,11 @@ pub enum Expression {
     },
     Match {
         condition: Box<Self>,
-        default: Option<Box<DefaultMatchArm>>,
-        arms: Vec<MatchArm>,
+        default: Option<Box<DefaultMatchArm<'a>>>,
+        arms: Vec<MatchArm<'a>>,
     },
     Throw {
-        value: Box<Expression>,
+        value: Box<Expression<'a>>,
     },
     Yield {
         key: Option<Box<Self>>,
@@ -504,31 +517,31 @@ pub enum Expression {
     },
 }
 
-#[derive(Debug, PartialEq, Clone)]
-pub struct Arg {
-    pub name: Option<SimpleIdentifier>,
-    pub value: Expression,
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct Arg<'a> {
+    pub name: Option<SimpleIdentifier<'a>>,
+    pub value: Expression<'a>,
     pub unpack: bool,
 }
 
-#[derive(Debug, PartialEq, Clone)]
-pub struct ClosureUse {
-    pub var: Expression,
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct ClosureUse<'a> {
+    pub var: Expression<'a>,
     pub by_ref: bool,
 }
 
-#[derive(Debug, PartialEq, Clone)]
-pub struct DefaultMatchArm {
-    pub body: Expression,
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct DefaultMatchArm<'a> {
+    pub body: Expression<'a>,
 }
 
-#[derive(Debug, PartialEq, Clone)]
-pub struct MatchArm {
-    pub conditions: Vec<Expression>,
-    pub body: Expression,
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct MatchArm<'a> {
+    pub conditions: Vec<Expression<'a>>,
+    pub body: Expression<'a>,
 }
 
-#[derive(Debug, Eq, PartialEq, Clone)]
+#[derive(Debug, Eq, PartialEq, Clone, Serialize)]
 pub enum MagicConst {
     Directory,
     File,
@@ -540,28 +553,28 @@ pub enum MagicConst {
     Trait,
 }
 
-#[derive(Debug, PartialEq, Clone)]
-pub enum StringPart {
-    Const(ByteString),
-    Expr(Box<Expression>),
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub enum StringPart<'a> {
+    Const(ByteString<'a>),
+    Expr(Box<Expression<'a>>),
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct ArrayItem {
-    pub key: Option<Expression>,
-    pub value: Expression,
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ArrayItem<'a> {
+    pub key: Option<Expression<'a>>,
+    pub value: Expression<'a>,
     pub unpack: bool,
     pub by_ref: bool,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct ListItem {
-    pub key: Option<Expression>,
-    pub value: Expression,
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ListItem<'a> {
+    pub key: Option<Expression<'a>>,
+    pub value: Expression<'a>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct ElseIf {
-    pub condition: Expression,
-    pub body: Block,
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ElseIf<'a> {
+    pub condition: Expression<'a>,
+    pub body: Block<'a>,
 }
@@ -0,0 +1,1974 @@
+//! A uniform view of the nodes in the AST, for tools that need to walk the tree
+//! without caring about the type of each node, such as finding the node under
+//! the cursor in an editor.
+
+use crate::lexer::source::LineIndex;
+use crate::lexer::token::Span;
+use crate::parser::ast::attributes::Attribute;
+use crate::parser::ast::attributes::AttributeGroup;
+use crate::parser::ast::classes::AnonymousClass;
+use crate::parser::ast::classes::Class;
+use crate::parser::ast::classes::ClassExtends;
+use crate::parser::ast::classes::ClassImplements;
+use crate::parser::ast::classes::ClassMember;
+use crate::parser::ast::comments::Comment;
+use crate::parser::ast::constant::ClassishConstant;
+use crate::parser::ast::constant::Constant;
+use crate::parser::ast::constant::ConstantEntry;
+use crate::parser::ast::enums::BackedEnum;
+use crate::parser::ast::enums::BackedEnumCase;
+use crate::parser::ast::enums::BackedEnumMember;
+use crate::parser::ast::enums::BackedEnumType;
+use crate::parser::ast::enums::UnitEnum;
+use crate::parser::ast::enums::UnitEnumCase;
+use crate::parser::ast::enums::UnitEnumMember;
+use crate::parser::ast::functions::ArrowFunction;
+use crate::parser::ast::functions::Closure;
+use crate::parser::ast::functions::ClosureUse;
+use crate::parser::ast::functions::Function;
+use crate::parser::ast::functions::FunctionParameter;
+use crate::parser::ast::functions::FunctionParameterList;
+use crate::parser::ast::functions::Method;
+use crate::parser::ast::functions::MethodParameter;
+use crate::parser::ast::functions::MethodParameterList;
+use crate::parser::ast::identifiers::DynamicIdentifier;
+use crate::parser::ast::identifiers::Identifier;
+use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::interfaces::Interface;
+use crate::parser::ast::interfaces::InterfaceExtends;
+use crate::parser::ast::interfaces::InterfaceMember;
+use crate::parser::ast::modifiers::ClassModifier;
+use crate::parser::ast::modifiers::ClassModifierGroup;
+use crate::parser::ast::modifiers::ConstantModifier;
+use crate::parser::ast::modifiers::ConstantModifierGroup;
+use crate::parser::ast::modifiers::MethodModifier;
+use crate::parser::ast::modifiers::MethodModifierGroup;
+use crate::parser::ast::modifiers::PromotedPropertyModifier;
+use crate::parser::ast::modifiers::PromotedPropertyModifierGroup;
+use crate::parser::ast::modifiers::PropertyModifier;
+use crate::parser::ast::modifiers::PropertyModifierGroup;
+use crate::parser::ast::modifiers::VisibilityModifier;
+use crate::parser::ast::operators::ArithmeticOperation;
+use crate::parser::ast::operators::AssignmentOperation;
+use crate::parser::ast::operators::BitwiseOperation;
+use crate::parser::ast::operators::ComparisonOperation;
+use crate::parser::ast::operators::LogicalOperation;
+use crate::parser::ast::properties::Property;
+use crate::parser::ast::properties::PropertyEntry;
+use crate::parser::ast::properties::VariableProperty;
+use crate::parser::ast::properties::VariablePropertyEntry;
+use crate::parser::ast::traits::Trait;
+use crate::parser::ast::traits::TraitMember;
+use crate::parser::ast::traits::TraitUsage;
+use crate::parser::ast::traits::TraitUsageAdaptation;
+use crate::parser::ast::try_block::CatchBlock;
+use crate::parser::ast::try_block::CatchType;
+use crate::parser::ast::try_block::FinallyBlock;
+use crate::parser::ast::try_block::TryBlock;
+use crate::parser::ast::variables::BracedVariableVariable;
+use crate::parser::ast::variables::SimpleVariable;
+use crate::parser::ast::variables::Variable;
+use crate::parser::ast::variables::VariableVariable;
+use crate::parser::ast::Arg;
+use crate::parser::ast::ArrayItem;
+use crate::parser::ast::Case;
+use crate::parser::ast::DeclareItem;
+use crate::parser::ast::DefaultMatchArm;
+use crate::parser::ast::ElseIf;
+use crate::parser::ast::Expression;
+use crate::parser::ast::ListItem;
+use crate::parser::ast::MatchArm;
+use crate::parser::ast::Program;
+use crate::parser::ast::Statement;
+use crate::parser::ast::StaticVar;
+use crate::parser::ast::StringPart;
+use crate::parser::ast::Type;
+use crate::parser::ast::Use;
+
+/// A reference to any node in the AST.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Node<'n, 'a> {
+    Attribute(&'n Attribute<'a>),
+    AttributeGroup(&'n AttributeGroup<'a>),
+    Class(&'n Class<'a>),
+    AnonymousClass(&'n AnonymousClass<'a>),
+    ClassExtends(&'n ClassExtends<'a>),
+    ClassImplements(&'n ClassImplements<'a>),
+    ClassMember(&'n ClassMember<'a>),
+    Comment(&'n Comment<'a>),
+    ConstantEntry(&'n ConstantEntry<'a>),
+    Constant(&'n Constant<'a>),
+    ClassishConstant(&'n ClassishConstant<'a>),
+    UnitEnumCase(&'n UnitEnumCase<'a>),
+    UnitEnumMember(&'n UnitEnumMember<'a>),
+    UnitEnum(&'n UnitEnum<'a>),
+    BackedEnumType(&'n BackedEnumType),
+    BackedEnumCase(&'n BackedEnumCase<'a>),
+    BackedEnumMember(&'n BackedEnumMember<'a>),
+    BackedEnum(&'n BackedEnum<'a>),
+    FunctionParameter(&'n FunctionParameter<'a>),
+    FunctionParameterList(&'n FunctionParameterList<'a>),
+    Function(&'n Function<'a>),
+    ClosureUse(&'n ClosureUse<'a>),
+    Closure(&'n Closure<'a>),
+    ArrowFunction(&'n ArrowFunction<'a>),
+    MethodParameter(&'n MethodParameter<'a>),
+    MethodParameterList(&'n MethodParameterList<'a>),
+    Method(&'n Method<'a>),
+    Identifier(&'n Identifier<'a>),
+    SimpleIdentifier(&'n SimpleIdentifier<'a>),
+    DynamicIdentifier(&'n DynamicIdentifier<'a>),
+    InterfaceMember(&'n InterfaceMember<'a>),
+    Interface(&'n Interface<'a>),
+    InterfaceExtends(&'n InterfaceExtends<'a>),
+    Type(&'n Type<'a>),
+    StaticVar(&'n StaticVar<'a>),
+    Statement(&'n Statement<'a>),
+    DeclareItem(&'n DeclareItem<'a>),
+    Case(&'n Case<'a>),
+    Use(&'n Use<'a>),
+    Expression(&'n Expression<'a>),
+    Arg(&'n Arg<'a>),
+    DefaultMatchArm(&'n DefaultMatchArm<'a>),
+    MatchArm(&'n MatchArm<'a>),
+    StringPart(&'n StringPart<'a>),
+    ArrayItem(&'n ArrayItem<'a>),
+    ListItem(&'n ListItem<'a>),
+    ElseIf(&'n ElseIf<'a>),
+    VisibilityModifier(&'n VisibilityModifier),
+    PromotedPropertyModifier(&'n PromotedPropertyModifier),
+    PromotedPropertyModifierGroup(&'n PromotedPropertyModifierGroup),
+    PropertyModifier(&'n PropertyModifier),
+    PropertyModifierGroup(&'n PropertyModifierGroup),
+    MethodModifier(&'n MethodModifier),
+    MethodModifierGroup(&'n MethodModifierGroup),
+    ClassModifier(&'n ClassModifier),
+    ClassModifierGroup(&'n ClassModifierGroup),
+    ConstantModifier(&'n ConstantModifier),
+    ConstantModifierGroup(&'n ConstantModifierGroup),
+    ArithmeticOperation(&'n ArithmeticOperation<'a>),
+    AssignmentOperation(&'n AssignmentOperation<'a>),
+    BitwiseOperation(&'n BitwiseOperation<'a>),
+    ComparisonOperation(&'n ComparisonOperation<'a>),
+    LogicalOperation(&'n LogicalOperation<'a>),
+    Property(&'n Property<'a>),
+    PropertyEntry(&'n PropertyEntry<'a>),
+    VariableProperty(&'n VariableProperty<'a>),
+    VariablePropertyEntry(&'n VariablePropertyEntry<'a>),
+    Trait(&'n Trait<'a>),
+    TraitMember(&'n TraitMember<'a>),
+    TraitUsage(&'n TraitUsage<'a>),
+    TraitUsageAdaptation(&'n TraitUsageAdaptation<'a>),
+    CatchType(&'n CatchType<'a>),
+    TryBlock(&'n TryBlock<'a>),
+    CatchBlock(&'n CatchBlock<'a>),
+    FinallyBlock(&'n FinallyBlock<'a>),
+    Variable(&'n Variable<'a>),
+    SimpleVariable(&'n SimpleVariable<'a>),
+    VariableVariable(&'n VariableVariable<'a>),
+    BracedVariableVariable(&'n BracedVariableVariable<'a>),
+}
+
+impl<'n, 'a> Node<'n, 'a> {
+    /// The nodes directly inside of this one, in the order they are declared in.
+    pub fn children(&self) -> Vec<Node<'n, 'a>> {
+        self.parts().0
+    }
+
+    /// The spans stored directly on this node, not including those of its children.
+    pub fn spans(&self) -> Vec<Span> {
+        self.parts().1
+    }
+
+    /// Where this node starts and ends.
+    ///
+    /// The AST only records where tokens start, so the node is taken to end at
+    /// its last span. Names and variables are the exception, since their length
+    /// is known. Nodes without any spans, such as literals, have no range.
+    pub fn range(&self) -> Option<(Span, Span)> {
+        let mut range = None;
+
+        let mut stack = vec![*self];
+        while let Some(node) = stack.pop() {
+            let (children, spans) = node.parts();
+
+            range = merge(range, node.own_range(&spans));
+            stack.extend(children);
+        }
+
+        range
+    }
+
+    // The range covered by the spans stored directly on this node.
+    fn own_range(&self, spans: &[Span]) -> Option<(Span, Span)> {
+        let length = match self {
+            Node::SimpleIdentifier(identifier) => identifier.name.len().saturating_sub(1),
+            // Including the `$`.
+            Node::SimpleVariable(variable) => variable.name.len(),
+            _ => 0,
+        };
+
+        spans.iter().fold(None, |range, span| {
+            merge(range, Some((*span, (span.0, span.1 + length))))
+        })
+    }
+
+    fn parts(&self) -> (Vec<Node<'n, 'a>>, Vec<Span>) {
+        let mut nodes = vec![];
+        let mut spans = vec![];
+
+        match *self {
+            Node::Attribute(node) => {
+                node.start.collect(&mut nodes, &mut spans);
+                node.end.collect(&mut nodes, &mut spans);
+                node.expression.collect(&mut nodes, &mut spans);
+            }
+            Node::AttributeGroup(node) => {
+                node.start.collect(&mut nodes, &mut spans);
+                node.end.collect(&mut nodes, &mut spans);
+                node.members.collect(&mut nodes, &mut spans);
+            }
+            Node::Class(node) => {
+                node.start.collect(&mut nodes, &mut spans);
+                node.end.collect(&mut nodes, &mut spans);
+                node.name.collect(&mut nodes, &mut spans);
+                node.extends.collect(&mut nodes, &mut spans);
+                node.implements.collect(&mut nodes, &mut spans);
+                node.attributes.collect(&mut nodes, &mut spans);
+                node.modifiers.collect(&mut nodes, &mut spans);
+                node.members.collect(&mut nodes, &mut spans);
+            }
+            Node::AnonymousClass(node) => {
+                node.start.collect(&mut nodes, &mut spans);
+                node.end.collect(&mut nodes, &mut spans);
+                node.extends.collect(&mut nodes, &mut spans);
+                node.implements.collect(&mut nodes, &mut spans);
+                node.attributes.collect(&mut nodes, &mut spans);
+                node.members.collect(&mut nodes, &mut spans);
+            }
+            Node::ClassExtends(node) => {
+                node.span.collect(&mut nodes, &mut spans);
+                node.parent.collect(&mut nodes, &mut spans);
+            }
+            Node::ClassImplements(node) => {
+                node.span.collect(&mut nodes, &mut spans);
+                node.interfaces.collect(&mut nodes, &mut spans);
+            }
+            Node::ClassMember(node) => match node {
+                ClassMember::Constant(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                ClassMember::TraitUsage(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                ClassMember::Property(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                ClassMember::VariableProperty(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                ClassMember::Method(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+            },
+            Node::Comment(node) => {
+                node.start.collect(&mut nodes, &mut spans);
+                node.end.collect(&mut nodes, &mut spans);
+            }
+            Node::ConstantEntry(node) => {
+                node.name.collect(&mut nodes, &mut spans);
+                node.value.collect(&mut nodes, &mut spans);
+            }
+            Node::Constant(node) => {
+                node.start.collect(&mut nodes, &mut spans);
+                node.end.collect(&mut nodes, &mut spans);
+                node.entries.collect(&mut nodes, &mut spans);
+            }
+            Node::ClassishConstant(node) => {
+                node.start.collect(&mut nodes, &mut spans);
+                node.end.collect(&mut nodes, &mut spans);
+                node.attributes.collect(&mut nodes, &mut spans);
+                node.modifiers.collect(&mut nodes, &mut spans);
+                node.entries.collect(&mut nodes, &mut spans);
+            }
+            Node::UnitEnumCase(node) => {
+                node.start.collect(&mut nodes, &mut spans);
+                node.end.collect(&mut nodes, &mut spans);
+                node.attributes.collect(&mut nodes, &mut spans);
+                node.name.collect(&mut nodes, &mut spans);
+            }
+            Node::UnitEnumMember(node) => match node {
+                UnitEnumMember::Case(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                UnitEnumMember::Method(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                UnitEnumMember::Constant(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+            },
+            Node::UnitEnum(node) => {
+                node.start.collect(&mut nodes, &mut spans);
+                node.end.collect(&mut nodes, &mut spans);
+                node.name.collect(&mut nodes, &mut spans);
+                node.attributes.collect(&mut nodes, &mut spans);
+                node.implements.collect(&mut nodes, &mut spans);
+                node.members.collect(&mut nodes, &mut spans);
+            }
+            Node::BackedEnumType(node) => match node {
+                BackedEnumType::String(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                BackedEnumType::Int(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+            },
+            Node::BackedEnumCase(node) => {
+                node.start.collect(&mut nodes, &mut spans);
+                node.end.collect(&mut nodes, &mut spans);
+                node.name.collect(&mut nodes, &mut spans);
+                node.attributes.collect(&mut nodes, &mut spans);
+                node.value.collect(&mut nodes, &mut spans);
+            }
+            Node::BackedEnumMember(node) => match node {
+                BackedEnumMember::Case(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                BackedEnumMember::Method(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                BackedEnumMember::Constant(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+            },
+            Node::BackedEnum(node) => {
+                node.start.collect(&mut nodes, &mut spans);
+                node.end.collect(&mut nodes, &mut spans);
+                node.name.collect(&mut nodes, &mut spans);
+                node.attributes.collect(&mut nodes, &mut spans);
+                node.implements.collect(&mut nodes, &mut spans);
+                node.backed_type.collect(&mut nodes, &mut spans);
+                node.members.collect(&mut nodes, &mut spans);
+            }
+            Node::FunctionParameter(node) => {
+                node.start.collect(&mut nodes, &mut spans);
+                node.end.collect(&mut nodes, &mut spans);
+                node.name.collect(&mut nodes, &mut spans);
+                node.attributes.collect(&mut nodes, &mut spans);
+                node.r#type.collect(&mut nodes, &mut spans);
+                node.default.collect(&mut nodes, &mut spans);
+            }
+            Node::FunctionParameterList(node) => {
+                node.start.collect(&mut nodes, &mut spans);
+                node.end.collect(&mut nodes, &mut spans);
+                node.members.collect(&mut nodes, &mut spans);
+            }
+            Node::Function(node) => {
+                node.start.collect(&mut nodes, &mut spans);
+                node.end.collect(&mut nodes, &mut spans);
+                node.name.collect(&mut nodes, &mut spans);
+                node.attributes.collect(&mut nodes, &mut spans);
+                node.parameters.collect(&mut nodes, &mut spans);
+                node.return_type.collect(&mut nodes, &mut spans);
+                node.body.collect(&mut nodes, &mut spans);
+            }
+            Node::ClosureUse(node) => {
+                node.var.collect(&mut nodes, &mut spans);
+            }
+            Node::Closure(node) => {
+                node.start.collect(&mut nodes, &mut spans);
+                node.end.collect(&mut nodes, &mut spans);
+                node.attributes.collect(&mut nodes, &mut spans);
+                node.parameters.collect(&mut nodes, &mut spans);
+                node.return_ty.collect(&mut nodes, &mut spans);
+                node.uses.collect(&mut nodes, &mut spans);
+                node.body.collect(&mut nodes, &mut spans);
+            }
+            Node::ArrowFunction(node) => {
+                node.start.collect(&mut nodes, &mut spans);
+                node.end.collect(&mut nodes, &mut spans);
+                node.attributes.collect(&mut nodes, &mut spans);
+                node.parameters.collect(&mut nodes, &mut spans);
+                node.return_type.collect(&mut nodes, &mut spans);
+                node.body.collect(&mut nodes, &mut spans);
+            }
+            Node::MethodParameter(node) => {
+                node.start.collect(&mut nodes, &mut spans);
+                node.end.collect(&mut nodes, &mut spans);
+                node.name.collect(&mut nodes, &mut spans);
+                node.attributes.collect(&mut nodes, &mut spans);
+                node.r#type.collect(&mut nodes, &mut spans);
+                node.default.collect(&mut nodes, &mut spans);
+                node.modifiers.collect(&mut nodes, &mut spans);
+            }
+            Node::MethodParameterList(node) => {
+                node.start.collect(&mut nodes, &mut spans);
+                node.end.collect(&mut nodes, &mut spans);
+                node.members.collect(&mut nodes, &mut spans);
+            }
+            Node::Method(node) => {
+                node.start.collect(&mut nodes, &mut spans);
+                node.end.collect(&mut nodes, &mut spans);
+                node.name.collect(&mut nodes, &mut spans);
+                node.attributes.collect(&mut nodes, &mut spans);
+                node.parameters.collect(&mut nodes, &mut spans);
+                node.body.collect(&mut nodes, &mut spans);
+                node.modifiers.collect(&mut nodes, &mut spans);
+                node.return_type.collect(&mut nodes, &mut spans);
+            }
+            Node::Identifier(node) => match node {
+                Identifier::SimpleIdentifier(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                Identifier::DynamicIdentifier(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+            },
+            Node::SimpleIdentifier(node) => {
+                node.span.collect(&mut nodes, &mut spans);
+            }
+            Node::DynamicIdentifier(node) => {
+                node.start.collect(&mut nodes, &mut spans);
+                node.expr.collect(&mut nodes, &mut spans);
+                node.end.collect(&mut nodes, &mut spans);
+            }
+            Node::InterfaceMember(node) => match node {
+                InterfaceMember::Constant(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                InterfaceMember::Method(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+            },
+            Node::Interface(node) => {
+                node.start.collect(&mut nodes, &mut spans);
+                node.end.collect(&mut nodes, &mut spans);
+                node.attributes.collect(&mut nodes, &mut spans);
+                node.name.collect(&mut nodes, &mut spans);
+                node.extends.collect(&mut nodes, &mut spans);
+                node.members.collect(&mut nodes, &mut spans);
+            }
+            Node::InterfaceExtends(node) => {
+                node.span.collect(&mut nodes, &mut spans);
+                node.parents.collect(&mut nodes, &mut spans);
+            }
+            Node::Type(node) => match node {
+                Type::Identifier(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                Type::Nullable(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                Type::Union(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                Type::Intersection(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                Type::Void => {}
+                Type::Null => {}
+                Type::True => {}
+                Type::False => {}
+                Type::Never => {}
+                Type::Float => {}
+                Type::Boolean => {}
+                Type::Integer => {}
+                Type::String => {}
+                Type::Array => {}
+                Type::Object => {}
+                Type::Mixed => {}
+                Type::Callable => {}
+                Type::Iterable => {}
+                Type::StaticReference => {}
+                Type::SelfReference => {}
+                Type::ParentReference => {}
+            },
+            Node::StaticVar(node) => {
+                node.var.collect(&mut nodes, &mut spans);
+                node.default.collect(&mut nodes, &mut spans);
+            }
+            Node::Statement(node) => match node {
+                Statement::InlineHtml(..) => {}
+                Statement::Goto { label } => {
+                    label.collect(&mut nodes, &mut spans);
+                }
+                Statement::Label { label } => {
+                    label.collect(&mut nodes, &mut spans);
+                }
+                Statement::HaltCompiler { .. } => {}
+                Statement::Static { vars } => {
+                    vars.collect(&mut nodes, &mut spans);
+                }
+                Statement::DoWhile { condition, body } => {
+                    condition.collect(&mut nodes, &mut spans);
+                    body.collect(&mut nodes, &mut spans);
+                }
+                Statement::While { condition, body } => {
+                    condition.collect(&mut nodes, &mut spans);
+                    body.collect(&mut nodes, &mut spans);
+                }
+                Statement::For {
+                    init,
+                    condition,
+                    r#loop,
+                    then,
+                } => {
+                    init.collect(&mut nodes, &mut spans);
+                    condition.collect(&mut nodes, &mut spans);
+                    r#loop.collect(&mut nodes, &mut spans);
+                    then.collect(&mut nodes, &mut spans);
+                }
+                Statement::Foreach {
+                    expr,
+                    key_var,
+                    value_var,
+                    body,
+                    ..
+                } => {
+                    expr.collect(&mut nodes, &mut spans);
+                    key_var.collect(&mut nodes, &mut spans);
+                    value_var.collect(&mut nodes, &mut spans);
+                    body.collect(&mut nodes, &mut spans);
+                }
+                Statement::Constant(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                Statement::Function(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                Statement::Class(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                Statement::Trait(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                Statement::Interface(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                Statement::If {
+                    condition,
+                    then,
+                    else_ifs,
+                    r#else,
+                } => {
+                    condition.collect(&mut nodes, &mut spans);
+                    then.collect(&mut nodes, &mut spans);
+                    else_ifs.collect(&mut nodes, &mut spans);
+                    r#else.collect(&mut nodes, &mut spans);
+                }
+                Statement::Return { value } => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                Statement::Switch { condition, cases } => {
+                    condition.collect(&mut nodes, &mut spans);
+                    cases.collect(&mut nodes, &mut spans);
+                }
+                Statement::Break { num } => {
+                    num.collect(&mut nodes, &mut spans);
+                }
+                Statement::Continue { num } => {
+                    num.collect(&mut nodes, &mut spans);
+                }
+                Statement::Echo { values } => {
+                    values.collect(&mut nodes, &mut spans);
+                }
+                Statement::Expression { expr } => {
+                    expr.collect(&mut nodes, &mut spans);
+                }
+                Statement::Namespace { name, body } => {
+                    name.collect(&mut nodes, &mut spans);
+                    body.collect(&mut nodes, &mut spans);
+                }
+                Statement::BracedNamespace { name, body } => {
+                    name.collect(&mut nodes, &mut spans);
+                    body.collect(&mut nodes, &mut spans);
+                }
+                Statement::Use { uses, .. } => {
+                    uses.collect(&mut nodes, &mut spans);
+                }
+                Statement::GroupUse { prefix, uses, .. } => {
+                    prefix.collect(&mut nodes, &mut spans);
+                    uses.collect(&mut nodes, &mut spans);
+                }
+                Statement::Comment(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                Statement::Try(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                Statement::UnitEnum(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                Statement::BackedEnum(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                Statement::Block { body } => {
+                    body.collect(&mut nodes, &mut spans);
+                }
+                Statement::Global { span, variables } => {
+                    span.collect(&mut nodes, &mut spans);
+                    variables.collect(&mut nodes, &mut spans);
+                }
+                Statement::Declare { declares, body } => {
+                    declares.collect(&mut nodes, &mut spans);
+                    body.collect(&mut nodes, &mut spans);
+                }
+                Statement::Noop(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+            },
+            Node::DeclareItem(node) => {
+                node.key.collect(&mut nodes, &mut spans);
+                node.value.collect(&mut nodes, &mut spans);
+            }
+            Node::Case(node) => {
+                node.condition.collect(&mut nodes, &mut spans);
+                node.body.collect(&mut nodes, &mut spans);
+            }
+            Node::Use(node) => {
+                node.name.collect(&mut nodes, &mut spans);
+                node.alias.collect(&mut nodes, &mut spans);
+            }
+            Node::Expression(node) => match node {
+                Expression::ArithmeticOperation(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                Expression::AssignmentOperation(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                Expression::BitwiseOperation(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                Expression::ComparisonOperation(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                Expression::LogicalOperation(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                Expression::Concat { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                Expression::Instanceof { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                Expression::Reference { span, right } => {
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                Expression::Parenthesized { start, expr, end } => {
+                    start.collect(&mut nodes, &mut spans);
+                    expr.collect(&mut nodes, &mut spans);
+                    end.collect(&mut nodes, &mut spans);
+                }
+                Expression::List { items } => {
+                    items.collect(&mut nodes, &mut spans);
+                }
+                Expression::Empty => {}
+                Expression::VariadicPlaceholder => {}
+                Expression::ErrorSuppress { span, expr } => {
+                    span.collect(&mut nodes, &mut spans);
+                    expr.collect(&mut nodes, &mut spans);
+                }
+                Expression::LiteralInteger { .. } => {}
+                Expression::LiteralFloat { .. } => {}
+                Expression::Identifier(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                Expression::Variable(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                Expression::Include { span, path, .. } => {
+                    span.collect(&mut nodes, &mut spans);
+                    path.collect(&mut nodes, &mut spans);
+                }
+                Expression::Call { target, args } => {
+                    target.collect(&mut nodes, &mut spans);
+                    args.collect(&mut nodes, &mut spans);
+                }
+                Expression::Static => {}
+                Expression::Self_ => {}
+                Expression::Parent => {}
+                Expression::Array { items } => {
+                    items.collect(&mut nodes, &mut spans);
+                }
+                Expression::Closure(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                Expression::ArrowFunction(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                Expression::New { target, span, args } => {
+                    target.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    args.collect(&mut nodes, &mut spans);
+                }
+                Expression::LiteralString { .. } => {}
+                Expression::InterpolatedString { parts } => {
+                    parts.collect(&mut nodes, &mut spans);
+                }
+                Expression::Heredoc { parts } => {
+                    parts.collect(&mut nodes, &mut spans);
+                }
+                Expression::Nowdoc { .. } => {}
+                Expression::ShellExec { parts } => {
+                    parts.collect(&mut nodes, &mut spans);
+                }
+                Expression::PropertyFetch { target, property } => {
+                    target.collect(&mut nodes, &mut spans);
+                    property.collect(&mut nodes, &mut spans);
+                }
+                Expression::NullsafePropertyFetch { target, property } => {
+                    target.collect(&mut nodes, &mut spans);
+                    property.collect(&mut nodes, &mut spans);
+                }
+                Expression::NullsafeMethodCall {
+                    target,
+                    method,
+                    args,
+                } => {
+                    target.collect(&mut nodes, &mut spans);
+                    method.collect(&mut nodes, &mut spans);
+                    args.collect(&mut nodes, &mut spans);
+                }
+                Expression::StaticPropertyFetch { target, property } => {
+                    target.collect(&mut nodes, &mut spans);
+                    property.collect(&mut nodes, &mut spans);
+                }
+                Expression::ConstFetch { target, constant } => {
+                    target.collect(&mut nodes, &mut spans);
+                    constant.collect(&mut nodes, &mut spans);
+                }
+                Expression::MethodCall {
+                    target,
+                    method,
+                    args,
+                } => {
+                    target.collect(&mut nodes, &mut spans);
+                    method.collect(&mut nodes, &mut spans);
+                    args.collect(&mut nodes, &mut spans);
+                }
+                Expression::StaticMethodCall {
+                    target,
+                    method,
+                    args,
+                } => {
+                    target.collect(&mut nodes, &mut spans);
+                    method.collect(&mut nodes, &mut spans);
+                    args.collect(&mut nodes, &mut spans);
+                }
+                Expression::AnonymousClass(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                Expression::Bool { .. } => {}
+                Expression::ArrayIndex { array, index } => {
+                    array.collect(&mut nodes, &mut spans);
+                    index.collect(&mut nodes, &mut spans);
+                }
+                Expression::Null => {}
+                Expression::MagicConst { span, .. } => {
+                    span.collect(&mut nodes, &mut spans);
+                }
+                Expression::Ternary {
+                    condition,
+                    then,
+                    r#else,
+                } => {
+                    condition.collect(&mut nodes, &mut spans);
+                    then.collect(&mut nodes, &mut spans);
+                    r#else.collect(&mut nodes, &mut spans);
+                }
+                Expression::Coalesce { lhs, rhs } => {
+                    lhs.collect(&mut nodes, &mut spans);
+                    rhs.collect(&mut nodes, &mut spans);
+                }
+                Expression::Clone { target } => {
+                    target.collect(&mut nodes, &mut spans);
+                }
+                Expression::Match {
+                    condition,
+                    default,
+                    arms,
+                } => {
+                    condition.collect(&mut nodes, &mut spans);
+                    default.collect(&mut nodes, &mut spans);
+                    arms.collect(&mut nodes, &mut spans);
+                }
+                Expression::Throw { value } => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                Expression::Yield { key, value } => {
+                    key.collect(&mut nodes, &mut spans);
+                    value.collect(&mut nodes, &mut spans);
+                }
+                Expression::YieldFrom { value } => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                Expression::BitwiseNot { span, value } => {
+                    span.collect(&mut nodes, &mut spans);
+                    value.collect(&mut nodes, &mut spans);
+                }
+                Expression::Print { span, value } => {
+                    span.collect(&mut nodes, &mut spans);
+                    value.collect(&mut nodes, &mut spans);
+                }
+                Expression::Cast { span, value, .. } => {
+                    span.collect(&mut nodes, &mut spans);
+                    value.collect(&mut nodes, &mut spans);
+                }
+            },
+            Node::Arg(node) => {
+                node.name.collect(&mut nodes, &mut spans);
+                node.value.collect(&mut nodes, &mut spans);
+            }
+            Node::DefaultMatchArm(node) => {
+                node.body.collect(&mut nodes, &mut spans);
+            }
+            Node::MatchArm(node) => {
+                node.conditions.collect(&mut nodes, &mut spans);
+                node.body.collect(&mut nodes, &mut spans);
+            }
+            Node::StringPart(node) => match node {
+                StringPart::Const(..) => {}
+                StringPart::Expr(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+            },
+            Node::ArrayItem(node) => {
+                node.key.collect(&mut nodes, &mut spans);
+                node.value.collect(&mut nodes, &mut spans);
+            }
+            Node::ListItem(node) => {
+                node.key.collect(&mut nodes, &mut spans);
+                node.value.collect(&mut nodes, &mut spans);
+            }
+            Node::ElseIf(node) => {
+                node.condition.collect(&mut nodes, &mut spans);
+                node.body.collect(&mut nodes, &mut spans);
+            }
+            Node::VisibilityModifier(node) => match node {
+                VisibilityModifier::Public { start, end } => {
+                    start.collect(&mut nodes, &mut spans);
+                    end.collect(&mut nodes, &mut spans);
+                }
+                VisibilityModifier::Protected { start, end } => {
+                    start.collect(&mut nodes, &mut spans);
+                    end.collect(&mut nodes, &mut spans);
+                }
+                VisibilityModifier::Private { start, end } => {
+                    start.collect(&mut nodes, &mut spans);
+                    end.collect(&mut nodes, &mut spans);
+                }
+            },
+            Node::PromotedPropertyModifier(node) => match node {
+                PromotedPropertyModifier::Public { start, end } => {
+                    start.collect(&mut nodes, &mut spans);
+                    end.collect(&mut nodes, &mut spans);
+                }
+                PromotedPropertyModifier::Protected { start, end } => {
+                    start.collect(&mut nodes, &mut spans);
+                    end.collect(&mut nodes, &mut spans);
+                }
+                PromotedPropertyModifier::Private { start, end } => {
+                    start.collect(&mut nodes, &mut spans);
+                    end.collect(&mut nodes, &mut spans);
+                }
+                PromotedPropertyModifier::Readonly { start, end } => {
+                    start.collect(&mut nodes, &mut spans);
+                    end.collect(&mut nodes, &mut spans);
+                }
+            },
+            Node::PromotedPropertyModifierGroup(node) => {
+                node.modifiers.collect(&mut nodes, &mut spans);
+            }
+            Node::PropertyModifier(node) => match node {
+                PropertyModifier::Public { start, end } => {
+                    start.collect(&mut nodes, &mut spans);
+                    end.collect(&mut nodes, &mut spans);
+                }
+                PropertyModifier::Protected { start, end } => {
+                    start.collect(&mut nodes, &mut spans);
+                    end.collect(&mut nodes, &mut spans);
+                }
+                PropertyModifier::Private { start, end } => {
+                    start.collect(&mut nodes, &mut spans);
+                    end.collect(&mut nodes, &mut spans);
+                }
+                PropertyModifier::Static { start, end } => {
+                    start.collect(&mut nodes, &mut spans);
+                    end.collect(&mut nodes, &mut spans);
+                }
+                PropertyModifier::Readonly { start, end } => {
+                    start.collect(&mut nodes, &mut spans);
+                    end.collect(&mut nodes, &mut spans);
+                }
+            },
+            Node::PropertyModifierGroup(node) => {
+                node.modifiers.collect(&mut nodes, &mut spans);
+            }
+            Node::MethodModifier(node) => match node {
+                MethodModifier::Final { start, end } => {
+                    start.collect(&mut nodes, &mut spans);
+                    end.collect(&mut nodes, &mut spans);
+                }
+                MethodModifier::Static { start, end } => {
+                    start.collect(&mut nodes, &mut spans);
+                    end.collect(&mut nodes, &mut spans);
+                }
+                MethodModifier::Abstract { start, end } => {
+                    start.collect(&mut nodes, &mut spans);
+                    end.collect(&mut nodes, &mut spans);
+                }
+                MethodModifier::Public { start, end } => {
+                    start.collect(&mut nodes, &mut spans);
+                    end.collect(&mut nodes, &mut spans);
+                }
+                MethodModifier::Protected { start, end } => {
+                    start.collect(&mut nodes, &mut spans);
+                    end.collect(&mut nodes, &mut spans);
+                }
+                MethodModifier::Private { start, end } => {
+                    start.collect(&mut nodes, &mut spans);
+                    end.collect(&mut nodes, &mut spans);
+                }
+            },
+            Node::MethodModifierGroup(node) => {
+                node.modifiers.collect(&mut nodes, &mut spans);
+            }
+            Node::ClassModifier(node) => match node {
+                ClassModifier::Final { start, end } => {
+                    start.collect(&mut nodes, &mut spans);
+                    end.collect(&mut nodes, &mut spans);
+                }
+                ClassModifier::Abstract { start, end } => {
+                    start.collect(&mut nodes, &mut spans);
+                    end.collect(&mut nodes, &mut spans);
+                }
+                ClassModifier::Readonly { start, end } => {
+                    start.collect(&mut nodes, &mut spans);
+                    end.collect(&mut nodes, &mut spans);
+                }
+            },
+            Node::ClassModifierGroup(node) => {
+                node.modifiers.collect(&mut nodes, &mut spans);
+            }
+            Node::ConstantModifier(node) => match node {
+                ConstantModifier::Final { start, end } => {
+                    start.collect(&mut nodes, &mut spans);
+                    end.collect(&mut nodes, &mut spans);
+                }
+                ConstantModifier::Public { start, end } => {
+                    start.collect(&mut nodes, &mut spans);
+                    end.collect(&mut nodes, &mut spans);
+                }
+                ConstantModifier::Protected { start, end } => {
+                    start.collect(&mut nodes, &mut spans);
+                    end.collect(&mut nodes, &mut spans);
+                }
+                ConstantModifier::Private { start, end } => {
+                    start.collect(&mut nodes, &mut spans);
+                    end.collect(&mut nodes, &mut spans);
+                }
+            },
+            Node::ConstantModifierGroup(node) => {
+                node.modifiers.collect(&mut nodes, &mut spans);
+            }
+            Node::ArithmeticOperation(node) => match node {
+                ArithmeticOperation::Addition { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                ArithmeticOperation::Subtraction { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                ArithmeticOperation::Multiplication { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                ArithmeticOperation::Division { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                ArithmeticOperation::Modulo { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                ArithmeticOperation::Exponentiation { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                ArithmeticOperation::Negation { span, right } => {
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                ArithmeticOperation::Identity { span, right } => {
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                ArithmeticOperation::PreIncrement { span, right } => {
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                ArithmeticOperation::PostIncrement { left, span } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                }
+                ArithmeticOperation::PreDecrement { span, right } => {
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                ArithmeticOperation::PostDecrement { left, span } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                }
+            },
+            Node::AssignmentOperation(node) => match node {
+                AssignmentOperation::Assign { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                AssignmentOperation::Addition { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                AssignmentOperation::Subtraction { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                AssignmentOperation::Multiplication { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                AssignmentOperation::Division { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                AssignmentOperation::Modulo { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                AssignmentOperation::Exponentiation { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                AssignmentOperation::Concat { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                AssignmentOperation::BitwiseAnd { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                AssignmentOperation::BitwiseOr { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                AssignmentOperation::BitwiseXor { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                AssignmentOperation::LeftShift { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                AssignmentOperation::RightShift { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                AssignmentOperation::Coalesce { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+            },
+            Node::BitwiseOperation(node) => match node {
+                BitwiseOperation::And { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                BitwiseOperation::Or { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                BitwiseOperation::Xor { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                BitwiseOperation::LeftShift { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                BitwiseOperation::RightShift { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                BitwiseOperation::Not { span, right } => {
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+            },
+            Node::ComparisonOperation(node) => match node {
+                ComparisonOperation::Equal { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                ComparisonOperation::Identical { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                ComparisonOperation::NotEqual { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                ComparisonOperation::AngledNotEqual { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                ComparisonOperation::NotIdentical { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                ComparisonOperation::LessThan { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                ComparisonOperation::GreaterThan { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                ComparisonOperation::LessThanOrEqual { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                ComparisonOperation::GreaterThanOrEqual { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                ComparisonOperation::Spaceship { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+            },
+            Node::LogicalOperation(node) => match node {
+                LogicalOperation::And { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                LogicalOperation::Or { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                LogicalOperation::Not { span, right } => {
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                LogicalOperation::LogicalAnd { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                LogicalOperation::LogicalOr { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+                LogicalOperation::LogicalXor { left, span, right } => {
+                    left.collect(&mut nodes, &mut spans);
+                    span.collect(&mut nodes, &mut spans);
+                    right.collect(&mut nodes, &mut spans);
+                }
+            },
+            Node::Property(node) => {
+                node.attributes.collect(&mut nodes, &mut spans);
+                node.r#type.collect(&mut nodes, &mut spans);
+                node.modifiers.collect(&mut nodes, &mut spans);
+                node.entries.collect(&mut nodes, &mut spans);
+            }
+            Node::PropertyEntry(node) => {
+                node.variable.collect(&mut nodes, &mut spans);
+                node.value.collect(&mut nodes, &mut spans);
+            }
+            Node::VariableProperty(node) => {
+                node.attributes.collect(&mut nodes, &mut spans);
+                node.r#type.collect(&mut nodes, &mut spans);
+                node.entries.collect(&mut nodes, &mut spans);
+            }
+            Node::VariablePropertyEntry(node) => {
+                node.variable.collect(&mut nodes, &mut spans);
+                node.value.collect(&mut nodes, &mut spans);
+            }
+            Node::Trait(node) => {
+                node.start.collect(&mut nodes, &mut spans);
+                node.end.collect(&mut nodes, &mut spans);
+                node.name.collect(&mut nodes, &mut spans);
+                node.attributes.collect(&mut nodes, &mut spans);
+                node.members.collect(&mut nodes, &mut spans);
+            }
+            Node::TraitMember(node) => match node {
+                TraitMember::Constant(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                TraitMember::TraitUsage(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                TraitMember::Property(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                TraitMember::VariableProperty(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                TraitMember::Method(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+            },
+            Node::TraitUsage(node) => {
+                node.traits.collect(&mut nodes, &mut spans);
+                node.adaptations.collect(&mut nodes, &mut spans);
+            }
+            Node::TraitUsageAdaptation(node) => match node {
+                TraitUsageAdaptation::Alias {
+                    r#trait,
+                    method,
+                    alias,
+                    visibility,
+                } => {
+                    r#trait.collect(&mut nodes, &mut spans);
+                    method.collect(&mut nodes, &mut spans);
+                    alias.collect(&mut nodes, &mut spans);
+                    visibility.collect(&mut nodes, &mut spans);
+                }
+                TraitUsageAdaptation::Visibility {
+                    r#trait,
+                    method,
+                    visibility,
+                } => {
+                    r#trait.collect(&mut nodes, &mut spans);
+                    method.collect(&mut nodes, &mut spans);
+                    visibility.collect(&mut nodes, &mut spans);
+                }
+                TraitUsageAdaptation::Precedence {
+                    r#trait,
+                    method,
+                    insteadof,
+                } => {
+                    r#trait.collect(&mut nodes, &mut spans);
+                    method.collect(&mut nodes, &mut spans);
+                    insteadof.collect(&mut nodes, &mut spans);
+                }
+            },
+            Node::CatchType(node) => match node {
+                CatchType::Identifier(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                CatchType::Union(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+            },
+            Node::TryBlock(node) => {
+                node.start.collect(&mut nodes, &mut spans);
+                node.end.collect(&mut nodes, &mut spans);
+                node.body.collect(&mut nodes, &mut spans);
+                node.catches.collect(&mut nodes, &mut spans);
+                node.finally.collect(&mut nodes, &mut spans);
+            }
+            Node::CatchBlock(node) => {
+                node.start.collect(&mut nodes, &mut spans);
+                node.end.collect(&mut nodes, &mut spans);
+                node.types.collect(&mut nodes, &mut spans);
+                node.var.collect(&mut nodes, &mut spans);
+                node.body.collect(&mut nodes, &mut spans);
+            }
+            Node::FinallyBlock(node) => {
+                node.start.collect(&mut nodes, &mut spans);
+                node.end.collect(&mut nodes, &mut spans);
+                node.body.collect(&mut nodes, &mut spans);
+            }
+            Node::Variable(node) => match node {
+                Variable::SimpleVariable(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                Variable::VariableVariable(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+                Variable::BracedVariableVariable(value) => {
+                    value.collect(&mut nodes, &mut spans);
+                }
+            },
+            Node::SimpleVariable(node) => {
+                node.span.collect(&mut nodes, &mut spans);
+            }
+            Node::VariableVariable(node) => {
+                node.span.collect(&mut nodes, &mut spans);
+                node.variable.collect(&mut nodes, &mut spans);
+            }
+            Node::BracedVariableVariable(node) => {
+                node.start.collect(&mut nodes, &mut spans);
+                node.variable.collect(&mut nodes, &mut spans);
+                node.end.collect(&mut nodes, &mut spans);
+            }
+        }
+
+        (nodes, spans)
+    }
+}
+
+/// The innermost node at a position, see `find_node_at`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeAt<'n, 'a> {
+    pub node: Node<'n, 'a>,
+    /// The nodes containing `node`, starting with the top-level statement.
+    pub ancestors: Vec<Node<'n, 'a>>,
+}
+
+/// Find the innermost node at the given line and column.
+///
+/// Only nodes whose range contains the position are considered, see `Node::range`.
+/// Nodes without spans, such as literals, are never returned; the closest node
+/// around them that has spans is returned instead.
+pub fn find_node_at<'n, 'a>(program: &'n Program<'a>, position: Span) -> Option<NodeAt<'n, 'a>> {
+    let contains = |range: Option<(Span, Span)>| {
+        range.is_some_and(|(start, end)| start <= position && position <= end)
+    };
+
+    for statement in program {
+        let tree = RangeTree::new(Node::Statement(statement));
+        if !contains(tree.entries[0].range) {
+            continue;
+        }
+
+        let mut path = vec![0];
+        while let Some(child) = tree.entries[*path.last().unwrap()]
+            .children
+            .iter()
+            .copied()
+            .find(|child| contains(tree.entries[*child].range))
+        {
+            path.push(child);
+        }
+
+        let mut path = path.into_iter().map(|entry| tree.entries[entry].node);
+        let node = path.next_back()?;
+
+        return Some(NodeAt {
+            node,
+            ancestors: path.collect(),
+        });
+    }
+
+    None
+}
+
+/// Find the innermost node at a byte offset into the source, see `find_node_at`.
+pub fn find_node_at_offset<'n, 'a>(
+    program: &'n Program<'a>,
+    index: &LineIndex,
+    offset: usize,
+) -> Option<NodeAt<'n, 'a>> {
+    find_node_at(program, index.span(offset))
+}
+
+// Every node below a root along with its range, computed in one pass rather than
+// walking each node's subtree again to find its range.
+struct RangeTree<'n, 'a> {
+    // In pre-order, so each node comes before its children.
+    entries: Vec<RangeEntry<'n, 'a>>,
+}
+
+struct RangeEntry<'n, 'a> {
+    node: Node<'n, 'a>,
+    range: Option<(Span, Span)>,
+    children: Vec<usize>,
+}
+
+impl<'n, 'a> RangeTree<'n, 'a> {
+    fn new(root: Node<'n, 'a>) -> Self {
+        let mut entries: Vec<RangeEntry> = vec![];
+
+        let mut stack: Vec<(Node, Option<usize>)> = vec![(root, None)];
+        while let Some((node, parent)) = stack.pop() {
+            let (children, spans) = node.parts();
+            let entry = entries.len();
+
+            if let Some(parent) = parent {
+                entries[parent].children.push(entry);
+            }
+            entries.push(RangeEntry {
+                node,
+                range: node.own_range(&spans),
+                children: vec![],
+            });
+
+            stack.extend(children.into_iter().rev().map(|child| (child, Some(entry))));
+        }
+
+        // Children always come after their parent, so by the time a node is reached
+        // the ranges of all of its children are complete.
+        for entry in (0..entries.len()).rev() {
+            let range = entries[entry]
+                .children
+                .iter()
+                .fold(entries[entry].range, |range, child| {
+                    merge(range, entries[*child].range)
+                });
+            entries[entry].range = range;
+        }
+
+        Self { entries }
+    }
+}
+
+fn merge(a: Option<(Span, Span)>, b: Option<(Span, Span)>) -> Option<(Span, Span)> {
+    match (a, b) {
+        (Some((start, end)), Some((other_start, other_end))) => {
+            Some((start.min(other_start), end.max(other_end)))
+        }
+        (range, None) | (None, range) => range,
+    }
+}
+
+trait Collect<'n, 'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, spans: &mut Vec<Span>);
+}
+
+impl<'n, 'a> Collect<'n, 'a> for Span {
+    fn collect(&'n self, _: &mut Vec<Node<'n, 'a>>, spans: &mut Vec<Span>) {
+        spans.push(*self);
+    }
+}
+
+impl<'n, 'a, T: Collect<'n, 'a>> Collect<'n, 'a> for Box<T> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, spans: &mut Vec<Span>) {
+        (**self).collect(nodes, spans);
+    }
+}
+
+impl<'n, 'a, T: Collect<'n, 'a>> Collect<'n, 'a> for Option<T> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, spans: &mut Vec<Span>) {
+        if let Some(value) = self {
+            value.collect(nodes, spans);
+        }
+    }
+}
+
+impl<'n, 'a, T: Collect<'n, 'a>> Collect<'n, 'a> for Vec<T> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, spans: &mut Vec<Span>) {
+        for value in self {
+            value.collect(nodes, spans);
+        }
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for Attribute<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::Attribute(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for AttributeGroup<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::AttributeGroup(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for Class<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::Class(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for AnonymousClass<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::AnonymousClass(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for ClassExtends<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::ClassExtends(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for ClassImplements<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::ClassImplements(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for ClassMember<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::ClassMember(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for Comment<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::Comment(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for ConstantEntry<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::ConstantEntry(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for Constant<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::Constant(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for ClassishConstant<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::ClassishConstant(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for UnitEnumCase<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::UnitEnumCase(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for UnitEnumMember<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::UnitEnumMember(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for UnitEnum<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::UnitEnum(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for BackedEnumType {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::BackedEnumType(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for BackedEnumCase<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::BackedEnumCase(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for BackedEnumMember<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::BackedEnumMember(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for BackedEnum<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::BackedEnum(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for FunctionParameter<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::FunctionParameter(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for FunctionParameterList<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::FunctionParameterList(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for Function<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::Function(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for ClosureUse<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::ClosureUse(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for Closure<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::Closure(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for ArrowFunction<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::ArrowFunction(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for MethodParameter<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::MethodParameter(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for MethodParameterList<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::MethodParameterList(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for Method<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::Method(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for Identifier<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::Identifier(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for SimpleIdentifier<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::SimpleIdentifier(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for DynamicIdentifier<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::DynamicIdentifier(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for InterfaceMember<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::InterfaceMember(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for Interface<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::Interface(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for InterfaceExtends<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::InterfaceExtends(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for Type<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::Type(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for StaticVar<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::StaticVar(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for Statement<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::Statement(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for DeclareItem<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::DeclareItem(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for Case<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::Case(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for Use<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::Use(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for Expression<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::Expression(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for Arg<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::Arg(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for DefaultMatchArm<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::DefaultMatchArm(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for MatchArm<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::MatchArm(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for StringPart<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::StringPart(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for ArrayItem<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::ArrayItem(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for ListItem<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::ListItem(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for ElseIf<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::ElseIf(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for VisibilityModifier {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::VisibilityModifier(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for PromotedPropertyModifier {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::PromotedPropertyModifier(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for PromotedPropertyModifierGroup {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::PromotedPropertyModifierGroup(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for PropertyModifier {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::PropertyModifier(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for PropertyModifierGroup {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::PropertyModifierGroup(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for MethodModifier {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::MethodModifier(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for MethodModifierGroup {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::MethodModifierGroup(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for ClassModifier {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::ClassModifier(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for ClassModifierGroup {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::ClassModifierGroup(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for ConstantModifier {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::ConstantModifier(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for ConstantModifierGroup {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::ConstantModifierGroup(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for ArithmeticOperation<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::ArithmeticOperation(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for AssignmentOperation<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::AssignmentOperation(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for BitwiseOperation<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::BitwiseOperation(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for ComparisonOperation<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::ComparisonOperation(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for LogicalOperation<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::LogicalOperation(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for Property<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::Property(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for PropertyEntry<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::PropertyEntry(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for VariableProperty<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::VariableProperty(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for VariablePropertyEntry<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::VariablePropertyEntry(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for Trait<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::Trait(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for TraitMember<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::TraitMember(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for TraitUsage<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::TraitUsage(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for TraitUsageAdaptation<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::TraitUsageAdaptation(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for CatchType<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::CatchType(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for TryBlock<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::TryBlock(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for CatchBlock<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::CatchBlock(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for FinallyBlock<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::FinallyBlock(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for Variable<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::Variable(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for SimpleVariable<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::SimpleVariable(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for VariableVariable<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::VariableVariable(self));
+    }
+}
+
+impl<'n, 'a> Collect<'n, 'a> for BracedVariableVariable<'a> {
+    fn collect(&'n self, nodes: &mut Vec<Node<'n, 'a>>, _: &mut Vec<Span>) {
+        nodes.push(Node::BracedVariableVariable(self));
+    }
+}
@@ -1,255 +1,257 @@
+use serde::Serialize;
+
 use crate::lexer::token::Span;
 
 use super::Expression;
 
-#[derive(Debug, Clone, PartialEq)]
-pub enum ArithmeticOperation {
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum ArithmeticOperation<'a> {
     Addition {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     Subtraction {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     Multiplication {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     Division {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     Modulo {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     Exponentiation {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     Negation {
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     Identity {
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     PreIncrement {
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     PostIncrement {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
     },
     PreDecrement {
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     PostDecrement {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
     },
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub enum AssignmentOperation {
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum AssignmentOperation<'a> {
     Assign {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     Addition {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     Subtraction {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     Multiplication {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     Division {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     Modulo {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     Exponentiation {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     Concat {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     BitwiseAnd {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     BitwiseOr {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     BitwiseXor {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     LeftShift {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     RightShift {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     Coalesce {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub enum BitwiseOperation {
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum BitwiseOperation<'a> {
     And {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     Or {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     Xor {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     LeftShift {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     RightShift {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     Not {
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub enum ComparisonOperation {
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum ComparisonOperation<'a> {
     Equal {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     Identical {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     NotEqual {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     AngledNotEqual {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     NotIdentical {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     LessThan {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     GreaterThan {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     LessThanOrEqual {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     GreaterThanOrEqual {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     Spaceship {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub enum LogicalOperation {
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum LogicalOperation<'a> {
     And {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     Or {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     Not {
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     LogicalAnd {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     LogicalOr {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
     LogicalXor {
-        left: Box<Expression>,
+        left: Box<Expression<'a>>,
         span: Span,
-        right: Box<Expression>,
+        right: Box<Expression<'a>>,
     },
 }
@@ -0,0 +1,1802 @@
+use crate::lexer::byte_string::ByteString;
+use crate::lexer::token::Token;
+use crate::lexer::token::TokenKind;
+use crate::parser::ast::attributes::Attribute;
+use crate::parser::ast::attributes::AttributeGroup;
+use crate::parser::ast::classes::AnonymousClass;
+use crate::parser::ast::classes::Class;
+use crate::parser::ast::classes::ClassExtends;
+use crate::parser::ast::classes::ClassImplements;
+use crate::parser::ast::classes::ClassMember;
+use crate::parser::ast::comments::Comment;
+use crate::parser::ast::constant::ClassishConstant;
+use crate::parser::ast::constant::Constant;
+use crate::parser::ast::constant::ConstantEntry;
+use crate::parser::ast::drop::take;
+use crate::parser::ast::enums::BackedEnum;
+use crate::parser::ast::enums::BackedEnumCase;
+use crate::parser::ast::enums::BackedEnumMember;
+use crate::parser::ast::enums::UnitEnum;
+use crate::parser::ast::enums::UnitEnumCase;
+use crate::parser::ast::enums::UnitEnumMember;
+use crate::parser::ast::functions::ArrowFunction;
+use crate::parser::ast::functions::Closure;
+use crate::parser::ast::functions::ClosureUse;
+use crate::parser::ast::functions::Function;
+use crate::parser::ast::functions::FunctionParameter;
+use crate::parser::ast::functions::FunctionParameterList;
+use crate::parser::ast::functions::Method;
+use crate::parser::ast::functions::MethodParameter;
+use crate::parser::ast::functions::MethodParameterList;
+use crate::parser::ast::identifiers::DynamicIdentifier;
+use crate::parser::ast::identifiers::Identifier;
+use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::interfaces::Interface;
+use crate::parser::ast::interfaces::InterfaceExtends;
+use crate::parser::ast::interfaces::InterfaceMember;
+use crate::parser::ast::operators::ArithmeticOperation;
+use crate::parser::ast::operators::AssignmentOperation;
+use crate::parser::ast::operators::BitwiseOperation;
+use crate::parser::ast::operators::ComparisonOperation;
+use crate::parser::ast::operators::LogicalOperation;
+use crate::parser::ast::properties::Property;
+use crate::parser::ast::properties::PropertyEntry;
+use crate::parser::ast::properties::VariableProperty;
+use crate::parser::ast::properties::VariablePropertyEntry;
+use crate::parser::ast::traits::Trait;
+use crate::parser::ast::traits::TraitMember;
+use crate::parser::ast::traits::TraitUsage;
+use crate::parser::ast::traits::TraitUsageAdaptation;
+use crate::parser::ast::try_block::CatchBlock;
+use crate::parser::ast::try_block::CatchType;
+use crate::parser::ast::try_block::FinallyBlock;
+use crate::parser::ast::try_block::TryBlock;
+use crate::parser::ast::variables::BracedVariableVariable;
+use crate::parser::ast::variables::SimpleVariable;
+use crate::parser::ast::variables::Variable;
+use crate::parser::ast::variables::VariableVariable;
+use crate::parser::ast::Arg;
+use crate::parser::ast::ArrayItem;
+use crate::parser::ast::Case;
+use crate::parser::ast::DeclareItem;
+use crate::parser::ast::DefaultMatchArm;
+use crate::parser::ast::ElseIf;
+use crate::parser::ast::Expression;
+use crate::parser::ast::ListItem;
+use crate::parser::ast::MatchArm;
+use crate::parser::ast::Statement;
+use crate::parser::ast::StaticVar;
+use crate::parser::ast::StringPart;
+use crate::parser::ast::Type;
+use crate::parser::ast::Use;
+use crate::parser::stack::ensure_sufficient_stack;
+
+/// Detach a value from the source code it was parsed from.
+///
+/// The lexer borrows names, strings and comments from the input wherever it can,
+/// so tokens and AST nodes share the lifetime of the source buffer. Converting
+/// them into their owned form copies any borrowed bytes, so the result can outlive
+/// the source, be cached or be sent to another thread.
+pub trait IntoOwned {
+    type Owned: 'static;
+
+    fn into_owned(self) -> Self::Owned;
+}
+
+impl IntoOwned for ByteString<'_> {
+    type Owned = ByteString<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ByteString::into_owned(self)
+    }
+}
+
+impl IntoOwned for Token<'_> {
+    type Owned = Token<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        Token {
+            kind: self.kind.into_owned(),
+            span: self.span,
+            symbol: self.symbol,
+        }
+    }
+}
+
+impl IntoOwned for TokenKind<'_> {
+    type Owned = TokenKind<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        match self {
+            TokenKind::Self_ => TokenKind::Self_,
+            TokenKind::Parent => TokenKind::Parent,
+            TokenKind::Backtick => TokenKind::Backtick,
+            TokenKind::StartDocString(label, kind) => {
+                TokenKind::StartDocString(label.into_owned(), kind)
+            }
+            TokenKind::EndDocString(label, kind, indentation) => {
+                TokenKind::EndDocString(label.into_owned(), kind, indentation)
+            }
+            TokenKind::From => TokenKind::From,
+            TokenKind::Print => TokenKind::Print,
+            TokenKind::Dollar => TokenKind::Dollar,
+            TokenKind::HaltCompiler => TokenKind::HaltCompiler,
+            TokenKind::Readonly => TokenKind::Readonly,
+            TokenKind::Global => TokenKind::Global,
+            TokenKind::Abstract => TokenKind::Abstract,
+            TokenKind::Ampersand => TokenKind::Ampersand,
+            TokenKind::AmpersandEquals => TokenKind::AmpersandEquals,
+            TokenKind::And => TokenKind::And,
+            TokenKind::AndEqual => TokenKind::AndEqual,
+            TokenKind::Array => TokenKind::Array,
+            TokenKind::ArrayCast => TokenKind::ArrayCast,
+            TokenKind::Arrow => TokenKind::Arrow,
+            TokenKind::NullsafeArrow => TokenKind::NullsafeArrow,
+            TokenKind::At => TokenKind::At,
+            TokenKind::As => TokenKind::As,
+            TokenKind::Asterisk => TokenKind::Asterisk,
+            TokenKind::Attribute => TokenKind::Attribute,
+            TokenKind::Bang => TokenKind::Bang,
+            TokenKind::BangEquals => TokenKind::BangEquals,
+            TokenKind::AngledLeftRight => TokenKind::AngledLeftRight,
+            TokenKind::BangDoubleEquals => TokenKind::BangDoubleEquals,
+            TokenKind::Spaceship => TokenKind::Spaceship,
+            TokenKind::BoolCast => TokenKind::BoolCast,
+            TokenKind::BooleanCast => TokenKind::BooleanCast,
+            TokenKind::BooleanAnd => TokenKind::BooleanAnd,
+            TokenKind::BooleanOr => TokenKind::BooleanOr,
+            TokenKind::Break => TokenKind::Break,
+            TokenKind::Callable => TokenKind::Callable,
+            TokenKind::Caret => TokenKind::Caret,
+            TokenKind::CaretEquals => TokenKind::CaretEquals,
+            TokenKind::Case => TokenKind::Case,
+            TokenKind::Catch => TokenKind::Catch,
+            TokenKind::Class => TokenKind::Class,
+            TokenKind::ClassConstant => TokenKind::ClassConstant,
+            TokenKind::TraitConstant => TokenKind::TraitConstant,
+            TokenKind::FunctionConstant => TokenKind::FunctionConstant,
+            TokenKind::MethodConstant => TokenKind::MethodConstant,
+            TokenKind::LineConstant => TokenKind::LineConstant,
+            TokenKind::FileConstant => TokenKind::FileConstant,
+            TokenKind::Clone => TokenKind::Clone,
+            TokenKind::MinusEquals => TokenKind::MinusEquals,
+            TokenKind::CloseTag => TokenKind::CloseTag,
+            TokenKind::Coalesce => TokenKind::Coalesce,
+            TokenKind::CoalesceEqual => TokenKind::CoalesceEqual,
+            TokenKind::AsteriskEqual => TokenKind::AsteriskEqual,
+            TokenKind::Colon => TokenKind::Colon,
+            TokenKind::Comma => TokenKind::Comma,
+            TokenKind::SingleLineComment(value) => TokenKind::SingleLineComment(value.into_owned()),
+            TokenKind::HashMarkComment(value) => TokenKind::HashMarkComment(value.into_owned()),
+            TokenKind::MultiLineComment(value) => TokenKind::MultiLineComment(value.into_owned()),
+            TokenKind::DocumentComment(value) => TokenKind::DocumentComment(value.into_owned()),
+            TokenKind::ConcatEqual => TokenKind::ConcatEqual,
+            TokenKind::Const => TokenKind::Const,
+            TokenKind::LiteralString(value) => TokenKind::LiteralString(value.into_owned()),
+            TokenKind::Continue => TokenKind::Continue,
+            TokenKind::CurlyOpen => TokenKind::CurlyOpen,
+            TokenKind::Declare => TokenKind::Declare,
+            TokenKind::Decrement => TokenKind::Decrement,
+            TokenKind::Default => TokenKind::Default,
+            TokenKind::DirConstant => TokenKind::DirConstant,
+            TokenKind::DivEqual => TokenKind::DivEqual,
+            TokenKind::Do => TokenKind::Do,
+            TokenKind::DocOpen(value) => TokenKind::DocOpen(value.into_owned()),
+            TokenKind::DollarLeftBrace => TokenKind::DollarLeftBrace,
+            TokenKind::Dot => TokenKind::Dot,
+            TokenKind::DotEquals => TokenKind::DotEquals,
+            TokenKind::DoubleArrow => TokenKind::DoubleArrow,
+            TokenKind::DoubleCast => TokenKind::DoubleCast,
+            TokenKind::RealCast => TokenKind::RealCast,
+            TokenKind::FloatCast => TokenKind::FloatCast,
+            TokenKind::DoubleColon => TokenKind::DoubleColon,
+            TokenKind::DoubleEquals => TokenKind::DoubleEquals,
+            TokenKind::DoubleQuote => TokenKind::DoubleQuote,
+            TokenKind::Echo => TokenKind::Echo,
+            TokenKind::Ellipsis => TokenKind::Ellipsis,
+            TokenKind::Else => TokenKind::Else,
+            TokenKind::ElseIf => TokenKind::ElseIf,
+            TokenKind::Empty => TokenKind::Empty,
+            TokenKind::EndDeclare => TokenKind::EndDeclare,
+            TokenKind::EndFor => TokenKind::EndFor,
+            TokenKind::EndForeach => TokenKind::EndForeach,
+            TokenKind::EndIf => TokenKind::EndIf,
+            TokenKind::EndSwitch => TokenKind::EndSwitch,
+            TokenKind::EndWhile => TokenKind::EndWhile,
+            TokenKind::Enum => TokenKind::Enum,
+            TokenKind::Eof => TokenKind::Eof,
+            TokenKind::Equals => TokenKind::Equals,
+            TokenKind::Extends => TokenKind::Extends,
+            TokenKind::False => TokenKind::False,
+            TokenKind::Final => TokenKind::Final,
+            TokenKind::Finally => TokenKind::Finally,
+            TokenKind::LiteralFloat(value) => TokenKind::LiteralFloat(value.into_owned()),
+            TokenKind::Fn => TokenKind::Fn,
+            TokenKind::For => TokenKind::For,
+            TokenKind::Foreach => TokenKind::Foreach,
+            TokenKind::FullyQualifiedIdentifier(value) => {
+                TokenKind::FullyQualifiedIdentifier(value.into_owned())
+            }
+            TokenKind::Function => TokenKind::Function,
+            TokenKind::Goto => TokenKind::Goto,
+            TokenKind::GreaterThan => TokenKind::GreaterThan,
+            TokenKind::GreaterThanEquals => TokenKind::GreaterThanEquals,
+            TokenKind::Identifier(value) => TokenKind::Identifier(value.into_owned()),
+            TokenKind::If => TokenKind::If,
+            TokenKind::Implements => TokenKind::Implements,
+            TokenKind::Include => TokenKind::Include,
+            TokenKind::IncludeOnce => TokenKind::IncludeOnce,
+            TokenKind::Increment => TokenKind::Increment,
+            TokenKind::InlineHtml(value) => TokenKind::InlineHtml(value.into_owned()),
+            TokenKind::Instanceof => TokenKind::Instanceof,
+            TokenKind::Insteadof => TokenKind::Insteadof,
+            TokenKind::Eval => TokenKind::Eval,
+            TokenKind::Exit => TokenKind::Exit,
+            TokenKind::Unset => TokenKind::Unset,
+            TokenKind::Isset => TokenKind::Isset,
+            TokenKind::List => TokenKind::List,
+            TokenKind::LiteralInteger(value) => TokenKind::LiteralInteger(value.into_owned()),
+            TokenKind::IntCast => TokenKind::IntCast,
+            TokenKind::IntegerCast => TokenKind::IntegerCast,
+            TokenKind::Interface => TokenKind::Interface,
+            TokenKind::LeftBrace => TokenKind::LeftBrace,
+            TokenKind::LeftBracket => TokenKind::LeftBracket,
+            TokenKind::LeftParen => TokenKind::LeftParen,
+            TokenKind::LeftShift => TokenKind::LeftShift,
+            TokenKind::LeftShiftEquals => TokenKind::LeftShiftEquals,
+            TokenKind::RightShift => TokenKind::RightShift,
+            TokenKind::RightShiftEquals => TokenKind::RightShiftEquals,
+            TokenKind::LessThan => TokenKind::LessThan,
+            TokenKind::LessThanEquals => TokenKind::LessThanEquals,
+            TokenKind::Match => TokenKind::Match,
+            TokenKind::Minus => TokenKind::Minus,
+            TokenKind::Namespace => TokenKind::Namespace,
+            TokenKind::NamespaceSeparator => TokenKind::NamespaceSeparator,
+            TokenKind::NamespaceConstant => TokenKind::NamespaceConstant,
+            TokenKind::New => TokenKind::New,
+            TokenKind::Null => TokenKind::Null,
+            TokenKind::ObjectCast => TokenKind::ObjectCast,
+            TokenKind::UnsetCast => TokenKind::UnsetCast,
+            TokenKind::OpenTag(kind) => TokenKind::OpenTag(kind),
+            TokenKind::Percent => TokenKind::Percent,
+            TokenKind::PercentEquals => TokenKind::PercentEquals,
+            TokenKind::Pipe => TokenKind::Pipe,
+            TokenKind::PipeEquals => TokenKind::PipeEquals,
+            TokenKind::Plus => TokenKind::Plus,
+            TokenKind::PlusEquals => TokenKind::PlusEquals,
+            TokenKind::Pow => TokenKind::Pow,
+            TokenKind::PowEquals => TokenKind::PowEquals,
+            TokenKind::Private => TokenKind::Private,
+            TokenKind::Protected => TokenKind::Protected,
+            TokenKind::Public => TokenKind::Public,
+            TokenKind::QualifiedIdentifier(value) => {
+                TokenKind::QualifiedIdentifier(value.into_owned())
+            }
+            TokenKind::Question => TokenKind::Question,
+            TokenKind::QuestionColon => TokenKind::QuestionColon,
+            TokenKind::Require => TokenKind::Require,
+            TokenKind::RequireOnce => TokenKind::RequireOnce,
+            TokenKind::Return => TokenKind::Return,
+            TokenKind::RightBrace => TokenKind::RightBrace,
+            TokenKind::RightBracket => TokenKind::RightBracket,
+            TokenKind::RightParen => TokenKind::RightParen,
+            TokenKind::SemiColon => TokenKind::SemiColon,
+            TokenKind::Slash => TokenKind::Slash,
+            TokenKind::SlashEquals => TokenKind::SlashEquals,
+            TokenKind::Static => TokenKind::Static,
+            TokenKind::StringCast => TokenKind::StringCast,
+            TokenKind::BinaryCast => TokenKind::BinaryCast,
+            TokenKind::StringPart(value) => TokenKind::StringPart(value.into_owned()),
+            TokenKind::Switch => TokenKind::Switch,
+            TokenKind::Throw => TokenKind::Throw,
+            TokenKind::Trait => TokenKind::Trait,
+            TokenKind::TripleEquals => TokenKind::TripleEquals,
+            TokenKind::True => TokenKind::True,
+            TokenKind::Try => TokenKind::Try,
+            TokenKind::Use => TokenKind::Use,
+            TokenKind::Var => TokenKind::Var,
+            TokenKind::Variable(value) => TokenKind::Variable(value.into_owned()),
+            TokenKind::Yield => TokenKind::Yield,
+            TokenKind::While => TokenKind::While,
+            TokenKind::BitwiseNot => TokenKind::BitwiseNot,
+            TokenKind::LogicalAnd => TokenKind::LogicalAnd,
+            TokenKind::LogicalOr => TokenKind::LogicalOr,
+            TokenKind::LogicalXor => TokenKind::LogicalXor,
+        }
+    }
+}
+
+impl<T: IntoOwned> IntoOwned for Box<T> {
+    type Owned = Box<T::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        Box::new((*self).into_owned())
+    }
+}
+
+impl<T: IntoOwned> IntoOwned for Option<T> {
+    type Owned = Option<T::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        self.map(IntoOwned::into_owned)
+    }
+}
+
+impl<T: IntoOwned> IntoOwned for Vec<T> {
+    type Owned = Vec<T::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        self.into_iter().map(IntoOwned::into_owned).collect()
+    }
+}
+
+impl IntoOwned for Attribute<'_> {
+    type Owned = Attribute<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        Attribute {
+            start: self.start,
+            end: self.end,
+            expression: self.expression.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for AttributeGroup<'_> {
+    type Owned = AttributeGroup<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        AttributeGroup {
+            start: self.start,
+            end: self.end,
+            members: self.members.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for Class<'_> {
+    type Owned = Class<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        Class {
+            start: self.start,
+            end: self.end,
+            name: self.name.into_owned(),
+            extends: self.extends.into_owned(),
+            implements: self.implements.into_owned(),
+            attributes: self.attributes.into_owned(),
+            modifiers: self.modifiers,
+            members: self.members.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for AnonymousClass<'_> {
+    type Owned = AnonymousClass<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        AnonymousClass {
+            start: self.start,
+            end: self.end,
+            extends: self.extends.into_owned(),
+            implements: self.implements.into_owned(),
+            attributes: self.attributes.into_owned(),
+            members: self.members.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for ClassExtends<'_> {
+    type Owned = ClassExtends<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ClassExtends {
+            span: self.span,
+            parent: self.parent.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for ClassImplements<'_> {
+    type Owned = ClassImplements<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ClassImplements {
+            span: self.span,
+            interfaces: self.interfaces.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for ClassMember<'_> {
+    type Owned = ClassMember<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        match self {
+            ClassMember::Constant(value) => ClassMember::Constant(value.into_owned()),
+            ClassMember::TraitUsage(value) => ClassMember::TraitUsage(value.into_owned()),
+            ClassMember::Property(value) => ClassMember::Property(value.into_owned()),
+            ClassMember::VariableProperty(value) => {
+                ClassMember::VariableProperty(value.into_owned())
+            }
+            ClassMember::Method(value) => ClassMember::Method(value.into_owned()),
+        }
+    }
+}
+
+impl IntoOwned for Comment<'_> {
+    type Owned = Comment<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        Comment {
+            start: self.start,
+            end: self.end,
+            format: self.format,
+            content: self.content.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for ConstantEntry<'_> {
+    type Owned = ConstantEntry<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ConstantEntry {
+            name: self.name.into_owned(),
+            value: self.value.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for Constant<'_> {
+    type Owned = Constant<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        Constant {
+            start: self.start,
+            end: self.end,
+            entries: self.entries.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for ClassishConstant<'_> {
+    type Owned = ClassishConstant<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ClassishConstant {
+            start: self.start,
+            end: self.end,
+            attributes: self.attributes.into_owned(),
+            modifiers: self.modifiers,
+            entries: self.entries.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for UnitEnumCase<'_> {
+    type Owned = UnitEnumCase<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        UnitEnumCase {
+            start: self.start,
+            end: self.end,
+            attributes: self.attributes.into_owned(),
+            name: self.name.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for UnitEnumMember<'_> {
+    type Owned = UnitEnumMember<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        match self {
+            UnitEnumMember::Case(value) => UnitEnumMember::Case(value.into_owned()),
+            UnitEnumMember::Method(value) => UnitEnumMember::Method(value.into_owned()),
+            UnitEnumMember::Constant(value) => UnitEnumMember::Constant(value.into_owned()),
+        }
+    }
+}
+
+impl IntoOwned for UnitEnum<'_> {
+    type Owned = UnitEnum<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        UnitEnum {
+            start: self.start,
+            end: self.end,
+            name: self.name.into_owned(),
+            attributes: self.attributes.into_owned(),
+            implements: self.implements.into_owned(),
+            members: self.members.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for BackedEnumCase<'_> {
+    type Owned = BackedEnumCase<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        BackedEnumCase {
+            start: self.start,
+            end: self.end,
+            name: self.name.into_owned(),
+            attributes: self.attributes.into_owned(),
+            value: self.value.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for BackedEnumMember<'_> {
+    type Owned = BackedEnumMember<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        match self {
+            BackedEnumMember::Case(value) => BackedEnumMember::Case(value.into_owned()),
+            BackedEnumMember::Method(value) => BackedEnumMember::Method(value.into_owned()),
+            BackedEnumMember::Constant(value) => BackedEnumMember::Constant(value.into_owned()),
+        }
+    }
+}
+
+impl IntoOwned for BackedEnum<'_> {
+    type Owned = BackedEnum<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        BackedEnum {
+            start: self.start,
+            end: self.end,
+            name: self.name.into_owned(),
+            attributes: self.attributes.into_owned(),
+            implements: self.implements.into_owned(),
+            backed_type: self.backed_type,
+            members: self.members.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for FunctionParameter<'_> {
+    type Owned = FunctionParameter<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        FunctionParameter {
+            start: self.start,
+            end: self.end,
+            name: self.name.into_owned(),
+            attributes: self.attributes.into_owned(),
+            r#type: self.r#type.into_owned(),
+            variadic: self.variadic,
+            default: self.default.into_owned(),
+            by_ref: self.by_ref,
+        }
+    }
+}
+
+impl IntoOwned for FunctionParameterList<'_> {
+    type Owned = FunctionParameterList<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        FunctionParameterList {
+            start: self.start,
+            end: self.end,
+            members: self.members.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for Function<'_> {
+    type Owned = Function<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        Function {
+            start: self.start,
+            end: self.end,
+            name: self.name.into_owned(),
+            attributes: self.attributes.into_owned(),
+            parameters: self.parameters.into_owned(),
+            return_type: self.return_type.into_owned(),
+            by_ref: self.by_ref,
+            body: self.body.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for ClosureUse<'_> {
+    type Owned = ClosureUse<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ClosureUse {
+            var: self.var.into_owned(),
+            by_ref: self.by_ref,
+        }
+    }
+}
+
+impl IntoOwned for Closure<'_> {
+    type Owned = Closure<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        Closure {
+            start: self.start,
+            end: self.end,
+            attributes: self.attributes.into_owned(),
+            parameters: self.parameters.into_owned(),
+            return_ty: self.return_ty.into_owned(),
+            uses: self.uses.into_owned(),
+            by_ref: self.by_ref,
+            body: self.body.into_owned(),
+            r#static: self.r#static,
+        }
+    }
+}
+
+impl IntoOwned for ArrowFunction<'_> {
+    type Owned = ArrowFunction<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ArrowFunction {
+            start: self.start,
+            end: self.end,
+            attributes: self.attributes.into_owned(),
+            parameters: self.parameters.into_owned(),
+            return_type: self.return_type.into_owned(),
+            by_ref: self.by_ref,
+            body: self.body.into_owned(),
+            r#static: self.r#static,
+        }
+    }
+}
+
+impl IntoOwned for MethodParameter<'_> {
+    type Owned = MethodParameter<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        MethodParameter {
+            start: self.start,
+            end: self.end,
+            name: self.name.into_owned(),
+            attributes: self.attributes.into_owned(),
+            r#type: self.r#type.into_owned(),
+            variadic: self.variadic,
+            default: self.default.into_owned(),
+            modifiers: self.modifiers,
+            by_ref: self.by_ref,
+        }
+    }
+}
+
+impl IntoOwned for MethodParameterList<'_> {
+    type Owned = MethodParameterList<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        MethodParameterList {
+            start: self.start,
+            end: self.end,
+            members: self.members.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for Method<'_> {
+    type Owned = Method<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        Method {
+            start: self.start,
+            end: self.end,
+            name: self.name.into_owned(),
+            attributes: self.attributes.into_owned(),
+            parameters: self.parameters.into_owned(),
+            body: self.body.into_owned(),
+            modifiers: self.modifiers,
+            return_type: self.return_type.into_owned(),
+            by_ref: self.by_ref,
+        }
+    }
+}
+
+impl IntoOwned for Identifier<'_> {
+    type Owned = Identifier<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        match self {
+            Identifier::SimpleIdentifier(value) => Identifier::SimpleIdentifier(value.into_owned()),
+            Identifier::DynamicIdentifier(value) => {
+                Identifier::DynamicIdentifier(value.into_owned())
+            }
+        }
+    }
+}
+
+impl IntoOwned for SimpleIdentifier<'_> {
+    type Owned = SimpleIdentifier<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        SimpleIdentifier {
+            span: self.span,
+            name: self.name.into_owned(),
+            symbol: self.symbol,
+        }
+    }
+}
+
+impl IntoOwned for DynamicIdentifier<'_> {
+    type Owned = DynamicIdentifier<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        DynamicIdentifier {
+            start: self.start,
+            expr: self.expr.into_owned(),
+            end: self.end,
+        }
+    }
+}
+
+impl IntoOwned for InterfaceMember<'_> {
+    type Owned = InterfaceMember<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        match self {
+            InterfaceMember::Constant(value) => InterfaceMember::Constant(value.into_owned()),
+            InterfaceMember::Method(value) => InterfaceMember::Method(value.into_owned()),
+        }
+    }
+}
+
+impl IntoOwned for Interface<'_> {
+    type Owned = Interface<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        Interface {
+            start: self.start,
+            end: self.end,
+            attributes: self.attributes.into_owned(),
+            name: self.name.into_owned(),
+            extends: self.extends.into_owned(),
+            members: self.members.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for InterfaceExtends<'_> {
+    type Owned = InterfaceExtends<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        InterfaceExtends {
+            span: self.span,
+            parents: self.parents.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for Type<'_> {
+    type Owned = Type<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        match self {
+            Type::Identifier(value) => Type::Identifier(value.into_owned()),
+            Type::Nullable(value) => Type::Nullable(value.into_owned()),
+            Type::Union(value) => Type::Union(value.into_owned()),
+            Type::Intersection(value) => Type::Intersection(value.into_owned()),
+            Type::Void => Type::Void,
+            Type::Null => Type::Null,
+            Type::True => Type::True,
+            Type::False => Type::False,
+            Type::Never => Type::Never,
+            Type::Float => Type::Float,
+            Type::Boolean => Type::Boolean,
+            Type::Integer => Type::Integer,
+            Type::String => Type::String,
+            Type::Array => Type::Array,
+            Type::Object => Type::Object,
+            Type::Mixed => Type::Mixed,
+            Type::Callable => Type::Callable,
+            Type::Iterable => Type::Iterable,
+            Type::StaticReference => Type::StaticReference,
+            Type::SelfReference => Type::SelfReference,
+            Type::ParentReference => Type::ParentReference,
+        }
+    }
+}
+
+impl IntoOwned for StaticVar<'_> {
+    type Owned = StaticVar<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        StaticVar {
+            var: self.var.into_owned(),
+            default: self.default.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for Statement<'_> {
+    type Owned = Statement<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        match self {
+            Statement::InlineHtml(value) => Statement::InlineHtml(value.into_owned()),
+            Statement::Goto { label } => Statement::Goto {
+                label: label.into_owned(),
+            },
+            Statement::Label { label } => Statement::Label {
+                label: label.into_owned(),
+            },
+            Statement::HaltCompiler { content } => Statement::HaltCompiler {
+                content: content.into_owned(),
+            },
+            Statement::Static { vars } => Statement::Static {
+                vars: vars.into_owned(),
+            },
+            Statement::DoWhile { condition, body } => Statement::DoWhile {
+                condition: condition.into_owned(),
+                body: body.into_owned(),
+            },
+            Statement::While { condition, body } => Statement::While {
+                condition: condition.into_owned(),
+                body: body.into_owned(),
+            },
+            Statement::For {
+                init,
+                condition,
+                r#loop,
+                then,
+            } => Statement::For {
+                init: init.into_owned(),
+                condition: condition.into_owned(),
+                r#loop: r#loop.into_owned(),
+                then: then.into_owned(),
+            },
+            Statement::Foreach {
+                expr,
+                by_ref,
+                key_var,
+                value_var,
+                body,
+            } => Statement::Foreach {
+                expr: expr.into_owned(),
+                by_ref,
+                key_var: key_var.into_owned(),
+                value_var: value_var.into_owned(),
+                body: body.into_owned(),
+            },
+            Statement::Constant(value) => Statement::Constant(value.into_owned()),
+            Statement::Function(value) => Statement::Function(value.into_owned()),
+            Statement::Class(value) => Statement::Class(value.into_owned()),
+            Statement::Trait(value) => Statement::Trait(value.into_owned()),
+            Statement::Interface(value) => Statement::Interface(value.into_owned()),
+            Statement::If {
+                condition,
+                then,
+                else_ifs,
+                r#else,
+            } => Statement::If {
+                condition: condition.into_owned(),
+                then: then.into_owned(),
+                else_ifs: else_ifs.into_owned(),
+                r#else: r#else.into_owned(),
+            },
+            Statement::Return { value } => Statement::Return {
+                value: value.into_owned(),
+            },
+            Statement::Switch { condition, cases } => Statement::Switch {
+                condition: condition.into_owned(),
+                cases: cases.into_owned(),
+            },
+            Statement::Break { num } => Statement::Break {
+                num: num.into_owned(),
+            },
+            Statement::Continue { num } => Statement::Continue {
+                num: num.into_owned(),
+            },
+            Statement::Echo { values } => Statement::Echo {
+                values: values.into_owned(),
+            },
+            Statement::Expression { expr } => Statement::Expression {
+                expr: expr.into_owned(),
+            },
+            Statement::Namespace { name, body } => Statement::Namespace {
+                name: name.into_owned(),
+                body: body.into_owned(),
+            },
+            Statement::BracedNamespace { name, body } => Statement::BracedNamespace {
+                name: name.into_owned(),
+                body: body.into_owned(),
+            },
+            Statement::Use { uses, kind } => Statement::Use {
+                uses: uses.into_owned(),
+                kind,
+            },
+            Statement::GroupUse { prefix, kind, uses } => Statement::GroupUse {
+                prefix: prefix.into_owned(),
+                kind,
+                uses: uses.into_owned(),
+            },
+            Statement::Comment(value) => Statement::Comment(value.into_owned()),
+            Statement::Try(value) => Statement::Try(value.into_owned()),
+            Statement::UnitEnum(value) => Statement::UnitEnum(value.into_owned()),
+            Statement::BackedEnum(value) => Statement::BackedEnum(value.into_owned()),
+            Statement::Block { body } => Statement::Block {
+                body: body.into_owned(),
+            },
+            Statement::Global { span, variables } => Statement::Global {
+                span,
+                variables: variables.into_owned(),
+            },
+            Statement::Declare { declares, body } => Statement::Declare {
+                declares: declares.into_owned(),
+                body: body.into_owned(),
+            },
+            Statement::Noop(value) => Statement::Noop(value),
+        }
+    }
+}
+
+impl IntoOwned for DeclareItem<'_> {
+    type Owned = DeclareItem<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        DeclareItem {
+            key: self.key.into_owned(),
+            value: self.value.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for Case<'_> {
+    type Owned = Case<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        Case {
+            condition: self.condition.into_owned(),
+            body: self.body.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for Use<'_> {
+    type Owned = Use<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        Use {
+            name: self.name.into_owned(),
+            alias: self.alias.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for Expression<'_> {
+    type Owned = Expression<'static>;
+
+    fn into_owned(mut self) -> Self::Owned {
+        fn boxed(expression: &mut Expression<'_>) -> Box<Expression<'static>> {
+            Box::new(take(expression).into_owned())
+        }
+
+        ensure_sufficient_stack(|| match &mut self {
+            Expression::ArithmeticOperation(value) => {
+                Expression::ArithmeticOperation(take(value).into_owned())
+            }
+            Expression::AssignmentOperation(value) => {
+                Expression::AssignmentOperation(take(value).into_owned())
+            }
+            Expression::BitwiseOperation(value) => {
+                Expression::BitwiseOperation(take(value).into_owned())
+            }
+            Expression::ComparisonOperation(value) => {
+                Expression::ComparisonOperation(take(value).into_owned())
+            }
+            Expression::LogicalOperation(value) => {
+                Expression::LogicalOperation(take(value).into_owned())
+            }
+            Expression::Concat { left, span, right } => Expression::Concat {
+                left: boxed(left),
+                span: *span,
+                right: boxed(right),
+            },
+            Expression::Instanceof { left, span, right } => Expression::Instanceof {
+                left: boxed(left),
+                span: *span,
+                right: boxed(right),
+            },
+            Expression::Reference { span, right } => Expression::Reference {
+                span: *span,
+                right: boxed(right),
+            },
+            Expression::Parenthesized { start, expr, end } => Expression::Parenthesized {
+                start: *start,
+                expr: boxed(expr),
+                end: *end,
+            },
+            Expression::List { items } => Expression::List {
+                items: take(items).into_owned(),
+            },
+            Expression::Empty => Expression::Empty,
+            Expression::VariadicPlaceholder => Expression::VariadicPlaceholder,
+            Expression::ErrorSuppress { span, expr } => Expression::ErrorSuppress {
+                span: *span,
+                expr: boxed(expr),
+            },
+            Expression::LiteralInteger { i } => Expression::LiteralInteger {
+                i: take(i).into_owned(),
+            },
+            Expression::LiteralFloat { f } => Expression::LiteralFloat {
+                f: take(f).into_owned(),
+            },
+            Expression::Identifier(value) => Expression::Identifier(take(value).into_owned()),
+            Expression::Variable(value) => Expression::Variable(take(value).into_owned()),
+            Expression::Include { span, kind, path } => Expression::Include {
+                span: *span,
+                kind: kind.clone(),
+                path: boxed(path),
+            },
+            Expression::Call { target, args } => Expression::Call {
+                target: boxed(target),
+                args: take(args).into_owned(),
+            },
+            Expression::Static => Expression::Static,
+            Expression::Self_ => Expression::Self_,
+            Expression::Parent => Expression::Parent,
+            Expression::Array { items } => Expression::Array {
+                items: take(items).into_owned(),
+            },
+            Expression::Closure(value) => Expression::Closure(take(value).into_owned()),
+            Expression::ArrowFunction(value) => Expression::ArrowFunction(take(value).into_owned()),
+            Expression::New { target, span, args } => Expression::New {
+                target: boxed(target),
+                span: *span,
+                args: take(args).into_owned(),
+            },
+            Expression::LiteralString { value } => Expression::LiteralString {
+                value: take(value).into_owned(),
+            },
+            Expression::InterpolatedString { parts } => Expression::InterpolatedString {
+                parts: take(parts).into_owned(),
+            },
+            Expression::Heredoc { parts } => Expression::Heredoc {
+                parts: take(parts).into_owned(),
+            },
+            Expression::Nowdoc { value } => Expression::Nowdoc {
+                value: take(value).into_owned(),
+            },
+            Expression::ShellExec { parts } => Expression::ShellExec {
+                parts: take(parts).into_owned(),
+            },
+            Expression::PropertyFetch { target, property } => Expression::PropertyFetch {
+                target: boxed(target),
+                property: boxed(property),
+            },
+            Expression::NullsafePropertyFetch { target, property } => {
+                Expression::NullsafePropertyFetch {
+                    target: boxed(target),
+                    property: boxed(property),
+                }
+            }
+            Expression::NullsafeMethodCall {
+                target,
+                method,
+                args,
+            } => Expression::NullsafeMethodCall {
+                target: boxed(target),
+                method: boxed(method),
+                args: take(args).into_owned(),
+            },
+            Expression::StaticPropertyFetch { target, property } => {
+                Expression::StaticPropertyFetch {
+                    target: boxed(target),
+                    property: boxed(property),
+                }
+            }
+            Expression::ConstFetch { target, constant } => Expression::ConstFetch {
+                target: boxed(target),
+                constant: take(constant).into_owned(),
+            },
+            Expression::MethodCall {
+                target,
+                method,
+                args,
+            } => Expression::MethodCall {
+                target: boxed(target),
+                method: boxed(method),
+                args: take(args).into_owned(),
+            },
+            Expression::StaticMethodCall {
+                target,
+                method,
+                args,
+            } => Expression::StaticMethodCall {
+                target: boxed(target),
+                method: boxed(method),
+                args: take(args).into_owned(),
+            },
+            Expression::AnonymousClass(value) => {
+                Expression::AnonymousClass(take(value).into_owned())
+            }
+            Expression::Bool { value } => Expression::Bool { value: *value },
+            Expression::ArrayIndex { array, index } => Expression::ArrayIndex {
+                array: boxed(array),
+                index: index.as_deref_mut().map(boxed),
+            },
+            Expression::Null => Expression::Null,
+            Expression::MagicConst { span, constant } => Expression::MagicConst {
+                span: *span,
+                constant: constant.clone(),
+            },
+            Expression::Ternary {
+                condition,
+                then,
+                r#else,
+            } => Expression::Ternary {
+                condition: boxed(condition),
+                then: then.as_deref_mut().map(boxed),
+                r#else: boxed(r#else),
+            },
+            Expression::Coalesce { lhs, rhs } => Expression::Coalesce {
+                lhs: boxed(lhs),
+                rhs: boxed(rhs),
+            },
+            Expression::Clone { target } => Expression::Clone {
+                target: boxed(target),
+            },
+            Expression::Match {
+                condition,
+                default,
+                arms,
+            } => Expression::Match {
+                condition: boxed(condition),
+                default: take(default).into_owned(),
+                arms: take(arms).into_owned(),
+            },
+            Expression::Throw { value } => Expression::Throw {
+                value: boxed(value),
+            },
+            Expression::Yield { key, value } => Expression::Yield {
+                key: key.as_deref_mut().map(boxed),
+                value: value.as_deref_mut().map(boxed),
+            },
+            Expression::YieldFrom { value } => Expression::YieldFrom {
+                value: boxed(value),
+            },
+            Expression::BitwiseNot { span, value } => Expression::BitwiseNot {
+                span: *span,
+                value: boxed(value),
+            },
+            Expression::Print { span, value } => Expression::Print {
+                span: *span,
+                value: boxed(value),
+            },
+            Expression::Cast { span, kind, value } => Expression::Cast {
+                span: *span,
+                kind: kind.clone(),
+                value: boxed(value),
+            },
+        })
+    }
+}
+
+impl IntoOwned for Arg<'_> {
+    type Owned = Arg<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        Arg {
+            name: self.name.into_owned(),
+            value: self.value.into_owned(),
+            unpack: self.unpack,
+        }
+    }
+}
+
+impl IntoOwned for DefaultMatchArm<'_> {
+    type Owned = DefaultMatchArm<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        DefaultMatchArm {
+            body: self.body.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for MatchArm<'_> {
+    type Owned = MatchArm<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        MatchArm {
+            conditions: self.conditions.into_owned(),
+            body: self.body.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for StringPart<'_> {
+    type Owned = StringPart<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        match self {
+            StringPart::Const(value) => StringPart::Const(value.into_owned()),
+            StringPart::Expr(value) => StringPart::Expr(value.into_owned()),
+        }
+    }
+}
+
+impl IntoOwned for ArrayItem<'_> {
+    type Owned = ArrayItem<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ArrayItem {
+            key: self.key.into_owned(),
+            value: self.value.into_owned(),
+            unpack: self.unpack,
+            by_ref: self.by_ref,
+        }
+    }
+}
+
+impl IntoOwned for ListItem<'_> {
+    type Owned = ListItem<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ListItem {
+            key: self.key.into_owned(),
+            value: self.value.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for ElseIf<'_> {
+    type Owned = ElseIf<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        ElseIf {
+            condition: self.condition.into_owned(),
+            body: self.body.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for ArithmeticOperation<'_> {
+    type Owned = ArithmeticOperation<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        match self {
+            ArithmeticOperation::Addition { left, span, right } => ArithmeticOperation::Addition {
+                left: left.into_owned(),
+                span,
+                right: right.into_owned(),
+            },
+            ArithmeticOperation::Subtraction { left, span, right } => {
+                ArithmeticOperation::Subtraction {
+                    left: left.into_owned(),
+                    span,
+                    right: right.into_owned(),
+                }
+            }
+            ArithmeticOperation::Multiplication { left, span, right } => {
+                ArithmeticOperation::Multiplication {
+                    left: left.into_owned(),
+                    span,
+                    right: right.into_owned(),
+                }
+            }
+            ArithmeticOperation::Division { left, span, right } => ArithmeticOperation::Division {
+                left: left.into_owned(),
+                span,
+                right: right.into_owned(),
+            },
+            ArithmeticOperation::Modulo { left, span, right } => ArithmeticOperation::Modulo {
+                left: left.into_owned(),
+                span,
+                right: right.into_owned(),
+            },
+            ArithmeticOperation::Exponentiation { left, span, right } => {
+                ArithmeticOperation::Exponentiation {
+                    left: left.into_owned(),
+                    span,
+                    right: right.into_owned(),
+                }
+            }
+            ArithmeticOperation::Negation { span, right } => ArithmeticOperation::Negation {
+                span,
+                right: right.into_owned(),
+            },
+            ArithmeticOperation::Identity { span, right } => ArithmeticOperation::Identity {
+                span,
+                right: right.into_owned(),
+            },
+            ArithmeticOperation::PreIncrement { span, right } => {
+                ArithmeticOperation::PreIncrement {
+                    span,
+                    right: right.into_owned(),
+                }
+            }
+            ArithmeticOperation::PostIncrement { left, span } => {
+                ArithmeticOperation::PostIncrement {
+                    left: left.into_owned(),
+                    span,
+                }
+            }
+            ArithmeticOperation::PreDecrement { span, right } => {
+                ArithmeticOperation::PreDecrement {
+                    span,
+                    right: right.into_owned(),
+                }
+            }
+            ArithmeticOperation::PostDecrement { left, span } => {
+                ArithmeticOperation::PostDecrement {
+                    left: left.into_owned(),
+                    span,
+                }
+            }
+        }
+    }
+}
+
+impl IntoOwned for AssignmentOperation<'_> {
+    type Owned = AssignmentOperation<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        match self {
+            AssignmentOperation::Assign { left, span, right } => AssignmentOperation::Assign {
+                left: left.into_owned(),
+                span,
+                right: right.into_owned(),
+            },
+            AssignmentOperation::Addition { left, span, right } => AssignmentOperation::Addition {
+                left: left.into_owned(),
+                span,
+                right: right.into_owned(),
+            },
+            AssignmentOperation::Subtraction { left, span, right } => {
+                AssignmentOperation::Subtraction {
+                    left: left.into_owned(),
+                    span,
+                    right: right.into_owned(),
+                }
+            }
+            AssignmentOperation::Multiplication { left, span, right } => {
+                AssignmentOperation::Multiplication {
+                    left: left.into_owned(),
+                    span,
+                    right: right.into_owned(),
+                }
+            }
+            AssignmentOperation::Division { left, span, right } => AssignmentOperation::Division {
+                left: left.into_owned(),
+                span,
+                right: right.into_owned(),
+            },
+            AssignmentOperation::Modulo { left, span, right } => AssignmentOperation::Modulo {
+                left: left.into_owned(),
+                span,
+                right: right.into_owned(),
+            },
+            AssignmentOperation::Exponentiation { left, span, right } => {
+                AssignmentOperation::Exponentiation {
+                    left: left.into_owned(),
+                    span,
+                    right: right.into_owned(),
+                }
+            }
+            AssignmentOperation::Concat { left, span, right } => AssignmentOperation::Concat {
+                left: left.into_owned(),
+                span,
+                right: right.into_owned(),
+            },
+            AssignmentOperation::BitwiseAnd { left, span, right } => {
+                AssignmentOperation::BitwiseAnd {
+                    left: left.into_owned(),
+                    span,
+                    right: right.into_owned(),
+                }
+            }
+            AssignmentOperation::BitwiseOr { left, span, right } => {
+                AssignmentOperation::BitwiseOr {
+                    left: left.into_owned(),
+                    span,
+                    right: right.into_owned(),
+                }
+            }
+            AssignmentOperation::BitwiseXor { left, span, right } => {
+                AssignmentOperation::BitwiseXor {
+                    left: left.into_owned(),
+                    span,
+                    right: right.into_owned(),
+                }
+            }
+            AssignmentOperation::LeftShift { left, span, right } => {
+                AssignmentOperation::LeftShift {
+                    left: left.into_owned(),
+                    span,
+                    right: right.into_owned(),
+                }
+            }
+            AssignmentOperation::RightShift { left, span, right } => {
+                AssignmentOperation::RightShift {
+                    left: left.into_owned(),
+                    span,
+                    right: right.into_owned(),
+                }
+            }
+            AssignmentOperation::Coalesce { left, span, right } => AssignmentOperation::Coalesce {
+                left: left.into_owned(),
+                span,
+                right: right.into_owned(),
+            },
+        }
+    }
+}
+
+impl IntoOwned for BitwiseOperation<'_> {
+    type Owned = BitwiseOperation<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        match self {
+            BitwiseOperation::And { left, span, right } => BitwiseOperation::And {
+                left: left.into_owned(),
+                span,
+                right: right.into_owned(),
+            },
+            BitwiseOperation::Or { left, span, right } => BitwiseOperation::Or {
+                left: left.into_owned(),
+                span,
+                right: right.into_owned(),
+            },
+            BitwiseOperation::Xor { left, span, right } => BitwiseOperation::Xor {
+                left: left.into_owned(),
+                span,
+                right: right.into_owned(),
+            },
+            BitwiseOperation::LeftShift { left, span, right } => BitwiseOperation::LeftShift {
+                left: left.into_owned(),
+                span,
+                right: right.into_owned(),
+            },
+            BitwiseOperation::RightShift { left, span, right } => BitwiseOperation::RightShift {
+                left: left.into_owned(),
+                span,
+                right: right.into_owned(),
+            },
+            BitwiseOperation::Not { span, right } => BitwiseOperation::Not {
+                span,
+                right: right.into_owned(),
+            },
+        }
+    }
+}
+
+impl IntoOwned for ComparisonOperation<'_> {
+    type Owned = ComparisonOperation<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        match self {
+            ComparisonOperation::Equal { left, span, right } => ComparisonOperation::Equal {
+                left: left.into_owned(),
+                span,
+                right: right.into_owned(),
+            },
+            ComparisonOperation::Identical { left, span, right } => {
+                ComparisonOperation::Identical {
+                    left: left.into_owned(),
+                    span,
+                    right: right.into_owned(),
+                }
+            }
+            ComparisonOperation::NotEqual { left, span, right } => ComparisonOperation::NotEqual {
+                left: left.into_owned(),
+                span,
+                right: right.into_owned(),
+            },
+            ComparisonOperation::AngledNotEqual { left, span, right } => {
+                ComparisonOperation::AngledNotEqual {
+                    left: left.into_owned(),
+                    span,
+                    right: right.into_owned(),
+                }
+            }
+            ComparisonOperation::NotIdentical { left, span, right } => {
+                ComparisonOperation::NotIdentical {
+                    left: left.into_owned(),
+                    span,
+                    right: right.into_owned(),
+                }
+            }
+            ComparisonOperation::LessThan { left, span, right } => ComparisonOperation::LessThan {
+                left: left.into_owned(),
+                span,
+                right: right.into_owned(),
+            },
+            ComparisonOperation::GreaterThan { left, span, right } => {
+                ComparisonOperation::GreaterThan {
+                    left: left.into_owned(),
+                    span,
+                    right: right.into_owned(),
+                }
+            }
+            ComparisonOperation::LessThanOrEqual { left, span, right } => {
+                ComparisonOperation::LessThanOrEqual {
+                    left: left.into_owned(),
+                    span,
+                    right: right.into_owned(),
+                }
+            }
+            ComparisonOperation::GreaterThanOrEqual { left, span, right } => {
+                ComparisonOperation::GreaterThanOrEqual {
+                    left: left.into_owned(),
+                    span,
+                    right: right.into_owned(),
+                }
+            }
+            ComparisonOperation::Spaceship { left, span, right } => {
+                ComparisonOperation::Spaceship {
+                    left: left.into_owned(),
+                    span,
+                    right: right.into_owned(),
+                }
+            }
+        }
+    }
+}
+
+impl IntoOwned for LogicalOperation<'_> {
+    type Owned = LogicalOperation<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        match self {
+            LogicalOperation::And { left, span, right } => LogicalOperation::And {
+                left: left.into_owned(),
+                span,
+                right: right.into_owned(),
+            },
+            LogicalOperation::Or { left, span, right } => LogicalOperation::Or {
+                left: left.into_owned(),
+                span,
+                right: right.into_owned(),
+            },
+            LogicalOperation::Not { span, right } => LogicalOperation::Not {
+                span,
+                right: right.into_owned(),
+            },
+            LogicalOperation::LogicalAnd { left, span, right } => LogicalOperation::LogicalAnd {
+                left: left.into_owned(),
+                span,
+                right: right.into_owned(),
+            },
+            LogicalOperation::LogicalOr { left, span, right } => LogicalOperation::LogicalOr {
+                left: left.into_owned(),
+                span,
+                right: right.into_owned(),
+            },
+            LogicalOperation::LogicalXor { left, span, right } => LogicalOperation::LogicalXor {
+                left: left.into_owned(),
+                span,
+                right: right.into_owned(),
+            },
+        }
+    }
+}
+
+impl IntoOwned for Property<'_> {
+    type Owned = Property<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        Property {
+            attributes: self.attributes.into_owned(),
+            r#type: self.r#type.into_owned(),
+            modifiers: self.modifiers,
+            entries: self.entries.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for PropertyEntry<'_> {
+    type Owned = PropertyEntry<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        PropertyEntry {
+            variable: self.variable.into_owned(),
+            value: self.value.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for VariableProperty<'_> {
+    type Owned = VariableProperty<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        VariableProperty {
+            attributes: self.attributes.into_owned(),
+            r#type: self.r#type.into_owned(),
+            entries: self.entries.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for VariablePropertyEntry<'_> {
+    type Owned = VariablePropertyEntry<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        VariablePropertyEntry {
+            variable: self.variable.into_owned(),
+            value: self.value.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for Trait<'_> {
+    type Owned = Trait<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        Trait {
+            start: self.start,
+            end: self.end,
+            name: self.name.into_owned(),
+            attributes: self.attributes.into_owned(),
+            members: self.members.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for TraitMember<'_> {
+    type Owned = TraitMember<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        match self {
+            TraitMember::Constant(value) => TraitMember::Constant(value.into_owned()),
+            TraitMember::TraitUsage(value) => TraitMember::TraitUsage(value.into_owned()),
+            TraitMember::Property(value) => TraitMember::Property(value.into_owned()),
+            TraitMember::VariableProperty(value) => {
+                TraitMember::VariableProperty(value.into_owned())
+            }
+            TraitMember::Method(value) => TraitMember::Method(value.into_owned()),
+        }
+    }
+}
+
+impl IntoOwned for TraitUsage<'_> {
+    type Owned = TraitUsage<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        TraitUsage {
+            traits: self.traits.into_owned(),
+            adaptations: self.adaptations.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for TraitUsageAdaptation<'_> {
+    type Owned = TraitUsageAdaptation<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        match self {
+            TraitUsageAdaptation::Alias {
+                r#trait,
+                method,
+                alias,
+                visibility,
+            } => TraitUsageAdaptation::Alias {
+                r#trait: r#trait.into_owned(),
+                method: method.into_owned(),
+                alias: alias.into_owned(),
+                visibility,
+            },
+            TraitUsageAdaptation::Visibility {
+                r#trait,
+                method,
+                visibility,
+            } => TraitUsageAdaptation::Visibility {
+                r#trait: r#trait.into_owned(),
+                method: method.into_owned(),
+                visibility,
+            },
+            TraitUsageAdaptation::Precedence {
+                r#trait,
+                method,
+                insteadof,
+            } => TraitUsageAdaptation::Precedence {
+                r#trait: r#trait.into_owned(),
+                method: method.into_owned(),
+                insteadof: insteadof.into_owned(),
+            },
+        }
+    }
+}
+
+impl IntoOwned for CatchType<'_> {
+    type Owned = CatchType<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        match self {
+            CatchType::Identifier(value) => CatchType::Identifier(value.into_owned()),
+            CatchType::Union(value) => CatchType::Union(value.into_owned()),
+        }
+    }
+}
+
+impl IntoOwned for TryBlock<'_> {
+    type Owned = TryBlock<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        TryBlock {
+            start: self.start,
+            end: self.end,
+            body: self.body.into_owned(),
+            catches: self.catches.into_owned(),
+            finally: self.finally.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for CatchBlock<'_> {
+    type Owned = CatchBlock<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        CatchBlock {
+            start: self.start,
+            end: self.end,
+            types: self.types.into_owned(),
+            var: self.var.into_owned(),
+            body: self.body.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for FinallyBlock<'_> {
+    type Owned = FinallyBlock<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        FinallyBlock {
+            start: self.start,
+            end: self.end,
+            body: self.body.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for Variable<'_> {
+    type Owned = Variable<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        match self {
+            Variable::SimpleVariable(value) => Variable::SimpleVariable(value.into_owned()),
+            Variable::VariableVariable(value) => Variable::VariableVariable(value.into_owned()),
+            Variable::BracedVariableVariable(value) => {
+                Variable::BracedVariableVariable(value.into_owned())
+            }
+        }
+    }
+}
+
+impl IntoOwned for SimpleVariable<'_> {
+    type Owned = SimpleVariable<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        SimpleVariable {
+            span: self.span,
+            name: self.name.into_owned(),
+            symbol: self.symbol,
+        }
+    }
+}
+
+impl IntoOwned for VariableVariable<'_> {
+    type Owned = VariableVariable<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        VariableVariable {
+            span: self.span,
+            variable: self.variable.into_owned(),
+        }
+    }
+}
+
+impl IntoOwned for BracedVariableVariable<'_> {
+    type Owned = BracedVariableVariable<'static>;
+
+    fn into_owned(self) -> Self::Owned {
+        BracedVariableVariable {
+            start: self.start,
+            variable: self.variable.into_owned(),
+            end: self.end,
+        }
+    }
+}
@@ -1,32 +1,34 @@
+use serde::Serialize;
+
 use crate::parser::ast::attributes::AttributeGroup;
 use crate::parser::ast::modifiers::PropertyModifierGroup;
 use crate::parser::ast::variables::SimpleVariable;
 use crate::parser::ast::Expression;
 use crate::parser::ast::Type;
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct Property {
-    pub attributes: Vec<AttributeGroup>,
-    pub r#type: Option<Type>,
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Property<'a> {
+    pub attributes: Vec<AttributeGroup<'a>>,
+    pub r#type: Option<Type<'a>>,
     pub modifiers: PropertyModifierGroup,
-    pub entries: Vec<PropertyEntry>,
+    pub entries: Vec<PropertyEntry<'a>>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct PropertyEntry {
-    pub variable: SimpleVariable,
-    pub value: Option<Expression>,
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PropertyEntry<'a> {
+    pub variable: SimpleVariable<'a>,
+    pub value: Option<Expression<'a>>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct VariableProperty {
-    pub attributes: Vec<AttributeGroup>,
-    pub r#type: Option<Type>,
-    pub entries: Vec<VariablePropertyEntry>,
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VariableProperty<'a> {
+    pub attributes: Vec<AttributeGroup<'a>>,
+    pub r#type: Option<Type<'a>>,
+    pub entries: Vec<VariablePropertyEntry<'a>>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct VariablePropertyEntry {
-    pub variable: SimpleVariable,
-    pub value: Option<Expression>,
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VariablePropertyEntry<'a> {
+    pub variable: SimpleVariable<'a>,
+    pub value: Option<Expression<'a>>,
 }
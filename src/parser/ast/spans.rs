@@ -0,0 +1,1530 @@
+use crate::lexer::token::Span;
+use crate::parser::ast::attributes::Attribute;
+use crate::parser::ast::attributes::AttributeGroup;
+use crate::parser::ast::classes::AnonymousClass;
+use crate::parser::ast::classes::Class;
+use crate::parser::ast::classes::ClassExtends;
+use crate::parser::ast::classes::ClassImplements;
+use crate::parser::ast::classes::ClassMember;
+use crate::parser::ast::comments::Comment;
+use crate::parser::ast::constant::ClassishConstant;
+use crate::parser::ast::constant::Constant;
+use crate::parser::ast::constant::ConstantEntry;
+use crate::parser::ast::enums::BackedEnum;
+use crate::parser::ast::enums::BackedEnumCase;
+use crate::parser::ast::enums::BackedEnumMember;
+use crate::parser::ast::enums::BackedEnumType;
+use crate::parser::ast::enums::UnitEnum;
+use crate::parser::ast::enums::UnitEnumCase;
+use crate::parser::ast::enums::UnitEnumMember;
+use crate::parser::ast::functions::ArrowFunction;
+use crate::parser::ast::functions::Closure;
+use crate::parser::ast::functions::ClosureUse;
+use crate::parser::ast::functions::Function;
+use crate::parser::ast::functions::FunctionParameter;
+use crate::parser::ast::functions::FunctionParameterList;
+use crate::parser::ast::functions::Method;
+use crate::parser::ast::functions::MethodParameter;
+use crate::parser::ast::functions::MethodParameterList;
+use crate::parser::ast::identifiers::DynamicIdentifier;
+use crate::parser::ast::identifiers::Identifier;
+use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::interfaces::Interface;
+use crate::parser::ast::interfaces::InterfaceExtends;
+use crate::parser::ast::interfaces::InterfaceMember;
+use crate::parser::ast::modifiers::ClassModifier;
+use crate::parser::ast::modifiers::ClassModifierGroup;
+use crate::parser::ast::modifiers::ConstantModifier;
+use crate::parser::ast::modifiers::ConstantModifierGroup;
+use crate::parser::ast::modifiers::MethodModifier;
+use crate::parser::ast::modifiers::MethodModifierGroup;
+use crate::parser::ast::modifiers::PromotedPropertyModifier;
+use crate::parser::ast::modifiers::PromotedPropertyModifierGroup;
+use crate::parser::ast::modifiers::PropertyModifier;
+use crate::parser::ast::modifiers::PropertyModifierGroup;
+use crate::parser::ast::modifiers::VisibilityModifier;
+use crate::parser::ast::operators::ArithmeticOperation;
+use crate::parser::ast::operators::AssignmentOperation;
+use crate::parser::ast::operators::BitwiseOperation;
+use crate::parser::ast::operators::ComparisonOperation;
+use crate::parser::ast::operators::LogicalOperation;
+use crate::parser::ast::properties::Property;
+use crate::parser::ast::properties::PropertyEntry;
+use crate::parser::ast::properties::VariableProperty;
+use crate::parser::ast::properties::VariablePropertyEntry;
+use crate::parser::ast::traits::Trait;
+use crate::parser::ast::traits::TraitMember;
+use crate::parser::ast::traits::TraitUsage;
+use crate::parser::ast::traits::TraitUsageAdaptation;
+use crate::parser::ast::try_block::CatchBlock;
+use crate::parser::ast::try_block::CatchType;
+use crate::parser::ast::try_block::FinallyBlock;
+use crate::parser::ast::try_block::TryBlock;
+use crate::parser::ast::variables::BracedVariableVariable;
+use crate::parser::ast::variables::SimpleVariable;
+use crate::parser::ast::variables::Variable;
+use crate::parser::ast::variables::VariableVariable;
+use crate::parser::ast::Arg;
+use crate::parser::ast::ArrayItem;
+use crate::parser::ast::Case;
+use crate::parser::ast::DeclareItem;
+use crate::parser::ast::DefaultMatchArm;
+use crate::parser::ast::ElseIf;
+use crate::parser::ast::Expression;
+use crate::parser::ast::ListItem;
+use crate::parser::ast::MatchArm;
+use crate::parser::ast::Statement;
+use crate::parser::ast::StaticVar;
+use crate::parser::ast::StringPart;
+use crate::parser::ast::Type;
+use crate::parser::ast::Use;
+use crate::parser::stack::ensure_sufficient_stack;
+/// Visit every span in a node and the nodes it contains, in no particular order.
+pub(crate) trait SpansMut {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span));
+}
+
+impl SpansMut for Span {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        f(self)
+    }
+}
+
+impl<T: SpansMut> SpansMut for Box<T> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        (**self).spans_mut(f)
+    }
+}
+
+impl<T: SpansMut> SpansMut for Option<T> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        if let Some(value) = self {
+            value.spans_mut(f);
+        }
+    }
+}
+
+impl<T: SpansMut> SpansMut for Vec<T> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        for value in self {
+            value.spans_mut(f);
+        }
+    }
+}
+
+impl SpansMut for Attribute<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.start.spans_mut(f);
+        self.end.spans_mut(f);
+        self.expression.spans_mut(f);
+    }
+}
+
+impl SpansMut for AttributeGroup<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.start.spans_mut(f);
+        self.end.spans_mut(f);
+        self.members.spans_mut(f);
+    }
+}
+
+impl SpansMut for Class<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.start.spans_mut(f);
+        self.end.spans_mut(f);
+        self.name.spans_mut(f);
+        self.extends.spans_mut(f);
+        self.implements.spans_mut(f);
+        self.attributes.spans_mut(f);
+        self.modifiers.spans_mut(f);
+        self.members.spans_mut(f);
+    }
+}
+
+impl SpansMut for AnonymousClass<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.start.spans_mut(f);
+        self.end.spans_mut(f);
+        self.extends.spans_mut(f);
+        self.implements.spans_mut(f);
+        self.attributes.spans_mut(f);
+        self.members.spans_mut(f);
+    }
+}
+
+impl SpansMut for ClassExtends<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.span.spans_mut(f);
+        self.parent.spans_mut(f);
+    }
+}
+
+impl SpansMut for ClassImplements<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.span.spans_mut(f);
+        self.interfaces.spans_mut(f);
+    }
+}
+
+impl SpansMut for ClassMember<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        match self {
+            ClassMember::Constant(value) => {
+                value.spans_mut(f);
+            }
+            ClassMember::TraitUsage(value) => {
+                value.spans_mut(f);
+            }
+            ClassMember::Property(value) => {
+                value.spans_mut(f);
+            }
+            ClassMember::VariableProperty(value) => {
+                value.spans_mut(f);
+            }
+            ClassMember::Method(value) => {
+                value.spans_mut(f);
+            }
+        }
+    }
+}
+
+impl SpansMut for Comment<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.start.spans_mut(f);
+        self.end.spans_mut(f);
+    }
+}
+
+impl SpansMut for ConstantEntry<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.name.spans_mut(f);
+        self.value.spans_mut(f);
+    }
+}
+
+impl SpansMut for Constant<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.start.spans_mut(f);
+        self.end.spans_mut(f);
+        self.entries.spans_mut(f);
+    }
+}
+
+impl SpansMut for ClassishConstant<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.start.spans_mut(f);
+        self.end.spans_mut(f);
+        self.attributes.spans_mut(f);
+        self.modifiers.spans_mut(f);
+        self.entries.spans_mut(f);
+    }
+}
+
+impl SpansMut for UnitEnumCase<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.start.spans_mut(f);
+        self.end.spans_mut(f);
+        self.attributes.spans_mut(f);
+        self.name.spans_mut(f);
+    }
+}
+
+impl SpansMut for UnitEnumMember<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        match self {
+            UnitEnumMember::Case(value) => {
+                value.spans_mut(f);
+            }
+            UnitEnumMember::Method(value) => {
+                value.spans_mut(f);
+            }
+            UnitEnumMember::Constant(value) => {
+                value.spans_mut(f);
+            }
+        }
+    }
+}
+
+impl SpansMut for UnitEnum<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.start.spans_mut(f);
+        self.end.spans_mut(f);
+        self.name.spans_mut(f);
+        self.attributes.spans_mut(f);
+        self.implements.spans_mut(f);
+        self.members.spans_mut(f);
+    }
+}
+
+impl SpansMut for BackedEnumType {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        match self {
+            BackedEnumType::String(value) => {
+                value.spans_mut(f);
+            }
+            BackedEnumType::Int(value) => {
+                value.spans_mut(f);
+            }
+        }
+    }
+}
+
+impl SpansMut for BackedEnumCase<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.start.spans_mut(f);
+        self.end.spans_mut(f);
+        self.name.spans_mut(f);
+        self.attributes.spans_mut(f);
+        self.value.spans_mut(f);
+    }
+}
+
+impl SpansMut for BackedEnumMember<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        match self {
+            BackedEnumMember::Case(value) => {
+                value.spans_mut(f);
+            }
+            BackedEnumMember::Method(value) => {
+                value.spans_mut(f);
+            }
+            BackedEnumMember::Constant(value) => {
+                value.spans_mut(f);
+            }
+        }
+    }
+}
+
+impl SpansMut for BackedEnum<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.start.spans_mut(f);
+        self.end.spans_mut(f);
+        self.name.spans_mut(f);
+        self.attributes.spans_mut(f);
+        self.implements.spans_mut(f);
+        self.backed_type.spans_mut(f);
+        self.members.spans_mut(f);
+    }
+}
+
+impl SpansMut for FunctionParameter<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.start.spans_mut(f);
+        self.end.spans_mut(f);
+        self.name.spans_mut(f);
+        self.attributes.spans_mut(f);
+        self.r#type.spans_mut(f);
+        self.default.spans_mut(f);
+    }
+}
+
+impl SpansMut for FunctionParameterList<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.start.spans_mut(f);
+        self.end.spans_mut(f);
+        self.members.spans_mut(f);
+    }
+}
+
+impl SpansMut for Function<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.start.spans_mut(f);
+        self.end.spans_mut(f);
+        self.name.spans_mut(f);
+        self.attributes.spans_mut(f);
+        self.parameters.spans_mut(f);
+        self.return_type.spans_mut(f);
+        self.body.spans_mut(f);
+    }
+}
+
+impl SpansMut for ClosureUse<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.var.spans_mut(f);
+    }
+}
+
+impl SpansMut for Closure<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.start.spans_mut(f);
+        self.end.spans_mut(f);
+        self.attributes.spans_mut(f);
+        self.parameters.spans_mut(f);
+        self.return_ty.spans_mut(f);
+        self.uses.spans_mut(f);
+        self.body.spans_mut(f);
+    }
+}
+
+impl SpansMut for ArrowFunction<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.start.spans_mut(f);
+        self.end.spans_mut(f);
+        self.attributes.spans_mut(f);
+        self.parameters.spans_mut(f);
+        self.return_type.spans_mut(f);
+        self.body.spans_mut(f);
+    }
+}
+
+impl SpansMut for MethodParameter<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.start.spans_mut(f);
+        self.end.spans_mut(f);
+        self.name.spans_mut(f);
+        self.attributes.spans_mut(f);
+        self.r#type.spans_mut(f);
+        self.default.spans_mut(f);
+        self.modifiers.spans_mut(f);
+    }
+}
+
+impl SpansMut for MethodParameterList<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.start.spans_mut(f);
+        self.end.spans_mut(f);
+        self.members.spans_mut(f);
+    }
+}
+
+impl SpansMut for Method<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.start.spans_mut(f);
+        self.end.spans_mut(f);
+        self.name.spans_mut(f);
+        self.attributes.spans_mut(f);
+        self.parameters.spans_mut(f);
+        self.body.spans_mut(f);
+        self.modifiers.spans_mut(f);
+        self.return_type.spans_mut(f);
+    }
+}
+
+impl SpansMut for Identifier<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        match self {
+            Identifier::SimpleIdentifier(value) => {
+                value.spans_mut(f);
+            }
+            Identifier::DynamicIdentifier(value) => {
+                value.spans_mut(f);
+            }
+        }
+    }
+}
+
+impl SpansMut for SimpleIdentifier<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.span.spans_mut(f);
+    }
+}
+
+impl SpansMut for DynamicIdentifier<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.start.spans_mut(f);
+        self.expr.spans_mut(f);
+        self.end.spans_mut(f);
+    }
+}
+
+impl SpansMut for InterfaceMember<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        match self {
+            InterfaceMember::Constant(value) => {
+                value.spans_mut(f);
+            }
+            InterfaceMember::Method(value) => {
+                value.spans_mut(f);
+            }
+        }
+    }
+}
+
+impl SpansMut for Interface<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.start.spans_mut(f);
+        self.end.spans_mut(f);
+        self.attributes.spans_mut(f);
+        self.name.spans_mut(f);
+        self.extends.spans_mut(f);
+        self.members.spans_mut(f);
+    }
+}
+
+impl SpansMut for InterfaceExtends<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.span.spans_mut(f);
+        self.parents.spans_mut(f);
+    }
+}
+
+impl SpansMut for Type<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        match self {
+            Type::Identifier(value) => {
+                value.spans_mut(f);
+            }
+            Type::Nullable(value) => {
+                value.spans_mut(f);
+            }
+            Type::Union(value) => {
+                value.spans_mut(f);
+            }
+            Type::Intersection(value) => {
+                value.spans_mut(f);
+            }
+            Type::Void => {}
+            Type::Null => {}
+            Type::True => {}
+            Type::False => {}
+            Type::Never => {}
+            Type::Float => {}
+            Type::Boolean => {}
+            Type::Integer => {}
+            Type::String => {}
+            Type::Array => {}
+            Type::Object => {}
+            Type::Mixed => {}
+            Type::Callable => {}
+            Type::Iterable => {}
+            Type::StaticReference => {}
+            Type::SelfReference => {}
+            Type::ParentReference => {}
+        }
+    }
+}
+
+impl SpansMut for StaticVar<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.var.spans_mut(f);
+        self.default.spans_mut(f);
+    }
+}
+
+impl SpansMut for Statement<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        match self {
+            Statement::InlineHtml(..) => {}
+            Statement::Goto { label } => {
+                label.spans_mut(f);
+            }
+            Statement::Label { label } => {
+                label.spans_mut(f);
+            }
+            Statement::HaltCompiler { .. } => {}
+            Statement::Static { vars } => {
+                vars.spans_mut(f);
+            }
+            Statement::DoWhile { condition, body } => {
+                condition.spans_mut(f);
+                body.spans_mut(f);
+            }
+            Statement::While { condition, body } => {
+                condition.spans_mut(f);
+                body.spans_mut(f);
+            }
+            Statement::For {
+                init,
+                condition,
+                r#loop,
+                then,
+            } => {
+                init.spans_mut(f);
+                condition.spans_mut(f);
+                r#loop.spans_mut(f);
+                then.spans_mut(f);
+            }
+            Statement::Foreach {
+                expr,
+                key_var,
+                value_var,
+                body,
+                ..
+            } => {
+                expr.spans_mut(f);
+                key_var.spans_mut(f);
+                value_var.spans_mut(f);
+                body.spans_mut(f);
+            }
+            Statement::Constant(value) => {
+                value.spans_mut(f);
+            }
+            Statement::Function(value) => {
+                value.spans_mut(f);
+            }
+            Statement::Class(value) => {
+                value.spans_mut(f);
+            }
+            Statement::Trait(value) => {
+                value.spans_mut(f);
+            }
+            Statement::Interface(value) => {
+                value.spans_mut(f);
+            }
+            Statement::If {
+                condition,
+                then,
+                else_ifs,
+                r#else,
+            } => {
+                condition.spans_mut(f);
+                then.spans_mut(f);
+                else_ifs.spans_mut(f);
+                r#else.spans_mut(f);
+            }
+            Statement::Return { value } => {
+                value.spans_mut(f);
+            }
+            Statement::Switch { condition, cases } => {
+                condition.spans_mut(f);
+                cases.spans_mut(f);
+            }
+            Statement::Break { num } => {
+                num.spans_mut(f);
+            }
+            Statement::Continue { num } => {
+                num.spans_mut(f);
+            }
+            Statement::Echo { values } => {
+                values.spans_mut(f);
+            }
+            Statement::Expression { expr } => {
+                expr.spans_mut(f);
+            }
+            Statement::Namespace { name, body } => {
+                name.spans_mut(f);
+                body.spans_mut(f);
+            }
+            Statement::BracedNamespace { name, body } => {
+                name.spans_mut(f);
+                body.spans_mut(f);
+            }
+            Statement::Use { uses, .. } => {
+                uses.spans_mut(f);
+            }
+            Statement::GroupUse { prefix, uses, .. } => {
+                prefix.spans_mut(f);
+                uses.spans_mut(f);
+            }
+            Statement::Comment(value) => {
+                value.spans_mut(f);
+            }
+            Statement::Try(value) => {
+                value.spans_mut(f);
+            }
+            Statement::UnitEnum(value) => {
+                value.spans_mut(f);
+            }
+            Statement::BackedEnum(value) => {
+                value.spans_mut(f);
+            }
+            Statement::Block { body } => {
+                body.spans_mut(f);
+            }
+            Statement::Global { span, variables } => {
+                span.spans_mut(f);
+                variables.spans_mut(f);
+            }
+            Statement::Declare { declares, body } => {
+                declares.spans_mut(f);
+                body.spans_mut(f);
+            }
+            Statement::Noop(value) => {
+                value.spans_mut(f);
+            }
+        }
+    }
+}
+
+impl SpansMut for DeclareItem<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.key.spans_mut(f);
+        self.value.spans_mut(f);
+    }
+}
+
+impl SpansMut for Case<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.condition.spans_mut(f);
+        self.body.spans_mut(f);
+    }
+}
+
+impl SpansMut for Use<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.name.spans_mut(f);
+        self.alias.spans_mut(f);
+    }
+}
+
+impl SpansMut for Expression<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        // Long chains of operators nest very deeply, see `drop.rs`.
+        ensure_sufficient_stack(|| match self {
+            Expression::ArithmeticOperation(value) => {
+                value.spans_mut(f);
+            }
+            Expression::AssignmentOperation(value) => {
+                value.spans_mut(f);
+            }
+            Expression::BitwiseOperation(value) => {
+                value.spans_mut(f);
+            }
+            Expression::ComparisonOperation(value) => {
+                value.spans_mut(f);
+            }
+            Expression::LogicalOperation(value) => {
+                value.spans_mut(f);
+            }
+            Expression::Concat { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            Expression::Instanceof { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            Expression::Reference { span, right } => {
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            Expression::Parenthesized { start, expr, end } => {
+                start.spans_mut(f);
+                expr.spans_mut(f);
+                end.spans_mut(f);
+            }
+            Expression::List { items } => {
+                items.spans_mut(f);
+            }
+            Expression::Empty => {}
+            Expression::VariadicPlaceholder => {}
+            Expression::ErrorSuppress { span, expr } => {
+                span.spans_mut(f);
+                expr.spans_mut(f);
+            }
+            Expression::LiteralInteger { .. } => {}
+            Expression::LiteralFloat { .. } => {}
+            Expression::Identifier(value) => {
+                value.spans_mut(f);
+            }
+            Expression::Variable(value) => {
+                value.spans_mut(f);
+            }
+            Expression::Include { span, path, .. } => {
+                span.spans_mut(f);
+                path.spans_mut(f);
+            }
+            Expression::Call { target, args } => {
+                target.spans_mut(f);
+                args.spans_mut(f);
+            }
+            Expression::Static => {}
+            Expression::Self_ => {}
+            Expression::Parent => {}
+            Expression::Array { items } => {
+                items.spans_mut(f);
+            }
+            Expression::Closure(value) => {
+                value.spans_mut(f);
+            }
+            Expression::ArrowFunction(value) => {
+                value.spans_mut(f);
+            }
+            Expression::New { target, span, args } => {
+                target.spans_mut(f);
+                span.spans_mut(f);
+                args.spans_mut(f);
+            }
+            Expression::LiteralString { .. } => {}
+            Expression::InterpolatedString { parts } => {
+                parts.spans_mut(f);
+            }
+            Expression::Heredoc { parts } => {
+                parts.spans_mut(f);
+            }
+            Expression::Nowdoc { .. } => {}
+            Expression::ShellExec { parts } => {
+                parts.spans_mut(f);
+            }
+            Expression::PropertyFetch { target, property } => {
+                target.spans_mut(f);
+                property.spans_mut(f);
+            }
+            Expression::NullsafePropertyFetch { target, property } => {
+                target.spans_mut(f);
+                property.spans_mut(f);
+            }
+            Expression::NullsafeMethodCall {
+                target,
+                method,
+                args,
+            } => {
+                target.spans_mut(f);
+                method.spans_mut(f);
+                args.spans_mut(f);
+            }
+            Expression::StaticPropertyFetch { target, property } => {
+                target.spans_mut(f);
+                property.spans_mut(f);
+            }
+            Expression::ConstFetch { target, constant } => {
+                target.spans_mut(f);
+                constant.spans_mut(f);
+            }
+            Expression::MethodCall {
+                target,
+                method,
+                args,
+            } => {
+                target.spans_mut(f);
+                method.spans_mut(f);
+                args.spans_mut(f);
+            }
+            Expression::StaticMethodCall {
+                target,
+                method,
+                args,
+            } => {
+                target.spans_mut(f);
+                method.spans_mut(f);
+                args.spans_mut(f);
+            }
+            Expression::AnonymousClass(value) => {
+                value.spans_mut(f);
+            }
+            Expression::Bool { .. } => {}
+            Expression::ArrayIndex { array, index } => {
+                array.spans_mut(f);
+                index.spans_mut(f);
+            }
+            Expression::Null => {}
+            Expression::MagicConst { span, .. } => {
+                span.spans_mut(f);
+            }
+            Expression::Ternary {
+                condition,
+                then,
+                r#else,
+            } => {
+                condition.spans_mut(f);
+                then.spans_mut(f);
+                r#else.spans_mut(f);
+            }
+            Expression::Coalesce { lhs, rhs } => {
+                lhs.spans_mut(f);
+                rhs.spans_mut(f);
+            }
+            Expression::Clone { target } => {
+                target.spans_mut(f);
+            }
+            Expression::Match {
+                condition,
+                default,
+                arms,
+            } => {
+                condition.spans_mut(f);
+                default.spans_mut(f);
+                arms.spans_mut(f);
+            }
+            Expression::Throw { value } => {
+                value.spans_mut(f);
+            }
+            Expression::Yield { key, value } => {
+                key.spans_mut(f);
+                value.spans_mut(f);
+            }
+            Expression::YieldFrom { value } => {
+                value.spans_mut(f);
+            }
+            Expression::BitwiseNot { span, value } => {
+                span.spans_mut(f);
+                value.spans_mut(f);
+            }
+            Expression::Print { span, value } => {
+                span.spans_mut(f);
+                value.spans_mut(f);
+            }
+            Expression::Cast { span, value, .. } => {
+                span.spans_mut(f);
+                value.spans_mut(f);
+            }
+        })
+    }
+}
+
+impl SpansMut for Arg<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.name.spans_mut(f);
+        self.value.spans_mut(f);
+    }
+}
+
+impl SpansMut for DefaultMatchArm<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.body.spans_mut(f);
+    }
+}
+
+impl SpansMut for MatchArm<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.conditions.spans_mut(f);
+        self.body.spans_mut(f);
+    }
+}
+
+impl SpansMut for StringPart<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        match self {
+            StringPart::Const(..) => {}
+            StringPart::Expr(value) => {
+                value.spans_mut(f);
+            }
+        }
+    }
+}
+
+impl SpansMut for ArrayItem<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.key.spans_mut(f);
+        self.value.spans_mut(f);
+    }
+}
+
+impl SpansMut for ListItem<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.key.spans_mut(f);
+        self.value.spans_mut(f);
+    }
+}
+
+impl SpansMut for ElseIf<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.condition.spans_mut(f);
+        self.body.spans_mut(f);
+    }
+}
+
+impl SpansMut for VisibilityModifier {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        match self {
+            VisibilityModifier::Public { start, end } => {
+                start.spans_mut(f);
+                end.spans_mut(f);
+            }
+            VisibilityModifier::Protected { start, end } => {
+                start.spans_mut(f);
+                end.spans_mut(f);
+            }
+            VisibilityModifier::Private { start, end } => {
+                start.spans_mut(f);
+                end.spans_mut(f);
+            }
+        }
+    }
+}
+
+impl SpansMut for PromotedPropertyModifier {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        match self {
+            PromotedPropertyModifier::Public { start, end } => {
+                start.spans_mut(f);
+                end.spans_mut(f);
+            }
+            PromotedPropertyModifier::Protected { start, end } => {
+                start.spans_mut(f);
+                end.spans_mut(f);
+            }
+            PromotedPropertyModifier::Private { start, end } => {
+                start.spans_mut(f);
+                end.spans_mut(f);
+            }
+            PromotedPropertyModifier::Readonly { start, end } => {
+                start.spans_mut(f);
+                end.spans_mut(f);
+            }
+        }
+    }
+}
+
+impl SpansMut for PromotedPropertyModifierGroup {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.modifiers.spans_mut(f);
+    }
+}
+
+impl SpansMut for PropertyModifier {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        match self {
+            PropertyModifier::Public { start, end } => {
+                start.spans_mut(f);
+                end.spans_mut(f);
+            }
+            PropertyModifier::Protected { start, end } => {
+                start.spans_mut(f);
+                end.spans_mut(f);
+            }
+            PropertyModifier::Private { start, end } => {
+                start.spans_mut(f);
+                end.spans_mut(f);
+            }
+            PropertyModifier::Static { start, end } => {
+                start.spans_mut(f);
+                end.spans_mut(f);
+            }
+            PropertyModifier::Readonly { start, end } => {
+                start.spans_mut(f);
+                end.spans_mut(f);
+            }
+        }
+    }
+}
+
+impl SpansMut for PropertyModifierGroup {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.modifiers.spans_mut(f);
+    }
+}
+
+impl SpansMut for MethodModifier {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        match self {
+            MethodModifier::Final { start, end } => {
+                start.spans_mut(f);
+                end.spans_mut(f);
+            }
+            MethodModifier::Static { start, end } => {
+                start.spans_mut(f);
+                end.spans_mut(f);
+            }
+            MethodModifier::Abstract { start, end } => {
+                start.spans_mut(f);
+                end.spans_mut(f);
+            }
+            MethodModifier::Public { start, end } => {
+                start.spans_mut(f);
+                end.spans_mut(f);
+            }
+            MethodModifier::Protected { start, end } => {
+                start.spans_mut(f);
+                end.spans_mut(f);
+            }
+            MethodModifier::Private { start, end } => {
+                start.spans_mut(f);
+                end.spans_mut(f);
+            }
+        }
+    }
+}
+
+impl SpansMut for MethodModifierGroup {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.modifiers.spans_mut(f);
+    }
+}
+
+impl SpansMut for ClassModifier {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        match self {
+            ClassModifier::Final { start, end } => {
+                start.spans_mut(f);
+                end.spans_mut(f);
+            }
+            ClassModifier::Abstract { start, end } => {
+                start.spans_mut(f);
+                end.spans_mut(f);
+            }
+            ClassModifier::Readonly { start, end } => {
+                start.spans_mut(f);
+                end.spans_mut(f);
+            }
+        }
+    }
+}
+
+impl SpansMut for ClassModifierGroup {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.modifiers.spans_mut(f);
+    }
+}
+
+impl SpansMut for ConstantModifier {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        match self {
+            ConstantModifier::Final { start, end } => {
+                start.spans_mut(f);
+                end.spans_mut(f);
+            }
+            ConstantModifier::Public { start, end } => {
+                start.spans_mut(f);
+                end.spans_mut(f);
+            }
+            ConstantModifier::Protected { start, end } => {
+                start.spans_mut(f);
+                end.spans_mut(f);
+            }
+            ConstantModifier::Private { start, end } => {
+                start.spans_mut(f);
+                end.spans_mut(f);
+            }
+        }
+    }
+}
+
+impl SpansMut for ConstantModifierGroup {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.modifiers.spans_mut(f);
+    }
+}
+
+impl SpansMut for ArithmeticOperation<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        match self {
+            ArithmeticOperation::Addition { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            ArithmeticOperation::Subtraction { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            ArithmeticOperation::Multiplication { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            ArithmeticOperation::Division { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            ArithmeticOperation::Modulo { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            ArithmeticOperation::Exponentiation { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            ArithmeticOperation::Negation { span, right } => {
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            ArithmeticOperation::Identity { span, right } => {
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            ArithmeticOperation::PreIncrement { span, right } => {
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            ArithmeticOperation::PostIncrement { left, span } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+            }
+            ArithmeticOperation::PreDecrement { span, right } => {
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            ArithmeticOperation::PostDecrement { left, span } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+            }
+        }
+    }
+}
+
+impl SpansMut for AssignmentOperation<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        match self {
+            AssignmentOperation::Assign { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            AssignmentOperation::Addition { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            AssignmentOperation::Subtraction { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            AssignmentOperation::Multiplication { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            AssignmentOperation::Division { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            AssignmentOperation::Modulo { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            AssignmentOperation::Exponentiation { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            AssignmentOperation::Concat { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            AssignmentOperation::BitwiseAnd { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            AssignmentOperation::BitwiseOr { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            AssignmentOperation::BitwiseXor { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            AssignmentOperation::LeftShift { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            AssignmentOperation::RightShift { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            AssignmentOperation::Coalesce { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+        }
+    }
+}
+
+impl SpansMut for BitwiseOperation<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        match self {
+            BitwiseOperation::And { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            BitwiseOperation::Or { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            BitwiseOperation::Xor { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            BitwiseOperation::LeftShift { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            BitwiseOperation::RightShift { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            BitwiseOperation::Not { span, right } => {
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+        }
+    }
+}
+
+impl SpansMut for ComparisonOperation<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        match self {
+            ComparisonOperation::Equal { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            ComparisonOperation::Identical { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            ComparisonOperation::NotEqual { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            ComparisonOperation::AngledNotEqual { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            ComparisonOperation::NotIdentical { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            ComparisonOperation::LessThan { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            ComparisonOperation::GreaterThan { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            ComparisonOperation::LessThanOrEqual { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            ComparisonOperation::GreaterThanOrEqual { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            ComparisonOperation::Spaceship { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+        }
+    }
+}
+
+impl SpansMut for LogicalOperation<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        match self {
+            LogicalOperation::And { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            LogicalOperation::Or { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            LogicalOperation::Not { span, right } => {
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            LogicalOperation::LogicalAnd { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            LogicalOperation::LogicalOr { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+            LogicalOperation::LogicalXor { left, span, right } => {
+                left.spans_mut(f);
+                span.spans_mut(f);
+                right.spans_mut(f);
+            }
+        }
+    }
+}
+
+impl SpansMut for Property<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.attributes.spans_mut(f);
+        self.r#type.spans_mut(f);
+        self.modifiers.spans_mut(f);
+        self.entries.spans_mut(f);
+    }
+}
+
+impl SpansMut for PropertyEntry<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.variable.spans_mut(f);
+        self.value.spans_mut(f);
+    }
+}
+
+impl SpansMut for VariableProperty<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.attributes.spans_mut(f);
+        self.r#type.spans_mut(f);
+        self.entries.spans_mut(f);
+    }
+}
+
+impl SpansMut for VariablePropertyEntry<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.variable.spans_mut(f);
+        self.value.spans_mut(f);
+    }
+}
+
+impl SpansMut for Trait<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.start.spans_mut(f);
+        self.end.spans_mut(f);
+        self.name.spans_mut(f);
+        self.attributes.spans_mut(f);
+        self.members.spans_mut(f);
+    }
+}
+
+impl SpansMut for TraitMember<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        match self {
+            TraitMember::Constant(value) => {
+                value.spans_mut(f);
+            }
+            TraitMember::TraitUsage(value) => {
+                value.spans_mut(f);
+            }
+            TraitMember::Property(value) => {
+                value.spans_mut(f);
+            }
+            TraitMember::VariableProperty(value) => {
+                value.spans_mut(f);
+            }
+            TraitMember::Method(value) => {
+                value.spans_mut(f);
+            }
+        }
+    }
+}
+
+impl SpansMut for TraitUsage<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.traits.spans_mut(f);
+        self.adaptations.spans_mut(f);
+    }
+}
+
+impl SpansMut for TraitUsageAdaptation<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        match self {
+            TraitUsageAdaptation::Alias {
+                r#trait,
+                method,
+                alias,
+                visibility,
+            } => {
+                r#trait.spans_mut(f);
+                method.spans_mut(f);
+                alias.spans_mut(f);
+                visibility.spans_mut(f);
+            }
+            TraitUsageAdaptation::Visibility {
+                r#trait,
+                method,
+                visibility,
+            } => {
+                r#trait.spans_mut(f);
+                method.spans_mut(f);
+                visibility.spans_mut(f);
+            }
+            TraitUsageAdaptation::Precedence {
+                r#trait,
+                method,
+                insteadof,
+            } => {
+                r#trait.spans_mut(f);
+                method.spans_mut(f);
+                insteadof.spans_mut(f);
+            }
+        }
+    }
+}
+
+impl SpansMut for CatchType<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        match self {
+            CatchType::Identifier(value) => {
+                value.spans_mut(f);
+            }
+            CatchType::Union(value) => {
+                value.spans_mut(f);
+            }
+        }
+    }
+}
+
+impl SpansMut for TryBlock<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.start.spans_mut(f);
+        self.end.spans_mut(f);
+        self.body.spans_mut(f);
+        self.catches.spans_mut(f);
+        self.finally.spans_mut(f);
+    }
+}
+
+impl SpansMut for CatchBlock<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.start.spans_mut(f);
+        self.end.spans_mut(f);
+        self.types.spans_mut(f);
+        self.var.spans_mut(f);
+        self.body.spans_mut(f);
+    }
+}
+
+impl SpansMut for FinallyBlock<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.start.spans_mut(f);
+        self.end.spans_mut(f);
+        self.body.spans_mut(f);
+    }
+}
+
+impl SpansMut for Variable<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        match self {
+            Variable::SimpleVariable(value) => {
+                value.spans_mut(f);
+            }
+            Variable::VariableVariable(value) => {
+                value.spans_mut(f);
+            }
+            Variable::BracedVariableVariable(value) => {
+                value.spans_mut(f);
+            }
+        }
+    }
+}
+
+impl SpansMut for SimpleVariable<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.span.spans_mut(f);
+    }
+}
+
+impl SpansMut for VariableVariable<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.span.spans_mut(f);
+        self.variable.spans_mut(f);
+    }
+}
+
+impl SpansMut for BracedVariableVariable<'_> {
+    fn spans_mut(&mut self, f: &mut dyn FnMut(&mut Span)) {
+        self.start.spans_mut(f);
+        self.variable.spans_mut(f);
+        self.end.spans_mut(f);
+    }
+}
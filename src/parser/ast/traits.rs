@@ -1,3 +1,5 @@
+use serde::Serialize;
+
 use crate::lexer::token::Span;
 use crate::parser::ast::attributes::AttributeGroup;
 use crate::parser::ast::constant::ClassishConstant;
@@ -7,46 +9,46 @@ use crate::parser::ast::modifiers::VisibilityModifier;
 use crate::parser::ast::properties::Property;
 use crate::parser::ast::properties::VariableProperty;
 
-#[derive(Debug, PartialEq, Clone)]
-pub struct Trait {
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct Trait<'a> {
     pub start: Span,
     pub end: Span,
-    pub name: SimpleIdentifier,
-    pub attributes: Vec<AttributeGroup>,
-    pub members: Vec<TraitMember>,
+    pub name: SimpleIdentifier<'a>,
+    pub attributes: Vec<AttributeGroup<'a>>,
+    pub members: Vec<TraitMember<'a>>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub enum TraitMember {
-    Constant(ClassishConstant),
-    TraitUsage(TraitUsage),
-    Property(Property),
-    VariableProperty(VariableProperty),
-    Method(Method),
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum TraitMember<'a> {
+    Constant(ClassishConstant<'a>),
+    TraitUsage(TraitUsage<'a>),
+    Property(Property<'a>),
+    VariableProperty(VariableProperty<'a>),
+    Method(Method<'a>),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct TraitUsage {
-    pub traits: Vec<SimpleIdentifier>,
-    pub adaptations: Vec<TraitUsageAdaptation>,
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TraitUsage<'a> {
+    pub traits: Vec<SimpleIdentifier<'a>>,
+    pub adaptations: Vec<TraitUsageAdaptation<'a>>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum TraitUsageAdaptation {
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum TraitUsageAdaptation<'a> {
     Alias {
-        r#trait: Option<SimpleIdentifier>,
-        method: SimpleIdentifier,
-        alias: SimpleIdentifier,
+        r#trait: Option<SimpleIdentifier<'a>>,
+        method: SimpleIdentifier<'a>,
+        alias: SimpleIdentifier<'a>,
         visibility: Option<VisibilityModifier>,
     },
     Visibility {
-        r#trait: Option<SimpleIdentifier>,
-        method: SimpleIdentifier,
+        r#trait: Option<SimpleIdentifier<'a>>,
+        method: SimpleIdentifier<'a>,
         visibility: VisibilityModifier,
     },
     Precedence {
-        r#trait: Option<SimpleIdentifier>,
-        method: SimpleIdentifier,
-        insteadof: Vec<SimpleIdentifier>,
+        r#trait: Option<SimpleIdentifier<'a>>,
+        method: SimpleIdentifier<'a>,
+        insteadof: Vec<SimpleIdentifier<'a>>,
     },
 }
@@ -6,32 +6,32 @@ use crate::parser::ast::Block;
 use crate::parser::ast::Expression;
 
 #[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
-pub enum CatchType {
-    Identifier(SimpleIdentifier),
-    Union(Vec<SimpleIdentifier>),
+pub enum CatchType<'a> {
+    Identifier(SimpleIdentifier<'a>),
+    Union(Vec<SimpleIdentifier<'a>>),
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct TryBlock {
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TryBlock<'a> {
     pub start: Span,
     pub end: Span,
-    pub body: Block,
-    pub catches: Vec<CatchBlock>,
-    pub finally: Option<FinallyBlock>,
+    pub body: Block<'a>,
+    pub catches: Vec<CatchBlock<'a>>,
+    pub finally: Option<FinallyBlock<'a>>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct CatchBlock {
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CatchBlock<'a> {
     pub start: Span,
     pub end: Span,
-    pub types: CatchType,
-    pub var: Option<Expression>,
-    pub body: Block,
+    pub types: CatchType<'a>,
+    pub var: Option<Expression<'a>>,
+    pub body: Block<'a>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct FinallyBlock {
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FinallyBlock<'a> {
     pub start: Span,
     pub end: Span,
-    pub body: Block,
+    pub body: Block<'a>,
 }
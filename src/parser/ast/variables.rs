@@ -3,36 +3,60 @@ use std::fmt::Display;
 use serde::{Deserialize, Serialize};
 
 use crate::lexer::byte_string::ByteString;
+use crate::lexer::interner::Symbol;
 use crate::lexer::token::Span;
 use crate::parser::ast::Expression;
 
-#[derive(Debug, PartialEq, Clone)]
-pub enum Variable {
-    SimpleVariable(SimpleVariable),
-    VariableVariable(VariableVariable),
-    BracedVariableVariable(BracedVariableVariable),
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub enum Variable<'a> {
+    SimpleVariable(SimpleVariable<'a>),
+    VariableVariable(VariableVariable<'a>),
+    BracedVariableVariable(BracedVariableVariable<'a>),
 }
 
-#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
-pub struct SimpleVariable {
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SimpleVariable<'a> {
     pub span: Span,
-    pub name: ByteString,
+    pub name: ByteString<'a>,
+    #[serde(skip)]
+    pub symbol: Option<Symbol>,
 }
 
-#[derive(Debug, PartialEq, Clone)]
-pub struct VariableVariable {
+impl<'a> PartialEq for SimpleVariable<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.span == other.span
+            && match (self.symbol, other.symbol) {
+                (Some(a), Some(b)) if a.is_comparable_to(b) => a == b,
+                _ => self.name == other.name,
+            }
+    }
+}
+
+impl<'a> Eq for SimpleVariable<'a> {}
+
+impl<'a> std::fmt::Debug for SimpleVariable<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SimpleVariable")
+            .field("span", &self.span)
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct VariableVariable<'a> {
     pub span: Span,
-    pub variable: Box<Variable>,
+    pub variable: Box<Variable<'a>>,
 }
 
-#[derive(Debug, PartialEq, Clone)]
-pub struct BracedVariableVariable {
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct BracedVariableVariable<'a> {
     pub start: Span,
-    pub variable: Box<Expression>,
+    pub variable: Box<Expression<'a>>,
     pub end: Span,
 }
 
-impl Display for SimpleVariable {
+impl<'a> Display for SimpleVariable<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "${}", self.name)
     }
@@ -1,55 +1,338 @@
 use std::fmt::Display;
 
+use serde::Serialize;
+
 use crate::lexer::error::SyntaxError;
 use crate::lexer::token::Span;
 use crate::parser::ast::Type;
 
 pub type ParseResult<T> = Result<T, ParseError>;
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Eq, PartialEq, Clone, Serialize)]
 pub enum ParseError {
     SyntaxError(SyntaxError),
-    ExpectedToken(Vec<String>, Option<String>, Span),
-    ExpectedIdentifier(Vec<String>, String, Span),
-    MultipleModifiers(String, Span),
-    MultipleVisibilityModifiers(Span),
-    UnexpectedToken(String, Span),
-    UnexpectedEndOfFile,
-    StandaloneTypeUsedInCombination(Type, Span),
-    TryWithoutCatchOrFinally(Span),
-    VariadicPromotedProperty(Span),
-    MissingTypeForReadonlyProperty(String, String, Span),
-    PromotedPropertyOutsideConstructor(Span),
-    PromotedPropertyOnAbstractConstructor(Span),
-    AbstractModifierOnNonAbstractClassMethod(Span),
-    ConstructorInEnum(String, Span),
-    MissingCaseValueForBackedEnum(String, String, Span),
-    CaseValueForUnitEnum(String, String, Span),
-    CannotUseModifierOnConstant(String, Span),
-    CannotUseModifierOnInterfaceConstant(String, Span),
-    CannotUseModifierOnPromotedProperty(String, Span),
-    CannotUseModifierOnProperty(String, Span),
-    CannotUseModifierOnClass(String, Span),
-    CannotUseModifierOnClassMethod(String, Span),
-    CannotUseModifierOnEnumMethod(String, Span),
-    CannotUseModifierOnInterfaceMethod(String, Span),
-    FinalModifierOnAbstractClassMember(Span),
-    FinalModifierOnPrivateConstant(Span),
-    FinalModifierOnAbstractClass(Span),
-    UnpredictableState(Span),
-    StaticPropertyUsingReadonlyModifier(String, String, Span),
-    ReadonlyPropertyHasDefaultValue(String, String, Span),
-    MixingBracedAndUnBracedNamespaceDeclarations(Span),
-    NestedNamespaceDeclarations(Span),
-    ForbiddenTypeUsedInProperty(String, String, Type, Span),
-    MatchExpressionWithMultipleDefaultArms(Span),
-    CannotFindTypeInCurrentScope(String, Span),
-    ExpectedItemDefinitionAfterAttributes(Span),
-    NestedDisjunctiveNormalFormTypes(Span),
-    IllegalSpreadOperator(Span),
-    CannotAssignReferenceToNonReferencableValue(Span),
-    CannotMixKeyedAndUnkeyedEntries(Span),
-    CannotUsePositionalArgumentAfterNamedArgument(Span),
+    ExpectedToken {
+        expected: Vec<String>,
+        found: Option<String>,
+        span: Span,
+    },
+    ExpectedIdentifier {
+        expected: Vec<String>,
+        found: String,
+        span: Span,
+    },
+    MultipleModifiers {
+        modifier: String,
+        span: Span,
+        first: Span,
+    },
+    MultipleVisibilityModifiers {
+        span: Span,
+        first: Span,
+    },
+    UnexpectedToken {
+        token: String,
+        span: Span,
+    },
+    UnexpectedEndOfFile {
+        span: Span,
+    },
+    StandaloneTypeUsedInCombination {
+        r#type: Type<'static>,
+        span: Span,
+    },
+    TryWithoutCatchOrFinally {
+        span: Span,
+    },
+    VariadicPromotedProperty {
+        span: Span,
+    },
+    MissingTypeForReadonlyProperty {
+        class: String,
+        property: String,
+        span: Span,
+    },
+    PromotedPropertyOutsideConstructor {
+        span: Span,
+    },
+    PromotedPropertyOnAbstractConstructor {
+        span: Span,
+    },
+    AbstractModifierOnNonAbstractClassMethod {
+        span: Span,
+    },
+    ConstructorInEnum {
+        r#enum: String,
+        span: Span,
+    },
+    MissingCaseValueForBackedEnum {
+        case: String,
+        r#enum: String,
+        span: Span,
+    },
+    CaseValueForUnitEnum {
+        case: String,
+        r#enum: String,
+        span: Span,
+    },
+    CannotUseModifierOnConstant {
+        modifier: String,
+        span: Span,
+    },
+    CannotUseModifierOnInterfaceConstant {
+        modifier: String,
+        span: Span,
+    },
+    CannotUseModifierOnPromotedProperty {
+        modifier: String,
+        span: Span,
+    },
+    CannotUseModifierOnProperty {
+        modifier: String,
+        span: Span,
+    },
+    CannotUseModifierOnClass {
+        modifier: String,
+        span: Span,
+    },
+    CannotUseModifierOnClassMethod {
+        modifier: String,
+        span: Span,
+    },
+    CannotUseModifierOnEnumMethod {
+        modifier: String,
+        span: Span,
+    },
+    CannotUseModifierOnInterfaceMethod {
+        modifier: String,
+        span: Span,
+    },
+    FinalModifierOnAbstractClassMember {
+        span: Span,
+    },
+    FinalModifierOnPrivateConstant {
+        span: Span,
+    },
+    FinalModifierOnAbstractClass {
+        span: Span,
+    },
+    UnpredictableState {
+        span: Span,
+    },
+    StaticPropertyUsingReadonlyModifier {
+        class: String,
+        property: String,
+        span: Span,
+    },
+    ReadonlyPropertyHasDefaultValue {
+        class: String,
+        property: String,
+        span: Span,
+    },
+    MixingBracedAndUnBracedNamespaceDeclarations {
+        span: Span,
+    },
+    NestedNamespaceDeclarations {
+        span: Span,
+    },
+    ForbiddenTypeUsedInProperty {
+        class: String,
+        property: String,
+        r#type: Type<'static>,
+        span: Span,
+    },
+    MatchExpressionWithMultipleDefaultArms {
+        span: Span,
+        first: Span,
+    },
+    CannotFindTypeInCurrentScope {
+        r#type: String,
+        span: Span,
+    },
+    ExpectedItemDefinitionAfterAttributes {
+        span: Span,
+    },
+    NestedDisjunctiveNormalFormTypes {
+        span: Span,
+    },
+    IllegalSpreadOperator {
+        span: Span,
+    },
+    CannotAssignReferenceToNonReferencableValue {
+        span: Span,
+    },
+    CannotMixKeyedAndUnkeyedEntries {
+        span: Span,
+    },
+    CannotUsePositionalArgumentAfterNamedArgument {
+        span: Span,
+    },
+    NestingTooDeep {
+        limit: usize,
+        span: Span,
+    },
+    InputTooLarge {
+        limit: usize,
+        size: usize,
+        span: Span,
+    },
+    TooManyTokens {
+        limit: usize,
+        span: Span,
+    },
+    TooManyNodes {
+        limit: usize,
+        span: Span,
+    },
+    Cancelled {
+        span: Span,
+    },
+}
+
+/// A secondary location that helps explain an error, such as the
+/// first occurrence of something that has been repeated.
+#[derive(Debug, Eq, PartialEq, Clone, Serialize)]
+pub struct RelatedSpan {
+    pub span: Span,
+    pub message: &'static str,
+}
+
+impl ParseError {
+    /// A stable, machine-readable code identifying the kind of error.
+    ///
+    /// Syntax errors produced by the lexer keep their own code, see [`SyntaxError::code`].
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Self::SyntaxError(error) => error.code(),
+            Self::ExpectedToken { .. } => "E0012",
+            Self::ExpectedIdentifier { .. } => "E0013",
+            Self::MultipleModifiers { .. } => "E0014",
+            Self::MultipleVisibilityModifiers { .. } => "E0015",
+            Self::UnexpectedToken { .. } => "E0016",
+            Self::UnexpectedEndOfFile { .. } => "E0017",
+            Self::StandaloneTypeUsedInCombination { .. } => "E0018",
+            Self::TryWithoutCatchOrFinally { .. } => "E0019",
+            Self::VariadicPromotedProperty { .. } => "E0020",
+            Self::MissingTypeForReadonlyProperty { .. } => "E0021",
+            Self::PromotedPropertyOutsideConstructor { .. } => "E0022",
+            Self::PromotedPropertyOnAbstractConstructor { .. } => "E0023",
+            Self::AbstractModifierOnNonAbstractClassMethod { .. } => "E0024",
+            Self::ConstructorInEnum { .. } => "E0025",
+            Self::MissingCaseValueForBackedEnum { .. } => "E0026",
+            Self::CaseValueForUnitEnum { .. } => "E0027",
+            Self::CannotUseModifierOnConstant { .. } => "E0028",
+            Self::CannotUseModifierOnInterfaceConstant { .. } => "E0029",
+            Self::CannotUseModifierOnPromotedProperty { .. } => "E0030",
+            Self::CannotUseModifierOnProperty { .. } => "E0031",
+            Self::CannotUseModifierOnClass { .. } => "E0032",
+            Self::CannotUseModifierOnClassMethod { .. } => "E0033",
+            Self::CannotUseModifierOnEnumMethod { .. } => "E0034",
+            Self::CannotUseModifierOnInterfaceMethod { .. } => "E0035",
+            Self::FinalModifierOnAbstractClassMember { .. } => "E0036",
+            Self::FinalModifierOnPrivateConstant { .. } => "E0037",
+            Self::FinalModifierOnAbstractClass { .. } => "E0038",
+            Self::UnpredictableState { .. } => "E0039",
+            Self::StaticPropertyUsingReadonlyModifier { .. } => "E0040",
+            Self::ReadonlyPropertyHasDefaultValue { .. } => "E0041",
+            Self::MixingBracedAndUnBracedNamespaceDeclarations { .. } => "E0042",
+            Self::NestedNamespaceDeclarations { .. } => "E0043",
+            Self::ForbiddenTypeUsedInProperty { .. } => "E0044",
+            Self::MatchExpressionWithMultipleDefaultArms { .. } => "E0045",
+            Self::CannotFindTypeInCurrentScope { .. } => "E0046",
+            Self::ExpectedItemDefinitionAfterAttributes { .. } => "E0047",
+            Self::NestedDisjunctiveNormalFormTypes { .. } => "E0048",
+            Self::IllegalSpreadOperator { .. } => "E0049",
+            Self::CannotAssignReferenceToNonReferencableValue { .. } => "E0050",
+            Self::CannotMixKeyedAndUnkeyedEntries { .. } => "E0051",
+            Self::CannotUsePositionalArgumentAfterNamedArgument { .. } => "E0052",
+            Self::NestingTooDeep { .. } => "E0053",
+            Self::InputTooLarge { .. } => "E0054",
+            Self::TooManyTokens { .. } => "E0055",
+            Self::TooManyNodes { .. } => "E0056",
+            Self::Cancelled { .. } => "E0057",
+        }
+    }
+
+    /// The primary location of the error in the source code.
+    pub const fn span(&self) -> Span {
+        match self {
+            Self::SyntaxError(error) => error.span(),
+            Self::ExpectedToken { span, .. }
+            | Self::ExpectedIdentifier { span, .. }
+            | Self::MultipleModifiers { span, .. }
+            | Self::MultipleVisibilityModifiers { span, .. }
+            | Self::UnexpectedToken { span, .. }
+            | Self::UnexpectedEndOfFile { span }
+            | Self::StandaloneTypeUsedInCombination { span, .. }
+            | Self::TryWithoutCatchOrFinally { span }
+            | Self::VariadicPromotedProperty { span }
+            | Self::MissingTypeForReadonlyProperty { span, .. }
+            | Self::PromotedPropertyOutsideConstructor { span }
+            | Self::PromotedPropertyOnAbstractConstructor { span }
+            | Self::AbstractModifierOnNonAbstractClassMethod { span }
+            | Self::ConstructorInEnum { span, .. }
+            | Self::MissingCaseValueForBackedEnum { span, .. }
+            | Self::CaseValueForUnitEnum { span, .. }
+            | Self::CannotUseModifierOnConstant { span, .. }
+            | Self::CannotUseModifierOnInterfaceConstant { span, .. }
+            | Self::CannotUseModifierOnPromotedProperty { span, .. }
+            | Self::CannotUseModifierOnProperty { span, .. }
+            | Self::CannotUseModifierOnClass { span, .. }
+            | Self::CannotUseModifierOnClassMethod { span, .. }
+            | Self::CannotUseModifierOnEnumMethod { span, .. }
+            | Self::CannotUseModifierOnInterfaceMethod { span, .. }
+            | Self::FinalModifierOnAbstractClassMember { span }
+            | Self::FinalModifierOnPrivateConstant { span }
+            | Self::FinalModifierOnAbstractClass { span }
+            | Self::UnpredictableState { span }
+            | Self::StaticPropertyUsingReadonlyModifier { span, .. }
+            | Self::ReadonlyPropertyHasDefaultValue { span, .. }
+            | Self::MixingBracedAndUnBracedNamespaceDeclarations { span }
+            | Self::NestedNamespaceDeclarations { span }
+            | Self::ForbiddenTypeUsedInProperty { span, .. }
+            | Self::MatchExpressionWithMultipleDefaultArms { span, .. }
+            | Self::CannotFindTypeInCurrentScope { span, .. }
+            | Self::ExpectedItemDefinitionAfterAttributes { span }
+            | Self::NestedDisjunctiveNormalFormTypes { span }
+            | Self::IllegalSpreadOperator { span }
+            | Self::CannotAssignReferenceToNonReferencableValue { span }
+            | Self::CannotMixKeyedAndUnkeyedEntries { span }
+            | Self::CannotUsePositionalArgumentAfterNamedArgument { span }
+            | Self::NestingTooDeep { span, .. }
+            | Self::InputTooLarge { span, .. }
+            | Self::TooManyTokens { span, .. }
+            | Self::TooManyNodes { span, .. }
+            | Self::Cancelled { span } => *span,
+        }
+    }
+
+    /// Secondary locations that are relevant to the error.
+    pub fn related_spans(&self) -> Vec<RelatedSpan> {
+        match self {
+            Self::MultipleModifiers { first, .. } => vec![RelatedSpan {
+                span: *first,
+                message: "first use of the modifier",
+            }],
+            Self::MultipleVisibilityModifiers { first, .. } => vec![RelatedSpan {
+                span: *first,
+                message: "first visibility modifier",
+            }],
+            Self::MatchExpressionWithMultipleDefaultArms { first, .. } => vec![RelatedSpan {
+                span: *first,
+                message: "first default arm",
+            }],
+            _ => vec![],
+        }
+    }
+}
+
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::SyntaxError(error) => Some(error),
+            _ => None,
+        }
+    }
 }
 
 impl From<SyntaxError> for ParseError {
@@ -62,12 +345,12 @@ impl Display for ParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::SyntaxError(e) => e.fmt(f),
-            Self::ExpectedToken(expected, found, span) => {
+            Self::ExpectedToken { expected, found, span } => {
                 let length  = expected.len();
                 let expected = if length >= 2 {
                     let (left, right) = expected.split_at(length - 1);
 
-                    format!("{}, or {}", left.join(", "), right[0])
+                    format!("{} or {}", left.join(", "), right[0])
                 } else {
                    expected.join("")
                 };
@@ -77,7 +360,7 @@ impl Display for ParseError {
                     None => write!(f, "Parse Error: unexpected end of file, expecting {} on line {} column {}", expected, span.0, span.1),
                 }
             },
-            Self::ExpectedIdentifier(expected, found, span) => {
+            Self::ExpectedIdentifier { expected, found, span } => {
                 let length  = expected.len();
                 let expected = if length >= 2 {
                     let (left, right) = expected.split_at(length - 1);
@@ -89,45 +372,50 @@ impl Display for ParseError {
 
                 write!(f, "Parse Error: unexpected identifier `{}`, expecting `{}` on line {} column {}", found, expected, span.0, span.1)
             },
-            Self::MissingTypeForReadonlyProperty(class, prop, span) => write!(f, "Parse Error: Readonly property {}::${} must have type on line {} column {}", class, prop, span.0, span.1),
-            Self::MultipleModifiers(modifier, span) => write!(f, "Parse Error: Multiple {} modifiers are not allowed on line {} column {}", modifier, span.0, span.1),
-            Self::MultipleVisibilityModifiers( span) => write!(f, "Parse Error: Multiple visibility modifiers are not allowed on line {} column {}", span.0, span.1),
-            Self::UnexpectedToken(message, span) => write!(f, "Parse Error: Unexpected token {} on line {} column {}", message, span.0, span.1),
-            Self::UnexpectedEndOfFile => write!(f, "Parse Error: unexpected end of file."),
-            Self::FinalModifierOnAbstractClassMember(span) => write!(f, "Parse Error: Cannot use 'final' as an abstract class member modifier on line {} column {}", span.0, span.1),
-            Self::CannotUseModifierOnConstant(modifier, span) => write!(f, "Parse Error: Cannot use '{}' as constant modifier on line {} column {}", modifier, span.0, span.1),
-            Self::CannotUseModifierOnInterfaceConstant(modifier, span) => write!(f, "Parse Error: Cannot use '{}' as an interface constant modifier on line {} column {}", modifier, span.0, span.1),
-            Self::CannotUseModifierOnProperty(modifier, span) => write!(f, "Parse Error: Cannot use '{}' as property modifier on line {} column {}", modifier, span.0, span.1),
-            Self::CannotUseModifierOnPromotedProperty(modifier, span) => write!(f, "Parse Error: Cannot use '{}' as promoted property modifier on line {} column {}", modifier, span.0, span.1),
-            Self::CannotUseModifierOnClass(modifier, span) => write!(f, "Parse Error: Cannot use '{}' as class modifier on line {} column {}", modifier, span.0, span.1),
-            Self::CannotUseModifierOnClassMethod(modifier, span) => write!(f, "Parse Error: Cannot use '{}' as class method modifier on line {} column {}", modifier, span.0, span.1),
-            Self::CannotUseModifierOnEnumMethod(modifier, span) => write!(f, "Parse Error: Cannot use '{}' as enum method modifier on line {} column {}", modifier, span.0, span.1),
-            Self::CannotUseModifierOnInterfaceMethod(modifier, span) => write!(f, "Parse Error: Cannot use '{}' as interface method modifier on line {} column {}", modifier, span.0, span.1),
-            Self::FinalModifierOnPrivateConstant(span) => write!(f, "Parse Error: Private constant cannot be final as it is not visible to other classes on line {} column {}", span.0, span.1),
-            Self::TryWithoutCatchOrFinally(span) => write!(f, "Parse Error: Cannot use try without catch or finally on line {} column {}", span.0, span.1),
-            Self::StandaloneTypeUsedInCombination(r#type, span) => write!(f, "Parse error: '{}' can only be used as a standalone type on line {} column {}", r#type, span.0, span.1),
-            Self::VariadicPromotedProperty(span) => write!(f, "Parse Error: Cannot declare variadic promoted property on line {} column {}", span.0, span.1),
-            Self::PromotedPropertyOutsideConstructor(span) => write!(f, "Parse Error: Cannot declare promoted property outside a constructor on line {} column {}", span.0, span.1),
-            Self::PromotedPropertyOnAbstractConstructor(span) => write!(f, "Parse Error: Cannot declare promoted property in an abstract constructor on line {} column {}", span.0, span.1),
-            Self::AbstractModifierOnNonAbstractClassMethod(span) => write!(f, "Parse Error: Cannot declare abstract methods on a non-abstract class on line {} column {}", span.0, span.1),
-            Self::FinalModifierOnAbstractClass(span) => write!(f, "Parse Error: Cannot use the final modifier on an abstract class on line {} column {}", span.0, span.1),
-            Self::ConstructorInEnum(name, span) => write!(f, "Parse Error: Enum '{}' cannot have a constructor on line {} column {}", name, span.0, span.1),
-            Self::MissingCaseValueForBackedEnum(case, name, span) => write!(f, "Parse Error: Case `{}` of backed enum `{}` must have a value on line {} column {}", case, name, span.0, span.1),
-            Self::CaseValueForUnitEnum(case, name, span) => write!(f, "Parse Error: Case `{}` of unit enum `{}` must not have a value on line {} column {}", case, name, span.0, span.1),
-            Self::StaticPropertyUsingReadonlyModifier(class, prop, span) => write!(f, "Parse Error: Static property {}:${} cannot be readonly on line {} column {}", class, prop, span.0, span.1),
-            Self::ReadonlyPropertyHasDefaultValue(class, prop, span) => write!(f, "Parse Error: Readonly property {}:${} cannot have a default value on line {} column {}", class, prop, span.0, span.1),
-            Self::MixingBracedAndUnBracedNamespaceDeclarations(span) => write!(f, "Parse Error: Cannot mix braced namespace declarations with unbraced namespace declarations on line {} column {}", span.0, span.1),
-            Self::NestedNamespaceDeclarations(span) => write!(f, "Parse Error: Namespace declarations cannot be mixed on line {} column {}", span.0, span.1),
-            Self::UnpredictableState(span) => write!(f, "Parse Error: Reached an unpredictable state on line {} column {}", span.0, span.1),
-            Self::ForbiddenTypeUsedInProperty(class, prop, ty, span) => write!(f, "Parse Error: Property {}::${} cannot have type `{}` on line {} column {}", class, prop, ty, span.0, span.1),
-            Self::MatchExpressionWithMultipleDefaultArms(span) => write!(f, "Parse Error: Match expressions may only contain one default arm on line {} column {}", span.0, span.1),
-            Self::CannotFindTypeInCurrentScope(ty, span) => write!(f, "Parse Error: Cannot find type `{}` in this scope on line {} on column {}", ty, span.0, span.1),
-            Self::ExpectedItemDefinitionAfterAttributes(span) => write!(f, "Parse Error: Expected item definition after attribute on line {} column {}", span.0, span.1),
-            Self::NestedDisjunctiveNormalFormTypes(span) => write!(f, "Parse Error: Nested disjunctive normal form types are not allowed on line {} column {}", span.0, span.1),
-            Self::IllegalSpreadOperator(span) => write!(f, "Parse Error: Cannot use spread operator on line {} column {}.", span.0, span.1),
-            Self::CannotAssignReferenceToNonReferencableValue(span) => write!(f, "Parse Error: cannot assign reference to non-referencable value on line {} column {}", span.0, span.1),
-            Self::CannotMixKeyedAndUnkeyedEntries(span) => write!(f, "Parse Error: cannot mix keyed and un-keyed entries on line {}", span.0),
-            Self::CannotUsePositionalArgumentAfterNamedArgument(span) => write!(f, "Parse Error: cannot use positional argument after named argument on line {}", span.0),
+            Self::MissingTypeForReadonlyProperty { class, property: prop, span } => write!(f, "Parse Error: Readonly property {}::${} must have type on line {} column {}", class, prop, span.0, span.1),
+            Self::MultipleModifiers { modifier, span, .. } => write!(f, "Parse Error: Multiple {} modifiers are not allowed on line {} column {}", modifier, span.0, span.1),
+            Self::MultipleVisibilityModifiers { span, .. } => write!(f, "Parse Error: Multiple visibility modifiers are not allowed on line {} column {}", span.0, span.1),
+            Self::UnexpectedToken { token: message, span } => write!(f, "Parse Error: Unexpected token {} on line {} column {}", message, span.0, span.1),
+            Self::UnexpectedEndOfFile { .. } => write!(f, "Parse Error: unexpected end of file."),
+            Self::FinalModifierOnAbstractClassMember { span } => write!(f, "Parse Error: Cannot use 'final' as an abstract class member modifier on line {} column {}", span.0, span.1),
+            Self::CannotUseModifierOnConstant { modifier, span } => write!(f, "Parse Error: Cannot use '{}' as constant modifier on line {} column {}", modifier, span.0, span.1),
+            Self::CannotUseModifierOnInterfaceConstant { modifier, span } => write!(f, "Parse Error: Cannot use '{}' as an interface constant modifier on line {} column {}", modifier, span.0, span.1),
+            Self::CannotUseModifierOnProperty { modifier, span } => write!(f, "Parse Error: Cannot use '{}' as property modifier on line {} column {}", modifier, span.0, span.1),
+            Self::CannotUseModifierOnPromotedProperty { modifier, span } => write!(f, "Parse Error: Cannot use '{}' as promoted property modifier on line {} column {}", modifier, span.0, span.1),
+            Self::CannotUseModifierOnClass { modifier, span } => write!(f, "Parse Error: Cannot use '{}' as class modifier on line {} column {}", modifier, span.0, span.1),
+            Self::CannotUseModifierOnClassMethod { modifier, span } => write!(f, "Parse Error: Cannot use '{}' as class method modifier on line {} column {}", modifier, span.0, span.1),
+            Self::CannotUseModifierOnEnumMethod { modifier, span } => write!(f, "Parse Error: Cannot use '{}' as enum method modifier on line {} column {}", modifier, span.0, span.1),
+            Self::CannotUseModifierOnInterfaceMethod { modifier, span } => write!(f, "Parse Error: Cannot use '{}' as interface method modifier on line {} column {}", modifier, span.0, span.1),
+            Self::FinalModifierOnPrivateConstant { span } => write!(f, "Parse Error: Private constant cannot be final as it is not visible to other classes on line {} column {}", span.0, span.1),
+            Self::TryWithoutCatchOrFinally { span } => write!(f, "Parse Error: Cannot use try without catch or finally on line {} column {}", span.0, span.1),
+            Self::StandaloneTypeUsedInCombination { r#type, span } => write!(f, "Parse error: '{}' can only be used as a standalone type on line {} column {}", r#type, span.0, span.1),
+            Self::VariadicPromotedProperty { span } => write!(f, "Parse Error: Cannot declare variadic promoted property on line {} column {}", span.0, span.1),
+            Self::PromotedPropertyOutsideConstructor { span } => write!(f, "Parse Error: Cannot declare promoted property outside a constructor on line {} column {}", span.0, span.1),
+            Self::PromotedPropertyOnAbstractConstructor { span } => write!(f, "Parse Error: Cannot declare promoted property in an abstract constructor on line {} column {}", span.0, span.1),
+            Self::AbstractModifierOnNonAbstractClassMethod { span } => write!(f, "Parse Error: Cannot declare abstract methods on a non-abstract class on line {} column {}", span.0, span.1),
+            Self::FinalModifierOnAbstractClass { span } => write!(f, "Parse Error: Cannot use the final modifier on an abstract class on line {} column {}", span.0, span.1),
+            Self::ConstructorInEnum { r#enum: name, span } => write!(f, "Parse Error: Enum '{}' cannot have a constructor on line {} column {}", name, span.0, span.1),
+            Self::MissingCaseValueForBackedEnum { case, r#enum: name, span } => write!(f, "Parse Error: Case `{}` of backed enum `{}` must have a value on line {} column {}", case, name, span.0, span.1),
+            Self::CaseValueForUnitEnum { case, r#enum: name, span } => write!(f, "Parse Error: Case `{}` of unit enum `{}` must not have a value on line {} column {}", case, name, span.0, span.1),
+            Self::StaticPropertyUsingReadonlyModifier { class, property: prop, span } => write!(f, "Parse Error: Static property {}:${} cannot be readonly on line {} column {}", class, prop, span.0, span.1),
+            Self::ReadonlyPropertyHasDefaultValue { class, property: prop, span } => write!(f, "Parse Error: Readonly property {}:${} cannot have a default value on line {} column {}", class, prop, span.0, span.1),
+            Self::MixingBracedAndUnBracedNamespaceDeclarations { span } => write!(f, "Parse Error: Cannot mix braced namespace declarations with unbraced namespace declarations on line {} column {}", span.0, span.1),
+            Self::NestedNamespaceDeclarations { span } => write!(f, "Parse Error: Namespace declarations cannot be mixed on line {} column {}", span.0, span.1),
+            Self::UnpredictableState { span } => write!(f, "Parse Error: Reached an unpredictable state on line {} column {}", span.0, span.1),
+            Self::ForbiddenTypeUsedInProperty { class, property: prop, r#type: ty, span } => write!(f, "Parse Error: Property {}::${} cannot have type `{}` on line {} column {}", class, prop, ty, span.0, span.1),
+            Self::MatchExpressionWithMultipleDefaultArms { span, .. } => write!(f, "Parse Error: Match expressions may only contain one default arm on line {} column {}", span.0, span.1),
+            Self::CannotFindTypeInCurrentScope { r#type: ty, span } => write!(f, "Parse Error: Cannot find type `{}` in this scope on line {} on column {}", ty, span.0, span.1),
+            Self::ExpectedItemDefinitionAfterAttributes { span } => write!(f, "Parse Error: Expected item definition after attribute on line {} column {}", span.0, span.1),
+            Self::NestedDisjunctiveNormalFormTypes { span } => write!(f, "Parse Error: Nested disjunctive normal form types are not allowed on line {} column {}", span.0, span.1),
+            Self::IllegalSpreadOperator { span } => write!(f, "Parse Error: Cannot use spread operator on line {} column {}.", span.0, span.1),
+            Self::CannotAssignReferenceToNonReferencableValue { span } => write!(f, "Parse Error: cannot assign reference to non-referencable value on line {} column {}", span.0, span.1),
+            Self::CannotMixKeyedAndUnkeyedEntries { span } => write!(f, "Parse Error: cannot mix keyed and un-keyed entries on line {}", span.0),
+            Self::CannotUsePositionalArgumentAfterNamedArgument { span } => write!(f, "Parse Error: cannot use positional argument after named argument on line {}", span.0),
+            Self::NestingTooDeep { limit, span } => write!(f, "Parse Error: Code is nested more than {} levels deep on line {} column {}", limit, span.0, span.1),
+            Self::InputTooLarge { limit, size, .. } => write!(f, "Parse Error: Input is {} bytes long, which is more than the limit of {} bytes", size, limit),
+            Self::TooManyTokens { limit, span } => write!(f, "Parse Error: Input contains more than {} tokens on line {} column {}", limit, span.0, span.1),
+            Self::TooManyNodes { limit, span } => write!(f, "Parse Error: Input contains more than {} expressions and statements on line {} column {}", limit, span.0, span.1),
+            Self::Cancelled { span } => write!(f, "Parse Error: Parsing was cancelled on line {} column {}", span.0, span.1),
         }
     }
 }
@@ -32,19 +32,29 @@ use super::ast::operators::BitwiseOperation;
 use super::ast::operators::ComparisonOperation;
 use super::ast::operators::LogicalOperation;
 
-pub fn lowest_precedence(state: &mut State) -> ParseResult<Expression> {
+pub fn lowest_precedence<'a>(state: &mut State<'a>) -> ParseResult<Expression<'a>> {
     for_precedence(state, Precedence::Lowest)
 }
 
-pub fn null_coalesce_precedence(state: &mut State) -> ParseResult<Expression> {
+pub fn null_coalesce_precedence<'a>(state: &mut State<'a>) -> ParseResult<Expression<'a>> {
     for_precedence(state, Precedence::NullCoalesce)
 }
 
-pub fn clone_or_new_precedence(state: &mut State) -> ParseResult<Expression> {
+pub fn clone_or_new_precedence<'a>(state: &mut State<'a>) -> ParseResult<Expression<'a>> {
     for_precedence(state, Precedence::CloneOrNew)
 }
 
-fn for_precedence(state: &mut State, precedence: Precedence) -> ParseResult<Expression> {
+fn for_precedence<'a>(
+    state: &mut State<'a>,
+    precedence: Precedence,
+) -> ParseResult<Expression<'a>> {
+    state.nested(|state| for_precedence_inner(state, precedence))
+}
+
+fn for_precedence_inner<'a>(
+    state: &mut State<'a>,
+    precedence: Precedence,
+) -> ParseResult<Expression<'a>> {
     let mut left = create(state)?;
 
     if state.current.kind == TokenKind::SemiColon {
@@ -86,7 +96,10 @@ fn for_precedence(state: &mut State, precedence: Precedence) -> ParseResult<Expr
             }
 
             if rpred == precedence && matches!(rpred.associativity(), Some(Associativity::Non)) {
-                return Err(ParseError::UnexpectedToken(kind.to_string(), span));
+                return Err(ParseError::UnexpectedToken {
+                    token: kind.to_string(),
+                    span,
+                });
             }
 
             state.next();
@@ -138,10 +151,10 @@ fn for_precedence(state: &mut State, precedence: Precedence) -> ParseResult<Expr
                             }
                             TokenKind::Instanceof if state.current.kind == TokenKind::Self_ => {
                                 if !state.has_class_scope {
-                                    return Err(ParseError::CannotFindTypeInCurrentScope(
-                                        state.current.kind.to_string(),
-                                        state.current.span,
-                                    ));
+                                    return Err(ParseError::CannotFindTypeInCurrentScope {
+                                        r#type: state.current.kind.to_string(),
+                                        span: state.current.span,
+                                    });
                                 }
 
                                 state.next();
@@ -154,10 +167,10 @@ fn for_precedence(state: &mut State, precedence: Precedence) -> ParseResult<Expr
                             }
                             TokenKind::Instanceof if state.current.kind == TokenKind::Parent => {
                                 if !state.has_class_scope {
-                                    return Err(ParseError::CannotFindTypeInCurrentScope(
-                                        state.current.kind.to_string(),
-                                        state.current.span,
-                                    ));
+                                    return Err(ParseError::CannotFindTypeInCurrentScope {
+                                        r#type: state.current.kind.to_string(),
+                                        span: state.current.span,
+                                    });
                                 }
 
                                 state.next();
@@ -170,10 +183,10 @@ fn for_precedence(state: &mut State, precedence: Precedence) -> ParseResult<Expr
                             }
                             TokenKind::Instanceof if state.current.kind == TokenKind::Static => {
                                 if !state.has_class_scope {
-                                    return Err(ParseError::CannotFindTypeInCurrentScope(
-                                        state.current.kind.to_string(),
-                                        state.current.span,
-                                    ));
+                                    return Err(ParseError::CannotFindTypeInCurrentScope {
+                                        r#type: state.current.kind.to_string(),
+                                        span: state.current.span,
+                                    });
                                 }
 
                                 state.next();
@@ -377,9 +390,9 @@ fn for_precedence(state: &mut State, precedence: Precedence) -> ParseResult<Expr
     Ok(left)
 }
 
-fn create(state: &mut State) -> ParseResult<Expression> {
+fn create<'a>(state: &mut State<'a>) -> ParseResult<Expression<'a>> {
     if state.is_eof() {
-        return Err(ParseError::UnexpectedEndOfFile);
+        return expected_token_err!("an expression", state);
     }
 
     attributes(state)
@@ -389,7 +402,7 @@ macro_rules! expressions {
     ($(#[before($else:ident), current($(|)? $( $current:pat_param )|+) $(, peek($(|)? $( $peek:pat_param )|+))?] $expr:ident($out:expr))+) => {
         $(
             #[inline(never)]
-            fn $expr(state: &mut State) -> ParseResult<Expression> {
+            fn $expr<'a>(state: &mut State<'a>) -> ParseResult<Expression<'a>> {
                 state.skip_comments();
 
                 match &state.current.kind {
@@ -403,7 +416,7 @@ macro_rules! expressions {
 
 expressions! {
     #[before(static_arrow_function), current(TokenKind::Attribute)]
-    attributes(|state: &mut State| {
+    attributes(|state: &mut State<'a>| {
         attributes::gather_attributes(state)?;
 
         match &state.current.kind {
@@ -418,30 +431,28 @@ expressions! {
             _ => {
                 // Note, we can get attributes and know their span, maybe use that in the
                 // error in the future?
-                Err(ParseError::ExpectedItemDefinitionAfterAttributes(
-                    state.current.span,
-                ))
+                Err(ParseError::ExpectedItemDefinitionAfterAttributes { span: state.current.span })
             }
         }
     })
 
     #[before(static_anonymous_function), current(TokenKind::Static), peek(TokenKind::Fn)]
-    static_arrow_function(|state: &mut State| {
+    static_arrow_function(|state: &mut State<'a>| {
         functions::arrow_function(state)
     })
 
     #[before(arrow_function), current(TokenKind::Static), peek(TokenKind::Function)]
-    static_anonymous_function(|state: &mut State| {
+    static_anonymous_function(|state: &mut State<'a>| {
         functions::anonymous_function(state)
     })
 
     #[before(anonymous_function), current(TokenKind::Fn)]
-    arrow_function(|state: &mut State| {
+    arrow_function(|state: &mut State<'a>| {
         functions::arrow_function(state)
     })
 
     #[before(reserved_identifier_function_call), current(TokenKind::Function)]
-    anonymous_function(|state: &mut State| {
+    anonymous_function(|state: &mut State<'a>| {
         functions::anonymous_function(state)
     })
 
@@ -450,7 +461,7 @@ expressions! {
         | TokenKind::Readonly   | TokenKind::Self_ | TokenKind::Parent
         | TokenKind::Enum       | TokenKind::From
     ), peek(TokenKind::LeftParen)]
-    reserved_identifier_function_call(|state: &mut State| {
+    reserved_identifier_function_call(|state: &mut State<'a>| {
         let ident = identifiers::ident_maybe_soft_reserved(state)?;
         let lhs = Expression::Identifier(Identifier::SimpleIdentifier(ident));
 
@@ -458,17 +469,17 @@ expressions! {
     })
 
     #[before(anonymous_class), current(TokenKind::List)]
-    list(|state: &mut State| {
+    list(|state: &mut State<'a>| {
         arrays::list_expression(state)
     })
 
     #[before(throw), current(TokenKind::New), peek(TokenKind::Class | TokenKind::Attribute)]
-    anonymous_class(|state: &mut State| {
+    anonymous_class(|state: &mut State<'a>| {
         classes::parse_anonymous(state)
     })
 
     #[before(r#yield), current(TokenKind::Throw)]
-    throw(|state: &mut State| {
+    throw(|state: &mut State<'a>| {
 
         state.next();
 
@@ -483,7 +494,7 @@ expressions! {
     })
 
     #[before(clone), current(TokenKind::Yield)]
-    r#yield(|state: &mut State| {
+    r#yield(|state: &mut State<'a>| {
         state.next();
 
         if state.current.kind == TokenKind::SemiColon {
@@ -527,7 +538,7 @@ expressions! {
     })
 
     #[before(r#true), current(TokenKind::Clone)]
-    clone(|state: &mut State| {
+    clone(|state: &mut State<'a>| {
         state.next();
 
         let target = for_precedence(state, Precedence::CloneOrNew)?;
@@ -538,28 +549,28 @@ expressions! {
     })
 
     #[before(r#false), current(TokenKind::True)]
-    r#true(|state: &mut State| {
+    r#true(|state: &mut State<'a>| {
         state.next();
 
         Ok(Expression::Bool { value: true })
     })
 
     #[before(null), current(TokenKind::False)]
-    r#false(|state: &mut State| {
+    r#false(|state: &mut State<'a>| {
         state.next();
 
         Ok(Expression::Bool { value: false })
     })
 
     #[before(literal_integer), current(TokenKind::Null)]
-    null(|state: &mut State| {
+    null(|state: &mut State<'a>| {
         state.next();
 
         Ok(Expression::Null)
     })
 
     #[before(literal_float), current(TokenKind::LiteralInteger(_))]
-    literal_integer(|state: &mut State| {
+    literal_integer(|state: &mut State<'a>| {
         if let TokenKind::LiteralInteger(i) = &state.current.kind {
             let e = Expression::LiteralInteger { i: i.clone() };
             state.next();
@@ -571,7 +582,7 @@ expressions! {
     })
 
     #[before(literal_string), current(TokenKind::LiteralFloat(_))]
-    literal_float(|state: &mut State| {
+    literal_float(|state: &mut State<'a>| {
         if let TokenKind::LiteralFloat(f) = &state.current.kind {
             let e = Expression::LiteralFloat { f: f.clone() };
 
@@ -584,7 +595,7 @@ expressions! {
     })
 
     #[before(string_part), current(TokenKind::LiteralString(_))]
-    literal_string(|state: &mut State| {
+    literal_string(|state: &mut State<'a>| {
         if let TokenKind::LiteralString(value) = &state.current.kind {
             let e = Expression::LiteralString { value: value.clone() };
             state.next();
@@ -596,12 +607,12 @@ expressions! {
     })
 
     #[before(start_doc_string), current(TokenKind::StringPart(_))]
-    string_part(|state: &mut State| {
+    string_part(|state: &mut State<'a>| {
         interpolated_string(state)
     })
 
     #[before(backtick), current(TokenKind::StartDocString(_, _))]
-    start_doc_string(|state: &mut State| {
+    start_doc_string(|state: &mut State<'a>| {
         if let TokenKind::StartDocString(_, kind) = &state.current.kind {
             let kind = *kind;
 
@@ -612,38 +623,38 @@ expressions! {
     })
 
     #[before(identifier), current(TokenKind::Backtick)]
-    backtick(|state: &mut State| {
+    backtick(|state: &mut State<'a>| {
         shell_exec(state)
     })
 
     #[before(self_postfix), current(TokenKind::Identifier(_) | TokenKind::QualifiedIdentifier(_) | TokenKind::FullyQualifiedIdentifier(_))]
-    identifier(|state: &mut State| {
+    identifier(|state: &mut State<'a>| {
         Ok(Expression::Identifier(Identifier::SimpleIdentifier(identifiers::full_name(state)?)))
     })
 
     #[before(static_postfix), current(TokenKind::Self_)]
-    self_postfix(|state: &mut State| {
+    self_postfix(|state: &mut State<'a>| {
         state.next();
 
         postfix(state, Expression::Self_, &TokenKind::DoubleColon)
     })
 
     #[before(parent_postfix), current(TokenKind::Static)]
-    static_postfix(|state: &mut State| {
+    static_postfix(|state: &mut State<'a>| {
         state.next();
 
         postfix(state, Expression::Static, &TokenKind::DoubleColon)
     })
 
     #[before(left_parenthesis), current(TokenKind::Parent)]
-    parent_postfix(|state: &mut State| {
+    parent_postfix(|state: &mut State<'a>| {
         state.next();
 
         postfix(state, Expression::Parent, &TokenKind::DoubleColon)
     })
 
     #[before(r#match), current(TokenKind::LeftParen)]
-    left_parenthesis(|state: &mut State| {
+    left_parenthesis(|state: &mut State<'a>| {
         let start = state.current.span;
         state.next();
 
@@ -655,22 +666,22 @@ expressions! {
     })
 
     #[before(array), current(TokenKind::Match)]
-    r#match(|state: &mut State| {
+    r#match(|state: &mut State<'a>| {
         control_flow::match_expression(state)
     })
 
     #[before(left_bracket), current(TokenKind::Array)]
-    array(|state: &mut State| {
+    array(|state: &mut State<'a>| {
         arrays::legacy_array_expression(state)
     })
 
     #[before(new), current(TokenKind::LeftBracket)]
-    left_bracket(|state: &mut State| {
+    left_bracket(|state: &mut State<'a>| {
         arrays::array_expression(state)
     })
 
     #[before(directory_magic_constant), current(TokenKind::New)]
-    new(|state: &mut State| {
+    new(|state: &mut State<'a>| {
         let span = state.current.span;
 
         state.next();
@@ -707,7 +718,7 @@ expressions! {
     })
 
     #[before(file_magic_constant), current(TokenKind::DirConstant)]
-    directory_magic_constant(|state: &mut State| {
+    directory_magic_constant(|state: &mut State<'a>| {
         let span = state.current.span;
         state.next();
 
@@ -718,7 +729,7 @@ expressions! {
     })
 
     #[before(line_magic_constant), current(TokenKind::FileConstant)]
-    file_magic_constant(|state: &mut State| {
+    file_magic_constant(|state: &mut State<'a>| {
         let span = state.current.span;
         state.next();
 
@@ -729,7 +740,7 @@ expressions! {
     })
 
     #[before(function_magic_constant), current(TokenKind::LineConstant)]
-    line_magic_constant(|state: &mut State| {
+    line_magic_constant(|state: &mut State<'a>| {
         let span = state.current.span;
         state.next();
 
@@ -740,7 +751,7 @@ expressions! {
     })
 
     #[before(class_magic_constant), current(TokenKind::FunctionConstant)]
-    function_magic_constant(|state: &mut State| {
+    function_magic_constant(|state: &mut State<'a>| {
         let span = state.current.span;
         state.next();
 
@@ -751,7 +762,7 @@ expressions! {
     })
 
     #[before(method_magic_constant), current(TokenKind::ClassConstant)]
-    class_magic_constant(|state: &mut State| {
+    class_magic_constant(|state: &mut State<'a>| {
         let span = state.current.span;
         state.next();
 
@@ -762,7 +773,7 @@ expressions! {
     })
 
     #[before(namespace_magic_constant), current(TokenKind::MethodConstant)]
-    method_magic_constant(|state: &mut State| {
+    method_magic_constant(|state: &mut State<'a>| {
         let span = state.current.span;
         state.next();
 
@@ -773,7 +784,7 @@ expressions! {
     })
 
     #[before(trait_magic_constant), current(TokenKind::NamespaceConstant)]
-    namespace_magic_constant(|state: &mut State| {
+    namespace_magic_constant(|state: &mut State<'a>| {
         let span = state.current.span;
         state.next();
 
@@ -784,7 +795,7 @@ expressions! {
     })
 
     #[before(include), current(TokenKind::TraitConstant)]
-    trait_magic_constant(|state: &mut State| {
+    trait_magic_constant(|state: &mut State<'a>| {
         let span = state.current.span;
         state.next();
 
@@ -795,7 +806,7 @@ expressions! {
     })
 
     #[before(cast_prefix), current(TokenKind::Include | TokenKind::IncludeOnce | TokenKind::Require | TokenKind::RequireOnce)]
-    include(|state: &mut State| {
+    include(|state: &mut State<'a>| {
         let kind: IncludeKind = (&state.current.kind).into();
         let span = state.current.span;
 
@@ -816,7 +827,7 @@ expressions! {
         | TokenKind::IntegerCast    | TokenKind::FloatCast      | TokenKind::DoubleCast
         | TokenKind::RealCast       | TokenKind::UnsetCast      | TokenKind::ArrayCast
     )]
-    cast_prefix(|state: &mut State| {
+    cast_prefix(|state: &mut State<'a>| {
         let span = state.current.span;
         let kind = state.current.kind.clone().into();
 
@@ -832,7 +843,7 @@ expressions! {
     })
 
     #[before(bang_prefix), current(TokenKind::Decrement | TokenKind::Increment | TokenKind::Minus | TokenKind::Plus)]
-    numeric_prefix(|state: &mut State| {
+    numeric_prefix(|state: &mut State<'a>| {
         let span = state.current.span;
         let op = state.current.kind.clone();
 
@@ -851,7 +862,7 @@ expressions! {
     })
 
     #[before(at_prefix), current(TokenKind::Bang)]
-    bang_prefix(|state: &mut State| {
+    bang_prefix(|state: &mut State<'a>| {
         let span = state.current.span;
 
         state.next();
@@ -865,7 +876,7 @@ expressions! {
     })
 
     #[before(print_prefix), current(TokenKind::At)]
-    at_prefix(|state: &mut State| {
+    at_prefix(|state: &mut State<'a>| {
         let span = state.current.span;
 
         state.next();
@@ -879,7 +890,7 @@ expressions! {
     })
 
     #[before(bitwise_prefix), current(TokenKind::Print)]
-    print_prefix(|state: &mut State| {
+    print_prefix(|state: &mut State<'a>| {
         let span = state.current.span;
 
         state.next();
@@ -893,7 +904,7 @@ expressions! {
     })
 
     #[before(variable), current(TokenKind::BitwiseNot)]
-    bitwise_prefix(|state: &mut State| {
+    bitwise_prefix(|state: &mut State<'a>| {
         let span = state.current.span;
 
         state.next();
@@ -904,19 +915,20 @@ expressions! {
     })
 
     #[before(unexpected_token), current(TokenKind::Dollar | TokenKind::DollarLeftBrace | TokenKind::Variable(_))]
-    variable(|state: &mut State| {
+    variable(|state: &mut State<'a>| {
         Ok(Expression::Variable(variables::dynamic_variable(state)?))
     })
 }
 
-fn unexpected_token(state: &mut State) -> ParseResult<Expression> {
-    Err(ParseError::UnexpectedToken(
-        state.current.kind.to_string(),
-        state.current.span,
-    ))
+fn unexpected_token<'a>(state: &mut State<'a>) -> ParseResult<Expression<'a>> {
+    expected_token_err!("an expression", state)
 }
 
-fn postfix(state: &mut State, lhs: Expression, op: &TokenKind) -> Result<Expression, ParseError> {
+fn postfix<'a>(
+    state: &mut State<'a>,
+    lhs: Expression<'a>,
+    op: &TokenKind<'a>,
+) -> Result<Expression<'a>, ParseError> {
     Ok(match op {
         TokenKind::Coalesce => {
             state.next();
@@ -993,6 +1005,7 @@ fn postfix(state: &mut State, lhs: Expression, op: &TokenKind) -> Result<Express
                     Expression::Identifier(Identifier::SimpleIdentifier(SimpleIdentifier {
                         span,
                         name: "class".into(),
+                        symbol: None,
                     }))
                 }
                 _ => {
@@ -1017,10 +1030,10 @@ fn postfix(state: &mut State, lhs: Expression, op: &TokenKind) -> Result<Express
                 }
                 // 1. If we have an identifier and the current token is not a left paren,
                 //    the resulting expression must be a constant fetch.
-                Expression::Identifier(Identifier::SimpleIdentifier(identifier)) => {
+                Expression::Identifier(Identifier::SimpleIdentifier(ref identifier)) => {
                     Expression::ConstFetch {
                         target: lhs,
-                        constant: identifier,
+                        constant: identifier.clone(),
                     }
                 }
                 // 3. If we haven't met any of the previous conditions, we can assume
@@ -1113,7 +1126,7 @@ fn postfix(state: &mut State, lhs: Expression, op: &TokenKind) -> Result<Express
 }
 
 #[inline(always)]
-fn interpolated_string(state: &mut State) -> ParseResult<Expression> {
+fn interpolated_string<'a>(state: &mut State<'a>) -> ParseResult<Expression<'a>> {
     let mut parts = Vec::new();
 
     while state.current.kind != TokenKind::DoubleQuote {
@@ -1128,7 +1141,7 @@ fn interpolated_string(state: &mut State) -> ParseResult<Expression> {
 }
 
 #[inline(always)]
-fn shell_exec(state: &mut State) -> ParseResult<Expression> {
+fn shell_exec<'a>(state: &mut State<'a>) -> ParseResult<Expression<'a>> {
     state.next();
 
     let mut parts = Vec::new();
@@ -1145,7 +1158,7 @@ fn shell_exec(state: &mut State) -> ParseResult<Expression> {
 }
 
 #[inline(always)]
-fn doc_string(state: &mut State, kind: DocStringKind) -> ParseResult<Expression> {
+fn doc_string<'a>(state: &mut State<'a>, kind: DocStringKind) -> ParseResult<Expression<'a>> {
     let span = state.current.span;
     state.next();
 
@@ -1185,12 +1198,12 @@ fn doc_string(state: &mut State, kind: DocStringKind) -> ParseResult<Expression>
                             // 1. If this line doesn't start with any whitespace,
                             //    we can return an error early because we know
                             //    the label was indented.
-                            if !bytes.starts_with(&[b' ']) && !bytes.starts_with(&[b'\t']) {
+                            if !bytes.starts_with(b" ") && !bytes.starts_with(b"\t") {
                                 return Err(ParseError::SyntaxError(
-                                    SyntaxError::InvalidDocBodyIndentationLevel(
-                                        indentation_amount,
+                                    SyntaxError::InvalidDocBodyIndentationLevel {
+                                        expected: indentation_amount,
                                         span,
-                                    ),
+                                    },
                                 ));
                             }
 
@@ -1198,7 +1211,7 @@ fn doc_string(state: &mut State, kind: DocStringKind) -> ParseResult<Expression>
                             //    type of whitespace, we can also return an error.
                             if !bytes.starts_with(&[indentation_char]) {
                                 return Err(ParseError::SyntaxError(
-                                    SyntaxError::InvalidDocIndentation(span),
+                                    SyntaxError::InvalidDocIndentation { span },
                                 ));
                             }
 
@@ -1212,21 +1225,18 @@ fn doc_string(state: &mut State, kind: DocStringKind) -> ParseResult<Expression>
                                 vec![indentation_char; indentation_amount];
                             if !bytes.starts_with(&expected_whitespace_buffer) {
                                 return Err(ParseError::SyntaxError(
-                                    SyntaxError::InvalidDocBodyIndentationLevel(
-                                        indentation_amount,
+                                    SyntaxError::InvalidDocBodyIndentationLevel {
+                                        expected: indentation_amount,
                                         span,
-                                    ),
+                                    },
                                 ));
                             }
 
                             // 4. All of the above checks have passed, so we know
                             //    there are no more possible errors. Let's now
                             //    strip the leading whitespace accordingly.
-                            *bytes = bytes
-                                .strip_prefix(&expected_whitespace_buffer[..])
-                                .unwrap()
-                                .into();
-                            new_line = bytes.ends_with(&[b'\n']);
+                            bytes.remove_prefix(expected_whitespace_buffer.len());
+                            new_line = bytes.ends_with(b"\n");
                         }
                         _ => continue,
                     }
@@ -1265,18 +1275,21 @@ fn doc_string(state: &mut State, kind: DocStringKind) -> ParseResult<Expression>
                     // 1. If this line doesn't start with any whitespace,
                     //    we can return an error early because we know
                     //    the label was indented.
-                    if !line.starts_with(&[b' ']) && !line.starts_with(&[b'\t']) {
+                    if !line.starts_with(b" ") && !line.starts_with(b"\t") {
                         return Err(ParseError::SyntaxError(
-                            SyntaxError::InvalidDocBodyIndentationLevel(indentation_amount, span),
+                            SyntaxError::InvalidDocBodyIndentationLevel {
+                                expected: indentation_amount,
+                                span,
+                            },
                         ));
                     }
 
                     // 2. If this line doesn't start with the correct
                     //    type of whitespace, we can also return an error.
                     if !line.starts_with(&[indentation_char]) {
-                        return Err(ParseError::SyntaxError(SyntaxError::InvalidDocIndentation(
-                            span,
-                        )));
+                        return Err(ParseError::SyntaxError(
+                            SyntaxError::InvalidDocIndentation { span },
+                        ));
                     }
 
                     // 3. We now know that the whitespace at the start of
@@ -1288,7 +1301,10 @@ fn doc_string(state: &mut State, kind: DocStringKind) -> ParseResult<Expression>
                     let expected_whitespace_buffer = vec![indentation_char; indentation_amount];
                     if !line.starts_with(&expected_whitespace_buffer) {
                         return Err(ParseError::SyntaxError(
-                            SyntaxError::InvalidDocBodyIndentationLevel(indentation_amount, span),
+                            SyntaxError::InvalidDocBodyIndentationLevel {
+                                expected: indentation_amount,
+                                span,
+                            },
                         ));
                     }
 
@@ -1316,10 +1332,10 @@ fn doc_string(state: &mut State, kind: DocStringKind) -> ParseResult<Expression>
     })
 }
 
-fn interpolated_string_part(state: &mut State) -> ParseResult<Option<StringPart>> {
+fn interpolated_string_part<'a>(state: &mut State<'a>) -> ParseResult<Option<StringPart<'a>>> {
     Ok(match &state.current.kind {
         TokenKind::StringPart(s) => {
-            let part = if s.len() > 0 {
+            let part = if !s.is_empty() {
                 Some(StringPart::Const(s.clone()))
             } else {
                 None
@@ -1425,7 +1441,7 @@ fn interpolated_string_part(state: &mut State) -> ParseResult<Option<StringPart>
     })
 }
 
-fn is_infix(t: &TokenKind) -> bool {
+fn is_infix<'a>(t: &TokenKind<'a>) -> bool {
     matches!(
         t,
         TokenKind::Pow
@@ -1476,7 +1492,7 @@ fn is_infix(t: &TokenKind) -> bool {
 }
 
 #[inline(always)]
-fn is_postfix(t: &TokenKind) -> bool {
+fn is_postfix<'a>(t: &TokenKind<'a>) -> bool {
     matches!(
         t,
         TokenKind::Increment
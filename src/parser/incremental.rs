@@ -0,0 +1,581 @@
+//! Incremental reparsing, for editors that reparse a file on every keystroke.
+//!
+//! A `Document` keeps the source code together with its AST and remembers where
+//! each top-level statement and class member starts. When the source is edited,
+//! only the statements or members that overlap the edit are lexed and parsed
+//! again, and everything else in the tree is kept. Whenever that can't be done
+//! safely, e.g. because the edit opens a string or a comment that runs past the
+//! end of the statement, the whole document is parsed again instead, so the
+//! result is always the same as a fresh parse of the new source.
+
+use std::collections::VecDeque;
+use std::ops::Range;
+use std::sync::OnceLock;
+
+use crate::lexer::error::SyntaxError;
+use crate::lexer::error::SyntaxResult;
+use crate::lexer::source::LineIndex;
+use crate::lexer::token::Span;
+use crate::lexer::token::Token;
+use crate::lexer::token::TokenKind;
+use crate::lexer::Lexer;
+use crate::parser;
+use crate::parser::ast::classes::ClassMember;
+use crate::parser::ast::owned::IntoOwned;
+use crate::parser::ast::spans::SpansMut;
+use crate::parser::ast::Program;
+use crate::parser::ast::Statement;
+use crate::parser::error::ParseError;
+use crate::parser::error::ParseResult;
+use crate::parser::internal::classes;
+use crate::parser::options::ParserOptions;
+use crate::parser::state::NamespaceType;
+use crate::parser::state::Scope;
+use crate::parser::state::State;
+
+/// A change to the source code: the bytes in `range` are replaced with `text`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    pub range: Range<usize>,
+    pub text: Vec<u8>,
+}
+
+impl TextEdit {
+    pub fn new(range: Range<usize>, text: impl Into<Vec<u8>>) -> Self {
+        Self {
+            range,
+            text: text.into(),
+        }
+    }
+}
+
+/// How much of a document was parsed again after an edit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reparse {
+    /// The whole document was lexed and parsed again.
+    Full,
+    /// Only the given byte range of the new source was lexed and parsed again.
+    Incremental { range: Range<usize> },
+}
+
+/// Source code and the result of parsing it, kept up to date as the source is edited.
+#[derive(Debug)]
+pub struct Document {
+    lexer: Lexer,
+    options: ParserOptions,
+    source: Vec<u8>,
+    result: ParseResult<Tree>,
+    // Lexed on first use, and again after each edit.
+    tokens: OnceLock<SyntaxResult<Vec<Token<'static>>>>,
+}
+
+#[derive(Debug)]
+struct Tree {
+    program: Program<'static>,
+    outline: Outline,
+}
+
+impl Document {
+    pub fn new(source: impl Into<Vec<u8>>) -> Self {
+        Self::with_options(Lexer::new(), ParserOptions::default(), source)
+    }
+
+    /// Create a document that is lexed and parsed with the given lexer and options.
+    ///
+    /// When only part of the document is parsed again, `max_tokens` and `max_nodes`
+    /// only apply to that part.
+    pub fn with_options(lexer: Lexer, options: ParserOptions, source: impl Into<Vec<u8>>) -> Self {
+        let source = source.into();
+        let result = parse(&lexer, &options, &source);
+
+        Self {
+            lexer,
+            options,
+            source,
+            result,
+            tokens: OnceLock::new(),
+        }
+    }
+
+    pub fn source(&self) -> &[u8] {
+        &self.source
+    }
+
+    pub fn program(&self) -> Result<&Program<'static>, &ParseError> {
+        self.result.as_ref().map(|tree| &tree.program)
+    }
+
+    /// The tokens of the whole source, lexed once and kept until the next edit.
+    pub fn tokens(&self) -> Result<&[Token<'static>], &SyntaxError> {
+        self.tokens
+            .get_or_init(|| {
+                self.lexer
+                    .tokenize(&self.source)
+                    .map(|tokens| tokens.into_iter().map(IntoOwned::into_owned).collect())
+            })
+            .as_ref()
+            .map(|tokens| &tokens[..])
+    }
+
+    /// Apply an edit to the source code and bring the AST up to date.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range of the edit is out of bounds.
+    pub fn edit(&mut self, edit: TextEdit) -> Reparse {
+        assert!(
+            edit.range.start <= edit.range.end && edit.range.end <= self.source.len(),
+            "edit range {:?} is out of bounds for a source of {} bytes",
+            edit.range,
+            self.source.len()
+        );
+
+        self.tokens.take();
+
+        let before = LineIndex::new(&self.source);
+        self.source
+            .splice(edit.range.clone(), edit.text.iter().copied());
+
+        if let Some(range) = self.reparse(&edit, &before) {
+            return Reparse::Incremental { range };
+        }
+
+        self.result = parse(&self.lexer, &self.options, &self.source);
+
+        Reparse::Full
+    }
+
+    // Parse the statements or members that overlap the edit, and splice them into
+    // the tree. Returns `None`, leaving the tree untouched, if that isn't possible.
+    fn reparse(&mut self, edit: &TextEdit, before: &LineIndex) -> Option<Range<usize>> {
+        let tree = self.result.as_mut().ok()?;
+        let delta = edit.text.len() as isize - edit.range.len() as isize;
+        let old_length = (self.source.len() as isize - delta) as usize;
+
+        // The innermost list of units whose units cover the edit.
+        let (index, units) = tree
+            .outline
+            .lists
+            .iter()
+            .enumerate()
+            .filter(|(_, units)| {
+                units.starts.first().is_some_and(|first| {
+                    before.offset(first.start) <= edit.range.start
+                        && edit.range.end <= units.end.map_or(old_length, |end| before.offset(end))
+                })
+            })
+            .max_by_key(|(_, units)| units.path.len())?;
+
+        let first = units
+            .starts
+            .iter()
+            .rposition(|unit| before.offset(unit.start) <= edit.range.start)?;
+        let last = units.starts[first..]
+            .iter()
+            .position(|unit| before.offset(unit.start) > edit.range.end)
+            .map_or(units.starts.len(), |position| first + position);
+
+        // The first unit after the edit, or the end of the list, and where it is now.
+        let next = units.starts.get(last).map(|unit| unit.start).or(units.end);
+        let start = before.offset(units.starts[first].start);
+        let end = next.map_or(self.source.len(), |next| {
+            (before.offset(next) as isize + delta) as usize
+        });
+
+        // Inline HTML isn't lexed in scripting mode, so it can't be relexed on its own.
+        if units.starts[first..]
+            .iter()
+            .take(last - first + 1)
+            .any(|unit| unit.inline_html)
+        {
+            return None;
+        }
+
+        // The units on either side must still begin and end where they did before.
+        if !is_boundary(&self.source[..start], b"{};") {
+            return None;
+        }
+        if end < self.source.len() && !is_boundary(&self.source[..end], b"};") {
+            return None;
+        }
+
+        let after = LineIndex::new(&self.source);
+        let origin = after.span(start);
+        let code = &self.source[start..end];
+
+        let mut tokens = self.lexer.clone().in_scripting_mode().tokenize(code).ok()?;
+        let relexable = tokens.iter().all(|token| {
+            !matches!(
+                token.kind,
+                TokenKind::OpenTag(_)
+                    | TokenKind::CloseTag
+                    | TokenKind::InlineHtml(_)
+                    | TokenKind::HaltCompiler
+                    | TokenKind::Namespace
+            )
+        });
+        // A comment that runs to the end of the code would continue past it in the full source.
+        let unterminated = end < self.source.len()
+            && tokens
+                .iter()
+                .rev()
+                .find(|token| token.kind != TokenKind::Eof)
+                .is_some_and(|token| match &token.kind {
+                    TokenKind::SingleLineComment(_) | TokenKind::HashMarkComment(_) => {
+                        !code.ends_with(b"\n")
+                    }
+                    TokenKind::MultiLineComment(comment) | TokenKind::DocumentComment(comment) => {
+                        comment.len() < 3 || !comment.ends_with(b"*/")
+                    }
+                    _ => false,
+                });
+        if !relexable || unterminated {
+            return None;
+        }
+
+        for token in tokens.iter_mut() {
+            token.span = relative_to(token.span, origin);
+        }
+
+        let mut state =
+            State::new(tokens.into_iter().map(Ok), self.options.clone()).with_depth(units.depth);
+        for scope in &units.scopes {
+            state.enter(scope.clone());
+        }
+        state.namespace_type = units.namespace_type.clone();
+        state.outline = Some(Outline::default());
+
+        let items = match units.kind {
+            UnitsKind::Program | UnitsKind::Namespace | UnitsKind::BracedNamespace => {
+                statements(&mut state, units.kind).map(Items::Statements)
+            }
+            UnitsKind::Class => {
+                let class = match units.scopes.last() {
+                    Some(Scope::Class(name, ..)) => name.name.to_string(),
+                    _ => return None,
+                };
+
+                members(&mut state, class).map(Items::Members)
+            }
+        }
+        .ok()?;
+
+        if state.error.is_some() {
+            return None;
+        }
+
+        let reparsed = state.outline.take()?;
+        let path = units.path.clone();
+
+        // Everything after the edit keeps its AST, but moves along with the source.
+        if let Some(next) = next {
+            let moved = after.span(end);
+            let mut update = |span: &mut Span| shift(span, next, moved);
+
+            tree.program.spans_mut(&mut update);
+            for units in tree.outline.lists.iter_mut() {
+                for unit in units.starts.iter_mut() {
+                    update(&mut unit.start);
+                }
+                if let Some(end) = &mut units.end {
+                    update(end);
+                }
+            }
+        }
+
+        match (list(&mut tree.program, &path)?, items) {
+            (List::Statements(list), Items::Statements(items)) => {
+                list.splice(first..last, items.into_iter().map(IntoOwned::into_owned));
+            }
+            (List::Members(list), Items::Members(items)) => {
+                list.splice(first..last, items.into_iter().map(IntoOwned::into_owned));
+            }
+            _ => unreachable!("the list of units doesn't match its kind"),
+        }
+
+        tree.outline.replace(index, first..last, reparsed);
+
+        Some(start..end)
+    }
+}
+
+fn parse(lexer: &Lexer, options: &ParserOptions, source: &[u8]) -> ParseResult<Tree> {
+    let tokens = lexer.tokenize(source)?;
+
+    let mut state = State::new(tokens.into_iter().map(Ok), options.clone());
+    state.outline = Some(Outline::default());
+
+    let mut program = Program::new();
+    let result = parser::program(&mut state, |statement| program.push(statement.into_owned()));
+
+    result.map(|_| Tree {
+        program,
+        outline: state.outline.take().unwrap_or_default(),
+    })
+}
+
+enum Items<'a> {
+    Statements(Vec<Statement<'a>>),
+    Members(Vec<ClassMember<'a>>),
+}
+
+enum List<'t> {
+    Statements(&'t mut Vec<Statement<'static>>),
+    Members(&'t mut Vec<ClassMember<'static>>),
+}
+
+// Find the list of units that `path` leads to.
+fn list<'t>(program: &'t mut Program<'static>, path: &[usize]) -> Option<List<'t>> {
+    let mut list = List::Statements(program);
+
+    for index in path {
+        list = match list {
+            List::Statements(statements) => match statements.get_mut(*index)? {
+                Statement::Namespace { body, .. } | Statement::BracedNamespace { body, .. } => {
+                    List::Statements(body)
+                }
+                Statement::Class(class) => List::Members(&mut class.members),
+                _ => return None,
+            },
+            List::Members(_) => return None,
+        };
+    }
+
+    Some(list)
+}
+
+// Parse top-level statements until the end of the input, the same way the
+// loops in `parser::program` and `namespaces` do.
+fn statements<'a>(state: &mut State<'a>, kind: UnitsKind) -> ParseResult<Vec<Statement<'a>>> {
+    let mut statements = vec![];
+
+    state.open_units(kind);
+    while !state.is_eof() {
+        if kind == UnitsKind::Program {
+            state.gather_comments();
+
+            if state.is_eof() {
+                break;
+            }
+        }
+
+        state.check_cancelled()?;
+        state.unit();
+        statements.push(parser::top_level_statement(state)?);
+
+        state.clear_comments();
+    }
+    state.close_units();
+
+    Ok(statements)
+}
+
+// Parse class members until the end of the input, the same way `classes::parse` does.
+fn members<'a>(state: &mut State<'a>, class: String) -> ParseResult<Vec<ClassMember<'a>>> {
+    let mut members = vec![];
+
+    state.open_units(UnitsKind::Class);
+    while !state.is_eof() {
+        state.gather_comments();
+
+        if state.is_eof() {
+            state.clear_comments();
+            break;
+        }
+
+        state.unit();
+        members.push(classes::member(state, class.clone())?);
+    }
+    state.close_units();
+
+    Ok(members)
+}
+
+// Whether the code that comes before a unit ends in a way that can't run on into it.
+fn is_boundary(before: &[u8], delimiters: &[u8]) -> bool {
+    match before.last() {
+        Some(byte) => byte.is_ascii_whitespace() || delimiters.contains(byte),
+        None => false,
+    }
+}
+
+// Move a span from code that was lexed on its own to where that code starts.
+fn relative_to(span: Span, origin: Span) -> Span {
+    if span.0 == 1 {
+        (origin.0, origin.1 + span.1 - 1)
+    } else {
+        (origin.0 + span.0 - 1, span.1)
+    }
+}
+
+// Move a span at or after `from` so that `from` ends up at `to`.
+fn shift(span: &mut Span, from: Span, to: Span) {
+    if *span < from {
+        return;
+    }
+
+    if span.0 == from.0 {
+        span.1 = span.1 - from.1 + to.1;
+    }
+    span.0 = span.0 - from.0 + to.0;
+}
+
+/// The kind of list that units are recorded for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum UnitsKind {
+    Program,
+    Namespace,
+    BracedNamespace,
+    Class,
+}
+
+/// Where each unit of the program starts, recorded while parsing.
+///
+/// A unit is a top-level statement, a statement directly inside of a namespace,
+/// or a member of a class declared in one of those. Each unit runs until the next
+/// one starts, or until the end of the list it's in.
+#[derive(Debug, Default)]
+pub(crate) struct Outline {
+    lists: Vec<Units>,
+    // The lists that are currently being parsed, or `None` for lists that aren't recorded.
+    open: Vec<Option<usize>>,
+}
+
+#[derive(Debug)]
+struct Units {
+    kind: UnitsKind,
+    // The indices of the units leading to this list, starting at the top of the program.
+    path: Vec<usize>,
+    // The state the parser was in when the list started.
+    scopes: Vec<Scope<'static>>,
+    namespace_type: Option<NamespaceType>,
+    depth: usize,
+    starts: Vec<Unit>,
+    // Where the token that ended the list starts, or `None` at the end of the input.
+    end: Option<Span>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Unit {
+    start: Span,
+    inline_html: bool,
+}
+
+impl Outline {
+    pub(crate) fn open(
+        &mut self,
+        kind: UnitsKind,
+        stack: &VecDeque<Scope>,
+        namespace_type: Option<NamespaceType>,
+        depth: usize,
+    ) {
+        let path = match self.open.last() {
+            None if self.lists.is_empty() => Some(vec![]),
+            // Namespaces are parsed as top-level statements, classes are nested in a statement,
+            // so anything deeper than that is declared inside of a function or a block.
+            Some(Some(parent)) => {
+                let parent = &self.lists[*parent];
+                let direct = parent.kind != UnitsKind::Class
+                    && depth == parent.depth + usize::from(kind == UnitsKind::Class);
+
+                match parent.starts.len().checked_sub(1) {
+                    Some(unit) if direct => {
+                        let mut path = parent.path.clone();
+                        path.push(unit);
+
+                        Some(path)
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+
+        let index = path.map(|path| {
+            self.lists.push(Units {
+                kind,
+                path,
+                scopes: stack.iter().cloned().map(scope_into_owned).collect(),
+                namespace_type,
+                depth,
+                starts: vec![],
+                end: None,
+            });
+
+            self.lists.len() - 1
+        });
+
+        self.open.push(index);
+    }
+
+    pub(crate) fn unit(&mut self, token: &Token) {
+        if let Some(Some(index)) = self.open.last() {
+            self.lists[*index].starts.push(Unit {
+                start: token.span,
+                inline_html: matches!(token.kind, TokenKind::InlineHtml(_)),
+            });
+        }
+    }
+
+    pub(crate) fn close(&mut self, token: &Token) {
+        if let Some(Some(index)) = self.open.pop() {
+            self.lists[index].end = match token.kind {
+                TokenKind::Eof => None,
+                _ => Some(token.span),
+            };
+        }
+    }
+
+    // Replace the units in `range` of a list with the ones that were parsed in their place.
+    fn replace(&mut self, index: usize, range: Range<usize>, reparsed: Outline) {
+        let path = self.lists[index].path.clone();
+        let depth = path.len();
+        let mut reparsed = reparsed.lists.into_iter();
+
+        let Some(root) = reparsed.next() else {
+            return;
+        };
+        let added = root.starts.len();
+        self.lists[index].starts.splice(range.clone(), root.starts);
+
+        // Lists inside the replaced units are gone, the ones after them have moved.
+        self.lists.retain(|units| {
+            !(units.path.len() > depth
+                && units.path.starts_with(&path)
+                && range.contains(&units.path[depth]))
+        });
+        for units in self.lists.iter_mut() {
+            if units.path.len() > depth
+                && units.path.starts_with(&path)
+                && units.path[depth] >= range.end
+            {
+                units.path[depth] = units.path[depth] + added - range.len();
+            }
+        }
+
+        self.lists.extend(reparsed.map(|mut units| {
+            units.path[0] += range.start;
+            units.path.splice(0..0, path.iter().copied());
+
+            units
+        }));
+    }
+}
+
+fn scope_into_owned(scope: Scope) -> Scope<'static> {
+    match scope {
+        Scope::Namespace(name) => Scope::Namespace(name.into_owned()),
+        Scope::BracedNamespace(name) => Scope::BracedNamespace(name.into_owned()),
+        Scope::Interface(name) => Scope::Interface(name.into_owned()),
+        Scope::Class(name, modifiers, has_parent) => {
+            Scope::Class(name.into_owned(), modifiers, has_parent)
+        }
+        Scope::Trait(name) => Scope::Trait(name.into_owned()),
+        Scope::Enum(name, backed) => Scope::Enum(name.into_owned(), backed),
+        Scope::AnonymousClass(has_parent) => Scope::AnonymousClass(has_parent),
+        Scope::Function(name) => Scope::Function(name.into_owned()),
+        Scope::Method(name, modifiers) => Scope::Method(name.into_owned(), modifiers),
+        Scope::AnonymousFunction(is_static) => Scope::AnonymousFunction(is_static),
+        Scope::ArrowFunction(is_static) => Scope::ArrowFunction(is_static),
+    }
+}
@@ -8,7 +8,7 @@ use crate::parser::expressions;
 use crate::parser::internal::utils;
 use crate::parser::state::State;
 
-pub fn list_expression(state: &mut State) -> ParseResult<Expression> {
+pub fn list_expression<'a>(state: &mut State<'a>) -> ParseResult<Expression<'a>> {
     utils::skip(state, TokenKind::List)?;
     utils::skip_left_parenthesis(state)?;
 
@@ -28,22 +28,24 @@ pub fn list_expression(state: &mut State) -> ParseResult<Expression> {
         let mut key = None;
 
         if state.current.kind == TokenKind::Ellipsis {
-            return Err(ParseError::IllegalSpreadOperator(state.current.span));
+            return Err(ParseError::IllegalSpreadOperator {
+                span: state.current.span,
+            });
         }
 
         if state.current.kind == TokenKind::Ampersand {
-            return Err(ParseError::CannotAssignReferenceToNonReferencableValue(
-                state.current.span,
-            ));
+            return Err(ParseError::CannotAssignReferenceToNonReferencableValue {
+                span: state.current.span,
+            });
         }
 
         let mut value = expressions::lowest_precedence(state)?;
 
         if state.current.kind == TokenKind::DoubleArrow {
             if !has_atleast_one_key && !items.is_empty() {
-                return Err(ParseError::CannotMixKeyedAndUnkeyedEntries(
-                    state.current.span,
-                ));
+                return Err(ParseError::CannotMixKeyedAndUnkeyedEntries {
+                    span: state.current.span,
+                });
             }
 
             state.next();
@@ -51,32 +53,33 @@ pub fn list_expression(state: &mut State) -> ParseResult<Expression> {
             key = Some(value);
 
             if state.current.kind == TokenKind::Ellipsis {
-                return Err(ParseError::IllegalSpreadOperator(state.current.span));
+                return Err(ParseError::IllegalSpreadOperator {
+                    span: state.current.span,
+                });
             }
 
             if state.current.kind == TokenKind::Ampersand {
-                return Err(ParseError::CannotAssignReferenceToNonReferencableValue(
-                    state.current.span,
-                ));
+                return Err(ParseError::CannotAssignReferenceToNonReferencableValue {
+                    span: state.current.span,
+                });
             }
 
             has_atleast_one_key = true;
             value = expressions::lowest_precedence(state)?;
         } else if has_atleast_one_key {
-            return Err(ParseError::CannotMixKeyedAndUnkeyedEntries(
-                state.current.span,
-            ));
+            return Err(ParseError::CannotMixKeyedAndUnkeyedEntries {
+                span: state.current.span,
+            });
         }
 
         items.push(ListItem { key, value });
 
         state.skip_comments();
-        if state.current.kind == TokenKind::Comma {
-            state.next();
-            state.skip_comments();
-        } else {
+        if !state.accept(TokenKind::Comma) {
             break;
         }
+
+        state.skip_comments();
     }
 
     utils::skip_right_parenthesis(state)?;
@@ -84,7 +87,7 @@ pub fn list_expression(state: &mut State) -> ParseResult<Expression> {
     Ok(Expression::List { items })
 }
 
-pub fn array_expression(state: &mut State) -> ParseResult<Expression> {
+pub fn array_expression<'a>(state: &mut State<'a>) -> ParseResult<Expression<'a>> {
     utils::skip(state, TokenKind::LeftBracket)?;
 
     let mut items = Vec::new();
@@ -124,7 +127,7 @@ pub fn array_expression(state: &mut State) -> ParseResult<Expression> {
     Ok(Expression::Array { items })
 }
 
-pub fn legacy_array_expression(state: &mut State) -> ParseResult<Expression> {
+pub fn legacy_array_expression<'a>(state: &mut State<'a>) -> ParseResult<Expression<'a>> {
     utils::skip(state, TokenKind::Array)?;
     utils::skip_left_parenthesis(state)?;
 
@@ -154,10 +157,10 @@ pub fn legacy_array_expression(state: &mut State) -> ParseResult<Expression> {
             state.next();
 
             if by_ref {
-                return Err(ParseError::UnexpectedToken(
-                    TokenKind::Ampersand.to_string(),
-                    amper_span,
-                ));
+                return Err(ParseError::UnexpectedToken {
+                    token: TokenKind::Ampersand.to_string(),
+                    span: amper_span,
+                });
             }
 
             key = Some(value);
@@ -179,9 +182,7 @@ pub fn legacy_array_expression(state: &mut State) -> ParseResult<Expression> {
             by_ref,
         });
 
-        if state.current.kind == TokenKind::Comma {
-            state.next();
-        } else {
+        if !state.accept(TokenKind::Comma) {
             break;
         }
 
@@ -193,7 +194,7 @@ pub fn legacy_array_expression(state: &mut State) -> ParseResult<Expression> {
     Ok(Expression::Array { items })
 }
 
-fn array_pair(state: &mut State) -> ParseResult<ArrayItem> {
+fn array_pair<'a>(state: &mut State<'a>) -> ParseResult<ArrayItem<'a>> {
     let mut key = None;
     let unpack = if state.current.kind == TokenKind::Ellipsis {
         state.next();
@@ -215,10 +216,10 @@ fn array_pair(state: &mut State) -> ParseResult<ArrayItem> {
         state.next();
 
         if by_ref {
-            return Err(ParseError::UnexpectedToken(
-                TokenKind::Ampersand.to_string(),
-                amper_span,
-            ));
+            return Err(ParseError::UnexpectedToken {
+                token: TokenKind::Ampersand.to_string(),
+                span: amper_span,
+            });
         }
 
         key = Some(value);
@@ -29,9 +29,7 @@ pub fn gather_attributes(state: &mut State) -> ParseResult<bool> {
             end,
         });
 
-        if state.current.kind == TokenKind::Comma {
-            state.next();
-        } else {
+        if !state.accept(TokenKind::Comma) {
             break;
         }
     }
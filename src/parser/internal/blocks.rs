@@ -6,7 +6,7 @@ use crate::parser::error::ParseResult;
 use crate::parser::internal::utils;
 use crate::parser::state::State;
 
-pub fn block_statement(state: &mut State) -> ParseResult<Statement> {
+pub fn block_statement<'a>(state: &mut State<'a>) -> ParseResult<Statement<'a>> {
     utils::skip_left_brace(state)?;
 
     let body = body(state, &TokenKind::RightBrace)?;
@@ -16,7 +16,7 @@ pub fn block_statement(state: &mut State) -> ParseResult<Statement> {
     Ok(Statement::Block { body })
 }
 
-pub fn body(state: &mut State, until: &TokenKind) -> ParseResult<Block> {
+pub fn body<'a>(state: &mut State<'a>, until: &TokenKind<'a>) -> ParseResult<Block<'a>> {
     state.skip_comments();
 
     let mut block = Block::new();
@@ -27,6 +27,7 @@ pub fn body(state: &mut State, until: &TokenKind) -> ParseResult<Block> {
             continue;
         }
 
+        state.check_cancelled()?;
         block.push(parser::statement(state)?);
         state.skip_comments();
     }
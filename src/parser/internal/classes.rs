@@ -8,6 +8,7 @@ use crate::parser::ast::identifiers::SimpleIdentifier;
 use crate::parser::ast::Expression;
 use crate::parser::ast::Statement;
 use crate::parser::error::ParseResult;
+use crate::parser::incremental::UnitsKind;
 use crate::parser::internal::attributes;
 use crate::parser::internal::constants::classish;
 use crate::parser::internal::functions::method;
@@ -21,7 +22,7 @@ use crate::parser::state::Scope;
 use crate::parser::state::State;
 use crate::scoped;
 
-pub fn parse(state: &mut State) -> ParseResult<Statement> {
+pub fn parse<'a>(state: &mut State<'a>) -> ParseResult<Statement<'a>> {
     let modifiers = modifiers::class_group(modifiers::collect(state)?)?;
 
     let start = utils::skip(state, TokenKind::Class)?;
@@ -60,9 +61,10 @@ pub fn parse(state: &mut State) -> ParseResult<Statement> {
     let classname = name.name.to_string();
     let members = scoped!(
         state,
-        Scope::Class(name.clone(), modifiers, extends.is_some()),
+        Scope::Class(name.clone(), modifiers.clone(), extends.is_some()),
         {
             let mut members = Vec::new();
+            state.open_units(UnitsKind::Class);
             while state.current.kind != TokenKind::RightBrace {
                 state.gather_comments();
 
@@ -71,8 +73,10 @@ pub fn parse(state: &mut State) -> ParseResult<Statement> {
                     break;
                 }
 
+                state.unit();
                 members.push(member(state, classname.clone())?);
             }
+            state.close_units();
 
             members
         }
@@ -87,11 +91,12 @@ pub fn parse(state: &mut State) -> ParseResult<Statement> {
         extends,
         implements,
         attributes,
+        modifiers,
         members,
     }))
 }
 
-pub fn parse_anonymous(state: &mut State) -> ParseResult<Expression> {
+pub fn parse_anonymous<'a>(state: &mut State<'a>) -> ParseResult<Expression<'a>> {
     let span = utils::skip(state, TokenKind::New)?;
 
     attributes::gather_attributes(state)?;
@@ -165,7 +170,7 @@ pub fn parse_anonymous(state: &mut State) -> ParseResult<Expression> {
     })
 }
 
-fn member(state: &mut State, class: String) -> ParseResult<ClassMember> {
+pub fn member<'a>(state: &mut State<'a>, class: String) -> ParseResult<ClassMember<'a>> {
     let has_attributes = attributes::gather_attributes(state)?;
 
     if !has_attributes && state.current.kind == TokenKind::Use {
@@ -9,7 +9,7 @@ use crate::parser::internal::identifiers;
 use crate::parser::internal::utils;
 use crate::parser::state::State;
 
-pub fn parse(state: &mut State) -> ParseResult<Constant> {
+pub fn parse<'a>(state: &mut State<'a>) -> ParseResult<Constant<'a>> {
     let start = utils::skip(state, TokenKind::Const)?;
 
     let mut entries = vec![];
@@ -25,9 +25,7 @@ pub fn parse(state: &mut State) -> ParseResult<Constant> {
 
         state.skip_comments();
 
-        if state.current.kind == TokenKind::Comma {
-            state.next();
-        } else {
+        if !state.accept(TokenKind::Comma) {
             break;
         }
     }
@@ -41,10 +39,10 @@ pub fn parse(state: &mut State) -> ParseResult<Constant> {
     })
 }
 
-pub fn classish(
-    state: &mut State,
+pub fn classish<'a>(
+    state: &mut State<'a>,
     modifiers: ConstantModifierGroup,
-) -> ParseResult<ClassishConstant> {
+) -> ParseResult<ClassishConstant<'a>> {
     let attributes = state.get_attributes();
 
     let start = utils::skip(state, TokenKind::Const)?;
@@ -62,9 +60,7 @@ pub fn classish(
 
         state.skip_comments();
 
-        if state.current.kind == TokenKind::Comma {
-            state.next();
-        } else {
+        if !state.accept(TokenKind::Comma) {
             break;
         }
     }
@@ -15,7 +15,7 @@ use crate::parser::internal::blocks;
 use crate::parser::internal::utils;
 use crate::parser::state::State;
 
-pub fn match_expression(state: &mut State) -> ParseResult<Expression> {
+pub fn match_expression<'a>(state: &mut State<'a>) -> ParseResult<Expression<'a>> {
     utils::skip(state, TokenKind::Match)?;
 
     utils::skip_left_parenthesis(state)?;
@@ -26,17 +26,20 @@ pub fn match_expression(state: &mut State) -> ParseResult<Expression> {
     utils::skip_left_brace(state)?;
 
     let mut default = None;
+    let mut default_span = None;
     let mut arms = Vec::new();
     while state.current.kind != TokenKind::RightBrace {
         state.skip_comments();
 
         if state.current.kind == TokenKind::Default {
-            if default.is_some() {
-                return Err(ParseError::MatchExpressionWithMultipleDefaultArms(
-                    state.current.span,
-                ));
+            if let Some(first) = default_span {
+                return Err(ParseError::MatchExpressionWithMultipleDefaultArms {
+                    span: state.current.span,
+                    first,
+                });
             }
 
+            default_span = Some(state.current.span);
             state.next();
 
             // match conditions can have an extra comma at the end, including `default`.
@@ -54,9 +57,7 @@ pub fn match_expression(state: &mut State) -> ParseResult<Expression> {
             while state.current.kind != TokenKind::DoubleArrow {
                 conditions.push(expressions::lowest_precedence(state)?);
 
-                if state.current.kind == TokenKind::Comma {
-                    state.next();
-                } else {
+                if !state.accept(TokenKind::Comma) {
                     break;
                 }
             }
@@ -72,9 +73,7 @@ pub fn match_expression(state: &mut State) -> ParseResult<Expression> {
             arms.push(MatchArm { conditions, body });
         }
 
-        if state.current.kind == TokenKind::Comma {
-            state.next();
-        } else {
+        if !state.accept(TokenKind::Comma) {
             break;
         }
     }
@@ -88,7 +87,7 @@ pub fn match_expression(state: &mut State) -> ParseResult<Expression> {
     })
 }
 
-pub fn switch_statement(state: &mut State) -> ParseResult<Statement> {
+pub fn switch_statement<'a>(state: &mut State<'a>) -> ParseResult<Statement<'a>> {
     utils::skip(state, TokenKind::Switch)?;
 
     utils::skip_left_parenthesis(state)?;
@@ -168,7 +167,7 @@ pub fn switch_statement(state: &mut State) -> ParseResult<Statement> {
     Ok(Statement::Switch { condition, cases })
 }
 
-pub fn if_statement(state: &mut State) -> ParseResult<Statement> {
+pub fn if_statement<'a>(state: &mut State<'a>) -> ParseResult<Statement<'a>> {
     utils::skip(state, TokenKind::If)?;
 
     utils::skip_left_parenthesis(state)?;
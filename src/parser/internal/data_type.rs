@@ -1,6 +1,7 @@
 use crate::expected_token;
 use crate::lexer::token::TokenKind;
 use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::owned::IntoOwned;
 use crate::parser::ast::Type;
 use crate::parser::error::ParseError;
 use crate::parser::error::ParseResult;
@@ -8,7 +9,7 @@ use crate::parser::internal::utils;
 use crate::parser::state::State;
 use crate::peek_token;
 
-pub fn data_type(state: &mut State) -> ParseResult<Type> {
+pub fn data_type<'a>(state: &mut State<'a>) -> ParseResult<Type<'a>> {
     if state.current.kind == TokenKind::Question {
         return nullable(state);
     }
@@ -36,7 +37,7 @@ pub fn data_type(state: &mut State) -> ParseResult<Type> {
     Ok(ty)
 }
 
-pub fn optional_data_type(state: &mut State) -> ParseResult<Option<Type>> {
+pub fn optional_data_type<'a>(state: &mut State<'a>) -> ParseResult<Option<Type<'a>>> {
     if state.current.kind == TokenKind::Question {
         return nullable(state).map(Some);
     }
@@ -69,7 +70,7 @@ pub fn optional_data_type(state: &mut State) -> ParseResult<Option<Type>> {
     }
 }
 
-fn dnf(state: &mut State) -> ParseResult<Type> {
+fn dnf<'a>(state: &mut State<'a>) -> ParseResult<Type<'a>> {
     // (A|B|..)&C.. or (A&B&..)|C..
     state.next();
     let ty = simple_data_type(state)?;
@@ -91,7 +92,7 @@ fn dnf(state: &mut State) -> ParseResult<Type> {
     ], state, ["`|`", "`&`"])
 }
 
-fn optional_simple_data_type(state: &mut State) -> ParseResult<Option<Type>> {
+fn optional_simple_data_type<'a>(state: &mut State<'a>) -> ParseResult<Option<Type<'a>>> {
     match state.current.kind.clone() {
         TokenKind::Array => {
             state.next();
@@ -122,10 +123,10 @@ fn optional_simple_data_type(state: &mut State) -> ParseResult<Option<Type>> {
             state.next();
 
             if !state.has_class_scope {
-                return Err(ParseError::CannotFindTypeInCurrentScope(
-                    "static".to_owned(),
-                    state.current.span,
-                ));
+                return Err(ParseError::CannotFindTypeInCurrentScope {
+                    r#type: "static".to_owned(),
+                    span: state.current.span,
+                });
             }
 
             Ok(Some(Type::StaticReference))
@@ -134,10 +135,10 @@ fn optional_simple_data_type(state: &mut State) -> ParseResult<Option<Type>> {
             state.next();
 
             if !state.has_class_scope {
-                return Err(ParseError::CannotFindTypeInCurrentScope(
-                    "self".to_owned(),
-                    state.current.span,
-                ));
+                return Err(ParseError::CannotFindTypeInCurrentScope {
+                    r#type: "self".to_owned(),
+                    span: state.current.span,
+                });
             }
 
             Ok(Some(Type::SelfReference))
@@ -146,16 +147,17 @@ fn optional_simple_data_type(state: &mut State) -> ParseResult<Option<Type>> {
             state.next();
 
             if !state.has_class_scope {
-                return Err(ParseError::CannotFindTypeInCurrentScope(
-                    "parent".to_owned(),
-                    state.current.span,
-                ));
+                return Err(ParseError::CannotFindTypeInCurrentScope {
+                    r#type: "parent".to_owned(),
+                    span: state.current.span,
+                });
             }
 
             Ok(Some(Type::ParentReference))
         }
         TokenKind::Identifier(id) => {
             let span = state.current.span;
+            let symbol = state.current.symbol;
             state.next();
 
             let name = &id[..];
@@ -175,44 +177,53 @@ fn optional_simple_data_type(state: &mut State) -> ParseResult<Option<Type>> {
                 b"false" => Ok(Some(Type::False)),
                 b"array" => Ok(Some(Type::Array)),
                 b"callable" => Ok(Some(Type::Callable)),
-                _ => Ok(Some(Type::Identifier(SimpleIdentifier { span, name: id }))),
+                _ => Ok(Some(Type::Identifier(SimpleIdentifier {
+                    span,
+                    name: id,
+                    symbol,
+                }))),
             }
         }
         TokenKind::QualifiedIdentifier(name) | TokenKind::FullyQualifiedIdentifier(name) => {
             let span = state.current.span;
+            let symbol = state.current.symbol;
             state.next();
 
-            Ok(Some(Type::Identifier(SimpleIdentifier { span, name })))
+            Ok(Some(Type::Identifier(SimpleIdentifier {
+                span,
+                name,
+                symbol,
+            })))
         }
         _ => Ok(None),
     }
 }
 
-fn simple_data_type(state: &mut State) -> ParseResult<Type> {
+fn simple_data_type<'a>(state: &mut State<'a>) -> ParseResult<Type<'a>> {
     optional_simple_data_type(state)?.ok_or_else(|| expected_token!(["a type"], state))
 }
 
-fn nullable(state: &mut State) -> ParseResult<Type> {
+fn nullable<'a>(state: &mut State<'a>) -> ParseResult<Type<'a>> {
     state.next();
 
     let ty = simple_data_type(state)?;
 
     if ty.standalone() {
-        return Err(ParseError::StandaloneTypeUsedInCombination(
-            ty,
-            state.current.span,
-        ));
+        return Err(ParseError::StandaloneTypeUsedInCombination {
+            r#type: ty.into_owned(),
+            span: state.current.span,
+        });
     }
 
     Ok(Type::Nullable(Box::new(ty)))
 }
 
-fn union(state: &mut State, other: Type, within_dnf: bool) -> ParseResult<Type> {
+fn union<'a>(state: &mut State<'a>, other: Type<'a>, within_dnf: bool) -> ParseResult<Type<'a>> {
     if other.standalone() {
-        return Err(ParseError::StandaloneTypeUsedInCombination(
-            other,
-            state.current.span,
-        ));
+        return Err(ParseError::StandaloneTypeUsedInCombination {
+            r#type: other.into_owned(),
+            span: state.current.span,
+        });
     }
 
     let mut types = vec![other];
@@ -235,9 +246,9 @@ fn union(state: &mut State, other: Type, within_dnf: bool) -> ParseResult<Type>
                 //     v-- get_union_type: within_dnf = true
                 //        v-- error
                 // F&(A|B|(D&S))
-                return Err(ParseError::NestedDisjunctiveNormalFormTypes(
-                    state.current.span,
-                ));
+                return Err(ParseError::NestedDisjunctiveNormalFormTypes {
+                    span: state.current.span,
+                });
             }
 
             state.next();
@@ -251,10 +262,10 @@ fn union(state: &mut State, other: Type, within_dnf: bool) -> ParseResult<Type>
         } else {
             let ty = simple_data_type(state)?;
             if ty.standalone() {
-                return Err(ParseError::StandaloneTypeUsedInCombination(
-                    ty,
-                    state.current.span,
-                ));
+                return Err(ParseError::StandaloneTypeUsedInCombination {
+                    r#type: ty.into_owned(),
+                    span: state.current.span,
+                });
             }
 
             ty
@@ -272,12 +283,16 @@ fn union(state: &mut State, other: Type, within_dnf: bool) -> ParseResult<Type>
     Ok(Type::Union(types))
 }
 
-fn instersection(state: &mut State, other: Type, within_dnf: bool) -> ParseResult<Type> {
+fn instersection<'a>(
+    state: &mut State<'a>,
+    other: Type<'a>,
+    within_dnf: bool,
+) -> ParseResult<Type<'a>> {
     if other.standalone() {
-        return Err(ParseError::StandaloneTypeUsedInCombination(
-            other,
-            state.current.span,
-        ));
+        return Err(ParseError::StandaloneTypeUsedInCombination {
+            r#type: other.into_owned(),
+            span: state.current.span,
+        });
     }
 
     let mut types = vec![other];
@@ -300,9 +315,9 @@ fn instersection(state: &mut State, other: Type, within_dnf: bool) -> ParseResul
                 //     v-- get_intersection_type: within_dnf = true
                 //        v-- error
                 // F|(A&B&(D|S))
-                return Err(ParseError::NestedDisjunctiveNormalFormTypes(
-                    state.current.span,
-                ));
+                return Err(ParseError::NestedDisjunctiveNormalFormTypes {
+                    span: state.current.span,
+                });
             }
 
             state.next();
@@ -316,10 +331,10 @@ fn instersection(state: &mut State, other: Type, within_dnf: bool) -> ParseResul
         } else {
             let ty = simple_data_type(state)?;
             if ty.standalone() {
-                return Err(ParseError::StandaloneTypeUsedInCombination(
-                    ty,
-                    state.current.span,
-                ));
+                return Err(ParseError::StandaloneTypeUsedInCombination {
+                    r#type: ty.into_owned(),
+                    span: state.current.span,
+                });
             }
 
             ty
@@ -20,7 +20,7 @@ use crate::parser::state::Scope;
 use crate::parser::state::State;
 use crate::scoped;
 
-pub fn parse(state: &mut State) -> ParseResult<Statement> {
+pub fn parse<'a>(state: &mut State<'a>) -> ParseResult<Statement<'a>> {
     let start = state.current.span;
 
     utils::skip(state, TokenKind::Enum)?;
@@ -47,9 +47,7 @@ pub fn parse(state: &mut State) -> ParseResult<Statement> {
         while state.current.kind != TokenKind::LeftBrace {
             implements.push(identifiers::full_name(state)?);
 
-            if state.current.kind == TokenKind::Comma {
-                state.next();
-            } else {
+            if !state.accept(TokenKind::Comma) {
                 break;
             }
         }
@@ -102,7 +100,7 @@ pub fn parse(state: &mut State) -> ParseResult<Statement> {
     }
 }
 
-fn unit_member(state: &mut State, enum_name: String) -> ParseResult<UnitEnumMember> {
+fn unit_member<'a>(state: &mut State<'a>, enum_name: String) -> ParseResult<UnitEnumMember<'a>> {
     attributes::gather_attributes(state)?;
 
     if state.current.kind == TokenKind::Case {
@@ -114,11 +112,11 @@ fn unit_member(state: &mut State, enum_name: String) -> ParseResult<UnitEnumMemb
         let name = identifiers::ident(state)?;
 
         if state.current.kind == TokenKind::Equals {
-            return Err(ParseError::CaseValueForUnitEnum(
-                name.to_string(),
-                state.named(&enum_name),
-                state.current.span,
-            ));
+            return Err(ParseError::CaseValueForUnitEnum {
+                case: name.to_string(),
+                r#enum: state.named(&enum_name),
+                span: state.current.span,
+            });
         }
 
         let end = utils::skip_semicolon(state)?;
@@ -141,7 +139,10 @@ fn unit_member(state: &mut State, enum_name: String) -> ParseResult<UnitEnumMemb
     functions::method(state, modifiers::enum_method_group(modifiers)?).map(UnitEnumMember::Method)
 }
 
-fn backed_member(state: &mut State, enum_name: String) -> ParseResult<BackedEnumMember> {
+fn backed_member<'a>(
+    state: &mut State<'a>,
+    enum_name: String,
+) -> ParseResult<BackedEnumMember<'a>> {
     attributes::gather_attributes(state)?;
 
     if state.current.kind == TokenKind::Case {
@@ -153,11 +154,11 @@ fn backed_member(state: &mut State, enum_name: String) -> ParseResult<BackedEnum
         let name = identifiers::ident(state)?;
 
         if state.current.kind == TokenKind::SemiColon {
-            return Err(ParseError::MissingCaseValueForBackedEnum(
-                name.to_string(),
-                state.named(&enum_name),
-                state.current.span,
-            ));
+            return Err(ParseError::MissingCaseValueForBackedEnum {
+                case: name.to_string(),
+                r#enum: state.named(&enum_name),
+                span: state.current.span,
+            });
         }
 
         utils::skip(state, TokenKind::Equals)?;
@@ -6,6 +6,7 @@ use crate::parser::ast::functions::ClosureUse;
 use crate::parser::ast::functions::Function;
 use crate::parser::ast::functions::Method;
 use crate::parser::ast::modifiers::MethodModifierGroup;
+use crate::parser::ast::variables::Variable;
 use crate::parser::ast::Expression;
 use crate::parser::ast::Statement;
 use crate::parser::error::ParseError;
@@ -16,11 +17,12 @@ use crate::parser::internal::data_type;
 use crate::parser::internal::identifiers;
 use crate::parser::internal::parameters;
 use crate::parser::internal::utils;
+use crate::parser::internal::variables;
 use crate::parser::state::Scope;
 use crate::parser::state::State;
 use crate::scoped;
 
-pub fn anonymous_function(state: &mut State) -> ParseResult<Expression> {
+pub fn anonymous_function<'a>(state: &mut State<'a>) -> ParseResult<Expression<'a>> {
     let start = state.current.span;
 
     let is_static = if state.current.kind == TokenKind::Static {
@@ -57,23 +59,16 @@ pub fn anonymous_function(state: &mut State) -> ParseResult<Expression> {
                 by_ref = true;
             }
 
-            // TODO(azjezz): this shouldn't call expr, we should have a function
-            // just for variables, so we don't have to go through the whole `match` in `expression(...)`
-            let var = match expressions::lowest_precedence(state)? {
-                s @ Expression::Variable { .. } => ClosureUse { var: s, by_ref },
-                _ => {
-                    return Err(ParseError::UnexpectedToken(
-                        "expected variable".into(),
-                        state.current.span,
-                    ))
-                }
+            let var = ClosureUse {
+                var: Expression::Variable(Variable::SimpleVariable(variables::simple_variable(
+                    state,
+                )?)),
+                by_ref,
             };
 
             uses.push(var);
 
-            if state.current.kind == TokenKind::Comma {
-                state.next();
-            } else {
+            if !state.accept(TokenKind::Comma) {
                 break;
             }
         }
@@ -110,7 +105,7 @@ pub fn anonymous_function(state: &mut State) -> ParseResult<Expression> {
     }))
 }
 
-pub fn arrow_function(state: &mut State) -> ParseResult<Expression> {
+pub fn arrow_function<'a>(state: &mut State<'a>) -> ParseResult<Expression<'a>> {
     let start = state.current.span;
 
     let is_static = if state.current.kind == TokenKind::Static {
@@ -160,7 +155,7 @@ pub fn arrow_function(state: &mut State) -> ParseResult<Expression> {
     }))
 }
 
-pub fn function(state: &mut State) -> ParseResult<Statement> {
+pub fn function<'a>(state: &mut State<'a>) -> ParseResult<Statement<'a>> {
     let start = state.current.span;
 
     utils::skip(state, TokenKind::Function)?;
@@ -209,7 +204,10 @@ pub fn function(state: &mut State) -> ParseResult<Statement> {
     }))
 }
 
-pub fn method(state: &mut State, modifiers: MethodModifierGroup) -> ParseResult<Method> {
+pub fn method<'a>(
+    state: &mut State<'a>,
+    modifiers: MethodModifierGroup,
+) -> ParseResult<Method<'a>> {
     let start = utils::skip(state, TokenKind::Function)?;
 
     let by_ref = if state.current.kind == TokenKind::Ampersand {
@@ -224,9 +222,7 @@ pub fn method(state: &mut State, modifiers: MethodModifierGroup) -> ParseResult<
     let has_body = expected_scope!([
             Scope::Class(_, class_modifiers, _) => {
                 if !class_modifiers.has_abstract() && modifiers.has_abstract() {
-                    return Err(ParseError::AbstractModifierOnNonAbstractClassMethod(
-                        state.current.span,
-                    ));
+                    return Err(ParseError::AbstractModifierOnNonAbstractClassMethod { span: state.current.span });
                 }
 
                 !modifiers.has_abstract()
@@ -235,10 +231,7 @@ pub fn method(state: &mut State, modifiers: MethodModifierGroup) -> ParseResult<
             Scope::Interface(_) => false,
             Scope::Enum(enum_name, _) => {
                 if name.to_string() == "__construct" {
-                    return Err(ParseError::ConstructorInEnum(
-                        state.named(&enum_name),
-                        state.current.span,
-                    ));
+                    return Err(ParseError::ConstructorInEnum { r#enum: state.named(&enum_name), span: state.current.span });
                 }
 
                 true
@@ -5,7 +5,7 @@ use crate::parser::internal::identifiers;
 use crate::parser::internal::utils;
 use crate::parser::state::State;
 
-pub fn label_statement(state: &mut State) -> ParseResult<Statement> {
+pub fn label_statement<'a>(state: &mut State<'a>) -> ParseResult<Statement<'a>> {
     let label = identifiers::ident(state)?;
 
     utils::skip_colon(state)?;
@@ -13,7 +13,7 @@ pub fn label_statement(state: &mut State) -> ParseResult<Statement> {
     Ok(Statement::Label { label })
 }
 
-pub fn goto_statement(state: &mut State) -> ParseResult<Statement> {
+pub fn goto_statement<'a>(state: &mut State<'a>) -> ParseResult<Statement<'a>> {
     utils::skip(state, TokenKind::Goto)?;
 
     let label = identifiers::ident(state)?;
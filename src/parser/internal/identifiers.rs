@@ -6,7 +6,7 @@ use crate::parser::state::State;
 
 use crate::peek_token;
 
-pub fn ident_of(state: &mut State, kinds: &[&str]) -> ParseResult<SimpleIdentifier> {
+pub fn ident_of<'a>(state: &mut State<'a>, kinds: &[&str]) -> ParseResult<SimpleIdentifier<'a>> {
     let ident = ident(state)?;
 
     let name = ident.name.to_string();
@@ -14,33 +14,34 @@ pub fn ident_of(state: &mut State, kinds: &[&str]) -> ParseResult<SimpleIdentifi
     if kinds.contains(&name.as_str()) {
         Ok(ident)
     } else {
-        Err(ParseError::ExpectedIdentifier(
-            kinds.iter().map(|s| s.to_string()).collect(),
-            name,
-            state.current.span,
-        ))
+        Err(ParseError::ExpectedIdentifier {
+            expected: kinds.iter().map(|s| s.to_string()).collect(),
+            found: name,
+            span: state.current.span,
+        })
     }
 }
 
 /// Expect an unqualified identifier such as Foo or Bar.
-pub fn ident(state: &mut State) -> ParseResult<SimpleIdentifier> {
+pub fn ident<'a>(state: &mut State<'a>) -> ParseResult<SimpleIdentifier<'a>> {
     if let TokenKind::Identifier(name) = state.current.kind.clone() {
         let span = state.current.span;
+        let symbol = state.current.symbol;
 
         state.next();
 
-        Ok(SimpleIdentifier { span, name })
+        Ok(SimpleIdentifier { span, name, symbol })
     } else {
-        Err(ParseError::ExpectedToken(
-            vec!["an identifier".to_owned()],
-            Some(state.current.kind.to_string()),
-            state.current.span,
-        ))
+        Err(ParseError::ExpectedToken {
+            expected: vec!["an identifier".to_owned()],
+            found: Some(state.current.kind.to_string()),
+            span: state.current.span,
+        })
     }
 }
 
 /// Expect an unqualified or qualified identifier such as Foo, Bar or Foo\Bar.
-pub fn name(state: &mut State) -> ParseResult<SimpleIdentifier> {
+pub fn name<'a>(state: &mut State<'a>) -> ParseResult<SimpleIdentifier<'a>> {
     let name = peek_token!([
         TokenKind::Identifier(name) | TokenKind::QualifiedIdentifier(name) => {
             name.clone()
@@ -48,18 +49,20 @@ pub fn name(state: &mut State) -> ParseResult<SimpleIdentifier> {
     ], state, "an identifier");
 
     let span = state.current.span;
+    let symbol = state.current.symbol;
     state.next();
 
-    Ok(SimpleIdentifier { span, name })
+    Ok(SimpleIdentifier { span, name, symbol })
 }
 
 /// Expect an optional unqualified or qualified identifier such as Foo, Bar or Foo\Bar.
-pub fn optional_name(state: &mut State) -> Option<SimpleIdentifier> {
+pub fn optional_name<'a>(state: &mut State<'a>) -> Option<SimpleIdentifier<'a>> {
     let ident = match &state.current.kind {
         TokenKind::Identifier(name) | TokenKind::QualifiedIdentifier(name) => {
             Some(SimpleIdentifier {
                 span: state.current.span,
                 name: name.clone(),
+                symbol: state.current.symbol,
             })
         }
         _ => None,
@@ -73,7 +76,7 @@ pub fn optional_name(state: &mut State) -> Option<SimpleIdentifier> {
 }
 
 /// Expect an unqualified, qualified or fully qualified identifier such as Foo, Foo\Bar or \Foo\Bar.
-pub fn full_name(state: &mut State) -> ParseResult<SimpleIdentifier> {
+pub fn full_name<'a>(state: &mut State<'a>) -> ParseResult<SimpleIdentifier<'a>> {
     let name = peek_token!([
             TokenKind::Identifier(name) | TokenKind::QualifiedIdentifier(name) | TokenKind::FullyQualifiedIdentifier(name) => {
                 name.clone()
@@ -81,39 +84,42 @@ pub fn full_name(state: &mut State) -> ParseResult<SimpleIdentifier> {
         ], state, "an identifier");
 
     let span = state.current.span;
+    let symbol = state.current.symbol;
     state.next();
 
-    Ok(SimpleIdentifier { span, name })
+    Ok(SimpleIdentifier { span, name, symbol })
 }
 
-pub fn ident_maybe_reserved(state: &mut State) -> ParseResult<SimpleIdentifier> {
+pub fn ident_maybe_reserved<'a>(state: &mut State<'a>) -> ParseResult<SimpleIdentifier<'a>> {
     match state.current.kind {
         _ if is_reserved_ident(&state.current.kind) => {
             let name = state.current.kind.to_string().into();
 
             let span = state.current.span;
+            let symbol = state.current.symbol;
             state.next();
 
-            Ok(SimpleIdentifier { span, name })
+            Ok(SimpleIdentifier { span, name, symbol })
         }
         _ => ident(state),
     }
 }
 
-pub fn ident_maybe_soft_reserved(state: &mut State) -> ParseResult<SimpleIdentifier> {
+pub fn ident_maybe_soft_reserved<'a>(state: &mut State<'a>) -> ParseResult<SimpleIdentifier<'a>> {
     match state.current.kind {
         _ if is_soft_reserved_ident(&state.current.kind) => {
             let name = state.current.kind.to_string().into();
             let span = state.current.span;
+            let symbol = state.current.symbol;
             state.next();
 
-            Ok(SimpleIdentifier { span, name })
+            Ok(SimpleIdentifier { span, name, symbol })
         }
         _ => ident(state),
     }
 }
 
-pub fn is_ident_maybe_soft_reserved(kind: &TokenKind) -> bool {
+pub fn is_ident_maybe_soft_reserved<'a>(kind: &TokenKind<'a>) -> bool {
     if let TokenKind::Identifier(_) = kind {
         return true;
     }
@@ -121,7 +127,7 @@ pub fn is_ident_maybe_soft_reserved(kind: &TokenKind) -> bool {
     is_soft_reserved_ident(kind)
 }
 
-pub fn is_ident_maybe_reserved(kind: &TokenKind) -> bool {
+pub fn is_ident_maybe_reserved<'a>(kind: &TokenKind<'a>) -> bool {
     if let TokenKind::Identifier(_) = kind {
         return true;
     }
@@ -129,7 +135,7 @@ pub fn is_ident_maybe_reserved(kind: &TokenKind) -> bool {
     is_reserved_ident(kind)
 }
 
-pub fn is_soft_reserved_ident(kind: &TokenKind) -> bool {
+pub fn is_soft_reserved_ident<'a>(kind: &TokenKind<'a>) -> bool {
     matches!(kind, |TokenKind::Parent| TokenKind::Self_
         | TokenKind::True
         | TokenKind::False
@@ -139,7 +145,7 @@ pub fn is_soft_reserved_ident(kind: &TokenKind) -> bool {
         | TokenKind::Readonly)
 }
 
-pub fn is_reserved_ident(kind: &TokenKind) -> bool {
+pub fn is_reserved_ident<'a>(kind: &TokenKind<'a>) -> bool {
     if is_soft_reserved_ident(kind) {
         return true;
     }
@@ -21,7 +21,7 @@ use crate::parser::state::Scope;
 use crate::parser::state::State;
 use crate::scoped;
 
-pub fn parse(state: &mut State) -> ParseResult<Statement> {
+pub fn parse<'a>(state: &mut State<'a>) -> ParseResult<Statement<'a>> {
     let start = utils::skip(state, TokenKind::Interface)?;
 
     let name = identifiers::ident(state)?;
@@ -64,7 +64,7 @@ pub fn parse(state: &mut State) -> ParseResult<Statement> {
     }))
 }
 
-fn member(state: &mut State) -> ParseResult<InterfaceMember> {
+fn member<'a>(state: &mut State<'a>) -> ParseResult<InterfaceMember<'a>> {
     attributes::gather_attributes(state)?;
 
     let modifiers = modifiers::collect(state)?;
@@ -77,7 +77,9 @@ fn member(state: &mut State) -> ParseResult<InterfaceMember> {
 }
 
 #[inline(always)]
-fn method_modifiers(input: Vec<(Span, TokenKind, Span)>) -> ParseResult<MethodModifierGroup> {
+fn method_modifiers<'a>(
+    input: Vec<(Span, TokenKind<'a>, Span)>,
+) -> ParseResult<MethodModifierGroup> {
     let modifiers = input
         .iter()
         .map(|(start, token, end)| match token {
@@ -89,10 +91,10 @@ fn method_modifiers(input: Vec<(Span, TokenKind, Span)>) -> ParseResult<MethodMo
                 start: *start,
                 end: *end,
             }),
-            _ => Err(ParseError::CannotUseModifierOnInterfaceMethod(
-                token.to_string(),
-                *start,
-            )),
+            _ => Err(ParseError::CannotUseModifierOnInterfaceMethod {
+                modifier: token.to_string(),
+                span: *start,
+            }),
         })
         .collect::<ParseResult<Vec<MethodModifier>>>()?;
 
@@ -100,7 +102,9 @@ fn method_modifiers(input: Vec<(Span, TokenKind, Span)>) -> ParseResult<MethodMo
 }
 
 #[inline(always)]
-fn constant_modifiers(input: Vec<(Span, TokenKind, Span)>) -> ParseResult<ConstantModifierGroup> {
+fn constant_modifiers<'a>(
+    input: Vec<(Span, TokenKind<'a>, Span)>,
+) -> ParseResult<ConstantModifierGroup> {
     let modifiers = input
         .iter()
         .map(|(start, token, end)| match token {
@@ -112,10 +116,10 @@ fn constant_modifiers(input: Vec<(Span, TokenKind, Span)>) -> ParseResult<Consta
                 start: *start,
                 end: *end,
             }),
-            _ => Err(ParseError::CannotUseModifierOnInterfaceConstant(
-                token.to_string(),
-                *start,
-            )),
+            _ => Err(ParseError::CannotUseModifierOnInterfaceConstant {
+                modifier: token.to_string(),
+                span: *start,
+            }),
         })
         .collect::<ParseResult<Vec<ConstantModifier>>>()?;
 
@@ -7,7 +7,7 @@ use crate::parser::internal::blocks;
 use crate::parser::internal::utils;
 use crate::parser::state::State;
 
-pub fn foreach_loop(state: &mut State) -> ParseResult<Statement> {
+pub fn foreach_loop<'a>(state: &mut State<'a>) -> ParseResult<Statement<'a>> {
     utils::skip(state, TokenKind::Foreach)?;
 
     utils::skip_left_parenthesis(state)?;
@@ -63,7 +63,7 @@ pub fn foreach_loop(state: &mut State) -> ParseResult<Statement> {
     })
 }
 
-pub fn for_loop(state: &mut State) -> ParseResult<Statement> {
+pub fn for_loop<'a>(state: &mut State<'a>) -> ParseResult<Statement<'a>> {
     utils::skip(state, TokenKind::For)?;
 
     utils::skip_left_parenthesis(state)?;
@@ -76,9 +76,7 @@ pub fn for_loop(state: &mut State) -> ParseResult<Statement> {
 
         init.push(expressions::lowest_precedence(state)?);
 
-        if state.current.kind == TokenKind::Comma {
-            state.next();
-        } else {
+        if !state.accept(TokenKind::Comma) {
             break;
         }
     }
@@ -93,9 +91,7 @@ pub fn for_loop(state: &mut State) -> ParseResult<Statement> {
 
         condition.push(expressions::lowest_precedence(state)?);
 
-        if state.current.kind == TokenKind::Comma {
-            state.next();
-        } else {
+        if !state.accept(TokenKind::Comma) {
             break;
         }
     }
@@ -109,9 +105,7 @@ pub fn for_loop(state: &mut State) -> ParseResult<Statement> {
 
         r#loop.push(expressions::lowest_precedence(state)?);
 
-        if state.current.kind == TokenKind::Comma {
-            state.next();
-        } else {
+        if !state.accept(TokenKind::Comma) {
             break;
         }
     }
@@ -141,7 +135,7 @@ pub fn for_loop(state: &mut State) -> ParseResult<Statement> {
     })
 }
 
-pub fn do_loop(state: &mut State) -> ParseResult<Statement> {
+pub fn do_loop<'a>(state: &mut State<'a>) -> ParseResult<Statement<'a>> {
     utils::skip(state, TokenKind::Do)?;
 
     let body = if state.current.kind == TokenKind::LeftBrace {
@@ -163,7 +157,7 @@ pub fn do_loop(state: &mut State) -> ParseResult<Statement> {
     Ok(Statement::DoWhile { condition, body })
 }
 
-pub fn while_loop(state: &mut State) -> ParseResult<Statement> {
+pub fn while_loop<'a>(state: &mut State<'a>) -> ParseResult<Statement<'a>> {
     utils::skip(state, TokenKind::While)?;
 
     utils::skip_left_parenthesis(state)?;
@@ -193,7 +187,7 @@ pub fn while_loop(state: &mut State) -> ParseResult<Statement> {
     Ok(Statement::While { condition, body })
 }
 
-pub fn continue_statement(state: &mut State) -> ParseResult<Statement> {
+pub fn continue_statement<'a>(state: &mut State<'a>) -> ParseResult<Statement<'a>> {
     utils::skip(state, TokenKind::Continue)?;
 
     let mut num = None;
@@ -206,7 +200,7 @@ pub fn continue_statement(state: &mut State) -> ParseResult<Statement> {
     Ok(Statement::Continue { num })
 }
 
-pub fn break_statement(state: &mut State) -> ParseResult<Statement> {
+pub fn break_statement<'a>(state: &mut State<'a>) -> ParseResult<Statement<'a>> {
     utils::skip(state, TokenKind::Break)?;
 
     let mut num = None;
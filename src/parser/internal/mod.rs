@@ -8,7 +8,7 @@ pub(in crate::parser) mod data_type;
 pub(in crate::parser) mod enums;
 pub(in crate::parser) mod functions;
 pub(in crate::parser) mod goto;
-pub(in crate::parser) mod identifiers;
+pub(crate) mod identifiers;
 pub(in crate::parser) mod interfaces;
 pub(in crate::parser) mod loops;
 pub(in crate::parser) mod modifiers;
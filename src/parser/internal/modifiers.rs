@@ -15,7 +15,7 @@ use crate::parser::error::ParseResult;
 use crate::parser::state::State;
 
 #[inline(always)]
-pub fn class_group(input: Vec<(Span, TokenKind, Span)>) -> ParseResult<ClassModifierGroup> {
+pub fn class_group<'a>(input: Vec<(Span, TokenKind<'a>, Span)>) -> ParseResult<ClassModifierGroup> {
     let mut has_final = false;
     let mut has_abstract = false;
 
@@ -29,7 +29,7 @@ pub fn class_group(input: Vec<(Span, TokenKind, Span)>) -> ParseResult<ClassModi
             TokenKind::Final => {
                 has_final = true;
                 if has_abstract {
-                    Err(ParseError::FinalModifierOnAbstractClassMember(*start))
+                    Err(ParseError::FinalModifierOnAbstractClassMember { span: *start })
                 } else {
                     Ok(ClassModifier::Final {
                         start: *start,
@@ -40,7 +40,7 @@ pub fn class_group(input: Vec<(Span, TokenKind, Span)>) -> ParseResult<ClassModi
             TokenKind::Abstract => {
                 has_abstract = true;
                 if has_final {
-                    Err(ParseError::FinalModifierOnAbstractClassMember(*start))
+                    Err(ParseError::FinalModifierOnAbstractClassMember { span: *start })
                 } else {
                     Ok(ClassModifier::Abstract {
                         start: *start,
@@ -48,10 +48,10 @@ pub fn class_group(input: Vec<(Span, TokenKind, Span)>) -> ParseResult<ClassModi
                     })
                 }
             }
-            _ => Err(ParseError::CannotUseModifierOnClass(
-                token.to_string(),
-                *start,
-            )),
+            _ => Err(ParseError::CannotUseModifierOnClass {
+                modifier: token.to_string(),
+                span: *start,
+            }),
         })
         .collect::<ParseResult<Vec<ClassModifier>>>()?;
 
@@ -59,7 +59,9 @@ pub fn class_group(input: Vec<(Span, TokenKind, Span)>) -> ParseResult<ClassModi
 }
 
 #[inline(always)]
-pub fn method_group(input: Vec<(Span, TokenKind, Span)>) -> ParseResult<MethodModifierGroup> {
+pub fn method_group<'a>(
+    input: Vec<(Span, TokenKind<'a>, Span)>,
+) -> ParseResult<MethodModifierGroup> {
     let mut has_final = false;
     let mut has_abstract = false;
 
@@ -69,7 +71,7 @@ pub fn method_group(input: Vec<(Span, TokenKind, Span)>) -> ParseResult<MethodMo
             TokenKind::Final => {
                 has_final = true;
                 if has_abstract {
-                    Err(ParseError::FinalModifierOnAbstractClassMember(*start))
+                    Err(ParseError::FinalModifierOnAbstractClassMember { span: *start })
                 } else {
                     Ok(MethodModifier::Final {
                         start: *start,
@@ -80,7 +82,7 @@ pub fn method_group(input: Vec<(Span, TokenKind, Span)>) -> ParseResult<MethodMo
             TokenKind::Abstract => {
                 has_abstract = true;
                 if has_final {
-                    Err(ParseError::FinalModifierOnAbstractClassMember(*start))
+                    Err(ParseError::FinalModifierOnAbstractClassMember { span: *start })
                 } else {
                     Ok(MethodModifier::Abstract {
                         start: *start,
@@ -104,17 +106,19 @@ pub fn method_group(input: Vec<(Span, TokenKind, Span)>) -> ParseResult<MethodMo
                 start: *start,
                 end: *end,
             }),
-            _ => Err(ParseError::CannotUseModifierOnClassMethod(
-                token.to_string(),
-                *start,
-            )),
+            _ => Err(ParseError::CannotUseModifierOnClassMethod {
+                modifier: token.to_string(),
+                span: *start,
+            }),
         })
         .collect::<ParseResult<Vec<MethodModifier>>>()?;
 
     Ok(MethodModifierGroup { modifiers })
 }
 
-pub fn enum_method_group(input: Vec<(Span, TokenKind, Span)>) -> ParseResult<MethodModifierGroup> {
+pub fn enum_method_group<'a>(
+    input: Vec<(Span, TokenKind<'a>, Span)>,
+) -> ParseResult<MethodModifierGroup> {
     let modifiers = input
         .iter()
         .map(|(start, token, end)| match token {
@@ -138,10 +142,10 @@ pub fn enum_method_group(input: Vec<(Span, TokenKind, Span)>) -> ParseResult<Met
                 start: *start,
                 end: *end,
             }),
-            _ => Err(ParseError::CannotUseModifierOnEnumMethod(
-                token.to_string(),
-                *start,
-            )),
+            _ => Err(ParseError::CannotUseModifierOnEnumMethod {
+                modifier: token.to_string(),
+                span: *start,
+            }),
         })
         .collect::<ParseResult<Vec<MethodModifier>>>()?;
 
@@ -149,7 +153,9 @@ pub fn enum_method_group(input: Vec<(Span, TokenKind, Span)>) -> ParseResult<Met
 }
 
 #[inline(always)]
-pub fn property_group(input: Vec<(Span, TokenKind, Span)>) -> ParseResult<PropertyModifierGroup> {
+pub fn property_group<'a>(
+    input: Vec<(Span, TokenKind<'a>, Span)>,
+) -> ParseResult<PropertyModifierGroup> {
     let modifiers = input
         .iter()
         .map(|(start, token, end)| match token {
@@ -173,10 +179,10 @@ pub fn property_group(input: Vec<(Span, TokenKind, Span)>) -> ParseResult<Proper
                 start: *start,
                 end: *end,
             }),
-            _ => Err(ParseError::CannotUseModifierOnProperty(
-                token.to_string(),
-                *start,
-            )),
+            _ => Err(ParseError::CannotUseModifierOnProperty {
+                modifier: token.to_string(),
+                span: *start,
+            }),
         })
         .collect::<ParseResult<Vec<PropertyModifier>>>()?;
 
@@ -184,8 +190,8 @@ pub fn property_group(input: Vec<(Span, TokenKind, Span)>) -> ParseResult<Proper
 }
 
 #[inline(always)]
-pub fn promoted_property_group(
-    input: Vec<(Span, TokenKind, Span)>,
+pub fn promoted_property_group<'a>(
+    input: Vec<(Span, TokenKind<'a>, Span)>,
 ) -> ParseResult<PromotedPropertyModifierGroup> {
     let modifiers = input
         .iter()
@@ -206,17 +212,19 @@ pub fn promoted_property_group(
                 start: *start,
                 end: *end,
             }),
-            _ => Err(ParseError::CannotUseModifierOnPromotedProperty(
-                token.to_string(),
-                *start,
-            )),
+            _ => Err(ParseError::CannotUseModifierOnPromotedProperty {
+                modifier: token.to_string(),
+                span: *start,
+            }),
         })
         .collect::<ParseResult<Vec<PromotedPropertyModifier>>>()?;
 
     Ok(PromotedPropertyModifierGroup { modifiers })
 }
 
-pub fn constant_group(input: Vec<(Span, TokenKind, Span)>) -> ParseResult<ConstantModifierGroup> {
+pub fn constant_group<'a>(
+    input: Vec<(Span, TokenKind<'a>, Span)>,
+) -> ParseResult<ConstantModifierGroup> {
     let mut has_final = false;
     let mut has_private = false;
 
@@ -234,7 +242,7 @@ pub fn constant_group(input: Vec<(Span, TokenKind, Span)>) -> ParseResult<Consta
             TokenKind::Private => {
                 has_private = true;
                 if has_final {
-                    Err(ParseError::FinalModifierOnPrivateConstant(*start))
+                    Err(ParseError::FinalModifierOnPrivateConstant { span: *start })
                 } else {
                     Ok(ConstantModifier::Private {
                         start: *start,
@@ -245,7 +253,7 @@ pub fn constant_group(input: Vec<(Span, TokenKind, Span)>) -> ParseResult<Consta
             TokenKind::Final => {
                 has_final = true;
                 if has_private {
-                    Err(ParseError::FinalModifierOnPrivateConstant(*start))
+                    Err(ParseError::FinalModifierOnPrivateConstant { span: *start })
                 } else {
                     Ok(ConstantModifier::Final {
                         start: *start,
@@ -253,19 +261,18 @@ pub fn constant_group(input: Vec<(Span, TokenKind, Span)>) -> ParseResult<Consta
                     })
                 }
             }
-            _ => Err(ParseError::CannotUseModifierOnConstant(
-                token.to_string(),
-                *start,
-            )),
+            _ => Err(ParseError::CannotUseModifierOnConstant {
+                modifier: token.to_string(),
+                span: *start,
+            }),
         })
         .collect::<ParseResult<Vec<ConstantModifier>>>()?;
 
     Ok(ConstantModifierGroup { modifiers })
 }
 
-pub fn collect(state: &mut State) -> ParseResult<Vec<(Span, TokenKind, Span)>> {
+pub fn collect<'a>(state: &mut State<'a>) -> ParseResult<Vec<(Span, TokenKind<'a>, Span)>> {
     let mut collected: Vec<(Span, TokenKind, Span)> = vec![];
-    let mut collected_tokens: Vec<TokenKind> = vec![];
 
     while let TokenKind::Private
     | TokenKind::Protected
@@ -275,43 +282,40 @@ pub fn collect(state: &mut State) -> ParseResult<Vec<(Span, TokenKind, Span)>> {
     | TokenKind::Static
     | TokenKind::Readonly = state.current.kind.clone()
     {
-        if collected_tokens.contains(&state.current.kind) {
-            return Err(ParseError::MultipleModifiers(
-                state.current.kind.to_string(),
-                state.current.span,
-            ));
+        if let Some((first, ..)) = collected
+            .iter()
+            .find(|(_, kind, _)| kind == &state.current.kind)
+        {
+            return Err(ParseError::MultipleModifiers {
+                modifier: state.current.kind.to_string(),
+                span: state.current.span,
+                first: *first,
+            });
         }
 
         // garud against multiple visibility modifiers, we don't care where these modifiers are used.
-        match state.current.kind {
-            TokenKind::Private
-                if collected_tokens.contains(&TokenKind::Protected)
-                    || collected_tokens.contains(&TokenKind::Public) =>
-            {
-                return Err(ParseError::MultipleVisibilityModifiers(state.current.span));
+        if is_visibility(&state.current.kind) {
+            if let Some((first, ..)) = collected.iter().find(|(_, kind, _)| is_visibility(kind)) {
+                return Err(ParseError::MultipleVisibilityModifiers {
+                    span: state.current.span,
+                    first: *first,
+                });
             }
-            TokenKind::Protected
-                if collected_tokens.contains(&TokenKind::Private)
-                    || collected_tokens.contains(&TokenKind::Public) =>
-            {
-                return Err(ParseError::MultipleVisibilityModifiers(state.current.span));
-            }
-            TokenKind::Public
-                if collected_tokens.contains(&TokenKind::Private)
-                    || collected_tokens.contains(&TokenKind::Protected) =>
-            {
-                return Err(ParseError::MultipleVisibilityModifiers(state.current.span));
-            }
-            _ => {}
-        };
+        }
 
         let start = state.current.span;
         let end = state.peek.span;
         collected.push((start, state.current.kind.clone(), end));
-        collected_tokens.push(state.current.kind.clone());
 
         state.next();
     }
 
     Ok(collected)
 }
+
+fn is_visibility<'a>(kind: &TokenKind<'a>) -> bool {
+    matches!(
+        kind,
+        TokenKind::Private | TokenKind::Protected | TokenKind::Public
+    )
+}
@@ -5,6 +5,7 @@ use crate::parser::ast::Block;
 use crate::parser::ast::Statement;
 use crate::parser::error::ParseError;
 use crate::parser::error::ParseResult;
+use crate::parser::incremental::UnitsKind;
 use crate::parser::internal::identifiers;
 use crate::parser::internal::utils;
 use crate::parser::state::NamespaceType;
@@ -12,7 +13,7 @@ use crate::parser::state::Scope;
 use crate::parser::state::State;
 use crate::scoped;
 
-pub fn namespace(state: &mut State) -> ParseResult<Statement> {
+pub fn namespace<'a>(state: &mut State<'a>) -> ParseResult<Statement<'a>> {
     state.next();
 
     let name = identifiers::optional_name(state);
@@ -20,9 +21,9 @@ pub fn namespace(state: &mut State) -> ParseResult<Statement> {
     if let Some(name) = &name {
         if state.current.kind != TokenKind::LeftBrace {
             if let Some(NamespaceType::Braced) = state.namespace_type() {
-                return Err(ParseError::MixingBracedAndUnBracedNamespaceDeclarations(
-                    state.current.span,
-                ));
+                return Err(ParseError::MixingBracedAndUnBracedNamespaceDeclarations {
+                    span: state.current.span,
+                });
             }
 
             return unbraced_namespace(state, name.clone());
@@ -30,25 +31,35 @@ pub fn namespace(state: &mut State) -> ParseResult<Statement> {
     }
 
     match state.namespace_type() {
-        Some(NamespaceType::Unbraced) => Err(
-            ParseError::MixingBracedAndUnBracedNamespaceDeclarations(state.current.span),
-        ),
+        Some(NamespaceType::Unbraced) => {
+            Err(ParseError::MixingBracedAndUnBracedNamespaceDeclarations {
+                span: state.current.span,
+            })
+        }
         Some(NamespaceType::Braced) if state.namespace().is_some() => {
-            Err(ParseError::NestedNamespaceDeclarations(state.current.span))
+            Err(ParseError::NestedNamespaceDeclarations {
+                span: state.current.span,
+            })
         }
         _ => braced_namespace(state, name),
     }
 }
 
-fn unbraced_namespace(state: &mut State, name: SimpleIdentifier) -> ParseResult<Statement> {
+fn unbraced_namespace<'a>(
+    state: &mut State<'a>,
+    name: SimpleIdentifier<'a>,
+) -> ParseResult<Statement<'a>> {
     let body = scoped!(state, Scope::Namespace(name.clone()), {
         let mut body = Block::new();
         // since this is an unbraced namespace, as soon as we encouter another
         // `namespace` token as a top level statement, this namespace scope ends.
         // otherwise we will end up with nested namespace statements.
+        state.open_units(UnitsKind::Namespace);
         while state.current.kind != TokenKind::Namespace && !state.is_eof() {
+            state.unit();
             body.push(parser::top_level_statement(state)?);
         }
+        state.close_units();
 
         body
     });
@@ -56,14 +67,20 @@ fn unbraced_namespace(state: &mut State, name: SimpleIdentifier) -> ParseResult<
     Ok(Statement::Namespace { name, body })
 }
 
-fn braced_namespace(state: &mut State, name: Option<SimpleIdentifier>) -> ParseResult<Statement> {
+fn braced_namespace<'a>(
+    state: &mut State<'a>,
+    name: Option<SimpleIdentifier<'a>>,
+) -> ParseResult<Statement<'a>> {
     utils::skip_left_brace(state)?;
 
     let body = scoped!(state, Scope::BracedNamespace(name.clone()), {
         let mut body = Block::new();
+        state.open_units(UnitsKind::BracedNamespace);
         while state.current.kind != TokenKind::RightBrace && !state.is_eof() {
+            state.unit();
             body.push(parser::top_level_statement(state)?);
         }
+        state.close_units();
 
         body
     });
@@ -4,6 +4,7 @@ use crate::parser::ast::functions::FunctionParameter;
 use crate::parser::ast::functions::FunctionParameterList;
 use crate::parser::ast::functions::MethodParameter;
 use crate::parser::ast::functions::MethodParameterList;
+use crate::parser::ast::owned::IntoOwned;
 use crate::parser::ast::Arg;
 use crate::parser::ast::Expression;
 use crate::parser::error::ParseError;
@@ -17,7 +18,9 @@ use crate::parser::internal::variables;
 use crate::parser::state::Scope;
 use crate::parser::state::State;
 
-pub fn function_parameter_list(state: &mut State) -> Result<FunctionParameterList, ParseError> {
+pub fn function_parameter_list<'a>(
+    state: &mut State<'a>,
+) -> Result<FunctionParameterList<'a>, ParseError> {
     let mut members = Vec::new();
 
     let list_start = state.current.span;
@@ -69,9 +72,7 @@ pub fn function_parameter_list(state: &mut State) -> Result<FunctionParameterLis
 
         state.skip_comments();
 
-        if state.current.kind == TokenKind::Comma {
-            state.next();
-        } else {
+        if !state.accept(TokenKind::Comma) {
             break;
         }
     }
@@ -90,14 +91,16 @@ pub fn function_parameter_list(state: &mut State) -> Result<FunctionParameterLis
 /// TODO(azjezz): split this into `method_parameter_list` and `abstract_method_parameter_list`?
 ///               abstract method parameter list won't have a promoted property, so some of the logic
 ///               here can be avoided for performance.
-pub fn method_parameter_list(state: &mut State) -> Result<MethodParameterList, ParseError> {
+pub fn method_parameter_list<'a>(
+    state: &mut State<'a>,
+) -> Result<MethodParameterList<'a>, ParseError> {
     let mut class_name = String::new();
-    let construct: i8 = match state.scope()? {
+    let construct: i8 = match state.scope() {
         Scope::Method(name, modifiers) => {
             if name.to_string() != "__construct" {
                 0
             } else {
-                match state.parent()? {
+                match state.parent() {
                     // can only have abstract ctor
                     Scope::Interface(_) => 1,
                     // can only have concret ctor
@@ -151,7 +154,9 @@ pub fn method_parameter_list(state: &mut State) -> Result<MethodParameterList, P
         if matches!(state.current.kind, TokenKind::Ellipsis) {
             state.next();
             if !modifiers.is_empty() {
-                return Err(ParseError::VariadicPromotedProperty(state.current.span));
+                return Err(ParseError::VariadicPromotedProperty {
+                    span: state.current.span,
+                });
             }
 
             variadic = true;
@@ -163,14 +168,14 @@ pub fn method_parameter_list(state: &mut State) -> Result<MethodParameterList, P
         if !modifiers.is_empty() {
             match construct {
                 0 => {
-                    return Err(ParseError::PromotedPropertyOutsideConstructor(
-                        state.current.span,
-                    ));
+                    return Err(ParseError::PromotedPropertyOutsideConstructor {
+                        span: state.current.span,
+                    });
                 }
                 1 => {
-                    return Err(ParseError::PromotedPropertyOnAbstractConstructor(
-                        state.current.span,
-                    ));
+                    return Err(ParseError::PromotedPropertyOnAbstractConstructor {
+                        span: state.current.span,
+                    });
                 }
                 _ => {}
             }
@@ -178,21 +183,21 @@ pub fn method_parameter_list(state: &mut State) -> Result<MethodParameterList, P
             match &ty {
                 Some(ty) => {
                     if ty.includes_callable() || ty.is_bottom() {
-                        return Err(ParseError::ForbiddenTypeUsedInProperty(
-                            class_name,
-                            var.to_string(),
-                            ty.clone(),
-                            state.current.span,
-                        ));
+                        return Err(ParseError::ForbiddenTypeUsedInProperty {
+                            class: class_name,
+                            property: var.to_string(),
+                            r#type: ty.clone().into_owned(),
+                            span: state.current.span,
+                        });
                     }
                 }
                 None => {
                     if modifiers.has_readonly() {
-                        return Err(ParseError::MissingTypeForReadonlyProperty(
-                            class_name,
-                            var.to_string(),
-                            state.current.span,
-                        ));
+                        return Err(ParseError::MissingTypeForReadonlyProperty {
+                            class: class_name,
+                            property: var.to_string(),
+                            span: state.current.span,
+                        });
                     }
                 }
             }
@@ -220,9 +225,7 @@ pub fn method_parameter_list(state: &mut State) -> Result<MethodParameterList, P
 
         state.skip_comments();
 
-        if state.current.kind == TokenKind::Comma {
-            state.next();
-        } else {
+        if !state.accept(TokenKind::Comma) {
             break;
         }
     }
@@ -238,7 +241,7 @@ pub fn method_parameter_list(state: &mut State) -> Result<MethodParameterList, P
     })
 }
 
-pub fn args_list(state: &mut State) -> ParseResult<Vec<Arg>> {
+pub fn args_list<'a>(state: &mut State<'a>) -> ParseResult<Vec<Arg<'a>>> {
     utils::skip_left_parenthesis(state)?;
     state.skip_comments();
 
@@ -260,9 +263,9 @@ pub fn args_list(state: &mut State) -> ParseResult<Vec<Arg>> {
         }
 
         if name.is_none() && has_used_named_arguments {
-            return Err(ParseError::CannotUsePositionalArgumentAfterNamedArgument(
-                state.current.span,
-            ));
+            return Err(ParseError::CannotUsePositionalArgumentAfterNamedArgument {
+                span: state.current.span,
+            });
         }
 
         if unpack && state.current.kind == TokenKind::RightParen {
@@ -284,12 +287,11 @@ pub fn args_list(state: &mut State) -> ParseResult<Vec<Arg>> {
         });
 
         state.skip_comments();
-        if state.current.kind == TokenKind::Comma {
-            state.next();
-            state.skip_comments();
-        } else {
+        if !state.accept(TokenKind::Comma) {
             break;
         }
+
+        state.skip_comments();
     }
 
     utils::skip_right_parenthesis(state)?;
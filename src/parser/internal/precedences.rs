@@ -41,7 +41,7 @@ pub enum Precedence {
 }
 
 impl Precedence {
-    pub fn infix(kind: &TokenKind) -> Self {
+    pub fn infix<'a>(kind: &TokenKind<'a>) -> Self {
         use TokenKind::*;
 
         match kind {
@@ -72,7 +72,7 @@ impl Precedence {
         }
     }
 
-    pub fn postfix(kind: &TokenKind) -> Self {
+    pub fn postfix<'a>(kind: &TokenKind<'a>) -> Self {
         use TokenKind::*;
 
         match kind {
@@ -1,5 +1,6 @@
 use crate::lexer::token::TokenKind;
 use crate::parser::ast::modifiers::PropertyModifierGroup;
+use crate::parser::ast::owned::IntoOwned;
 use crate::parser::ast::properties::Property;
 use crate::parser::ast::properties::PropertyEntry;
 use crate::parser::ast::properties::VariableProperty;
@@ -12,11 +13,11 @@ use crate::parser::internal::utils;
 use crate::parser::internal::variables;
 use crate::parser::state::State;
 
-pub fn parse(
-    state: &mut State,
+pub fn parse<'a>(
+    state: &mut State<'a>,
     class: String,
     modifiers: PropertyModifierGroup,
-) -> ParseResult<Property> {
+) -> ParseResult<Property<'a>> {
     let ty = data_type::optional_data_type(state)?;
 
     let mut entries = vec![];
@@ -30,40 +31,40 @@ pub fn parse(
 
         if modifiers.has_readonly() {
             if modifiers.has_static() {
-                return Err(ParseError::StaticPropertyUsingReadonlyModifier(
+                return Err(ParseError::StaticPropertyUsingReadonlyModifier {
                     class,
-                    variable.to_string(),
-                    state.current.span,
-                ));
+                    property: variable.to_string(),
+                    span: state.current.span,
+                });
             }
 
             if value.is_some() {
-                return Err(ParseError::ReadonlyPropertyHasDefaultValue(
+                return Err(ParseError::ReadonlyPropertyHasDefaultValue {
                     class,
-                    variable.to_string(),
-                    state.current.span,
-                ));
+                    property: variable.to_string(),
+                    span: state.current.span,
+                });
             }
         }
 
         match &ty {
             Some(ty) => {
                 if ty.includes_callable() || ty.is_bottom() {
-                    return Err(ParseError::ForbiddenTypeUsedInProperty(
+                    return Err(ParseError::ForbiddenTypeUsedInProperty {
                         class,
-                        variable.to_string(),
-                        ty.clone(),
-                        state.current.span,
-                    ));
+                        property: variable.to_string(),
+                        r#type: ty.clone().into_owned(),
+                        span: state.current.span,
+                    });
                 }
             }
             None => {
                 if modifiers.has_readonly() {
-                    return Err(ParseError::MissingTypeForReadonlyProperty(
+                    return Err(ParseError::MissingTypeForReadonlyProperty {
                         class,
-                        variable.to_string(),
-                        state.current.span,
-                    ));
+                        property: variable.to_string(),
+                        span: state.current.span,
+                    });
                 }
             }
         }
@@ -72,9 +73,7 @@ pub fn parse(
 
         state.skip_comments();
 
-        if state.current.kind == TokenKind::Comma {
-            state.next();
-        } else {
+        if !state.accept(TokenKind::Comma) {
             break;
         }
     }
@@ -89,7 +88,7 @@ pub fn parse(
     })
 }
 
-pub fn parse_var(state: &mut State, class: String) -> ParseResult<VariableProperty> {
+pub fn parse_var<'a>(state: &mut State<'a>, class: String) -> ParseResult<VariableProperty<'a>> {
     utils::skip(state, TokenKind::Var)?;
 
     let ty = data_type::optional_data_type(state)?;
@@ -105,12 +104,12 @@ pub fn parse_var(state: &mut State, class: String) -> ParseResult<VariableProper
 
         if let Some(ty) = &ty {
             if ty.includes_callable() || ty.is_bottom() {
-                return Err(ParseError::ForbiddenTypeUsedInProperty(
+                return Err(ParseError::ForbiddenTypeUsedInProperty {
                     class,
-                    variable.to_string(),
-                    ty.clone(),
-                    state.current.span,
-                ));
+                    property: variable.to_string(),
+                    r#type: ty.clone().into_owned(),
+                    span: state.current.span,
+                });
             }
         }
 
@@ -118,9 +117,7 @@ pub fn parse_var(state: &mut State, class: String) -> ParseResult<VariableProper
 
         state.skip_comments();
 
-        if state.current.kind == TokenKind::Comma {
-            state.next();
-        } else {
+        if !state.accept(TokenKind::Comma) {
             break;
         }
     }
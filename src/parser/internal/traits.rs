@@ -20,7 +20,7 @@ use crate::parser::state::State;
 use crate::peek_token;
 use crate::scoped;
 
-pub fn usage(state: &mut State) -> ParseResult<TraitUsage> {
+pub fn usage<'a>(state: &mut State<'a>) -> ParseResult<TraitUsage<'a>> {
     state.next();
 
     let mut traits = Vec::new();
@@ -161,7 +161,7 @@ pub fn usage(state: &mut State) -> ParseResult<TraitUsage> {
     })
 }
 
-pub fn parse(state: &mut State) -> ParseResult<Statement> {
+pub fn parse<'a>(state: &mut State<'a>) -> ParseResult<Statement<'a>> {
     let start = utils::skip(state, TokenKind::Trait)?;
     let name = identifiers::ident(state)?;
     let class = name.name.to_string();
@@ -194,7 +194,7 @@ pub fn parse(state: &mut State) -> ParseResult<Statement> {
     }))
 }
 
-fn member(state: &mut State, class: String) -> ParseResult<TraitMember> {
+fn member<'a>(state: &mut State<'a>, class: String) -> ParseResult<TraitMember<'a>> {
     let has_attributes = attributes::gather_attributes(state)?;
 
     if !has_attributes && state.current.kind == TokenKind::Use {
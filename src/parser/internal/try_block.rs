@@ -12,7 +12,7 @@ use crate::parser::internal::identifiers;
 use crate::parser::internal::utils;
 use crate::parser::state::State;
 
-pub fn try_block(state: &mut State) -> ParseResult<Statement> {
+pub fn try_block<'a>(state: &mut State<'a>) -> ParseResult<Statement<'a>> {
     let start = state.current.span;
 
     state.next();
@@ -78,7 +78,7 @@ pub fn try_block(state: &mut State) -> ParseResult<Statement> {
     }
 
     if catches.is_empty() && finally.is_none() {
-        return Err(ParseError::TryWithoutCatchOrFinally(start));
+        return Err(ParseError::TryWithoutCatchOrFinally { span: start });
     }
 
     let end = state.current.span;
@@ -93,7 +93,7 @@ pub fn try_block(state: &mut State) -> ParseResult<Statement> {
 }
 
 #[inline(always)]
-fn catch_type(state: &mut State) -> ParseResult<CatchType> {
+fn catch_type<'a>(state: &mut State<'a>) -> ParseResult<CatchType<'a>> {
     let id = identifiers::full_name(state)?;
 
     if state.current.kind == TokenKind::Pipe {
@@ -7,7 +7,7 @@ use crate::parser::internal::identifiers;
 use crate::parser::internal::utils;
 use crate::parser::state::State;
 
-pub fn use_statement(state: &mut State) -> ParseResult<Statement> {
+pub fn use_statement<'a>(state: &mut State<'a>) -> ParseResult<Statement<'a>> {
     state.next();
 
     let kind = match state.current.kind {
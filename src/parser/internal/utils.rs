@@ -1,6 +1,5 @@
 use crate::lexer::token::Span;
 use crate::lexer::token::TokenKind;
-use crate::parser::error::ParseError;
 use crate::parser::error::ParseResult;
 use crate::parser::state::State;
 
@@ -13,17 +12,7 @@ pub fn skip_semicolon(state: &mut State) -> ParseResult<Span> {
         state.next();
         state.skip_comments();
     } else if state.current.kind != TokenKind::CloseTag {
-        let found = if state.current.kind == TokenKind::Eof {
-            None
-        } else {
-            Some(state.current.kind.to_string())
-        };
-
-        return Err(ParseError::ExpectedToken(
-            vec!["`;`".to_string()],
-            found,
-            end,
-        ));
+        return Err(state.expected_token(vec!["`;`".to_string()]));
     } else {
         state.next();
     }
@@ -80,7 +69,7 @@ pub fn skip_colon(state: &mut State) -> ParseResult<Span> {
     Ok(span)
 }
 
-pub fn skip(state: &mut State, kind: TokenKind) -> ParseResult<Span> {
+pub fn skip<'a>(state: &mut State<'a>, kind: TokenKind<'a>) -> ParseResult<Span> {
     state.skip_comments();
 
     if state.current.kind == kind {
@@ -91,21 +80,11 @@ pub fn skip(state: &mut State, kind: TokenKind) -> ParseResult<Span> {
 
         Ok(end)
     } else {
-        let found = if state.current.kind == TokenKind::Eof {
-            None
-        } else {
-            Some(state.current.kind.to_string())
-        };
-
-        Err(ParseError::ExpectedToken(
-            vec![format!("`{}`", kind)],
-            found,
-            state.current.span,
-        ))
+        Err(state.expected_token(vec![format!("`{}`", kind)]))
     }
 }
 
-pub fn skip_any_of(state: &mut State, kinds: &[TokenKind]) -> ParseResult<Span> {
+pub fn skip_any_of<'a>(state: &mut State<'a>, kinds: &[TokenKind<'a>]) -> ParseResult<Span> {
     state.skip_comments();
 
     if kinds.contains(&state.current.kind) {
@@ -116,23 +95,13 @@ pub fn skip_any_of(state: &mut State, kinds: &[TokenKind]) -> ParseResult<Span>
 
         Ok(end)
     } else {
-        let found = if state.current.kind == TokenKind::Eof {
-            None
-        } else {
-            Some(state.current.kind.to_string())
-        };
-
-        Err(ParseError::ExpectedToken(
-            kinds.iter().map(|kind| format!("`{}`", kind)).collect(),
-            found,
-            state.current.span,
-        ))
+        Err(state.expected_token(kinds.iter().map(|kind| format!("`{}`", kind)).collect()))
     }
 }
 
-pub fn at_least_one_comma_separated<T>(
-    state: &mut State,
-    func: &(dyn Fn(&mut State) -> ParseResult<T>),
+pub fn at_least_one_comma_separated<'a, T>(
+    state: &mut State<'a>,
+    func: &dyn Fn(&mut State<'a>) -> ParseResult<T>,
 ) -> ParseResult<Vec<T>> {
     let mut result: Vec<T> = vec![];
     loop {
@@ -140,11 +109,9 @@ pub fn at_least_one_comma_separated<T>(
 
         state.skip_comments();
 
-        if state.current.kind != TokenKind::Comma {
+        if !state.accept(TokenKind::Comma) {
             break;
         }
-
-        state.next();
     }
 
     Ok(result)
@@ -9,24 +9,30 @@ use crate::parser::expressions;
 use crate::parser::internal::utils;
 use crate::parser::state::State;
 
-pub fn simple_variable(state: &mut State) -> ParseResult<SimpleVariable> {
+pub fn simple_variable<'a>(state: &mut State<'a>) -> ParseResult<SimpleVariable<'a>> {
     if let TokenKind::Variable(name) = state.current.kind.clone() {
         let span = state.current.span;
+        let symbol = state.current.symbol;
         state.next();
 
-        return Ok(SimpleVariable { span, name });
+        return Ok(SimpleVariable { span, name, symbol });
     }
 
     expected_token_err!("a variable", state)
 }
 
-pub fn dynamic_variable(state: &mut State) -> ParseResult<Variable> {
+pub fn dynamic_variable<'a>(state: &mut State<'a>) -> ParseResult<Variable<'a>> {
     match state.current.kind.clone() {
         TokenKind::Variable(name) => {
             let span = state.current.span;
+            let symbol = state.current.symbol;
             state.next();
 
-            Ok(Variable::SimpleVariable(SimpleVariable { span, name }))
+            Ok(Variable::SimpleVariable(SimpleVariable {
+                span,
+                name,
+                symbol,
+            }))
         }
         TokenKind::DollarLeftBrace => {
             let start = state.current.span;
@@ -29,6 +29,7 @@ macro_rules! peek_token {
 macro_rules! expect_token {
     ([ $($(|)? $( $pattern:pat_param )|+ $( if $guard: expr )? => $out:expr),+ $(,)? ], $state:expr, [ $($message:literal),+ $(,)? ]) => {{
         $state.skip_comments();
+        let mut expected = std::mem::take(&mut $state.expected);
         let token = $state.pull();
         match token.kind {
             $(
@@ -36,19 +37,21 @@ macro_rules! expect_token {
                     $out
                 },
             )+
-            TokenKind::Eof => {
-                return Err($crate::parser::error::ParseError::ExpectedToken(
-                    vec![$($message.into(),)+],
-                    None,
-                    token.span,
-                ))
-            },
-            _ => {
-                return Err($crate::parser::error::ParseError::ExpectedToken(
-                    vec![$($message.into(),)+],
-                    Some(token.kind.to_string()),
-                    token.span,
-                ))
+            kind => {
+                $(
+                    if !expected.iter().any(|e| e == $message) {
+                        expected.push($message.into());
+                    }
+                )+
+
+                return Err($crate::parser::error::ParseError::ExpectedToken {
+                    expected,
+                    found: match kind {
+                        TokenKind::Eof => None,
+                        kind => Some(kind.to_string()),
+                    },
+                    span: token.span,
+                })
             }
         }
     }};
@@ -98,22 +101,7 @@ macro_rules! expected_token_err {
 #[macro_export]
 macro_rules! expected_token {
     ([ $($expected:literal),+ $(,)? ], $state:expr $(,)?) => {{
-        match &$state.current.kind {
-            TokenKind::Eof => {
-                $crate::parser::error::ParseError::ExpectedToken(
-                    vec![$($expected.into()),+],
-                    None,
-                    $state.current.span,
-                )
-            },
-            _ => {
-                $crate::parser::error::ParseError::ExpectedToken(
-                    vec![$($expected.into()),+],
-                    Some($state.current.kind.to_string()),
-                    $state.current.span,
-                )
-            }
-        }
+        $state.expected_token(vec![$($expected.to_string()),+])
     }};
 
     ($expected:literal, $state:expr $(,)?) => {
@@ -124,13 +112,11 @@ macro_rules! expected_token {
 #[macro_export]
 macro_rules! expected_scope {
     ([ $($(|)? $( $pattern:pat_param )|+ $( if $guard: expr )? => $out:expr),+ $(,)? ], $state:expr) => {{
-        match $state.scope().cloned()? {
+        match $state.scope().clone() {
             $(
                 $( $pattern )|+ $( if $guard )? => $out,
             )+
-            _ => {
-                return Err($crate::parser::error::ParseError::UnpredictableState($state.current.span));
-            }
+            scope => unreachable!("shouldn't reach scope `{:?}`", scope),
         }
     }};
 }
@@ -1,17 +1,25 @@
 use crate::expect_literal;
+use crate::expected_token_err;
+use crate::lexer::error::SyntaxResult;
 use crate::lexer::token::Token;
 use crate::lexer::token::TokenKind;
+use crate::parser::ast::classes::ClassMember;
 use crate::parser::ast::comments::Comment;
 use crate::parser::ast::comments::CommentFormat;
+use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::modifiers::ClassModifier;
+use crate::parser::ast::modifiers::ClassModifierGroup;
 use crate::parser::ast::variables::Variable;
-use crate::parser::ast::{DeclareItem, Expression, Program, Statement, StaticVar};
+use crate::parser::ast::{DeclareItem, Expression, Program, Statement, StaticVar, Type};
 use crate::parser::error::ParseError;
 use crate::parser::error::ParseResult;
+use crate::parser::incremental::UnitsKind;
 use crate::parser::internal::attributes;
 use crate::parser::internal::blocks;
 use crate::parser::internal::classes;
 use crate::parser::internal::constants;
 use crate::parser::internal::control_flow;
+use crate::parser::internal::data_type;
 use crate::parser::internal::enums;
 use crate::parser::internal::functions;
 use crate::parser::internal::goto;
@@ -24,20 +32,41 @@ use crate::parser::internal::try_block;
 use crate::parser::internal::uses;
 use crate::parser::internal::utils;
 use crate::parser::internal::variables;
+use crate::parser::options::ParserOptions;
+use crate::parser::state::Scope;
 use crate::parser::state::State;
+use crate::scoped;
 
 pub mod ast;
 pub mod error;
+pub mod incremental;
+pub mod options;
 
 mod expressions;
-mod internal;
+pub(crate) mod internal;
 mod macros;
+pub(crate) mod stack;
 mod state;
 
-pub fn parse(tokens: Vec<Token>) -> ParseResult<Program> {
-    let mut state = State::new(tokens);
+pub fn parse<'a>(tokens: Vec<Token<'a>>) -> ParseResult<Program<'a>> {
+    ParserOptions::default().parse(tokens)
+}
+
+/// Parse a program from tokens that are produced on demand, such as a `TokenStream`
+/// returned by `Lexer::stream`.
+///
+/// Only the few tokens the parser needs to look ahead are kept in memory. If the
+/// lexer fails, its error is returned, even if the parser failed first as a result.
+pub fn parse_stream<'a, I>(tokens: I) -> ParseResult<Program<'a>>
+where
+    I: IntoIterator<Item = SyntaxResult<Token<'a>>>,
+    I::IntoIter: 'a,
+{
+    ParserOptions::default().parse_stream(tokens)
+}
 
-    let mut ast = Program::new();
+fn program<'a>(state: &mut State<'a>, mut push: impl FnMut(Statement<'a>)) -> ParseResult<()> {
+    state.open_units(UnitsKind::Program);
 
     while state.current.kind != TokenKind::Eof {
         if matches!(
@@ -59,15 +88,120 @@ pub fn parse(tokens: Vec<Token>) -> ParseResult<Program> {
             continue;
         }
 
-        ast.push(top_level_statement(&mut state)?);
+        state.check_cancelled()?;
+        state.unit();
+        push(top_level_statement(state)?);
 
         state.clear_comments();
     }
 
-    Ok(ast.to_vec())
+    state.close_units();
+
+    Ok(())
+}
+
+/// Parse a standalone expression, such as `$a->b['c'] ?? null`.
+///
+/// The input may start with an opening tag, but doesn't need to, and
+/// must not contain anything after the expression.
+pub fn parse_expression<'a>(tokens: Vec<Token<'a>>) -> ParseResult<Expression<'a>> {
+    let mut state = State::new(tokens.into_iter().map(Ok), ParserOptions::default());
+
+    fragment_start(&mut state)?;
+    let expression = expressions::lowest_precedence(&mut state)?;
+    fragment_end(&mut state)?;
+
+    Ok(expression)
+}
+
+/// Parse a single standalone statement, such as `return $foo;`.
+///
+/// Statements that are only allowed at the top level of a file, such as
+/// `namespace`, `use` and `__halt_compiler()`, are accepted too.
+pub fn parse_statement<'a>(tokens: Vec<Token<'a>>) -> ParseResult<Statement<'a>> {
+    let mut state = State::new(tokens.into_iter().map(Ok), ParserOptions::default());
+
+    fragment_start(&mut state)?;
+    let statement = top_level_statement(&mut state)?;
+    fragment_end(&mut state)?;
+
+    Ok(statement)
+}
+
+/// Parse a standalone type, such as `int|string|null`.
+///
+/// The type is parsed as if it was written inside of a class that extends
+/// another class, so `self`, `static` and `parent` are accepted.
+pub fn parse_type<'a>(tokens: Vec<Token<'a>>) -> ParseResult<Type<'a>> {
+    let mut state = State::new(tokens.into_iter().map(Ok), ParserOptions::default());
+
+    fragment_start(&mut state)?;
+    let scope = fragment_class_scope(&state);
+    let r#type = scoped!(state, scope, { data_type::data_type(&mut state)? });
+    fragment_end(&mut state)?;
+
+    Ok(r#type)
+}
+
+/// Parse a single class member, such as a property, method, constant or trait usage.
+///
+/// The member is parsed as if it was declared inside of an abstract class that
+/// extends another class, so both abstract and concrete methods are accepted, as
+/// well as references to `parent`.
+pub fn parse_class_member<'a>(tokens: Vec<Token<'a>>) -> ParseResult<ClassMember<'a>> {
+    let mut state = State::new(tokens.into_iter().map(Ok), ParserOptions::default());
+
+    fragment_start(&mut state)?;
+
+    let scope = fragment_class_scope(&state);
+    let member = scoped!(state, scope, {
+        state.gather_comments();
+
+        classes::member(&mut state, "class@anonymous".to_string())?
+    });
+
+    fragment_end(&mut state)?;
+
+    Ok(member)
+}
+
+/// The scope of an abstract class that extends another class, which standalone
+/// types and class members are parsed in.
+fn fragment_class_scope<'a>(state: &State<'a>) -> Scope<'a> {
+    let name = SimpleIdentifier {
+        span: state.current.span,
+        name: "class@anonymous".into(),
+        symbol: None,
+    };
+    let modifiers = ClassModifierGroup {
+        modifiers: vec![ClassModifier::Abstract {
+            start: state.current.span,
+            end: state.current.span,
+        }],
+    };
+
+    Scope::Class(name, modifiers, true)
+}
+
+fn fragment_start(state: &mut State) -> ParseResult<()> {
+    state.skip_comments();
+    utils::skip_open_tag(state)?;
+    state.skip_comments();
+
+    Ok(())
+}
+
+fn fragment_end(state: &mut State) -> ParseResult<()> {
+    state.skip_comments();
+
+    if !state.is_eof() {
+        return expected_token_err!("end of file", state);
+    }
+
+    Ok(())
 }
 
-fn top_level_statement(state: &mut State) -> ParseResult<Statement> {
+fn top_level_statement<'a>(state: &mut State<'a>) -> ParseResult<Statement<'a>> {
     state.skip_comments();
 
     let statement = match &state.current.kind {
@@ -99,7 +233,11 @@ fn top_level_statement(state: &mut State) -> ParseResult<Statement> {
     Ok(statement)
 }
 
-fn statement(state: &mut State) -> ParseResult<Statement> {
+fn statement<'a>(state: &mut State<'a>) -> ParseResult<Statement<'a>> {
+    state.nested(statement_inner)
+}
+
+fn statement_inner<'a>(state: &mut State<'a>) -> ParseResult<Statement<'a>> {
     let has_attributes = attributes::gather_attributes(state)?;
 
     // FIXME: There's a better place to put this but night-time brain doesn't know where.
@@ -120,37 +258,28 @@ fn statement(state: &mut State) -> ParseResult<Statement> {
                 if identifiers::is_ident_maybe_soft_reserved(&state.peek.kind)
                     || state.peek.kind == TokenKind::Ampersand =>
             {
-                // FIXME: This is incredibly hacky but we don't have a way to look at
-                // the next N tokens right now. We could probably do with a `peek_buf()`
-                // method like the Lexer has.
-                if state.peek.kind == TokenKind::Ampersand {
-                    if let Some((_, token)) = state.iter.clone().enumerate().next() {
-                        if !matches!(
-                            token,
-                            Token {
-                                kind: TokenKind::Identifier(_),
-                                ..
-                            }
-                        ) {
-                            let expr = expressions::lowest_precedence(state)?;
-
-                            utils::skip_semicolon(state)?;
-
-                            return Ok(Statement::Expression { expr });
-                        }
-                    }
+                // `function &() {}` is a closure returning by reference, not a function declaration.
+                if state.peek.kind == TokenKind::Ampersand
+                    && !matches!(
+                        state.lookahead(2).kind,
+                        TokenKind::Identifier(_) | TokenKind::Eof
+                    )
+                {
+                    let expr = expressions::lowest_precedence(state)?;
 
-                    functions::function(state)?
-                } else {
-                    functions::function(state)?
+                    utils::skip_semicolon(state)?;
+
+                    return Ok(Statement::Expression { expr });
                 }
+
+                functions::function(state)?
             }
             _ => {
                 // Note, we can get attributes and know their span, maybe use that in the
                 // error in the future?
-                return Err(ParseError::ExpectedItemDefinitionAfterAttributes(
-                    state.current.span,
-                ));
+                return Err(ParseError::ExpectedItemDefinitionAfterAttributes {
+                    span: state.current.span,
+                });
             }
         }
     } else {
@@ -168,30 +297,21 @@ fn statement(state: &mut State) -> ParseResult<Statement> {
                 if identifiers::is_ident_maybe_soft_reserved(&state.peek.kind)
                     || state.peek.kind == TokenKind::Ampersand =>
             {
-                // FIXME: This is incredibly hacky but we don't have a way to look at
-                // the next N tokens right now. We could probably do with a `peek_buf()`
-                // method like the Lexer has.
-                if state.peek.kind == TokenKind::Ampersand {
-                    if let Some((_, token)) = state.iter.clone().enumerate().next() {
-                        if !matches!(
-                            token,
-                            Token {
-                                kind: TokenKind::Identifier(_),
-                                ..
-                            }
-                        ) {
-                            let expr = expressions::lowest_precedence(state)?;
-
-                            utils::skip_semicolon(state)?;
-
-                            return Ok(Statement::Expression { expr });
-                        }
-                    }
+                // `function &() {}` is a closure returning by reference, not a function declaration.
+                if state.peek.kind == TokenKind::Ampersand
+                    && !matches!(
+                        state.lookahead(2).kind,
+                        TokenKind::Identifier(_) | TokenKind::Eof
+                    )
+                {
+                    let expr = expressions::lowest_precedence(state)?;
 
-                    functions::function(state)?
-                } else {
-                    functions::function(state)?
+                    utils::skip_semicolon(state)?;
+
+                    return Ok(Statement::Expression { expr });
                 }
+
+                functions::function(state)?
             }
             TokenKind::Goto => goto::goto_statement(state)?,
             TokenKind::Identifier(_) if state.peek.kind == TokenKind::Colon => {
@@ -211,9 +331,7 @@ fn statement(state: &mut State) -> ParseResult<Statement> {
 
                     declares.push(DeclareItem { key, value });
 
-                    if state.current.kind == TokenKind::Comma {
-                        state.next();
-                    } else {
+                    if !state.accept(TokenKind::Comma) {
                         break;
                     }
                 }
@@ -249,9 +367,7 @@ fn statement(state: &mut State) -> ParseResult<Statement> {
                 loop {
                     variables.push(variables::dynamic_variable(state)?);
 
-                    if state.current.kind == TokenKind::Comma {
-                        state.next();
-                    } else {
+                    if !state.accept(TokenKind::Comma) {
                         break;
                     }
                 }
@@ -281,9 +397,7 @@ fn statement(state: &mut State) -> ParseResult<Statement> {
                         default,
                     });
 
-                    if state.current.kind == TokenKind::Comma {
-                        state.next();
-                    } else {
+                    if !state.accept(TokenKind::Comma) {
                         break;
                     }
                 }
@@ -369,9 +483,7 @@ fn statement(state: &mut State) -> ParseResult<Statement> {
                 loop {
                     values.push(expressions::lowest_precedence(state)?);
 
-                    if state.current.kind == TokenKind::Comma {
-                        state.next();
-                    } else {
+                    if !state.accept(TokenKind::Comma) {
                         break;
                     }
                 }
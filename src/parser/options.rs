@@ -0,0 +1,155 @@
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use crate::lexer::error::SyntaxResult;
+use crate::lexer::token::Token;
+use crate::lexer::Lexer;
+use crate::parser::ast::Program;
+use crate::parser::error::ParseError;
+use crate::parser::error::ParseResult;
+use crate::parser::program;
+use crate::parser::state::State;
+
+/// Options that control how much work the parser is willing to do.
+///
+/// The free functions in `parser` use the default options, which are suitable
+/// for any code a person would write by hand. The other limits are off by
+/// default; set them when parsing input you don't control.
+#[derive(Debug, Clone)]
+pub struct ParserOptions {
+    /// How deeply expressions and statements may be nested inside one another.
+    ///
+    /// Each level of nesting uses some of the stack, so this keeps deeply nested
+    /// input from overflowing it. Input that goes past the limit is rejected
+    /// with `ParseError::NestingTooDeep`.
+    pub max_depth: usize,
+    /// The largest input, in bytes, accepted by `parse_source`.
+    ///
+    /// Larger input is rejected with `ParseError::InputTooLarge` before it is lexed.
+    pub max_input_size: Option<usize>,
+    /// How many tokens the parser reads before giving up with `ParseError::TooManyTokens`.
+    pub max_tokens: Option<usize>,
+    /// How many expressions and statements the parser builds before giving up
+    /// with `ParseError::TooManyNodes`.
+    pub max_nodes: Option<usize>,
+    /// Checked between statements, so that a long parse can be stopped from
+    /// another thread. A cancelled parse fails with `ParseError::Cancelled`.
+    pub cancellation: Option<CancellationToken>,
+}
+
+impl ParserOptions {
+    pub const DEFAULT_MAX_DEPTH: usize = 256;
+
+    pub const fn new() -> Self {
+        Self {
+            max_depth: Self::DEFAULT_MAX_DEPTH,
+            max_input_size: None,
+            max_tokens: None,
+            max_nodes: None,
+            cancellation: None,
+        }
+    }
+
+    pub const fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+
+        self
+    }
+
+    pub const fn with_max_input_size(mut self, max_input_size: usize) -> Self {
+        self.max_input_size = Some(max_input_size);
+
+        self
+    }
+
+    pub const fn with_max_tokens(mut self, max_tokens: usize) -> Self {
+        self.max_tokens = Some(max_tokens);
+
+        self
+    }
+
+    pub const fn with_max_nodes(mut self, max_nodes: usize) -> Self {
+        self.max_nodes = Some(max_nodes);
+
+        self
+    }
+
+    pub fn with_cancellation(mut self, cancellation: CancellationToken) -> Self {
+        self.cancellation = Some(cancellation);
+
+        self
+    }
+
+    /// Lex and parse `input` with the given lexer, enforcing `max_input_size`.
+    ///
+    /// Tokens are streamed from the lexer, so the other limits also bound how
+    /// much of the input gets lexed.
+    pub fn parse_source<'a, B: ?Sized + AsRef<[u8]>>(
+        &self,
+        lexer: &Lexer,
+        input: &'a B,
+    ) -> ParseResult<Program<'a>> {
+        let size = input.as_ref().len();
+        if let Some(limit) = self.max_input_size {
+            if size > limit {
+                return Err(ParseError::InputTooLarge {
+                    limit,
+                    size,
+                    span: (1, 1),
+                });
+            }
+        }
+
+        self.parse_stream(lexer.stream(input))
+    }
+
+    pub fn parse<'a>(&self, tokens: Vec<Token<'a>>) -> ParseResult<Program<'a>> {
+        self.parse_stream(tokens.into_iter().map(Ok))
+    }
+
+    /// See `parser::parse_stream`.
+    pub fn parse_stream<'a, I>(&self, tokens: I) -> ParseResult<Program<'a>>
+    where
+        I: IntoIterator<Item = SyntaxResult<Token<'a>>>,
+        I::IntoIter: 'a,
+    {
+        let mut state = State::new(tokens, self.clone());
+
+        let mut ast = Program::new();
+        let result = program(&mut state, |statement| ast.push(statement));
+
+        match state.error.take() {
+            Some(error) => Err(error),
+            None => result.map(|_| ast),
+        }
+    }
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A flag that tells a parse running on another thread to stop.
+///
+/// Clones share the same flag, so keep one and hand the other to `ParserOptions`.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
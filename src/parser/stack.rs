@@ -0,0 +1,13 @@
+// Debug builds use a lot more stack per level of nesting than release builds,
+// so the red zone is generous enough for either to parse one more level.
+const RED_ZONE: usize = 128 * 1024;
+const STACK_SIZE: usize = 2 * 1024 * 1024;
+
+/// Run `f`, first switching to a freshly allocated stack segment if the current
+/// one is about to run out.
+///
+/// Wrapping each recursive step in this means the depth of the recursion is only
+/// limited by `ParserOptions::max_depth`, not by the size of the thread's stack.
+pub(crate) fn ensure_sufficient_stack<R>(f: impl FnOnce() -> R) -> R {
+    stacker::maybe_grow(RED_ZONE, STACK_SIZE, f)
+}
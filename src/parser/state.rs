@@ -1,7 +1,7 @@
 use std::collections::VecDeque;
 use std::fmt::Display;
-use std::vec::IntoIter;
 
+use crate::lexer::error::SyntaxResult;
 use crate::lexer::token::Token;
 use crate::lexer::token::TokenKind;
 use crate::parser::ast::attributes::AttributeGroup;
@@ -10,6 +10,10 @@ use crate::parser::ast::modifiers::ClassModifierGroup;
 use crate::parser::ast::modifiers::MethodModifierGroup;
 use crate::parser::error::ParseError;
 use crate::parser::error::ParseResult;
+use crate::parser::incremental::Outline;
+use crate::parser::incremental::UnitsKind;
+use crate::parser::options::ParserOptions;
+use crate::parser::stack::ensure_sufficient_stack;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum NamespaceType {
@@ -18,57 +22,152 @@ pub enum NamespaceType {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
-pub enum Scope {
-    Namespace(SimpleIdentifier),
-    BracedNamespace(Option<SimpleIdentifier>),
-
-    Interface(SimpleIdentifier),
-    Class(SimpleIdentifier, ClassModifierGroup, bool),
-    Trait(SimpleIdentifier),
-    Enum(SimpleIdentifier, bool),
+pub enum Scope<'a> {
+    Namespace(SimpleIdentifier<'a>),
+    BracedNamespace(Option<SimpleIdentifier<'a>>),
+
+    Interface(SimpleIdentifier<'a>),
+    Class(SimpleIdentifier<'a>, ClassModifierGroup, bool),
+    Trait(SimpleIdentifier<'a>),
+    Enum(SimpleIdentifier<'a>, bool),
     AnonymousClass(bool),
 
-    Function(SimpleIdentifier),
-    Method(SimpleIdentifier, MethodModifierGroup),
+    Function(SimpleIdentifier<'a>),
+    Method(SimpleIdentifier<'a>, MethodModifierGroup),
     AnonymousFunction(bool),
     ArrowFunction(bool),
 }
 
-#[derive(Debug, Clone)]
-pub struct State {
-    pub stack: VecDeque<Scope>,
-    pub current: Token,
-    pub peek: Token,
-    pub iter: IntoIter<Token>,
-    pub comments: Vec<Token>,
-    pub attributes: Vec<AttributeGroup>,
+pub struct State<'a> {
+    pub stack: VecDeque<Scope<'a>>,
+    pub current: Token<'a>,
+    pub peek: Token<'a>,
+    pub comments: Vec<Token<'a>>,
+    pub attributes: Vec<AttributeGroup<'a>>,
     pub namespace_type: Option<NamespaceType>,
     pub has_class_scope: bool,
     pub has_class_parent_scope: bool,
+    pub expected: Vec<String>,
+    /// The error that stopped the lexer, if any, or `ParseError::TooManyTokens`.
+    ///
+    /// Once reading tokens fails, the parser sees the end of the file instead.
+    pub error: Option<ParseError>,
+    pub options: ParserOptions,
+    /// Where top-level statements and class members start, if they are being
+    /// recorded for incremental reparsing.
+    pub outline: Option<Outline>,
+    // How deeply the expression or statement currently being parsed is nested.
+    depth: usize,
+    // How many tokens and nodes have been read and built so far, see `ParserOptions`.
+    tokens_read: usize,
+    nodes: usize,
+    tokens: Box<dyn Iterator<Item = SyntaxResult<Token<'a>>> + 'a>,
+    // Tokens that have been read past `peek` by `lookahead()`.
+    buffer: VecDeque<Token<'a>>,
 }
 
-impl State {
-    pub fn new(tokens: Vec<Token>) -> Self {
-        let mut iter = tokens.into_iter();
-
-        Self {
+impl<'a> State<'a> {
+    pub fn new<I>(tokens: I, options: ParserOptions) -> Self
+    where
+        I: IntoIterator<Item = SyntaxResult<Token<'a>>>,
+        I::IntoIter: 'a,
+    {
+        let mut state = Self {
             stack: VecDeque::with_capacity(32),
-            current: iter.next().unwrap_or_default(),
-            peek: iter.next().unwrap_or_default(),
-            iter,
+            current: Token::default(),
+            peek: Token::default(),
             comments: vec![],
             namespace_type: None,
             has_class_scope: false,
             has_class_parent_scope: false,
             attributes: vec![],
+            expected: vec![],
+            error: None,
+            options,
+            outline: None,
+            depth: 0,
+            tokens_read: 0,
+            nodes: 0,
+            tokens: Box::new(tokens.into_iter()),
+            buffer: VecDeque::new(),
+        };
+
+        state.current = state.read();
+        state.peek = state.read();
+
+        state
+    }
+
+    /// Parse a nested expression or statement, failing if the input is nested
+    /// deeper than `ParserOptions::max_depth` or contains more than
+    /// `ParserOptions::max_nodes` expressions and statements.
+    pub fn nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> ParseResult<T>) -> ParseResult<T> {
+        if self.depth >= self.options.max_depth {
+            return Err(ParseError::NestingTooDeep {
+                limit: self.options.max_depth,
+                span: self.current.span,
+            });
+        }
+
+        self.nodes += 1;
+        if let Some(limit) = self.options.max_nodes {
+            if self.nodes > limit {
+                return Err(ParseError::TooManyNodes {
+                    limit,
+                    span: self.current.span,
+                });
+            }
+        }
+
+        self.depth += 1;
+        let result = ensure_sufficient_stack(|| parse(self));
+        self.depth -= 1;
+
+        result
+    }
+
+    /// Start a list of top-level statements or class members, see `incremental::Outline`.
+    pub fn open_units(&mut self, kind: UnitsKind) {
+        if let Some(outline) = &mut self.outline {
+            outline.open(kind, &self.stack, self.namespace_type.clone(), self.depth);
+        }
+    }
+
+    /// Record that the next statement or member in the current list starts here.
+    pub fn unit(&mut self) {
+        if let Some(outline) = &mut self.outline {
+            outline.unit(&self.current);
         }
     }
 
-    pub fn attribute(&mut self, attr: AttributeGroup) {
+    pub fn close_units(&mut self) {
+        if let Some(outline) = &mut self.outline {
+            outline.close(&self.current);
+        }
+    }
+
+    /// Start parsing as if `depth` expressions and statements had already been entered.
+    pub fn with_depth(mut self, depth: usize) -> Self {
+        self.depth = depth;
+
+        self
+    }
+
+    /// Fail with `ParseError::Cancelled` if the parse has been cancelled.
+    pub fn check_cancelled(&self) -> ParseResult<()> {
+        match &self.options.cancellation {
+            Some(cancellation) if cancellation.is_cancelled() => Err(ParseError::Cancelled {
+                span: self.current.span,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn attribute(&mut self, attr: AttributeGroup<'a>) {
         self.attributes.push(attr);
     }
 
-    pub fn get_attributes(&mut self) -> Vec<AttributeGroup> {
+    pub fn get_attributes(&mut self) -> Vec<AttributeGroup<'a>> {
         let mut attributes = vec![];
 
         std::mem::swap(&mut self.attributes, &mut attributes);
@@ -87,7 +186,7 @@ impl State {
         self.namespace_type.clone()
     }
 
-    pub fn namespace(&self) -> Option<&Scope> {
+    pub fn namespace(&self) -> Option<&Scope<'a>> {
         for scope in &self.stack {
             match scope {
                 Scope::Namespace(_) | Scope::BracedNamespace(_) => {
@@ -109,19 +208,27 @@ impl State {
         }
     }
 
-    pub fn scope(&self) -> ParseResult<&Scope> {
-        self.stack
-            .back()
-            .ok_or(ParseError::UnpredictableState(self.current.span))
+    /// The innermost scope.
+    ///
+    /// This is only used while parsing methods, which are always parsed inside
+    /// of the scope of their class, trait, interface or enum, and inside of
+    /// their own scope once their name has been read.
+    pub fn scope(&self) -> &Scope<'a> {
+        match self.stack.back() {
+            Some(scope) => scope,
+            None => unreachable!("methods are always parsed inside of a scope"),
+        }
     }
 
-    pub fn parent(&self) -> ParseResult<&Scope> {
-        self.stack
-            .get(self.stack.len() - 2)
-            .ok_or(ParseError::UnpredictableState(self.current.span))
+    /// The scope that encloses the innermost scope, see `scope()`.
+    pub fn parent(&self) -> &Scope<'a> {
+        match self.stack.len().checked_sub(2) {
+            Some(index) => &self.stack[index],
+            None => unreachable!("method scopes are always entered inside of a classish scope"),
+        }
     }
 
-    pub fn enter(&mut self, scope: Scope) {
+    pub fn enter(&mut self, scope: Scope<'a>) {
         match &scope {
             Scope::Namespace(_) => {
                 self.namespace_type = Some(NamespaceType::Unbraced);
@@ -166,7 +273,7 @@ impl State {
         }
     }
 
-    pub fn clear_comments(&mut self) -> Vec<Token> {
+    pub fn clear_comments(&mut self) -> Vec<Token<'a>> {
         let mut comments = vec![];
 
         std::mem::swap(&mut self.comments, &mut comments);
@@ -178,16 +285,59 @@ impl State {
         self.current.kind == TokenKind::Eof
     }
 
-    pub fn pull(&mut self) -> Token {
-        let mut current: Token = Default::default();
+    /// Move past the current token if it is of the given kind.
+    ///
+    /// When it isn't, the kind is remembered as an alternative that would
+    /// have been accepted here, so that an error reported at this position
+    /// can list it.
+    pub fn accept(&mut self, kind: TokenKind<'a>) -> bool {
+        if self.current.kind == kind {
+            self.next();
+
+            return true;
+        }
+
+        self.expect(format!("`{}`", kind));
+
+        false
+    }
+
+    /// Remember an alternative that would have been accepted at the current position.
+    pub fn expect(&mut self, expected: String) {
+        if !self.expected.contains(&expected) {
+            self.expected.push(expected);
+        }
+    }
+
+    /// Create an error for the current token, listing every alternative
+    /// that would have been accepted at this position.
+    pub fn expected_token(&self, expected: Vec<String>) -> ParseError {
+        let mut alternatives = self.expected.clone();
+        for expected in expected {
+            if !alternatives.contains(&expected) {
+                alternatives.push(expected);
+            }
+        }
+
+        ParseError::ExpectedToken {
+            expected: alternatives,
+            found: match self.current.kind {
+                TokenKind::Eof => None,
+                _ => Some(self.current.kind.to_string()),
+            },
+            span: self.current.span,
+        }
+    }
+
+    pub fn pull(&mut self) -> Token<'a> {
+        let mut current: Token<'a> = Default::default();
 
         std::mem::swap(&mut current, &mut self.current);
         std::mem::swap(&mut self.current, &mut self.peek);
 
-        // peek already contains default.
-        if let Some(t) = self.iter.next() {
-            self.peek = t;
-        }
+        self.peek = self.advance();
+
+        self.expected.clear();
 
         current
     }
@@ -196,7 +346,66 @@ impl State {
         // move peek to current
         std::mem::swap(&mut self.current, &mut self.peek);
 
-        self.peek = self.iter.next().unwrap_or_default()
+        self.peek = self.advance();
+
+        self.expected.clear();
+    }
+
+    /// Look at the token `n` positions after the current one, without moving past it.
+    ///
+    /// `lookahead(1)` is the same as `peek`. Tokens beyond `peek` are read from the
+    /// input as needed and kept until the parser reaches them.
+    pub fn lookahead(&mut self, n: usize) -> &Token<'a> {
+        match n {
+            0 => &self.current,
+            1 => &self.peek,
+            _ => {
+                while self.buffer.len() < n - 1 {
+                    let token = self.read();
+
+                    self.buffer.push_back(token);
+                }
+
+                &self.buffer[n - 2]
+            }
+        }
+    }
+
+    fn advance(&mut self) -> Token<'a> {
+        match self.buffer.pop_front() {
+            Some(token) => token,
+            None => self.read(),
+        }
+    }
+
+    fn read(&mut self) -> Token<'a> {
+        if self.error.is_some() {
+            return Token::default();
+        }
+
+        match self.tokens.next() {
+            Some(Ok(token)) => {
+                self.tokens_read += 1;
+
+                match self.options.max_tokens {
+                    Some(limit) if self.tokens_read > limit => {
+                        self.error = Some(ParseError::TooManyTokens {
+                            limit,
+                            span: token.span,
+                        });
+
+                        Token::default()
+                    }
+                    _ => token,
+                }
+            }
+            Some(Err(error)) => {
+                self.error = Some(ParseError::SyntaxError(error));
+
+                Token::default()
+            }
+            None => Token::default(),
+        }
     }
 
     fn update_scope(&mut self) {
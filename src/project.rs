@@ -0,0 +1,372 @@
+//! Parsing every file of a project in parallel.
+//!
+//! ```ignore
+//! let result = Project::new("path/to/project")
+//!     .with_exclude("vendor")
+//!     .parse()?;
+//!
+//! for (path, diagnostic) in result.diagnostics() {
+//!     eprintln!("{}: {}", path.display(), diagnostic.message);
+//! }
+//! ```
+
+use std::any::Any;
+use std::fmt::Display;
+use std::io;
+use std::panic;
+use std::panic::AssertUnwindSafe;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+use std::thread;
+
+use crate::diagnostics::Diagnostic;
+use crate::lexer::Lexer;
+use crate::parser::ast::owned::IntoOwned;
+use crate::parser::ast::Program;
+use crate::parser::error::ParseError;
+use crate::parser::options::ParserOptions;
+
+// Parsing a deeply nested file, and copying it into an owned tree, recurses
+// deeply, so workers get more stack than the default 2 MiB.
+const WORKER_STACK_SIZE: usize = 16 * 1024 * 1024;
+
+/// A directory of PHP files, and which of them to parse.
+///
+/// Files are found by matching their path, relative to the root and with `/`
+/// as the separator, against glob patterns. `*` and `?` match any characters,
+/// or a single character, within one path segment and `**` matches any number
+/// of segments. Without any include patterns, every `.php` file is included.
+///
+/// Directories that match an exclude pattern are skipped entirely, so excluding
+/// `vendor` leaves out everything below it.
+#[derive(Debug, Clone)]
+pub struct Project {
+    root: PathBuf,
+    include: Vec<Glob>,
+    exclude: Vec<Glob>,
+    threads: Option<usize>,
+    lexer: Lexer,
+    options: ParserOptions,
+}
+
+/// The outcome of parsing one file of a project.
+#[derive(Debug)]
+pub struct ParsedFile {
+    /// The path of the file, i.e. the project's root joined with its relative path.
+    pub path: PathBuf,
+    pub source: Vec<u8>,
+    pub result: Result<Program<'static>, FileError>,
+}
+
+#[derive(Debug)]
+pub enum FileError {
+    Read(io::Error),
+    Parse(ParseError),
+    /// The lexer or parser panicked, with the panic's message.
+    Panic(String),
+}
+
+impl Display for FileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Read(error) => write!(f, "failed to read file: {}", error),
+            Self::Parse(error) => error.fmt(f),
+            Self::Panic(message) => write!(f, "the parser panicked: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for FileError {}
+
+/// Every file of a project that was parsed, in the order of their paths.
+#[derive(Debug)]
+pub struct ProjectResult {
+    pub files: Vec<ParsedFile>,
+}
+
+impl ProjectResult {
+    /// The files that couldn't be read or parsed, along with why.
+    pub fn errors(&self) -> impl Iterator<Item = (&Path, &FileError)> {
+        self.files
+            .iter()
+            .filter_map(|file| Some((file.path.as_path(), file.result.as_ref().err()?)))
+    }
+
+    /// The diagnostics of every file that couldn't be read or parsed.
+    ///
+    /// Files that couldn't be read, or that made the parser panic, get a
+    /// diagnostic at the start of the file.
+    pub fn diagnostics(&self) -> impl Iterator<Item = (&Path, Diagnostic)> {
+        self.errors().map(|(path, error)| {
+            let diagnostic = match error {
+                FileError::Parse(error) => Diagnostic::from(error),
+                FileError::Read(error) => {
+                    Diagnostic::error("E0065", format!("failed to read file: {}", error), (1, 1))
+                }
+                FileError::Panic(message) => {
+                    Diagnostic::error("E0066", format!("the parser panicked: {}", message), (1, 1))
+                }
+            };
+
+            (path, diagnostic)
+        })
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.errors().next().is_none()
+    }
+}
+
+impl Project {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            include: vec![],
+            exclude: vec![],
+            threads: None,
+            lexer: Lexer::new(),
+            options: ParserOptions::default(),
+        }
+    }
+
+    /// Only include files that match `pattern`, or one of the other include patterns.
+    pub fn with_include(mut self, pattern: &str) -> Self {
+        self.include.push(Glob::new(pattern));
+
+        self
+    }
+
+    /// Leave out files and directories that match `pattern`.
+    pub fn with_exclude(mut self, pattern: &str) -> Self {
+        self.exclude.push(Glob::new(pattern));
+
+        self
+    }
+
+    /// Parse with the given number of threads, rather than one per available core.
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = Some(threads.max(1));
+
+        self
+    }
+
+    pub fn with_lexer(mut self, lexer: Lexer) -> Self {
+        self.lexer = lexer;
+
+        self
+    }
+
+    pub fn with_options(mut self, options: ParserOptions) -> Self {
+        self.options = options;
+
+        self
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Find the files of the project, sorted by their path.
+    pub fn files(&self) -> io::Result<Vec<PathBuf>> {
+        let mut files = vec![];
+        self.walk(&self.root, "", &mut files)?;
+        files.sort();
+
+        Ok(files)
+    }
+
+    /// Find and parse the files of the project.
+    ///
+    /// Only failing to list a directory is an error here. Files that can't be
+    /// read or parsed, or that make the parser panic, are reported in the
+    /// result, and don't stop the others.
+    pub fn parse(&self) -> io::Result<ProjectResult> {
+        let paths = self.files()?;
+
+        let threads = self
+            .threads
+            .or_else(|| thread::available_parallelism().ok().map(usize::from))
+            .unwrap_or(1)
+            .min(paths.len().max(1));
+
+        // Workers take the next file from a shared counter, so a few large files
+        // don't hold up a whole chunk of the project.
+        let next = AtomicUsize::new(0);
+        let files = Mutex::new((0..paths.len()).map(|_| None).collect::<Vec<_>>());
+
+        thread::scope(|scope| {
+            for _ in 0..threads {
+                thread::Builder::new()
+                    .stack_size(WORKER_STACK_SIZE)
+                    .spawn_scoped(scope, || loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(path) = paths.get(index) else {
+                            break;
+                        };
+
+                        let file = self.parse_file(path.clone());
+                        files.lock().unwrap()[index] = Some(file);
+                    })?;
+            }
+
+            Ok::<_, io::Error>(())
+        })?;
+
+        let files = files.into_inner().unwrap().into_iter().flatten().collect();
+
+        Ok(ProjectResult { files })
+    }
+
+    fn parse_file(&self, path: PathBuf) -> ParsedFile {
+        let source = match std::fs::read(&path) {
+            Ok(source) => source,
+            Err(error) => {
+                return ParsedFile {
+                    path,
+                    source: vec![],
+                    result: Err(FileError::Read(error)),
+                }
+            }
+        };
+
+        // A bug in the parser shouldn't take the rest of the project down with it.
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            self.options
+                .parse_source(&self.lexer, &source)
+                .map(|program| program.into_owned())
+        }))
+        .map_err(|payload| FileError::Panic(panic_message(payload)))
+        .and_then(|result| result.map_err(FileError::Parse));
+
+        ParsedFile {
+            path,
+            source,
+            result,
+        }
+    }
+
+    fn walk(&self, directory: &Path, relative: &str, files: &mut Vec<PathBuf>) -> io::Result<()> {
+        for entry in std::fs::read_dir(directory)? {
+            let entry = entry?;
+            let name = entry.file_name();
+            let relative = match relative {
+                "" => name.to_string_lossy().into_owned(),
+                _ => format!("{}/{}", relative, name.to_string_lossy()),
+            };
+
+            if self.exclude.iter().any(|glob| glob.matches(&relative)) {
+                continue;
+            }
+
+            // Symlinked directories aren't followed, since they can form cycles.
+            let path = entry.path();
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                self.walk(&path, &relative, files)?;
+            } else if file_type.is_symlink() && path.is_dir() {
+                continue;
+            } else if self.includes(&relative) {
+                files.push(path);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn includes(&self, relative: &str) -> bool {
+        if self.include.is_empty() {
+            return relative.ends_with(".php");
+        }
+
+        self.include.iter().any(|glob| glob.matches(relative))
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast_ref::<&str>() {
+            Some(message) => message.to_string(),
+            None => "unknown panic".to_string(),
+        },
+    }
+}
+
+/// A pattern matched against `/` separated paths, see `Project`.
+#[derive(Debug, Clone)]
+pub(crate) struct Glob {
+    segments: Vec<String>,
+}
+
+impl Glob {
+    pub(crate) fn new(pattern: &str) -> Self {
+        Self {
+            segments: pattern
+                .split('/')
+                .filter(|segment| !segment.is_empty())
+                .map(String::from)
+                .collect(),
+        }
+    }
+
+    pub(crate) fn matches(&self, path: &str) -> bool {
+        let path = path.split('/').collect::<Vec<_>>();
+
+        matches_segments(&self.segments, &path)
+    }
+}
+
+fn matches_segments(pattern: &[String], path: &[&str]) -> bool {
+    wildcard(
+        pattern,
+        path,
+        |segment| segment == "**",
+        |segment, name| matches_segment(segment.as_bytes(), name.as_bytes()),
+    )
+}
+
+fn matches_segment(pattern: &[u8], name: &[u8]) -> bool {
+    wildcard(
+        pattern,
+        name,
+        |byte| *byte == b'*',
+        |expected, found| *expected == b'?' || expected == found,
+    )
+}
+
+// Match `items` against a pattern in which some elements, the stars, match any
+// number of items. When the rest of the pattern stops matching, only the last
+// star needs to take one more item, since any earlier star could have been
+// extended in the same way. This keeps matching linear in practice, instead of
+// trying every combination of skips.
+fn wildcard<P, T>(
+    pattern: &[P],
+    items: &[T],
+    is_star: impl Fn(&P) -> bool,
+    matches: impl Fn(&P, &T) -> bool,
+) -> bool {
+    let (mut p, mut i) = (0, 0);
+    // The position in the pattern after the last star, and the item it matched up to.
+    let mut star = None;
+
+    while i < items.len() {
+        if p < pattern.len() && is_star(&pattern[p]) {
+            p += 1;
+            star = Some((p, i));
+        } else if p < pattern.len() && matches(&pattern[p], &items[i]) {
+            p += 1;
+            i += 1;
+        } else if let Some((after, matched)) = star {
+            p = after;
+            i = matched + 1;
+            star = Some((after, i));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(is_star)
+}
@@ -0,0 +1,754 @@
+//! Folding ranges and selection ranges, as used by editors to collapse regions
+//! of code and to grow a selection to the syntax around it.
+//!
+//! The AST only records where some of its nodes start, so most of the structure
+//! comes from the tokens: matching brackets, the `:` blocks of the alternative
+//! control flow syntax, heredocs and the statements or list items between them.
+
+use std::ops::Range;
+
+use crate::lexer::source::token_ranges;
+use crate::lexer::source::LineIndex;
+use crate::lexer::token::Span;
+use crate::lexer::token::Token;
+use crate::lexer::token::TokenKind;
+use crate::parser::ast::node::Node;
+use crate::parser::ast::Statement;
+use crate::parser::internal::identifiers::is_reserved_ident;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FoldingRangeKind {
+    Comment,
+    Imports,
+}
+
+/// A region of lines that can be folded away, leaving `start_line` visible.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FoldingRange {
+    /// The first line, which stays visible when the range is folded.
+    pub start_line: usize,
+    /// The last line that's hidden. Closing brackets are left visible.
+    pub end_line: usize,
+    pub kind: Option<FoldingRangeKind>,
+}
+
+/// Compute the folding ranges of `source`, sorted by their first line.
+///
+/// `tokens` must be the tokens `source` was lexed into and `program` what they
+/// were parsed into. Consecutive `use` statements are only found in the AST,
+/// so pass an empty program when the source doesn't parse to still get the rest.
+///
+/// Blocks in braces or in the alternative syntax, arrays, multi-line match arms,
+/// heredocs, consecutive `use` statements and comments spanning several lines,
+/// including runs of single-line comments, are folded.
+pub fn folding_ranges(source: &[u8], tokens: &[Token], program: &[Statement]) -> Vec<FoldingRange> {
+    let structure = Structure::new(source, tokens);
+    let line = |token: usize| structure.tokens[token].span.0;
+
+    let mut ranges = vec![];
+    let mut push = |start_line: usize, end_line: usize, kind| {
+        if end_line > start_line {
+            ranges.push(FoldingRange {
+                start_line,
+                end_line,
+                kind,
+            });
+        }
+    };
+
+    for (i, group) in structure.groups.iter().enumerate() {
+        let foldable = match group.kind {
+            GroupKind::Brace | GroupKind::Bracket | GroupKind::Alternative => true,
+            GroupKind::Heredoc => true,
+            GroupKind::Paren => {
+                group.open > 0 && structure.tokens[group.open - 1].kind == TokenKind::Array
+            }
+            GroupKind::Attribute | GroupKind::Interpolation | GroupKind::String => false,
+        };
+
+        if foldable {
+            let end = if group.closed {
+                structure.last_line(group.close) - 1
+            } else {
+                line(group.close.max(group.open + 1) - 1)
+            };
+
+            push(line(group.open), end, None);
+        }
+
+        if structure.is_match_body(i) {
+            for (start, end) in structure.items(Some(i)) {
+                push(line(start), structure.last_line(end), None);
+            }
+        }
+    }
+
+    // Comments, where consecutive single-line comments fold together.
+    let mut run: Option<(usize, usize)> = None;
+    for (i, token) in structure.tokens.iter().enumerate() {
+        match token.kind {
+            TokenKind::MultiLineComment(_) | TokenKind::DocumentComment(_) => {
+                push(
+                    token.span.0,
+                    structure.last_line(i),
+                    Some(FoldingRangeKind::Comment),
+                );
+            }
+            TokenKind::SingleLineComment(_) | TokenKind::HashMarkComment(_) => {
+                let own_line = i == 0 || structure.tokens[i - 1].span.0 < token.span.0;
+                run = match run {
+                    Some((start, end)) if own_line && token.span.0 == end + 1 => {
+                        Some((start, token.span.0))
+                    }
+                    _ => {
+                        if let Some((start, end)) = run {
+                            push(start, end, Some(FoldingRangeKind::Comment));
+                        }
+
+                        own_line.then_some((token.span.0, token.span.0))
+                    }
+                };
+                continue;
+            }
+            _ => {}
+        }
+
+        if let Some((start, end)) = run.take() {
+            push(start, end, Some(FoldingRangeKind::Comment));
+        }
+    }
+    if let Some((start, end)) = run {
+        push(start, end, Some(FoldingRangeKind::Comment));
+    }
+
+    imports(program, &mut push);
+
+    ranges.sort_by_key(|range| (range.start_line, usize::MAX - range.end_line));
+    ranges.dedup();
+
+    ranges
+}
+
+// Fold runs of `use` statements in the program and in namespaces.
+fn imports(block: &[Statement], push: &mut impl FnMut(usize, usize, Option<FoldingRangeKind>)) {
+    let mut run: Option<(usize, usize)> = None;
+
+    for statement in block {
+        match statement {
+            Statement::Use { .. } | Statement::GroupUse { .. } => {
+                let Some((start, end)) = Node::Statement(statement).range() else {
+                    continue;
+                };
+
+                run = Some(match run {
+                    Some((first, _)) => (first, end.0),
+                    None => (start.0, end.0),
+                });
+                continue;
+            }
+            Statement::Namespace { body, .. } | Statement::BracedNamespace { body, .. } => {
+                imports(body, push);
+            }
+            _ => {}
+        }
+
+        if let Some((start, end)) = run.take() {
+            push(start, end, Some(FoldingRangeKind::Imports));
+        }
+    }
+
+    if let Some((start, end)) = run {
+        push(start, end, Some(FoldingRangeKind::Imports));
+    }
+}
+
+/// Compute the ranges a selection at `offset` grows through, from the innermost out.
+///
+/// Each range contains the one before it, going from the token at the offset
+/// through e.g. the argument it's in, the argument list, the call, the statement
+/// and the block around it, up to the whole source. Ranges are byte offsets.
+///
+/// `tokens` and `program` are as for `folding_ranges`. Nodes of the AST that
+/// record where they start and end, such as closures and parameter lists, add
+/// ranges that the tokens alone don't give.
+pub fn selection_ranges(
+    source: &[u8],
+    tokens: &[Token],
+    program: &[Statement],
+    offset: usize,
+) -> Vec<Range<usize>> {
+    let structure = Structure::new(source, tokens);
+
+    let mut candidates = vec![];
+
+    // The token at the offset, or the one just before it when the offset is at its end.
+    let token = structure
+        .ranges
+        .partition_point(|range| range.start <= offset)
+        .checked_sub(1)
+        .filter(|i| offset <= structure.ranges[*i].end);
+
+    let (mut group, mut unit) = match token {
+        Some(token) => {
+            candidates.push(structure.ranges[token].clone());
+
+            match structure.delimits[token] {
+                Some(group) => (
+                    structure.groups[group].parent,
+                    Some((structure.groups[group].open, structure.last(group))),
+                ),
+                None => (structure.containing[token], Some((token, token))),
+            }
+        }
+        None => {
+            let group = structure
+                .groups
+                .iter()
+                .enumerate()
+                .rfind(|(i, _)| structure.range(*i).contains(&offset))
+                .map(|(i, _)| i);
+
+            (group, None)
+        }
+    };
+
+    // The call or index expression a name is the callee of, as in `bar` in `bar(1)`.
+    if let Some((token, _)) = unit.filter(|(first, last)| first == last) {
+        let call = structure.delimits.get(token + 1).copied().flatten();
+
+        if let Some(call) = call.filter(|call| structure.groups[*call].open == token + 1) {
+            let last = structure.last(call);
+
+            if let Some(chain) = structure.chain(group, token + 1, last) {
+                candidates.push(structure.span_range(chain, last));
+            }
+        }
+    }
+
+    loop {
+        if let Some((first, last)) = unit {
+            if let Some(chain) = structure.chain(group, first, last) {
+                candidates.push(structure.span_range(chain, last));
+            }
+
+            if let Some((start, end)) = structure
+                .items(group)
+                .into_iter()
+                .find(|(start, end)| *start <= first && last <= *end)
+            {
+                candidates.push(structure.span_range(start, end));
+
+                // The statement without its semicolon.
+                if end > start && structure.tokens[end].kind == TokenKind::SemiColon {
+                    candidates.push(structure.span_range(start, end - 1));
+                }
+            }
+        }
+
+        let Some(g) = group else {
+            break;
+        };
+
+        if let Some(inner) = structure.inner(g) {
+            candidates.push(inner);
+        }
+        candidates.push(structure.range(g));
+
+        unit = Some((structure.groups[g].open, structure.last(g)));
+        group = structure.groups[g].parent;
+    }
+
+    // Nodes with both a start and an end are exact, as long as they don't cut a
+    // bracketed group in half.
+    let mut stack = program.iter().map(Node::Statement).collect::<Vec<_>>();
+    while let Some(node) = stack.pop() {
+        let spans = node.spans();
+        if spans.len() >= 2 {
+            let first = structure.token_at(*spans.iter().min().unwrap());
+            let last = structure.token_at(*spans.iter().max().unwrap());
+
+            if let (Some(first), Some(last)) = (first, last) {
+                if structure.level(first) == structure.level(last) {
+                    candidates.push(structure.span_range(first, last));
+                }
+            }
+        }
+
+        stack.extend(node.children());
+    }
+
+    candidates.push(0..source.len());
+
+    candidates.sort_by_key(|range| (range.len(), usize::MAX - range.start));
+
+    let mut ranges: Vec<Range<usize>> = vec![];
+    for range in candidates {
+        let contains_offset = range.start <= offset && offset <= range.end;
+        let contains_last = ranges.last().is_none_or(|last| {
+            *last != range && range.start <= last.start && last.end <= range.end
+        });
+
+        if contains_offset && contains_last {
+            ranges.push(range);
+        }
+    }
+
+    ranges
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GroupKind {
+    Brace,
+    Paren,
+    Bracket,
+    Attribute,
+    // The `{$...}` and `${...}` inside of strings.
+    Interpolation,
+    String,
+    Heredoc,
+    // The body of e.g. `if (...): ... endif;`, up to the `else`, `elseif` or `endif`.
+    Alternative,
+}
+
+#[derive(Debug, Clone)]
+struct Group {
+    kind: GroupKind,
+    open: usize,
+    // The closing delimiter, or, when `closed` is false, the token just past
+    // the end of the group: an `else` that ends an alternative block or the end
+    // of the input for a group that is never closed.
+    close: usize,
+    closed: bool,
+    parent: Option<usize>,
+}
+
+// The tokens of a source, grouped by their delimiters.
+struct Structure<'t, 'a> {
+    tokens: Vec<&'t Token<'a>>,
+    ranges: Vec<Range<usize>>,
+    groups: Vec<Group>,
+    // The group each token opens or closes.
+    delimits: Vec<Option<usize>>,
+    // The innermost group each token is inside of, not counting delimiters.
+    containing: Vec<Option<usize>>,
+    index: LineIndex,
+}
+
+impl<'t, 'a> Structure<'t, 'a> {
+    fn new(source: &[u8], tokens: &'t [Token<'a>]) -> Self {
+        let index = LineIndex::new(source);
+
+        let (tokens, ranges): (Vec<_>, Vec<_>) = tokens
+            .iter()
+            .zip(token_ranges(source, &index, tokens))
+            .filter_map(|(token, range)| Some((token, range?)))
+            .unzip();
+
+        let mut structure = Self {
+            delimits: vec![None; tokens.len()],
+            containing: vec![None; tokens.len()],
+            tokens,
+            ranges,
+            groups: vec![],
+            index,
+        };
+        structure.group();
+
+        structure
+    }
+
+    fn group(&mut self) {
+        let mut stack: Vec<usize> = vec![];
+
+        for i in 0..self.tokens.len() {
+            let kind = &self.tokens[i].kind;
+            let top = stack.last().map(|group| self.groups[*group].kind);
+
+            let closes = match kind {
+                TokenKind::RightBrace => Some(&[GroupKind::Brace, GroupKind::Interpolation][..]),
+                TokenKind::RightParen => Some(&[GroupKind::Paren][..]),
+                TokenKind::RightBracket => Some(&[GroupKind::Bracket, GroupKind::Attribute][..]),
+                TokenKind::EndDocString(..) => Some(&[GroupKind::Heredoc][..]),
+                TokenKind::DoubleQuote | TokenKind::Backtick if top == Some(GroupKind::String) => {
+                    Some(&[GroupKind::String][..])
+                }
+                TokenKind::EndIf
+                | TokenKind::EndWhile
+                | TokenKind::EndFor
+                | TokenKind::EndForeach
+                | TokenKind::EndSwitch
+                | TokenKind::EndDeclare => Some(&[GroupKind::Alternative][..]),
+                _ => None,
+            };
+
+            if let Some(kinds) = closes {
+                if let Some(position) = stack
+                    .iter()
+                    .rposition(|group| kinds.contains(&self.groups[*group].kind))
+                {
+                    for group in stack.drain(position..).rev() {
+                        self.groups[group].close = i;
+                    }
+
+                    let group = self.stack_group(i);
+                    self.groups[group].closed = true;
+                    self.delimits[i] = Some(group);
+                }
+
+                self.containing[i] = stack.last().copied();
+                continue;
+            }
+
+            // An `else` or `elseif` ends the alternative block of the `if` before it,
+            // unless it follows a block in braces.
+            if matches!(kind, TokenKind::Else | TokenKind::ElseIf)
+                && top == Some(GroupKind::Alternative)
+                && i > 0
+                && self.tokens[i - 1].kind != TokenKind::RightBrace
+            {
+                let group = stack.pop().unwrap();
+                self.groups[group].close = i;
+            }
+
+            self.containing[i] = stack.last().copied();
+
+            let opens = match kind {
+                TokenKind::LeftBrace => Some(GroupKind::Brace),
+                TokenKind::CurlyOpen | TokenKind::DollarLeftBrace => Some(GroupKind::Interpolation),
+                TokenKind::LeftParen => Some(GroupKind::Paren),
+                TokenKind::LeftBracket => Some(GroupKind::Bracket),
+                TokenKind::Attribute => Some(GroupKind::Attribute),
+                TokenKind::StartDocString(..) => Some(GroupKind::Heredoc),
+                TokenKind::DoubleQuote | TokenKind::Backtick => Some(GroupKind::String),
+                TokenKind::Colon if self.opens_alternative(i) => Some(GroupKind::Alternative),
+                _ => None,
+            };
+
+            if let Some(kind) = opens {
+                self.groups.push(Group {
+                    kind,
+                    open: i,
+                    close: self.tokens.len(),
+                    closed: false,
+                    parent: stack.last().copied(),
+                });
+
+                let group = self.groups.len() - 1;
+                self.delimits[i] = Some(group);
+                stack.push(group);
+            }
+        }
+    }
+
+    // The group that was just closed at token `i`, i.e. the last one ending there.
+    fn stack_group(&self, i: usize) -> usize {
+        self.groups
+            .iter()
+            .rposition(|group| group.close == i && group.open < i)
+            .unwrap()
+    }
+
+    // Whether the `:` at `i` starts a block in the alternative syntax.
+    fn opens_alternative(&self, i: usize) -> bool {
+        let Some(previous) = i.checked_sub(1) else {
+            return false;
+        };
+
+        match self.tokens[previous].kind {
+            TokenKind::Else => true,
+            TokenKind::RightParen => {
+                let Some(group) = self.delimits[previous] else {
+                    return false;
+                };
+
+                let open = self.groups[group].open;
+                open > 0
+                    && matches!(
+                        self.tokens[open - 1].kind,
+                        TokenKind::If
+                            | TokenKind::ElseIf
+                            | TokenKind::While
+                            | TokenKind::For
+                            | TokenKind::Foreach
+                            | TokenKind::Switch
+                            | TokenKind::Declare
+                    )
+            }
+            _ => false,
+        }
+    }
+
+    // The last token of a group, including its closing delimiter.
+    fn last(&self, group: usize) -> usize {
+        let group = &self.groups[group];
+
+        if group.closed {
+            group.close
+        } else {
+            (group.close - 1).max(group.open)
+        }
+    }
+
+    fn range(&self, group: usize) -> Range<usize> {
+        self.span_range(self.groups[group].open, self.last(group))
+    }
+
+    fn inner(&self, group: usize) -> Option<Range<usize>> {
+        let first = self.groups[group].open + 1;
+        let last = if self.groups[group].closed {
+            self.groups[group].close.checked_sub(1)?
+        } else {
+            self.last(group)
+        };
+
+        (first <= last).then(|| self.span_range(first, last))
+    }
+
+    fn span_range(&self, first: usize, last: usize) -> Range<usize> {
+        self.ranges[first].start..self.ranges[last].end
+    }
+
+    fn last_line(&self, token: usize) -> usize {
+        self.index.span(self.ranges[token].end.saturating_sub(1)).0
+    }
+
+    fn token_at(&self, span: Span) -> Option<usize> {
+        self.tokens
+            .binary_search_by_key(&span, |token| token.span)
+            .ok()
+    }
+
+    // The group a token belongs to, counting delimiters as part of their group's parent.
+    fn level(&self, token: usize) -> Option<usize> {
+        match self.delimits[token] {
+            Some(group) => self.groups[group].parent,
+            None => self.containing[token],
+        }
+    }
+
+    fn is_match_body(&self, group: usize) -> bool {
+        let open = self.groups[group].open;
+        if self.groups[group].kind != GroupKind::Brace || open == 0 {
+            return false;
+        }
+
+        match self.delimits[open - 1] {
+            Some(condition) if self.tokens[open - 1].kind == TokenKind::RightParen => {
+                let start = self.groups[condition].open;
+                start > 0 && self.tokens[start - 1].kind == TokenKind::Match
+            }
+            _ => false,
+        }
+    }
+
+    // The statements or list items directly inside of a group, or at the top level,
+    // as the first and last token of each.
+    fn items(&self, group: Option<usize>) -> Vec<(usize, usize)> {
+        let (start, end, kind) = match group {
+            Some(group) => {
+                let first = self.groups[group].open + 1;
+                let end = if self.groups[group].closed {
+                    self.groups[group].close
+                } else {
+                    self.last(group) + 1
+                };
+
+                (first, end, self.groups[group].kind)
+            }
+            None => (0, self.tokens.len(), GroupKind::Brace),
+        };
+
+        let statements = match kind {
+            GroupKind::Brace => !group.is_some_and(|group| self.is_match_body(group)),
+            GroupKind::Alternative => true,
+            GroupKind::Paren | GroupKind::Bracket | GroupKind::Attribute => false,
+            GroupKind::Interpolation | GroupKind::String | GroupKind::Heredoc => {
+                return match start < end {
+                    true => vec![(start, end - 1)],
+                    false => vec![],
+                };
+            }
+        };
+
+        let mut items = vec![];
+        let mut item: Option<(usize, usize)> = None;
+        let mut i = start;
+        while i < end {
+            let token = &self.tokens[i];
+
+            // Each token or group at this level, taken as a whole.
+            let last = match self.delimits[i] {
+                Some(group) if self.groups[group].open == i => self.last(group).min(end - 1),
+                _ => i,
+            };
+
+            let separator = match token.kind {
+                TokenKind::Comma => true,
+                TokenKind::SemiColon => !statements,
+                TokenKind::OpenTag(_) | TokenKind::CloseTag => statements,
+                _ => false,
+            };
+            let comment = matches!(
+                token.kind,
+                TokenKind::SingleLineComment(_)
+                    | TokenKind::HashMarkComment(_)
+                    | TokenKind::MultiLineComment(_)
+                    | TokenKind::DocumentComment(_)
+            );
+
+            if separator {
+                items.extend(item.take());
+            } else if !(comment && item.is_none()) {
+                let first = item.map_or(i, |(first, _)| first);
+                item = Some((first, last));
+
+                let ends = statements
+                    && match token.kind {
+                        TokenKind::SemiColon | TokenKind::InlineHtml(_) => true,
+                        TokenKind::LeftBrace => self.ends_statement(first, last, end),
+                        _ => false,
+                    };
+                if ends {
+                    items.extend(item.take());
+                }
+            }
+
+            i = last + 1;
+        }
+        items.extend(item);
+
+        items
+    }
+
+    // Whether the block that ends at `last` also ends the statement starting at `first`.
+    fn ends_statement(&self, first: usize, last: usize, end: usize) -> bool {
+        let next = (last + 1..end).find(|i| {
+            !matches!(
+                self.tokens[*i].kind,
+                TokenKind::SingleLineComment(_)
+                    | TokenKind::HashMarkComment(_)
+                    | TokenKind::MultiLineComment(_)
+                    | TokenKind::DocumentComment(_)
+            )
+        });
+
+        let Some(next) = next else {
+            return true;
+        };
+
+        match &self.tokens[next].kind {
+            TokenKind::Else | TokenKind::ElseIf | TokenKind::Catch | TokenKind::Finally => false,
+            TokenKind::While => self.tokens[first].kind != TokenKind::Do,
+            kind => !continues_expression(kind),
+        }
+    }
+
+    // The call or index expression around the group from `first` to `last`,
+    // e.g. `$this->foo(...)` for the argument list of `foo`.
+    fn chain(&self, group: Option<usize>, first: usize, last: usize) -> Option<usize> {
+        let own = self.delimits[first]?;
+        if !matches!(self.groups[own].kind, GroupKind::Paren | GroupKind::Bracket) {
+            return None;
+        }
+
+        let mut start = first;
+        while start > 0 {
+            let previous = start - 1;
+            if self.level(previous) != group {
+                break;
+            }
+
+            let kind = &self.tokens[previous].kind;
+            let member = previous > 0
+                && matches!(
+                    self.tokens[previous - 1].kind,
+                    TokenKind::Arrow | TokenKind::NullsafeArrow | TokenKind::DoubleColon
+                );
+
+            start = match self.delimits[previous] {
+                Some(group)
+                    if self.groups[group].close == previous && self.groups[group].closed =>
+                {
+                    self.groups[group].open
+                }
+                _ if matches!(
+                    kind,
+                    TokenKind::Variable(_)
+                        | TokenKind::Identifier(_)
+                        | TokenKind::QualifiedIdentifier(_)
+                        | TokenKind::FullyQualifiedIdentifier(_)
+                        | TokenKind::Arrow
+                        | TokenKind::NullsafeArrow
+                        | TokenKind::DoubleColon
+                        | TokenKind::Dollar
+                        | TokenKind::Static
+                        | TokenKind::Self_
+                        | TokenKind::Parent
+                        | TokenKind::New
+                ) || (member && is_reserved_ident(kind)) =>
+                {
+                    previous
+                }
+                _ => break,
+            };
+
+            if matches!(kind, TokenKind::New) {
+                break;
+            }
+        }
+
+        (start < first && last >= first).then_some(start)
+    }
+}
+
+// Whether a token after a closing brace continues the expression it closes,
+// as in `function () {} ;` or `match ($x) {} + 1`.
+fn continues_expression(kind: &TokenKind) -> bool {
+    matches!(
+        kind,
+        TokenKind::SemiColon
+            | TokenKind::Comma
+            | TokenKind::RightParen
+            | TokenKind::RightBracket
+            | TokenKind::LeftParen
+            | TokenKind::LeftBracket
+            | TokenKind::Arrow
+            | TokenKind::NullsafeArrow
+            | TokenKind::DoubleColon
+            | TokenKind::DoubleArrow
+            | TokenKind::Question
+            | TokenKind::QuestionColon
+            | TokenKind::Colon
+            | TokenKind::Coalesce
+            | TokenKind::Dot
+            | TokenKind::Plus
+            | TokenKind::Minus
+            | TokenKind::Asterisk
+            | TokenKind::Slash
+            | TokenKind::Percent
+            | TokenKind::Pow
+            | TokenKind::Equals
+            | TokenKind::DoubleEquals
+            | TokenKind::TripleEquals
+            | TokenKind::BangEquals
+            | TokenKind::BangDoubleEquals
+            | TokenKind::AngledLeftRight
+            | TokenKind::Spaceship
+            | TokenKind::LessThan
+            | TokenKind::LessThanEquals
+            | TokenKind::GreaterThan
+            | TokenKind::GreaterThanEquals
+            | TokenKind::BooleanAnd
+            | TokenKind::BooleanOr
+            | TokenKind::LogicalAnd
+            | TokenKind::LogicalOr
+            | TokenKind::LogicalXor
+            | TokenKind::Ampersand
+            | TokenKind::Pipe
+            | TokenKind::Caret
+            | TokenKind::LeftShift
+            | TokenKind::RightShift
+            | TokenKind::Instanceof
+            | TokenKind::CloseTag
+    )
+}
@@ -0,0 +1,614 @@
+//! Semantic classification of tokens for syntax highlighting.
+//!
+//! The lexer alone can't tell `enum` the keyword from `enum` the name of a
+//! method, or a function name from a constant, so tokens are classified using
+//! the AST first and fall back to their lexical kind.
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use crate::lexer::source::token_ranges;
+use crate::lexer::source::LineIndex;
+use crate::lexer::token::Span;
+use crate::lexer::token::Token;
+use crate::lexer::token::TokenKind;
+use crate::parser::ast::identifiers::Identifier;
+use crate::parser::ast::node::Node;
+use crate::parser::ast::traits::TraitUsageAdaptation;
+use crate::parser::ast::try_block::CatchType;
+use crate::parser::ast::variables::Variable;
+use crate::parser::ast::Expression;
+use crate::parser::ast::Statement;
+use crate::parser::ast::Type;
+use crate::parser::ast::UseKind;
+use crate::parser::internal::identifiers::is_reserved_ident;
+
+/// The token types of the LSP legend, indexed by `SemanticTokenKind::lsp`.
+pub const TOKEN_TYPES: &[&str] = &[
+    "keyword",
+    "class",
+    "function",
+    "method",
+    "property",
+    "parameter",
+    "variable",
+    "type",
+    "decorator",
+    "string",
+    "number",
+    "comment",
+];
+
+/// The token modifiers of the LSP legend, as bits of `SemanticTokenKind::lsp`.
+pub const TOKEN_MODIFIERS: &[&str] = &["declaration", "readonly", "documentation"];
+
+const DECLARATION: u32 = 1 << 0;
+const READONLY: u32 = 1 << 1;
+const DOCUMENTATION: u32 = 1 << 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SemanticTokenKind {
+    Keyword,
+    Class,
+    Function,
+    Method,
+    Property,
+    Parameter,
+    Variable,
+    Constant,
+    Type,
+    Attribute,
+    String,
+    Number,
+    Comment,
+    DocblockTag,
+}
+
+impl SemanticTokenKind {
+    /// The index into `TOKEN_TYPES` and the `TOKEN_MODIFIERS` bits for this kind.
+    ///
+    /// Constants are read-only variables and docblock tags are documentation
+    /// keywords, as there are no standard LSP types for them.
+    pub fn lsp(&self) -> (u32, u32) {
+        match self {
+            Self::Keyword => (0, 0),
+            Self::Class => (1, 0),
+            Self::Function => (2, 0),
+            Self::Method => (3, 0),
+            Self::Property => (4, 0),
+            Self::Parameter => (5, 0),
+            Self::Variable => (6, 0),
+            Self::Constant => (6, READONLY),
+            Self::Type => (7, 0),
+            Self::Attribute => (8, 0),
+            Self::String => (9, 0),
+            Self::Number => (10, 0),
+            Self::Comment => (11, 0),
+            Self::DocblockTag => (0, DOCUMENTATION),
+        }
+    }
+}
+
+/// A classified piece of the source, which never spans more than one line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SemanticToken {
+    pub span: Span,
+    /// The length in bytes.
+    pub length: usize,
+    pub kind: SemanticTokenKind,
+    /// Whether this is the name of a class, function, method, property, constant
+    /// or parameter where it's declared.
+    pub declaration: bool,
+}
+
+/// Classify the tokens of `source`.
+///
+/// `tokens` must be the tokens `source` was lexed into and `program` what they
+/// were parsed into. When the source doesn't parse, pass an empty program to
+/// get a classification based on the tokens alone.
+///
+/// Tokens that span several lines, such as comments and heredocs, are split
+/// into one semantic token per line, and tags such as `@param` in docblocks
+/// get their own tokens. Punctuation, operators and names that can't be
+/// classified are left out.
+pub fn semantic_tokens(
+    source: &[u8],
+    tokens: &[Token],
+    program: &[Statement],
+) -> Vec<SemanticToken> {
+    let mut roles = HashMap::new();
+    let mut stack = program
+        .iter()
+        .rev()
+        .map(Node::Statement)
+        .collect::<Vec<_>>();
+    while let Some(node) = stack.pop() {
+        collect_roles(&node, &mut roles);
+
+        stack.extend(node.children().into_iter().rev());
+    }
+
+    let index = LineIndex::new(source);
+    let ranges = token_ranges(source, &index, tokens);
+
+    let mut semantic = vec![];
+    for (token, range) in tokens.iter().zip(ranges) {
+        let Some(Range { start, end }) = range else {
+            continue;
+        };
+
+        let is_name = matches!(
+            token.kind,
+            TokenKind::Identifier(_)
+                | TokenKind::QualifiedIdentifier(_)
+                | TokenKind::FullyQualifiedIdentifier(_)
+                | TokenKind::Variable(_)
+        ) || is_reserved_ident(&token.kind);
+
+        let (kind, declaration) = match roles.get(&token.span) {
+            Some(role) if is_name => *role,
+            _ => match classify(&token.kind, &source[start..end]) {
+                Some(kind) => (kind, false),
+                None => continue,
+            },
+        };
+
+        if kind == SemanticTokenKind::Comment && matches!(token.kind, TokenKind::DocumentComment(_))
+        {
+            docblock(&index, source, start, end, &mut semantic);
+        } else {
+            split_lines(&index, source, start, end, kind, declaration, &mut semantic);
+        }
+    }
+
+    semantic
+}
+
+/// Encode semantic tokens the way the LSP `textDocument/semanticTokens` request
+/// expects them: five numbers per token, with lines and characters relative to
+/// the previous token, and characters counted in UTF-16 code units.
+pub fn encode(source: &[u8], tokens: &[SemanticToken]) -> Vec<u32> {
+    let index = LineIndex::new(source);
+
+    let mut data = Vec::with_capacity(tokens.len() * 5);
+    let mut previous = (0, 0);
+    for token in tokens {
+        let line_start = index.line_start(token.span.0);
+        let start = line_start + token.span.1 - 1;
+
+        let line = token.span.0 as u32 - 1;
+        let character = utf16_len(&source[line_start..start]);
+        let length = utf16_len(&source[start..start + token.length]);
+        let (r#type, mut modifiers) = token.kind.lsp();
+        if token.declaration {
+            modifiers |= DECLARATION;
+        }
+
+        let delta_line = line - previous.0;
+        let delta_character = if delta_line == 0 {
+            character - previous.1
+        } else {
+            character
+        };
+
+        data.extend([delta_line, delta_character, length, r#type, modifiers]);
+        previous = (line, character);
+    }
+
+    data
+}
+
+// Record the role of the names directly inside of a node. Nodes are visited
+// outside in, and the first role recorded for a name wins.
+fn collect_roles(node: &Node, roles: &mut HashMap<Span, (SemanticTokenKind, bool)>) {
+    use SemanticTokenKind as Kind;
+
+    let mut set = |span: Span, kind: Kind, declaration: bool| {
+        roles.entry(span).or_insert((kind, declaration));
+    };
+
+    match node {
+        Node::Statement(statement) => match statement {
+            Statement::Use { uses, kind } => {
+                let kind = match kind {
+                    UseKind::Normal => Kind::Class,
+                    UseKind::Function => Kind::Function,
+                    UseKind::Const => Kind::Constant,
+                };
+
+                for r#use in uses {
+                    set(r#use.name.span, kind, false);
+                    if let Some(alias) = &r#use.alias {
+                        set(alias.span, kind, true);
+                    }
+                }
+            }
+            Statement::Function(function) => set(function.name.span, Kind::Function, true),
+            Statement::Class(class) => set(class.name.span, Kind::Class, true),
+            Statement::Interface(interface) => set(interface.name.span, Kind::Class, true),
+            Statement::Trait(r#trait) => set(r#trait.name.span, Kind::Class, true),
+            Statement::UnitEnum(r#enum) => {
+                set(r#enum.name.span, Kind::Class, true);
+                for name in &r#enum.implements {
+                    set(name.span, Kind::Class, false);
+                }
+            }
+            Statement::BackedEnum(r#enum) => {
+                set(r#enum.name.span, Kind::Class, true);
+                for name in &r#enum.implements {
+                    set(name.span, Kind::Class, false);
+                }
+            }
+            _ => {}
+        },
+        Node::ClassExtends(extends) => set(extends.parent.span, Kind::Class, false),
+        Node::ClassImplements(implements) => {
+            for name in &implements.interfaces {
+                set(name.span, Kind::Class, false);
+            }
+        }
+        Node::InterfaceExtends(extends) => {
+            for name in &extends.parents {
+                set(name.span, Kind::Class, false);
+            }
+        }
+        Node::TraitUsage(usage) => {
+            for name in &usage.traits {
+                set(name.span, Kind::Class, false);
+            }
+        }
+        Node::TraitUsageAdaptation(adaptation) => match adaptation {
+            TraitUsageAdaptation::Alias {
+                r#trait,
+                method,
+                alias,
+                ..
+            } => {
+                if let Some(r#trait) = r#trait {
+                    set(r#trait.span, Kind::Class, false);
+                }
+                set(method.span, Kind::Method, false);
+                set(alias.span, Kind::Method, true);
+            }
+            TraitUsageAdaptation::Visibility {
+                r#trait, method, ..
+            } => {
+                if let Some(r#trait) = r#trait {
+                    set(r#trait.span, Kind::Class, false);
+                }
+                set(method.span, Kind::Method, false);
+            }
+            TraitUsageAdaptation::Precedence {
+                r#trait,
+                method,
+                insteadof,
+            } => {
+                if let Some(r#trait) = r#trait {
+                    set(r#trait.span, Kind::Class, false);
+                }
+                set(method.span, Kind::Method, false);
+                for name in insteadof {
+                    set(name.span, Kind::Class, false);
+                }
+            }
+        },
+        Node::Method(method) => set(method.name.span, Kind::Method, true),
+        Node::FunctionParameter(parameter) => set(parameter.name.span, Kind::Parameter, true),
+        Node::MethodParameter(parameter) => {
+            let kind = if parameter.modifiers.is_empty() {
+                Kind::Parameter
+            } else {
+                Kind::Property
+            };
+
+            set(parameter.name.span, kind, true);
+        }
+        Node::Property(property) => {
+            for entry in &property.entries {
+                set(entry.variable.span, Kind::Property, true);
+            }
+        }
+        Node::VariableProperty(property) => {
+            for entry in &property.entries {
+                set(entry.variable.span, Kind::Property, true);
+            }
+        }
+        Node::ConstantEntry(entry) => set(entry.name.span, Kind::Constant, true),
+        Node::UnitEnumCase(case) => set(case.name.span, Kind::Constant, true),
+        Node::BackedEnumCase(case) => set(case.name.span, Kind::Constant, true),
+        Node::CatchType(types) => match types {
+            CatchType::Identifier(name) => set(name.span, Kind::Class, false),
+            CatchType::Union(names) => {
+                for name in names {
+                    set(name.span, Kind::Class, false);
+                }
+            }
+        },
+        Node::Type(Type::Identifier(name)) => set(name.span, Kind::Class, false),
+        Node::Attribute(attribute) => {
+            let name = match &attribute.expression {
+                Expression::Call { target, .. } => name_span(target),
+                expression => name_span(expression),
+            };
+
+            if let Some(span) = name {
+                set(span, Kind::Attribute, false);
+            }
+        }
+        Node::Arg(arg) => {
+            if let Some(name) = &arg.name {
+                set(name.span, Kind::Parameter, false);
+            }
+        }
+        Node::Expression(expression) => match expression {
+            Expression::Call { target, .. } => {
+                if let Some(span) = name_span(target) {
+                    set(span, Kind::Function, false);
+                }
+            }
+            Expression::New { target, .. } | Expression::Instanceof { right: target, .. } => {
+                if let Some(span) = name_span(target) {
+                    set(span, Kind::Class, false);
+                }
+            }
+            Expression::MethodCall { method, .. }
+            | Expression::NullsafeMethodCall { method, .. } => {
+                if let Some(span) = name_span(method) {
+                    set(span, Kind::Method, false);
+                }
+            }
+            Expression::StaticMethodCall { target, method, .. } => {
+                if let Some(span) = name_span(target) {
+                    set(span, Kind::Class, false);
+                }
+                if let Some(span) = name_span(method) {
+                    set(span, Kind::Method, false);
+                }
+            }
+            Expression::PropertyFetch { property, .. }
+            | Expression::NullsafePropertyFetch { property, .. } => {
+                if let Some(span) = name_span(property) {
+                    set(span, Kind::Property, false);
+                }
+            }
+            Expression::StaticPropertyFetch { target, property } => {
+                if let Some(span) = name_span(target) {
+                    set(span, Kind::Class, false);
+                }
+                if let Expression::Variable(Variable::SimpleVariable(variable)) = property.as_ref()
+                {
+                    set(variable.span, Kind::Property, false);
+                }
+            }
+            Expression::ConstFetch { target, constant } => {
+                if let Some(span) = name_span(target) {
+                    set(span, Kind::Class, false);
+                }
+                if !constant.name.eq_ignore_ascii_case(b"class") {
+                    set(constant.span, Kind::Constant, false);
+                }
+            }
+            Expression::Identifier(Identifier::SimpleIdentifier(name)) => {
+                set(name.span, Kind::Constant, false)
+            }
+            _ => {}
+        },
+        Node::SimpleVariable(variable) => set(variable.span, Kind::Variable, false),
+        _ => {}
+    }
+}
+
+fn name_span(expression: &Expression) -> Option<Span> {
+    match expression {
+        Expression::Identifier(Identifier::SimpleIdentifier(name)) => Some(name.span),
+        _ => None,
+    }
+}
+
+fn classify(kind: &TokenKind, text: &[u8]) -> Option<SemanticTokenKind> {
+    let kind = match kind {
+        TokenKind::SingleLineComment(_)
+        | TokenKind::HashMarkComment(_)
+        | TokenKind::MultiLineComment(_)
+        | TokenKind::DocumentComment(_) => SemanticTokenKind::Comment,
+        TokenKind::LiteralString(_)
+        | TokenKind::StringPart(_)
+        | TokenKind::DoubleQuote
+        | TokenKind::Backtick
+        | TokenKind::DocOpen(_)
+        | TokenKind::StartDocString(..)
+        | TokenKind::EndDocString(..) => SemanticTokenKind::String,
+        TokenKind::LiteralInteger(_) | TokenKind::LiteralFloat(_) => SemanticTokenKind::Number,
+        TokenKind::Variable(_) => SemanticTokenKind::Variable,
+        // Built-in types have no spans in the AST, but any other use of these
+        // names, such as a call to a function named `string`, has one.
+        TokenKind::Identifier(_) if is_builtin_type(text) => SemanticTokenKind::Type,
+        kind if is_keyword(kind) => SemanticTokenKind::Keyword,
+        _ => return None,
+    };
+
+    Some(kind)
+}
+
+fn is_builtin_type(name: &[u8]) -> bool {
+    [
+        "int", "integer", "float", "double", "bool", "boolean", "string", "object", "mixed",
+        "void", "never", "iterable",
+    ]
+    .iter()
+    .any(|builtin| name.eq_ignore_ascii_case(builtin.as_bytes()))
+}
+
+fn is_keyword(kind: &TokenKind) -> bool {
+    is_reserved_ident(kind)
+        || matches!(
+            kind,
+            TokenKind::HaltCompiler
+                | TokenKind::Global
+                | TokenKind::Array
+                | TokenKind::As
+                | TokenKind::Break
+                | TokenKind::Callable
+                | TokenKind::Case
+                | TokenKind::Catch
+                | TokenKind::Class
+                | TokenKind::ClassConstant
+                | TokenKind::TraitConstant
+                | TokenKind::FunctionConstant
+                | TokenKind::MethodConstant
+                | TokenKind::LineConstant
+                | TokenKind::FileConstant
+                | TokenKind::DirConstant
+                | TokenKind::NamespaceConstant
+                | TokenKind::Const
+                | TokenKind::Continue
+                | TokenKind::Declare
+                | TokenKind::Default
+                | TokenKind::Do
+                | TokenKind::Echo
+                | TokenKind::Else
+                | TokenKind::ElseIf
+                | TokenKind::Empty
+                | TokenKind::EndDeclare
+                | TokenKind::EndFor
+                | TokenKind::EndForeach
+                | TokenKind::EndIf
+                | TokenKind::EndSwitch
+                | TokenKind::EndWhile
+                | TokenKind::Extends
+                | TokenKind::Finally
+                | TokenKind::Fn
+                | TokenKind::Foreach
+                | TokenKind::Function
+                | TokenKind::Goto
+                | TokenKind::If
+                | TokenKind::Implements
+                | TokenKind::Insteadof
+                | TokenKind::Unset
+                | TokenKind::Isset
+                | TokenKind::List
+                | TokenKind::Interface
+                | TokenKind::Match
+                | TokenKind::Namespace
+                | TokenKind::Print
+                | TokenKind::Return
+                | TokenKind::Switch
+                | TokenKind::Throw
+                | TokenKind::Trait
+                | TokenKind::Try
+                | TokenKind::Use
+                | TokenKind::Var
+                | TokenKind::Yield
+                | TokenKind::While
+                | TokenKind::ArrayCast
+                | TokenKind::BoolCast
+                | TokenKind::BooleanCast
+                | TokenKind::DoubleCast
+                | TokenKind::RealCast
+                | TokenKind::FloatCast
+                | TokenKind::IntCast
+                | TokenKind::IntegerCast
+                | TokenKind::ObjectCast
+                | TokenKind::UnsetCast
+                | TokenKind::StringCast
+                | TokenKind::BinaryCast
+        )
+}
+
+// Split a docblock into comments and the tags inside of it.
+fn docblock(
+    index: &LineIndex,
+    source: &[u8],
+    start: usize,
+    end: usize,
+    semantic: &mut Vec<SemanticToken>,
+) {
+    let mut from = start;
+    let mut i = start;
+    while i < end {
+        let tag = source[i] == b'@'
+            && (i == start || matches!(source[i - 1], b' ' | b'\t' | b'\n' | b'*' | b'{'))
+            && source.get(i + 1).is_some_and(u8::is_ascii_alphabetic);
+        if !tag {
+            i += 1;
+            continue;
+        }
+
+        let mut j = i + 1;
+        while j < end
+            && (source[j].is_ascii_alphanumeric()
+                || matches!(source[j], b'_' | b'-' | b'\\' | b':'))
+        {
+            j += 1;
+        }
+
+        split_lines(
+            index,
+            source,
+            from,
+            i,
+            SemanticTokenKind::Comment,
+            false,
+            semantic,
+        );
+        split_lines(
+            index,
+            source,
+            i,
+            j,
+            SemanticTokenKind::DocblockTag,
+            false,
+            semantic,
+        );
+        from = j;
+        i = j;
+    }
+
+    split_lines(
+        index,
+        source,
+        from,
+        end,
+        SemanticTokenKind::Comment,
+        false,
+        semantic,
+    );
+}
+
+fn split_lines(
+    index: &LineIndex,
+    source: &[u8],
+    start: usize,
+    end: usize,
+    kind: SemanticTokenKind,
+    declaration: bool,
+    semantic: &mut Vec<SemanticToken>,
+) {
+    let mut start = start;
+    while start < end {
+        let line_end = source[start..end]
+            .iter()
+            .position(|byte| *byte == b'\n')
+            .map_or(end, |position| start + position);
+
+        let mut length = line_end - start;
+        if source[start..line_end].ends_with(b"\r") {
+            length -= 1;
+        }
+
+        if length > 0 {
+            semantic.push(SemanticToken {
+                span: index.span(start),
+                length,
+                kind,
+                declaration,
+            });
+        }
+
+        start = line_end + 1;
+    }
+}
+
+fn utf16_len(bytes: &[u8]) -> u32 {
+    String::from_utf8_lossy(bytes).encode_utf16().count() as u32
+}
@@ -0,0 +1,359 @@
+//! A hierarchical outline of the declarations in a program, as shown by
+//! editors in outline views and breadcrumbs.
+
+use serde::Serialize;
+
+use crate::lexer::token::Span;
+use crate::parser::ast::classes::ClassMember;
+use crate::parser::ast::constant::ClassishConstant;
+use crate::parser::ast::enums::BackedEnumMember;
+use crate::parser::ast::enums::UnitEnumMember;
+use crate::parser::ast::functions::Method;
+use crate::parser::ast::identifiers::SimpleIdentifier;
+use crate::parser::ast::interfaces::InterfaceMember;
+use crate::parser::ast::modifiers::ClassModifier;
+use crate::parser::ast::modifiers::ConstantModifier;
+use crate::parser::ast::modifiers::MethodModifier;
+use crate::parser::ast::modifiers::PromotedPropertyModifier;
+use crate::parser::ast::modifiers::PropertyModifier;
+use crate::parser::ast::node::Node;
+use crate::parser::ast::properties::Property;
+use crate::parser::ast::properties::VariableProperty;
+use crate::parser::ast::traits::TraitMember;
+use crate::parser::ast::variables::SimpleVariable;
+use crate::parser::ast::Program;
+use crate::parser::ast::Statement;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SymbolKind {
+    Namespace,
+    Class,
+    Interface,
+    Trait,
+    Enum,
+    EnumCase,
+    Method,
+    Property,
+    Constant,
+    Function,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SymbolModifier {
+    Public,
+    Protected,
+    Private,
+    Static,
+    Abstract,
+    Final,
+    Readonly,
+}
+
+/// A declaration in the outline of a program.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DocumentSymbol {
+    pub kind: SymbolKind,
+    /// The name as it is written, without the `$` of properties.
+    pub name: String,
+    pub modifiers: Vec<SymbolModifier>,
+    /// The whole declaration, including attributes and modifiers, see `Node::range`.
+    pub span: (Span, Span),
+    pub name_span: Span,
+    /// The members of a class-like symbol, or the declarations inside of a namespace.
+    pub children: Vec<DocumentSymbol>,
+}
+
+/// Build the outline of a program.
+///
+/// Besides top-level declarations, declarations nested in blocks such as
+/// `if (!function_exists('foo')) { ... }` are included as well, but declarations
+/// inside of function and method bodies are not. The global namespace of a
+/// braced `namespace { ... }` block is left out, and its declarations are
+/// listed at the top level instead.
+pub fn symbols(program: &Program) -> Vec<DocumentSymbol> {
+    let mut symbols = vec![];
+
+    for statement in program {
+        statement_symbols(statement, &mut symbols);
+    }
+
+    symbols
+}
+
+fn statement_symbols(statement: &Statement, symbols: &mut Vec<DocumentSymbol>) {
+    let node = Node::Statement(statement);
+
+    match statement {
+        Statement::Namespace { name, body }
+        | Statement::BracedNamespace {
+            name: Some(name),
+            body,
+        } => {
+            let mut children = vec![];
+            for statement in body {
+                statement_symbols(statement, &mut children);
+            }
+
+            symbols.push(symbol(SymbolKind::Namespace, name, node).with_children(children));
+        }
+        Statement::BracedNamespace { name: None, body } => {
+            for statement in body {
+                statement_symbols(statement, symbols);
+            }
+        }
+        Statement::Class(class) => {
+            let modifiers = class
+                .modifiers
+                .modifiers
+                .iter()
+                .map(|modifier| match modifier {
+                    ClassModifier::Final { .. } => SymbolModifier::Final,
+                    ClassModifier::Abstract { .. } => SymbolModifier::Abstract,
+                    ClassModifier::Readonly { .. } => SymbolModifier::Readonly,
+                })
+                .collect();
+
+            let mut children = vec![];
+            for member in &class.members {
+                match member {
+                    ClassMember::Constant(constant) => constant_symbols(constant, &mut children),
+                    ClassMember::Property(property) => property_symbols(property, &mut children),
+                    ClassMember::VariableProperty(property) => {
+                        variable_property_symbols(property, &mut children)
+                    }
+                    ClassMember::Method(method) => method_symbols(method, &mut children),
+                    ClassMember::TraitUsage(_) => {}
+                }
+            }
+
+            symbols.push(
+                symbol(SymbolKind::Class, &class.name, node)
+                    .with_modifiers(modifiers)
+                    .with_children(children),
+            );
+        }
+        Statement::Interface(interface) => {
+            let mut children = vec![];
+            for member in &interface.members {
+                match member {
+                    InterfaceMember::Constant(constant) => {
+                        constant_symbols(constant, &mut children)
+                    }
+                    InterfaceMember::Method(method) => method_symbols(method, &mut children),
+                }
+            }
+
+            symbols
+                .push(symbol(SymbolKind::Interface, &interface.name, node).with_children(children));
+        }
+        Statement::Trait(r#trait) => {
+            let mut children = vec![];
+            for member in &r#trait.members {
+                match member {
+                    TraitMember::Constant(constant) => constant_symbols(constant, &mut children),
+                    TraitMember::Property(property) => property_symbols(property, &mut children),
+                    TraitMember::VariableProperty(property) => {
+                        variable_property_symbols(property, &mut children)
+                    }
+                    TraitMember::Method(method) => method_symbols(method, &mut children),
+                    TraitMember::TraitUsage(_) => {}
+                }
+            }
+
+            symbols.push(symbol(SymbolKind::Trait, &r#trait.name, node).with_children(children));
+        }
+        Statement::UnitEnum(r#enum) => {
+            let mut children = vec![];
+            for member in &r#enum.members {
+                match member {
+                    UnitEnumMember::Case(case) => children.push(symbol(
+                        SymbolKind::EnumCase,
+                        &case.name,
+                        Node::UnitEnumCase(case),
+                    )),
+                    UnitEnumMember::Constant(constant) => constant_symbols(constant, &mut children),
+                    UnitEnumMember::Method(method) => method_symbols(method, &mut children),
+                }
+            }
+
+            symbols.push(symbol(SymbolKind::Enum, &r#enum.name, node).with_children(children));
+        }
+        Statement::BackedEnum(r#enum) => {
+            let mut children = vec![];
+            for member in &r#enum.members {
+                match member {
+                    BackedEnumMember::Case(case) => children.push(symbol(
+                        SymbolKind::EnumCase,
+                        &case.name,
+                        Node::BackedEnumCase(case),
+                    )),
+                    BackedEnumMember::Constant(constant) => {
+                        constant_symbols(constant, &mut children)
+                    }
+                    BackedEnumMember::Method(method) => method_symbols(method, &mut children),
+                }
+            }
+
+            symbols.push(symbol(SymbolKind::Enum, &r#enum.name, node).with_children(children));
+        }
+        Statement::Function(function) => {
+            symbols.push(symbol(SymbolKind::Function, &function.name, node));
+        }
+        Statement::Constant(constant) => {
+            for entry in &constant.entries {
+                symbols.push(symbol(SymbolKind::Constant, &entry.name, node));
+            }
+        }
+        // Look for declarations in nested blocks, without going into functions.
+        _ => {
+            let mut stack = node.children();
+            stack.reverse();
+
+            while let Some(node) = stack.pop() {
+                match node {
+                    Node::Statement(statement) => statement_symbols(statement, symbols),
+                    Node::Closure(_) | Node::ArrowFunction(_) | Node::AnonymousClass(_) => {}
+                    _ => stack.extend(node.children().into_iter().rev()),
+                }
+            }
+        }
+    }
+}
+
+fn method_symbols(method: &Method, symbols: &mut Vec<DocumentSymbol>) {
+    let modifiers = method
+        .modifiers
+        .modifiers
+        .iter()
+        .map(|modifier| match modifier {
+            MethodModifier::Final { .. } => SymbolModifier::Final,
+            MethodModifier::Static { .. } => SymbolModifier::Static,
+            MethodModifier::Abstract { .. } => SymbolModifier::Abstract,
+            MethodModifier::Public { .. } => SymbolModifier::Public,
+            MethodModifier::Protected { .. } => SymbolModifier::Protected,
+            MethodModifier::Private { .. } => SymbolModifier::Private,
+        })
+        .collect();
+
+    symbols.push(
+        symbol(SymbolKind::Method, &method.name, Node::Method(method)).with_modifiers(modifiers),
+    );
+
+    // Promoted constructor parameters declare properties as well.
+    for parameter in &method.parameters.members {
+        if parameter.modifiers.is_empty() {
+            continue;
+        }
+
+        let modifiers = parameter
+            .modifiers
+            .modifiers
+            .iter()
+            .map(|modifier| match modifier {
+                PromotedPropertyModifier::Public { .. } => SymbolModifier::Public,
+                PromotedPropertyModifier::Protected { .. } => SymbolModifier::Protected,
+                PromotedPropertyModifier::Private { .. } => SymbolModifier::Private,
+                PromotedPropertyModifier::Readonly { .. } => SymbolModifier::Readonly,
+            })
+            .collect();
+
+        symbols.push(
+            variable_symbol(&parameter.name, Node::MethodParameter(parameter))
+                .with_modifiers(modifiers),
+        );
+    }
+}
+
+fn property_symbols(property: &Property, symbols: &mut Vec<DocumentSymbol>) {
+    let modifiers = property
+        .modifiers
+        .modifiers
+        .iter()
+        .map(|modifier| match modifier {
+            PropertyModifier::Public { .. } => SymbolModifier::Public,
+            PropertyModifier::Protected { .. } => SymbolModifier::Protected,
+            PropertyModifier::Private { .. } => SymbolModifier::Private,
+            PropertyModifier::Static { .. } => SymbolModifier::Static,
+            PropertyModifier::Readonly { .. } => SymbolModifier::Readonly,
+        })
+        .collect::<Vec<_>>();
+
+    for entry in &property.entries {
+        symbols.push(
+            variable_symbol(&entry.variable, Node::Property(property))
+                .with_modifiers(modifiers.clone()),
+        );
+    }
+}
+
+fn variable_property_symbols(property: &VariableProperty, symbols: &mut Vec<DocumentSymbol>) {
+    for entry in &property.entries {
+        symbols.push(
+            variable_symbol(&entry.variable, Node::VariableProperty(property))
+                .with_modifiers(vec![SymbolModifier::Public]),
+        );
+    }
+}
+
+fn constant_symbols(constant: &ClassishConstant, symbols: &mut Vec<DocumentSymbol>) {
+    let modifiers = constant
+        .modifiers
+        .modifiers
+        .iter()
+        .map(|modifier| match modifier {
+            ConstantModifier::Final { .. } => SymbolModifier::Final,
+            ConstantModifier::Public { .. } => SymbolModifier::Public,
+            ConstantModifier::Protected { .. } => SymbolModifier::Protected,
+            ConstantModifier::Private { .. } => SymbolModifier::Private,
+        })
+        .collect::<Vec<_>>();
+
+    for entry in &constant.entries {
+        symbols.push(
+            symbol(
+                SymbolKind::Constant,
+                &entry.name,
+                Node::ClassishConstant(constant),
+            )
+            .with_modifiers(modifiers.clone()),
+        );
+    }
+}
+
+fn symbol(kind: SymbolKind, name: &SimpleIdentifier, node: Node) -> DocumentSymbol {
+    DocumentSymbol {
+        kind,
+        name: name.name.to_string(),
+        modifiers: vec![],
+        span: node.range().unwrap_or((name.span, name.span)),
+        name_span: name.span,
+        children: vec![],
+    }
+}
+
+fn variable_symbol(variable: &SimpleVariable, node: Node) -> DocumentSymbol {
+    DocumentSymbol {
+        kind: SymbolKind::Property,
+        name: variable.name.to_string(),
+        modifiers: vec![],
+        span: node.range().unwrap_or((variable.span, variable.span)),
+        name_span: variable.span,
+        children: vec![],
+    }
+}
+
+impl DocumentSymbol {
+    fn with_modifiers(mut self, modifiers: Vec<SymbolModifier>) -> Self {
+        self.modifiers = modifiers;
+
+        self
+    }
+
+    fn with_children(mut self, children: Vec<DocumentSymbol>) -> Self {
+        self.children = children;
+
+        self
+    }
+}
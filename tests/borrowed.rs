@@ -0,0 +1,85 @@
+use php_parser_rs::lexer::token::TokenKind;
+use php_parser_rs::lexer::Lexer;
+use php_parser_rs::parse;
+use php_parser_rs::parser::ast::owned::IntoOwned;
+use php_parser_rs::parser::ast::{Expression, Program, Statement};
+
+static LEXER: Lexer = Lexer::new();
+
+#[test]
+fn test_tokens_borrow_from_source() {
+    let tokens = LEXER
+        .tokenize("<html><?php // comment\nFoo\\Bar::$baz . 'qux' . \"quux\" . 1_000 . 100;")
+        .unwrap();
+
+    let borrowed = tokens
+        .iter()
+        .filter_map(|token| match &token.kind {
+            TokenKind::InlineHtml(bytes)
+            | TokenKind::SingleLineComment(bytes)
+            | TokenKind::QualifiedIdentifier(bytes)
+            | TokenKind::Variable(bytes)
+            | TokenKind::LiteralString(bytes)
+            | TokenKind::LiteralInteger(bytes) => Some((bytes.to_vec(), bytes.is_borrowed())),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        borrowed,
+        vec![
+            (b"<html>".to_vec(), true),
+            (b"// comment".to_vec(), true),
+            (b"Foo\\Bar".to_vec(), true),
+            (b"baz".to_vec(), true),
+            (b"qux".to_vec(), true),
+            (b"quux".to_vec(), true),
+            // Separators are removed from numbers, so they have to be copied.
+            (b"1000".to_vec(), false),
+            (b"100".to_vec(), true),
+        ]
+    );
+}
+
+#[test]
+fn test_escaped_strings_are_owned() {
+    let tokens = LEXER.tokenize(r#"<?php 'it\'s'; "a\nb";"#).unwrap();
+
+    let strings = tokens
+        .iter()
+        .filter_map(|token| match &token.kind {
+            TokenKind::LiteralString(bytes) => Some((bytes.to_vec(), bytes.is_borrowed())),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        strings,
+        vec![(b"it's".to_vec(), false), (b"a\nb".to_vec(), false)]
+    );
+}
+
+#[test]
+fn test_owned_program_outlives_source() {
+    let program: Program<'static> = {
+        let source = String::from("<?php function foo(string $bar) { return $bar; }");
+        let tokens = LEXER.tokenize(&source).unwrap();
+        let program = parse(tokens).unwrap();
+
+        program.into_owned()
+    };
+
+    match &program[..] {
+        [Statement::Function(function)] => {
+            assert_eq!(function.name.name, b"foo");
+            assert!(!function.name.name.is_borrowed());
+            assert!(matches!(
+                &function.body[..],
+                [Statement::Return {
+                    value: Some(Expression::Variable(_))
+                }]
+            ));
+        }
+        _ => panic!("unexpected program: {:?}", program),
+    }
+}
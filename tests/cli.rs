@@ -0,0 +1,104 @@
+mod common;
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+use common::project;
+
+fn run(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_php-parser-rs"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    // The binary may exit before reading its input, e.g. when its arguments are invalid.
+    let written = child.stdin.take().unwrap().write_all(stdin.as_bytes());
+    if let Err(error) = written {
+        assert_eq!(error.kind(), std::io::ErrorKind::BrokenPipe);
+    }
+
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8(output.stderr.clone()).unwrap()
+}
+
+#[test]
+fn test_tokens_from_stdin() {
+    let output = run(&["tokens", "--stdin"], "<?php $a;");
+
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        "1:1\tOpenTag(Full)\n1:7\tVariable(\"a\")\n1:9\tSemiColon\n"
+    );
+}
+
+#[test]
+fn test_ast_as_json() {
+    let output = run(&["ast", "--stdin", "--format", "json"], "<?php echo 1;");
+    assert!(output.status.success());
+
+    let ast: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(ast[0]["Echo"]["values"][0]["LiteralInteger"]["i"], "1");
+
+    let output = run(&["ast", "--stdin", "--format", "yaml"], "<?php echo 1;");
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_ast_reports_parse_errors() {
+    let output = run(&["ast", "--stdin"], "<?php\necho 1\n");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).is_empty());
+    assert!(stderr(&output).starts_with("error[E0012]: unexpected end of file"));
+    assert!(stderr(&output).contains("--> <stdin>:3:1"));
+}
+
+#[test]
+fn test_check_walks_directories() {
+    let root = project(
+        "cli",
+        "check",
+        &[
+            ("src/a.php", "<?php echo 1;"),
+            ("src/nested/b.php", "<?php echo 1"),
+            ("src/notes.txt", "not php"),
+        ],
+    );
+
+    let output = run(&["check", root.join("src").to_str().unwrap()], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).contains("b.php:1:13"));
+    assert_eq!(stderr(&output), "checked 2 files: 1 with errors\n");
+
+    let output = run(
+        &[
+            "check",
+            "--format",
+            "json",
+            root.join("src/a.php").to_str().unwrap(),
+        ],
+        "",
+    );
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "");
+    assert_eq!(stderr(&output), "checked 1 file: 0 with errors\n");
+}
+
+#[test]
+fn test_usage_errors() {
+    assert!(run(&[], "").status.success());
+    assert_eq!(run(&["frobnicate"], "").status.code(), Some(2));
+    assert_eq!(run(&["tokens"], "").status.code(), Some(2));
+    assert_eq!(run(&["check", "--verbose"], "").status.code(), Some(2));
+}
@@ -0,0 +1,32 @@
+//! Helpers shared by the integration tests.
+
+// Each test crate only uses some of these.
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+
+/// Write `files` into a fresh directory under the target directory, and return its path.
+///
+/// `group` keeps test crates that run at the same time out of each other's way.
+pub fn project(group: &str, name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let root = PathBuf::from(env!("CARGO_TARGET_TMPDIR"))
+        .join(group)
+        .join(name);
+    let _ = std::fs::remove_dir_all(&root);
+
+    for (path, code) in files {
+        let path = root.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, code).unwrap();
+    }
+
+    root
+}
+
+/// `path` relative to `root`, with `/` as the separator.
+pub fn relative(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap()
+        .to_string_lossy()
+        .replace('\\', "/")
+}
@@ -0,0 +1,203 @@
+mod common;
+
+use std::path::PathBuf;
+
+use php_parser_rs::composer::{Composer, ComposerError, Violation};
+use php_parser_rs::index::DeclarationIndex;
+
+use common::{project, relative};
+
+const COMPOSER_JSON: &str = r#"{
+    "name": "acme/app",
+    "autoload": {
+        "psr-4": {
+            "App\\": "src/",
+            "App\\Http\\": ["http/", "legacy/http/"]
+        },
+        "psr-0": { "Legacy_": "lib/" },
+        "classmap": ["database/"],
+        "files": ["helpers.php", "missing.php"],
+        "exclude-from-classmap": ["database/old/"]
+    },
+    "autoload-dev": {
+        "psr-4": { "App\\Tests\\": "tests/" }
+    }
+}"#;
+
+fn app(name: &str) -> (PathBuf, Composer, DeclarationIndex) {
+    let root = project(
+        "composer",
+        name,
+        &[
+            ("composer.json", COMPOSER_JSON),
+            (
+                "src/Models/User.php",
+                "<?php namespace App\\Models; class User {}",
+            ),
+            (
+                "src/Models/Post.php",
+                "<?php namespace App\\Models; class Article {}",
+            ),
+            ("src/Kernel.php", "<?php namespace Other; class Kernel {}"),
+            (
+                "http/Controller.php",
+                "<?php namespace App\\Http; abstract class Controller {}",
+            ),
+            (
+                "lib/Legacy/Mail/Sender.php",
+                "<?php class Legacy_Mail_Sender {}",
+            ),
+            (
+                "database/seeds.php",
+                "<?php class Seeder {} class UserSeeder {}",
+            ),
+            ("database/more.php", "<?php class UserSeeder {}"),
+            (
+                "database/old/Seeder.php",
+                "<?php class OldSeeder {} class Seeder {}",
+            ),
+            ("helpers.php", "<?php function app() {} class Helper {}"),
+            (
+                "tests/UserTest.php",
+                "<?php namespace App\\Tests; class UserTest {}",
+            ),
+            (
+                "vendor/acme/lib/src/Foo.php",
+                "<?php namespace App; class Foo {}",
+            ),
+            ("bin/console.php", "<?php class Console {}"),
+        ],
+    );
+
+    let composer = Composer::load(&root).unwrap();
+    let result = composer.project().parse().unwrap();
+    assert!(result.is_ok());
+
+    let index = DeclarationIndex::from_project(&result);
+
+    (root, composer, index)
+}
+
+#[test]
+fn test_autoload_sections_are_read() {
+    let composer = Composer::from_json("", COMPOSER_JSON.as_bytes()).unwrap();
+
+    assert_eq!(
+        composer.autoload.psr4,
+        vec![
+            ("App\\".to_string(), vec!["src/".to_string()]),
+            (
+                "App\\Http\\".to_string(),
+                vec!["http/".to_string(), "legacy/http/".to_string()]
+            ),
+        ]
+    );
+    assert_eq!(composer.autoload.classmap, vec!["database/"]);
+    assert_eq!(composer.autoload_dev.psr4.len(), 1);
+    assert_eq!(composer.vendor_dir, "vendor");
+
+    assert!(matches!(
+        Composer::from_json("", br#"{ "autoload": { "psr-4": ["src/"] } }"#),
+        Err(ComposerError::Invalid(_))
+    ));
+    assert!(matches!(
+        Composer::from_json("", b"{"),
+        Err(ComposerError::Json(_))
+    ));
+}
+
+#[test]
+fn test_expected_paths() {
+    let composer = Composer::from_json("/app", COMPOSER_JSON.as_bytes()).unwrap();
+
+    assert_eq!(
+        composer.expected_paths("\\App\\Http\\Controller"),
+        vec![
+            PathBuf::from("/app/http/Controller.php"),
+            PathBuf::from("/app/legacy/http/Controller.php"),
+            PathBuf::from("/app/src/Http/Controller.php"),
+        ]
+    );
+    assert_eq!(
+        composer.expected_paths("Legacy_Mail_Sender"),
+        vec![PathBuf::from("/app/lib/Legacy/Mail/Sender.php")]
+    );
+    assert!(composer.expected_paths("Vendor\\Foo").is_empty());
+}
+
+#[test]
+fn test_violations() {
+    let (root, composer, index) = app("violations");
+
+    let violations = composer.verify(&index);
+    let described = violations
+        .iter()
+        .map(|violation| match violation {
+            Violation::Misplaced {
+                class,
+                path,
+                expected,
+                ..
+            } => format!(
+                "misplaced {} in {}, expected {:?}",
+                class,
+                relative(&root, path),
+                expected.as_ref().map(|expected| relative(&root, expected))
+            ),
+            Violation::Ambiguous { class, paths } => format!(
+                "ambiguous {} in {:?}",
+                class,
+                paths
+                    .iter()
+                    .map(|path| relative(&root, path))
+                    .collect::<Vec<_>>()
+            ),
+            Violation::MissingPath { path } => format!("missing {}", relative(&root, path)),
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        described,
+        vec![
+            "missing missing.php",
+            "misplaced Other\\Kernel in src/Kernel.php, expected None",
+            "misplaced App\\Models\\Article in src/Models/Post.php, expected Some(\"src/Models/Article.php\")",
+            "ambiguous UserSeeder in [\"database/more.php\", \"database/seeds.php\"]",
+        ]
+    );
+
+    assert!(violations[2]
+        .to_string()
+        .starts_with("class App\\Models\\Article in "));
+}
+
+#[test]
+fn test_classmap() {
+    let (root, composer, index) = app("classmap");
+
+    let classmap = composer.classmap(&index);
+    let classes = classmap
+        .classes
+        .iter()
+        .map(|(class, path)| (class.as_str(), relative(&root, path)))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        classes,
+        vec![
+            ("App\\Http\\Controller", "http/Controller.php".to_string()),
+            ("App\\Models\\User", "src/Models/User.php".to_string()),
+            ("App\\Tests\\UserTest", "tests/UserTest.php".to_string()),
+            (
+                "Legacy_Mail_Sender",
+                "lib/Legacy/Mail/Sender.php".to_string()
+            ),
+            ("Seeder", "database/seeds.php".to_string()),
+            ("UserSeeder", "database/more.php".to_string()),
+        ]
+    );
+
+    let php = classmap.to_php(&root);
+    assert!(php.starts_with("<?php\n\nreturn array(\n"));
+    assert!(php.contains("    'App\\\\Models\\\\User' => __DIR__ . '/src/Models/User.php',\n"));
+}
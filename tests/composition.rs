@@ -0,0 +1,167 @@
+use std::path::Path;
+
+use php_parser_rs::composition::{compose, Composition, Conflict};
+use php_parser_rs::hierarchy::{class_likes, Hierarchy};
+use php_parser_rs::lexer::Lexer;
+use php_parser_rs::parse;
+use php_parser_rs::parser::ast::modifiers::Visibility;
+
+static LEXER: Lexer = Lexer::new();
+
+fn compose_class(code: &str, class: &str) -> Composition {
+    let tokens = LEXER.tokenize(code).unwrap();
+    let program = parse(tokens).unwrap();
+
+    let mut hierarchy = Hierarchy::new();
+    hierarchy.update("test.php", class_likes(Path::new("test.php"), &program));
+
+    let (_, class_like) = hierarchy.get(class).unwrap();
+    compose(&hierarchy, class_like)
+}
+
+fn methods(composition: &Composition) -> Vec<(&str, &str, Visibility)> {
+    composition
+        .methods
+        .iter()
+        .map(|method| {
+            (
+                method.name.as_str(),
+                method.origin.as_str(),
+                method.visibility.clone(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_adaptations() {
+    let composition = compose_class(
+        r#"<?php
+
+namespace App;
+
+trait Hello {
+    public function hello() {}
+    public function greet() {}
+}
+
+trait World {
+    public function hello() {}
+    private function world() {}
+}
+
+trait Greeting {
+    use Hello;
+
+    abstract public function name();
+}
+
+class Greeter {
+    use Greeting, World {
+        Greeting::hello insteadof World;
+        World::hello as protected worldHello;
+        world as public;
+        greet as private;
+    }
+
+    public function name() {}
+    public function greet() {}
+}
+"#,
+        "App\\Greeter",
+    );
+
+    assert!(composition.conflicts.is_empty());
+    assert_eq!(
+        methods(&composition),
+        vec![
+            ("name", "App\\Greeter", Visibility::Public),
+            ("greet", "App\\Greeter", Visibility::Public),
+            ("hello", "App\\Hello", Visibility::Public),
+            ("worldHello", "App\\World", Visibility::Protected),
+            ("world", "App\\World", Visibility::Public),
+        ]
+    );
+
+    let hello = composition.method("HELLO").unwrap();
+    assert_eq!(hello.r#trait.as_deref(), Some("App\\Greeting"));
+    assert_eq!(composition.method("worldhello").unwrap().method, "hello");
+}
+
+#[test]
+fn test_abstract_methods_are_satisfied_by_other_traits() {
+    let composition = compose_class(
+        r#"<?php
+trait HasName { abstract public function name(); }
+trait Named { public function name() { return 'named'; } }
+class User { use HasName, Named; }
+"#,
+        "User",
+    );
+
+    assert!(composition.conflicts.is_empty());
+    assert_eq!(
+        methods(&composition),
+        vec![("name", "Named", Visibility::Public)]
+    );
+    assert!(!composition.methods[0].is_abstract);
+}
+
+#[test]
+fn test_conflicts() {
+    let composition = compose_class(
+        r#"<?php
+trait A { public function hello() {} public function bye() {} }
+trait B { public function hello() {} public function bye() {} }
+trait C { use A; }
+class Greeter {
+    use A, B, C {
+        A::bye insteadof B;
+        A::missing as other;
+        D::bye insteadof B;
+        hello as hi;
+    }
+}
+"#,
+        "Greeter",
+    );
+
+    assert_eq!(
+        composition.conflicts,
+        vec![
+            Conflict::MissingMethod {
+                r#trait: Some("A".to_string()),
+                method: "missing".to_string(),
+                span: (8, 12),
+            },
+            Conflict::UnusedTrait {
+                r#trait: "D".to_string(),
+                span: (9, 9),
+            },
+            Conflict::AmbiguousAlias {
+                method: "hello".to_string(),
+                traits: vec!["A".to_string(), "B".to_string(), "C".to_string()],
+                span: (10, 9),
+            },
+            Conflict::Collision {
+                method: "hello".to_string(),
+                first: "A".to_string(),
+                second: "B".to_string(),
+                span: (6, 12),
+            },
+            Conflict::Collision {
+                method: "hi".to_string(),
+                first: "A".to_string(),
+                second: "B".to_string(),
+                span: (6, 12),
+            },
+        ]
+    );
+
+    // `C` brings in the same methods of `A` again, which isn't a collision.
+    assert_eq!(
+        composition.conflicts[3].to_string(),
+        "method `hello` of trait `B` collides with the one of trait `A`"
+    );
+    assert_eq!(composition.method("bye").unwrap().origin, "A");
+}
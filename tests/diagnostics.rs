@@ -0,0 +1,136 @@
+use php_parser_rs::diagnostics::{render, Diagnostic, RenderFormat, Severity};
+use php_parser_rs::lexer::Lexer;
+use pretty_assertions::assert_str_eq;
+
+static LEXER: Lexer = Lexer::new();
+
+fn diagnostic(code: &str) -> Diagnostic {
+    match LEXER.tokenize(code) {
+        Ok(tokens) => php_parser_rs::parse(tokens).err().unwrap().into(),
+        Err(error) => error.into(),
+    }
+}
+
+#[test]
+fn test_render_plain() {
+    let code = "<?php\n\nmatch ($a) {\n    default => 1,\n\n\n    default => 2,\n};\n";
+    let output = render(
+        &diagnostic(code),
+        code.as_bytes(),
+        Some("index.php"),
+        RenderFormat::Plain,
+    );
+
+    assert_str_eq!(
+        output,
+        "\
+error[E0045]: Match expressions may only contain one default arm
+ --> index.php:7:5
+  |
+4 |     default => 1,
+  |     ------- first default arm
+...
+7 |     default => 2,
+  |     ^^^^^^^
+  |
+  = help: remove one of the `default` arms
+"
+    );
+}
+
+#[test]
+fn test_render_keeps_tabs_and_points_at_end_of_file() {
+    let code = "<?php\nclass Foo {\n\tpublic private $bar;\n}";
+    let output = render(
+        &diagnostic(code),
+        code.as_bytes(),
+        None,
+        RenderFormat::Plain,
+    );
+
+    assert!(output.contains(
+        "3 | \tpublic private $bar;\n  | \t------ first visibility modifier\n  | \t       ^^^^^^^\n"
+    ));
+
+    let code = "<?php $a = ";
+    let output = render(
+        &diagnostic(code),
+        code.as_bytes(),
+        None,
+        RenderFormat::Plain,
+    );
+
+    assert_str_eq!(
+        output,
+        "\
+error[E0012]: unexpected end of file, expecting an expression
+ --> <input>:1:12
+  |
+1 | <?php $a =
+  |            ^
+"
+    );
+}
+
+#[test]
+fn test_render_syntax_error() {
+    let code = "<?php \"\\u{zz}\"";
+    let output = render(
+        &diagnostic(code),
+        code.as_bytes(),
+        None,
+        RenderFormat::Plain,
+    );
+
+    assert!(output.starts_with("error[E0007]: invalid unicode escape\n"));
+    assert!(output.ends_with("1 | <?php \"\\u{zz}\"\n  |           ^^\n"));
+}
+
+#[test]
+fn test_render_ansi() {
+    let code = "<?php $a = ;";
+    let output = render(&diagnostic(code), code.as_bytes(), None, RenderFormat::Ansi);
+
+    assert!(output.starts_with("\x1b[1;31merror[E0012]\x1b[0m"));
+    assert!(output.contains("\x1b[1;31m^\x1b[0m"));
+}
+
+#[test]
+fn test_render_json() {
+    let code = "<?php class Foo { public private $bar; }";
+    let output = render(
+        &diagnostic(code),
+        code.as_bytes(),
+        Some("index.php"),
+        RenderFormat::Json,
+    );
+
+    assert_eq!(
+        output,
+        r#"{"code":"E0015","labels":[{"message":"first visibility modifier","span":[1,19]}],"message":"Multiple visibility modifiers are not allowed","notes":["a member can only have one of `public`, `protected` or `private`"],"path":"index.php","severity":"error","span":[1,26]}"#
+    );
+}
+
+#[test]
+fn test_custom_diagnostic() {
+    let diagnostic = Diagnostic {
+        severity: Severity::Warning,
+        ..Diagnostic::error("W0001", "unused variable", (1, 7))
+    }
+    .with_note("prefix the variable with an underscore");
+
+    let output = render(&diagnostic, b"<?php $foo = 1;", None, RenderFormat::Plain);
+
+    assert_str_eq!(
+        output,
+        "\
+warning[W0001]: unused variable
+ --> <input>:1:7
+  |
+1 | <?php $foo = 1;
+  |       ^^^^
+  |
+  = help: prefix the variable with an underscore
+"
+    );
+}
@@ -0,0 +1,169 @@
+use std::error::Error;
+
+use php_parser_rs::lexer::error::SyntaxError;
+use php_parser_rs::lexer::Lexer;
+use php_parser_rs::parser::error::ParseError;
+use php_parser_rs::parser::error::RelatedSpan;
+
+static LEXER: Lexer = Lexer::new();
+
+fn parse_error(code: &str) -> ParseError {
+    let tokens = LEXER.tokenize(code).unwrap();
+
+    php_parser_rs::parse(tokens).err().unwrap()
+}
+
+#[test]
+fn test_error_codes() {
+    let error = parse_error("<?php match ($a) { default => 1, default => 2 };");
+    assert_eq!(error.code(), "E0045");
+    assert_eq!(error.span(), (1, 34));
+
+    let error = LEXER.tokenize("<?php \"\\u{zz}\"").err().unwrap();
+    assert_eq!(error.code(), "E0007");
+    assert_eq!(ParseError::from(error).code(), "E0007");
+}
+
+#[test]
+fn test_related_spans() {
+    let error = parse_error("<?php match ($a) { default => 1, default => 2 };");
+    assert_eq!(
+        error.related_spans(),
+        vec![RelatedSpan {
+            span: (1, 20),
+            message: "first default arm",
+        }]
+    );
+
+    let error = parse_error("<?php class Foo { public private $bar; }");
+    assert_eq!(
+        error,
+        ParseError::MultipleVisibilityModifiers {
+            span: (1, 26),
+            first: (1, 19),
+        }
+    );
+    assert_eq!(error.related_spans()[0].span, (1, 19));
+
+    let error = parse_error("<?php class Foo { static public static $bar; }");
+    assert_eq!(error.related_spans()[0].span, (1, 19));
+
+    let error = parse_error("<?php $a = ;");
+    assert!(error.related_spans().is_empty());
+}
+
+#[test]
+fn test_error_source() {
+    let error = ParseError::from(SyntaxError::UnexpectedEndOfFile { span: (1, 5) });
+    let source = error.source().unwrap();
+    assert_eq!(
+        source.to_string(),
+        "Syntax Error: unexpected end of file on line 1 column 5"
+    );
+
+    assert!(parse_error("<?php $a = ;").source().is_none());
+}
+
+#[test]
+fn test_error_serialization() {
+    let error = parse_error("<?php class Foo { public private $bar; }");
+    assert_eq!(
+        serde_json::to_string(&error).unwrap(),
+        r#"{"MultipleVisibilityModifiers":{"span":[1,26],"first":[1,19]}}"#
+    );
+
+    let error = ParseError::from(SyntaxError::UnexpectedCharacter {
+        character: b'!',
+        span: (2, 1),
+    });
+    assert_eq!(
+        serde_json::to_string(&error).unwrap(),
+        r#"{"SyntaxError":{"UnexpectedCharacter":{"character":33,"span":[2,1]}}}"#
+    );
+}
+
+#[test]
+fn test_expected_alternatives() {
+    let error = parse_error("<?php foo($a $b);");
+    assert_eq!(
+        error.to_string(),
+        "Parse Error: unexpected token `$b`, expecting `,` or `)` on line 1 column 14"
+    );
+
+    let error = parse_error("<?php for ($i = 0, $j = 0 $i < 10; $i++) {}");
+    assert_eq!(
+        error,
+        ParseError::ExpectedToken {
+            expected: vec!["`,`".into(), "`;`".into()],
+            found: Some("$i".into()),
+            span: (1, 27),
+        }
+    );
+
+    let error = parse_error("<?php $a = ");
+    assert_eq!(
+        error.to_string(),
+        "Parse Error: unexpected end of file, expecting an expression on line 0 column 0"
+    );
+
+    let error = parse_error("<?php $a = );");
+    assert_eq!(
+        error.to_string(),
+        "Parse Error: unexpected token `)`, expecting an expression on line 1 column 12"
+    );
+
+    let error = parse_error("<?php function () use ($a->b) {};");
+    assert_eq!(
+        error.to_string(),
+        "Parse Error: unexpected token `->`, expecting `,` or `)` on line 1 column 26"
+    );
+}
+
+#[test]
+fn test_unterminated_doc_string_labels() {
+    for code in [
+        "<?php $a = <<<EOT",
+        "<?php $a = <<<'EOT",
+        "<?php $a = <<<\"EOT\"",
+    ] {
+        assert!(matches!(
+            LEXER.tokenize(code),
+            Err(SyntaxError::UnexpectedEndOfFile { .. })
+        ));
+    }
+
+    assert!(matches!(
+        LEXER.tokenize("<?php $a = <<<EOT ;"),
+        Err(SyntaxError::UnexpectedCharacter {
+            character: b' ',
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_incomplete_methods_report_positional_errors() {
+    for code in [
+        "<?php class A { function __construct(",
+        "<?php class A extends B { abstract function __construct(",
+        "<?php abstract class A { abstract function foo(",
+        "<?php trait A { function __construct(public",
+        "<?php interface A { function __construct(",
+        "<?php enum A { function foo(",
+        "<?php new class { function __construct(private",
+        "<?php function foo() { return new class { function __construct(",
+    ] {
+        let error = parse_error(code);
+
+        assert!(
+            !matches!(error, ParseError::UnpredictableState { .. }),
+            "{}: {:?}",
+            code,
+            error
+        );
+    }
+
+    let tokens = LEXER.tokenize("<?php function __construct(").unwrap();
+    let error = php_parser_rs::parse_class_member(tokens).err().unwrap();
+    assert!(matches!(error, ParseError::ExpectedToken { .. }));
+}
@@ -0,0 +1,305 @@
+use std::path::Path;
+
+use php_parser_rs::evaluator::{diagnostics, EvaluationError, Evaluator, Value};
+use php_parser_rs::index::NameTable;
+use php_parser_rs::lexer::Lexer;
+use php_parser_rs::names::Scope;
+use php_parser_rs::{parse, parse_expression};
+
+static LEXER: Lexer = Lexer::new();
+
+fn evaluate_with(evaluator: &Evaluator, code: &str) -> Result<Value, EvaluationError> {
+    let code = format!("<?php {}", code);
+    let tokens = LEXER.tokenize(code.as_bytes()).unwrap();
+    let expression = parse_expression(tokens).unwrap();
+
+    evaluator.evaluate(&expression)
+}
+
+fn evaluate(code: &str) -> Value {
+    evaluate_with(&Evaluator::new(), code).unwrap()
+}
+
+fn string(value: &str) -> Value {
+    Value::String(value.to_string().into())
+}
+
+fn invalid(code: &str) -> String {
+    match evaluate_with(&Evaluator::new(), code) {
+        Err(EvaluationError::Invalid { message, .. }) => message,
+        result => panic!("expected an invalid expression, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_arithmetic_and_type_juggling() {
+    assert_eq!(evaluate("1 + 2 * 3"), Value::Int(7));
+    assert_eq!(evaluate("0x1F | 0b100 | 0o10 | 010"), Value::Int(31));
+    assert_eq!(evaluate("7 / 2"), Value::Float(3.5));
+    assert_eq!(evaluate("8 / 2"), Value::Int(4));
+    assert_eq!(evaluate("-7 % 3"), Value::Int(-1));
+    assert_eq!(evaluate("2 ** 10"), Value::Int(1024));
+    assert_eq!(evaluate("2 ** -1"), Value::Float(0.5));
+    assert_eq!(
+        evaluate("PHP_INT_MAX + 1"),
+        Value::Float(9.223372036854776e18)
+    );
+    assert_eq!(
+        evaluate("PHP_INT_MAX + 1 == 9223372036854775808"),
+        Value::Bool(true)
+    );
+    assert_eq!(
+        evaluate("123456789012345678901234567890"),
+        Value::Float(1.2345678901234568e29)
+    );
+    assert_eq!(evaluate("'5' + '1.5'"), Value::Float(6.5));
+    assert_eq!(evaluate("' 12 ' * true"), Value::Int(12));
+    assert_eq!(evaluate("-null"), Value::Int(0));
+    assert_eq!(evaluate("1 << 3 >> 1"), Value::Int(4));
+    assert_eq!(evaluate("~5"), Value::Int(-6));
+    assert_eq!(evaluate("'ab' ^ '  '"), string("AB"));
+
+    assert_eq!(evaluate("'a' . 1 . true . null . 1.5"), string("a111.5"));
+    assert_eq!(evaluate("'' . (0.1 + 0.2)"), string("0.3"));
+    assert_eq!(evaluate("1e25 . ''"), string("1.0E+25"));
+    assert_eq!(
+        evaluate("0.00001 . '|' . -0.0 . '|' . INF"),
+        string("1.0E-5|-0|INF")
+    );
+    assert_eq!(evaluate("\"a\\tb\" . <<<EOT\n  c\n  EOT"), string("a\tbc"));
+
+    assert_eq!(invalid("1 / 0"), "division by zero");
+    assert_eq!(invalid("1 % 0.5"), "modulo by zero");
+    assert_eq!(
+        invalid("'abc' + 1"),
+        "unsupported operand types: string + int"
+    );
+    assert_eq!(invalid("[] - 1"), "unsupported operand types: array - int");
+    assert_eq!(invalid("1 << -1"), "bit shift by negative number");
+    assert_eq!(
+        invalid("[PHP_INT_MAX => 1, 2]"),
+        "cannot add element to the array as the next element is already occupied"
+    );
+}
+
+#[test]
+fn test_array_to_string_conversion_warns() {
+    let evaluator = Evaluator::new();
+
+    assert_eq!(
+        evaluate_with(&evaluator, "[1] . ''").unwrap(),
+        string("Array")
+    );
+    assert_eq!(
+        evaluator
+            .take_warnings()
+            .into_iter()
+            .map(|warning| warning.message)
+            .collect::<Vec<_>>(),
+        vec!["array to string conversion"]
+    );
+
+    assert_eq!(
+        evaluate_with(&evaluator, "true ?: [1] . ''").unwrap(),
+        Value::Bool(true)
+    );
+    assert!(evaluator.take_warnings().is_empty());
+}
+
+#[test]
+fn test_comparisons_and_logic() {
+    let cases = [
+        ("1 == '1.0'", true),
+        ("'abc' == 0", false),
+        ("null == false", true),
+        ("null == ''", true),
+        ("null < -1", true),
+        ("'10' < '9'", false),
+        ("'10' < '9a'", true),
+        ("[1, 2] == [1 => 2, 0 => 1]", true),
+        ("[1, 2] === [1 => 2, 0 => 1]", false),
+        ("[1, 2, 3] > [5, 6]", true),
+        ("[] > 100", true),
+        ("1 === 1.0", false),
+        ("NAN == NAN", false),
+        ("NAN < 1 or NAN > 1 or NAN >= 1", false),
+        ("'abc' != 'ABC' and 1 <> 2", true),
+        ("true xor true", false),
+        ("!0", true),
+    ];
+
+    for (code, expected) in cases {
+        assert_eq!(evaluate(code), Value::Bool(expected), "{}", code);
+    }
+
+    assert_eq!(evaluate("1 <=> 2"), Value::Int(-1));
+    assert_eq!(evaluate("'b' <=> 'a'"), Value::Int(1));
+    assert_eq!(evaluate("0 ?: 'default'"), string("default"));
+    assert_eq!(evaluate("1 ? 'yes' : 'no'"), string("yes"));
+    assert_eq!(evaluate("null ?? 'default'"), string("default"));
+    assert_eq!(evaluate("['a' => 1]['b'] ?? 2"), Value::Int(2));
+
+    // Operands that are skipped are still checked, but not evaluated.
+    assert_eq!(evaluate("false && 1 / 0"), Value::Bool(false));
+    assert_eq!(evaluate("true ? 1 : UNKNOWN"), Value::Int(1));
+    assert!(matches!(
+        evaluate_with(&Evaluator::new(), "true or $variable"),
+        Err(EvaluationError::NotConstant { .. })
+    ));
+}
+
+#[test]
+fn test_arrays_constants_and_context() {
+    let array = evaluate(
+        "['a', 5 => 'b', 'c', '7' => 'd', '07' => 'e', true => 'f', ...[10 => 'g', 'x' => 'h']]",
+    );
+    let Value::Array(array) = array else {
+        panic!("expected an array");
+    };
+    assert_eq!(
+        array
+            .iter()
+            .map(|(key, value)| format!("{}: {:?}", key, value))
+            .collect::<Vec<_>>(),
+        vec![
+            "0: String(\"a\")",
+            "5: String(\"b\")",
+            "6: String(\"c\")",
+            "7: String(\"d\")",
+            "\"07\": String(\"e\")",
+            "1: String(\"f\")",
+            "8: String(\"g\")",
+            "\"x\": String(\"h\")",
+        ]
+    );
+    assert_eq!(evaluate("['a', 'b'][1] . 'xyz'[-1]"), string("bz"));
+    assert_eq!(invalid("['a'][2]"), "undefined array key 2");
+
+    let mut scope = Scope::new("App");
+    scope.import(NameTable::Constant, "Vendor\\VERSION", None);
+
+    let mut evaluator = Evaluator::new()
+        .with_scope(scope)
+        .with_file("/app/src/Models/User.php")
+        .with_class("App\\Models\\User")
+        .with_parent("App\\Models\\Model")
+        .with_function("save");
+    evaluator.define_constant("\\App\\LIMIT", Value::Int(10));
+    evaluator.define_constant("Vendor\\VERSION", string("1.2"));
+    evaluator.define_class_constant("App\\Models\\Model", "TABLE", string("models"));
+
+    let evaluate = |code| evaluate_with(&evaluator, code);
+
+    assert_eq!(evaluate("LIMIT * 2 + 0.5"), Ok(Value::Float(20.5)));
+    assert_eq!(evaluate("\\App\\LIMIT"), Ok(Value::Int(10)));
+    assert_eq!(evaluate("VERSION"), Ok(string("1.2")));
+    assert_eq!(evaluate("PHP_EOL"), Ok(string("\n")));
+    assert_eq!(evaluate("E_ALL & ~E_NOTICE"), Ok(Value::Int(32759)));
+    assert_eq!(evaluate("parent::TABLE"), Ok(string("models")));
+    assert_eq!(evaluate("Other::class"), Ok(string("App\\Other")));
+    assert_eq!(
+        evaluate("__DIR__ . '|' . __LINE__ . '|' . __NAMESPACE__ . '|' . __METHOD__"),
+        Ok(string("/app/src/Models|1|App|App\\Models\\User::save"))
+    );
+
+    assert!(matches!(
+        evaluate("MISSING"),
+        Err(EvaluationError::Unknown { name, .. }) if name == "App\\MISSING"
+    ));
+    assert!(matches!(
+        evaluate("self::MISSING"),
+        Err(EvaluationError::Unknown { name, .. }) if name == "App\\Models\\User::MISSING"
+    ));
+    assert!(matches!(
+        evaluate("static::TABLE"),
+        Err(EvaluationError::NotConstant { .. })
+    ));
+    assert!(matches!(
+        evaluate_with(&Evaluator::new(), "__TRAIT__"),
+        Err(EvaluationError::Unknown { name, .. }) if name == "__TRAIT__"
+    ));
+}
+
+#[test]
+fn test_diagnostics() {
+    let code = r#"<?php
+
+namespace App;
+
+const ZERO = 0;
+define('App\ONE', 1);
+
+#[Route(path: '/' . $prefix)]
+class Controller {
+    const RATIO = ONE / ZERO;
+    const REMOTE = \Vendor\Config::RATIO / 0;
+    public $items = [1, 2, ...[3]];
+    public $callback = foo();
+    public $logger = [new Logger()];
+    const LABEL = 'Items: ' . [1];
+
+    public function __construct(
+        private int $limit = ONE << 2,
+        private int $offset = -ZERO % ZERO,
+    ) {}
+}
+
+enum Status: int {
+    case Active = 1 + ONE;
+    case Inactive = PHP_VERSION_ID > 80000 ? $x : 0;
+}
+
+function handle($request = new Request(), $mode = ['a'][1]) {}
+"#;
+    let tokens = LEXER.tokenize(code.as_bytes()).unwrap();
+    let program = parse(tokens).unwrap();
+
+    let diagnostics = diagnostics(Path::new("index.php"), &program)
+        .into_iter()
+        .map(|diagnostic| (diagnostic.code, diagnostic.message, diagnostic.span))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            (
+                "E0063",
+                "an attribute argument must be a constant expression".to_string(),
+                (8, 21)
+            ),
+            ("E0064", "division by zero".to_string(), (10, 19)),
+            (
+                "E0063",
+                "the default value of a property must be a constant expression".to_string(),
+                (13, 24)
+            ),
+            (
+                "E0063",
+                "new expressions are not supported in the default value of a property".to_string(),
+                (14, 23)
+            ),
+            ("E0067", "array to string conversion".to_string(), (15, 29)),
+            ("E0064", "modulo by zero".to_string(), (19, 31)),
+            (
+                "E0063",
+                "the value of an enum case must be a constant expression".to_string(),
+                (25, 46)
+            ),
+            ("E0064", "undefined array key 1".to_string(), (28, 43)),
+        ]
+    );
+}
+
+#[test]
+fn test_long_operator_chains_evaluate_on_a_small_stack() {
+    std::thread::Builder::new()
+        .stack_size(256 * 1024)
+        .spawn(|| {
+            let code = format!("1{}", " + 1".repeat(50_000));
+
+            assert_eq!(evaluate(&code), Value::Int(50_001));
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+}
@@ -0,0 +1,117 @@
+use php_parser_rs::lexer::Lexer;
+use php_parser_rs::parser::ast::classes::ClassMember;
+use php_parser_rs::parser::ast::{Expression, Statement, Type};
+use php_parser_rs::parser::error::ParseError;
+use php_parser_rs::{parse_class_member, parse_expression, parse_statement, parse_type};
+
+static LEXER: Lexer = Lexer::new();
+
+#[test]
+fn test_parse_expression() {
+    let tokens = LEXER.tokenize("<?php $a->b['c'] ?? null").unwrap();
+    let expression = parse_expression(tokens).unwrap();
+
+    assert!(matches!(expression, Expression::Coalesce { .. }));
+}
+
+#[test]
+fn test_parse_expression_requires_whole_input() {
+    let tokens = LEXER.tokenize("<?php $a + $b; $c").unwrap();
+    let error = parse_expression(tokens).err().unwrap();
+
+    assert!(matches!(error, ParseError::ExpectedToken { .. }));
+    assert_eq!(
+        error.to_string(),
+        "Parse Error: unexpected token `;`, expecting end of file on line 1 column 14"
+    );
+}
+
+#[test]
+fn test_parse_statement() {
+    let tokens = LEXER.tokenize("<?php return $foo;").unwrap();
+    let statement = parse_statement(tokens).unwrap();
+
+    assert!(matches!(statement, Statement::Return { value: Some(_) }));
+}
+
+#[test]
+fn test_parse_type() {
+    let tokens = LEXER.tokenize("<?php int|string|null").unwrap();
+
+    assert_eq!(
+        parse_type(tokens).unwrap(),
+        Type::Union(vec![Type::Integer, Type::String, Type::Null])
+    );
+}
+
+#[test]
+fn test_parse_class_relative_types() {
+    for (code, expected) in [
+        ("<?php self", Type::SelfReference),
+        ("<?php static", Type::StaticReference),
+        ("<?php parent", Type::ParentReference),
+        ("<?php ?self", Type::Nullable(Box::new(Type::SelfReference))),
+    ] {
+        let tokens = LEXER.tokenize(code).unwrap();
+
+        assert_eq!(parse_type(tokens).unwrap(), expected, "{}", code);
+    }
+}
+
+#[test]
+fn test_parse_top_level_statements() {
+    let tokens = LEXER.tokenize("<?php namespace A;").unwrap();
+    assert!(matches!(
+        parse_statement(tokens).unwrap(),
+        Statement::Namespace { .. }
+    ));
+
+    let tokens = LEXER.tokenize("<?php use A\\B;").unwrap();
+    assert!(matches!(
+        parse_statement(tokens).unwrap(),
+        Statement::Use { .. }
+    ));
+
+    let tokens = LEXER.tokenize("<?php __halt_compiler();").unwrap();
+    assert!(matches!(
+        parse_statement(tokens).unwrap(),
+        Statement::HaltCompiler { .. }
+    ));
+}
+
+#[test]
+fn test_parse_class_member() {
+    let tokens = LEXER
+        .tokenize("<?php abstract protected function foo(): int;")
+        .unwrap();
+
+    assert!(matches!(
+        parse_class_member(tokens).unwrap(),
+        ClassMember::Method(_)
+    ));
+
+    let tokens = LEXER
+        .tokenize("<?php public function __construct(private int $a) { parent::__construct(); }")
+        .unwrap();
+
+    assert!(matches!(
+        parse_class_member(tokens).unwrap(),
+        ClassMember::Method(_)
+    ));
+}
+
+#[test]
+fn test_parse_without_open_tag() {
+    let lexer = Lexer::new().in_scripting_mode();
+
+    let tokens = lexer.tokenize("$a->b['c'] ?? null").unwrap();
+    assert!(matches!(
+        parse_expression(tokens).unwrap(),
+        Expression::Coalesce { .. }
+    ));
+
+    let tokens = lexer.tokenize("echo 'foo'; ?>bar").unwrap();
+    let program = php_parser_rs::parse(tokens).unwrap();
+    assert!(matches!(program[0], Statement::Echo { .. }));
+    assert!(matches!(program[1], Statement::InlineHtml(_)));
+}
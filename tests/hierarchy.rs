@@ -0,0 +1,321 @@
+use std::path::Path;
+
+use php_parser_rs::hierarchy::{class_likes, ClassLike, Hierarchy};
+use php_parser_rs::index::{DeclarationKind, NameTable};
+use php_parser_rs::lexer::Lexer;
+use php_parser_rs::names::Scope;
+use php_parser_rs::parse;
+
+static LEXER: Lexer = Lexer::new();
+
+fn declare(path: &str, code: &str) -> Vec<ClassLike> {
+    let tokens = LEXER.tokenize(code).unwrap();
+    let program = parse(tokens).unwrap();
+
+    class_likes(Path::new(path), &program)
+}
+
+fn hierarchy(files: &[(&str, &str)]) -> Hierarchy {
+    let mut hierarchy = Hierarchy::new();
+    for (path, code) in files {
+        hierarchy.update(*path, declare(path, code));
+    }
+
+    hierarchy
+}
+
+fn names<'h>(class_likes: Vec<(&'h Path, &'h ClassLike)>) -> Vec<&'h str> {
+    class_likes
+        .into_iter()
+        .map(|(_, class_like)| class_like.name.as_str())
+        .collect()
+}
+
+#[test]
+fn test_names_are_resolved_with_imports() {
+    let mut scope = Scope::new("App");
+    scope.import(NameTable::Class, "\\Vendor\\Http\\Request", None);
+    scope.import(NameTable::Class, "Vendor\\Models", Some("M"));
+    scope.import(NameTable::Function, "Vendor\\helper", None);
+    scope.import(NameTable::Constant, "Vendor\\VERSION", None);
+
+    assert_eq!(scope.resolve_class("request"), "Vendor\\Http\\Request");
+    assert_eq!(scope.resolve_class("M\\User"), "Vendor\\Models\\User");
+    assert_eq!(scope.resolve_class("\\M\\User"), "M\\User");
+    assert_eq!(scope.resolve_class("namespace\\User"), "App\\User");
+    assert_eq!(scope.resolve_class("User"), "App\\User");
+    assert_eq!(scope.resolve_class("static"), "static");
+
+    assert_eq!(
+        scope.resolve(NameTable::Function, "helper"),
+        "Vendor\\helper"
+    );
+    assert_eq!(scope.resolve(NameTable::Function, "strlen"), "App\\strlen");
+    assert_eq!(
+        scope.fallback(NameTable::Function, "strlen").as_deref(),
+        Some("strlen")
+    );
+    assert_eq!(scope.fallback(NameTable::Function, "helper"), None);
+
+    // Constant names are case sensitive, so `version` isn't imported.
+    assert_eq!(
+        scope.resolve(NameTable::Constant, "version"),
+        "App\\version"
+    );
+    assert_eq!(
+        scope.resolve(NameTable::Constant, "VERSION"),
+        "Vendor\\VERSION"
+    );
+}
+
+#[test]
+fn test_class_likes() {
+    let class_likes = declare(
+        "src/app.php",
+        r#"<?php
+
+namespace App;
+
+use Vendor\Contracts\{Arrayable, Jsonable as Json};
+use Vendor\Model;
+
+interface HasName extends Arrayable, Json {}
+trait Named { use \Vendor\Concerns\HasAttributes; }
+enum Status: string implements HasName { case Active = 'active'; }
+
+final class User extends Model implements HasName {
+    use Named;
+
+    public function handler() {
+        return new class extends User implements \Countable {};
+    }
+}
+"#,
+    );
+
+    let summary = class_likes
+        .iter()
+        .map(|class_like| {
+            (
+                class_like.kind,
+                class_like.name.as_str(),
+                class_like
+                    .supertypes()
+                    .map(|reference| reference.name.as_str())
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        summary,
+        vec![
+            (
+                DeclarationKind::Interface,
+                "App\\HasName",
+                vec![
+                    "Vendor\\Contracts\\Arrayable",
+                    "Vendor\\Contracts\\Jsonable"
+                ]
+            ),
+            (
+                DeclarationKind::Trait,
+                "App\\Named",
+                vec!["Vendor\\Concerns\\HasAttributes"]
+            ),
+            (DeclarationKind::Enum, "App\\Status", vec!["App\\HasName"]),
+            (
+                DeclarationKind::Class,
+                "App\\User",
+                vec!["Vendor\\Model", "App\\HasName", "App\\Named"]
+            ),
+            (
+                DeclarationKind::Class,
+                "class@anonymoussrc/app.php:16:20",
+                vec!["App\\User", "Countable"]
+            ),
+        ]
+    );
+
+    assert_eq!(class_likes[3].span, (12, 13));
+    assert_eq!(class_likes[3].extends[0].span, (12, 26));
+}
+
+#[test]
+fn test_queries() {
+    let hierarchy = hierarchy(&[
+        (
+            "contracts.php",
+            r#"<?php
+namespace App\Contracts;
+interface Entity {}
+interface Named extends Entity, \JsonSerializable {}
+trait Timestamps {}
+trait SoftDeletes { use Timestamps; }
+"#,
+        ),
+        (
+            "models.php",
+            r#"<?php
+namespace App\Models;
+use App\Contracts\{Named, SoftDeletes};
+abstract class Model implements \Stringable {}
+class User extends Model implements Named { use SoftDeletes; }
+class Admin extends User {}
+class Post extends Model { use \App\Contracts\Timestamps; }
+"#,
+        ),
+    ]);
+
+    assert_eq!(
+        hierarchy.ancestors("\\app\\models\\ADMIN"),
+        vec!["App\\Models\\User", "App\\Models\\Model"]
+    );
+    assert_eq!(
+        hierarchy.interfaces("App\\Models\\Admin"),
+        vec![
+            "App\\Contracts\\Named",
+            "App\\Contracts\\Entity",
+            "JsonSerializable",
+            "Stringable"
+        ]
+    );
+    assert_eq!(
+        hierarchy.interfaces("App\\Contracts\\Named"),
+        vec!["App\\Contracts\\Entity", "JsonSerializable"]
+    );
+    assert_eq!(
+        names(hierarchy.descendants("App\\Models\\Model")),
+        vec![
+            "App\\Models\\User",
+            "App\\Models\\Post",
+            "App\\Models\\Admin"
+        ]
+    );
+    assert_eq!(
+        names(hierarchy.descendants("App\\Contracts\\Entity")),
+        vec![
+            "App\\Contracts\\Named",
+            "App\\Models\\User",
+            "App\\Models\\Admin"
+        ]
+    );
+    assert_eq!(
+        names(hierarchy.trait_users("App\\Contracts\\Timestamps")),
+        vec![
+            "App\\Contracts\\SoftDeletes",
+            "App\\Models\\Post",
+            "App\\Models\\User"
+        ]
+    );
+    assert!(hierarchy.diagnostics().is_empty());
+}
+
+#[test]
+fn test_cycles_and_missing_parents() {
+    let mut hierarchy = hierarchy(&[
+        ("a.php", "<?php class A extends B {}"),
+        ("b.php", "<?php class B extends C {} class C extends A {}"),
+        ("d.php", "<?php class D extends A implements Missing {}"),
+    ]);
+
+    let diagnostics = hierarchy
+        .diagnostics()
+        .into_iter()
+        .map(|(path, diagnostic)| {
+            (
+                path.to_str().unwrap(),
+                diagnostic.code,
+                diagnostic.message,
+                diagnostic.span,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            (
+                "a.php",
+                "E0058",
+                "`A` inherits from itself".to_string(),
+                (1, 23)
+            ),
+            (
+                "b.php",
+                "E0058",
+                "`B` inherits from itself".to_string(),
+                (1, 23)
+            ),
+            (
+                "b.php",
+                "E0058",
+                "`C` inherits from itself".to_string(),
+                (1, 44)
+            ),
+            (
+                "d.php",
+                "E0059",
+                "unknown interface `Missing`".to_string(),
+                (1, 36)
+            ),
+        ]
+    );
+    assert_eq!(
+        hierarchy.diagnostics()[0].1.notes,
+        vec!["the cycle is `A` -> `B` -> `C` -> `A`"]
+    );
+
+    // Ancestors stop once they come back around.
+    assert_eq!(hierarchy.ancestors("D"), vec!["A", "B", "C"]);
+
+    hierarchy.update("b.php", declare("b.php", "<?php class B {}"));
+    let diagnostics = hierarchy.diagnostics();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].1.message, "unknown interface `Missing`");
+    assert_eq!(names(hierarchy.descendants("B")), vec!["A", "D"]);
+}
+
+#[test]
+fn test_builtin_and_external_names_are_known() {
+    let code = r#"<?php
+namespace App;
+
+class Subject implements \SplSubject, \SplObserver {}
+class Iterator extends \IteratorIterator {}
+class Numbers extends \SplFixedArray {}
+class Stack extends \SplStack {}
+class Queue extends \SplQueue {}
+class DatabaseError extends \PDOException {}
+class ReflectionError extends \ReflectionException {}
+class Failure extends \AssertionError {}
+class NoMatch extends \UnhandledMatchError {}
+class Arguments extends \ArgumentCountError {}
+class Zone extends \DateTimeZone {}
+class Xml extends \SimpleXMLElement {}
+class Engine implements \Random\Engine {}
+class Cache extends \Redis {}
+class Store extends \Memcached {}
+"#;
+    let mut hierarchy = Hierarchy::new();
+    hierarchy.update("a.php", declare("a.php", code));
+
+    let unknown = |hierarchy: &Hierarchy| {
+        hierarchy
+            .diagnostics()
+            .into_iter()
+            .map(|(_, diagnostic)| diagnostic.message)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        unknown(&hierarchy),
+        vec![
+            "unknown parent class `Redis`",
+            "unknown parent class `Memcached`"
+        ]
+    );
+
+    let mut hierarchy = Hierarchy::new().with_external(["redis", "\\Memcached"]);
+    hierarchy.update("a.php", declare("a.php", code));
+    assert_eq!(unknown(&hierarchy), Vec::<String>::new());
+}
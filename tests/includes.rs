@@ -0,0 +1,255 @@
+mod common;
+
+use std::path::{Path, PathBuf};
+
+use php_parser_rs::includes::{includes, Include, IncludeGraph};
+use php_parser_rs::lexer::Lexer;
+use php_parser_rs::parse;
+use php_parser_rs::parser::ast::IncludeKind;
+use php_parser_rs::project::Project;
+
+use common::project;
+
+static LEXER: Lexer = Lexer::new();
+
+fn paths(path: &str, code: &str) -> Vec<Option<String>> {
+    let tokens = LEXER.tokenize(code).unwrap();
+    let program = parse(tokens).unwrap();
+
+    includes(Path::new(path), &program)
+        .into_iter()
+        .map(|include| include.path.map(|path| path.to_string_lossy().into_owned()))
+        .collect()
+}
+
+#[test]
+fn test_include_paths() {
+    assert_eq!(
+        paths(
+            "/app/src/index.php",
+            r#"<?php
+require 'config.php';
+require_once __DIR__ . '/../lib/util.php';
+include dirname(__FILE__) . DIRECTORY_SEPARATOR . "partials/header.php";
+include(dirname(__DIR__, 2) . '/vendor/autoload.php');
+include '/etc/app.php';
+include $file;
+include __DIR__ . "/$name.php";
+"#,
+        ),
+        vec![
+            Some("/app/src/config.php".to_string()),
+            Some("/app/lib/util.php".to_string()),
+            Some("/app/src/partials/header.php".to_string()),
+            Some("/vendor/autoload.php".to_string()),
+            Some("/etc/app.php".to_string()),
+            None,
+            None,
+        ]
+    );
+}
+
+#[test]
+fn test_include_paths_with_constants() {
+    assert_eq!(
+        paths(
+            "/app/index.php",
+            r#"<?php
+
+namespace App;
+
+const VIEWS = __DIR__ . '/views';
+define('App\LAYOUTS', VIEWS . '/layouts');
+define('LOOP', LOOP . '/x');
+
+function render() {
+    include VIEWS . '/home.php';
+    include \App\LAYOUTS . '/main.php';
+    include LOOP;
+    include UNDEFINED . '/x.php';
+}
+"#,
+        ),
+        vec![
+            Some("/app/views/home.php".to_string()),
+            Some("/app/views/layouts/main.php".to_string()),
+            None,
+            None,
+        ]
+    );
+}
+
+#[test]
+fn test_graph() {
+    let files = [
+        (
+            "index.php",
+            "<?php\nrequire __DIR__ . '/lib/a.php';\ninclude 'missing.php';\nrequire $path;\ninclude 'template.html';",
+        ),
+        ("lib/a.php", "<?php\ninclude_once 'b.php';"),
+        ("lib/b.php", "<?php\nrequire_once __DIR__ . '/a.php';"),
+        ("template.html", "<p>Hello</p>"),
+    ];
+    let root = project("includes", "graph", &files);
+
+    let result = Project::new(&root).parse().unwrap();
+    let graph = IncludeGraph::from_project(&result);
+
+    let index = root.join("index.php");
+    let a = root.join("lib/a.php");
+    let b = root.join("lib/b.php");
+
+    assert_eq!(
+        graph.dependencies(&index),
+        vec![
+            a.as_path(),
+            root.join("missing.php").as_path(),
+            root.join("template.html").as_path()
+        ]
+    );
+    assert_eq!(graph.dependents(&a), vec![index.as_path(), b.as_path()]);
+    assert_eq!(
+        graph.transitive_dependencies(&index),
+        vec![
+            a.as_path(),
+            root.join("missing.php").as_path(),
+            root.join("template.html").as_path(),
+            b.as_path()
+        ]
+    );
+    assert_eq!(graph.transitive_dependencies(&a), vec![b.as_path()]);
+
+    let diagnostics = graph
+        .diagnostics()
+        .into_iter()
+        .map(|(path, diagnostic)| {
+            (
+                path.strip_prefix(&root).unwrap().to_str().unwrap(),
+                diagnostic.code,
+                diagnostic.span,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            ("index.php", "E0061", (3, 1)),
+            ("index.php", "E0060", (4, 1)),
+            ("lib/a.php", "E0062", (2, 1)),
+        ]
+    );
+
+    let cycle = &graph.diagnostics()[2].1;
+    assert_eq!(cycle.message, "file includes itself");
+    assert_eq!(
+        cycle.notes,
+        vec![format!(
+            "the cycle is `{}` -> `{}` -> `{}`",
+            a.display(),
+            b.display(),
+            a.display()
+        )]
+    );
+}
+
+#[test]
+fn test_each_cycle_is_reported_once() {
+    let include = |line: usize, path: &str| Include {
+        kind: IncludeKind::Require,
+        span: (line, 1),
+        path: Some(PathBuf::from(path)),
+    };
+
+    let mut graph = IncludeGraph::new();
+    graph.update("/app/a.php", vec![include(1, "/app/b.php")]);
+    graph.update("/app/b.php", vec![include(1, "/app/c.php")]);
+    graph.update(
+        "/app/c.php",
+        vec![include(1, "/app/a.php"), include(2, "/app/d.php")],
+    );
+    graph.update(
+        "/app/d.php",
+        vec![include(1, "/app/e.php"), include(2, "/app/d.php")],
+    );
+    graph.update("/app/e.php", vec![]);
+    graph.update("/app/f.php", vec![include(1, "/app/a.php")]);
+
+    let diagnostics = graph
+        .diagnostics()
+        .into_iter()
+        .map(|(path, diagnostic)| (path.to_str().unwrap(), diagnostic.span, diagnostic.notes))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            (
+                "/app/a.php",
+                (1, 1),
+                vec![
+                    "the cycle is `/app/a.php` -> `/app/b.php` -> `/app/c.php` -> `/app/a.php`"
+                        .to_string()
+                ]
+            ),
+            (
+                "/app/d.php",
+                (2, 1),
+                vec!["the cycle is `/app/d.php` -> `/app/d.php`".to_string()]
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_graph_with_a_relative_root() {
+    let root = project(
+        "includes",
+        "relative",
+        &[
+            ("index.php", "<?php\nrequire __DIR__ . '/lib/a.php';"),
+            ("lib/a.php", "<?php\ninclude_once 'b.php';"),
+            ("lib/b.php", "<?php\nrequire_once __DIR__ . '/a.php';"),
+        ],
+    );
+    let current = std::env::current_dir().unwrap();
+    let relative = Path::new(".").join(root.strip_prefix(&current).unwrap());
+
+    let result = Project::new(&relative).parse().unwrap();
+    let graph = IncludeGraph::from_project(&result);
+
+    let a = root.join("lib/a.php");
+    let b = root.join("lib/b.php");
+    assert_eq!(
+        graph.dependencies(&relative.join("index.php")),
+        vec![a.as_path()]
+    );
+    assert_eq!(
+        graph.dependents(&relative.join("lib/a.php")),
+        vec![root.join("index.php").as_path(), b.as_path()]
+    );
+
+    let codes = graph
+        .diagnostics()
+        .into_iter()
+        .map(|(path, diagnostic)| (path.strip_prefix(&root).unwrap(), diagnostic.code))
+        .collect::<Vec<_>>();
+    assert_eq!(codes, vec![(Path::new("lib/a.php"), "E0062")]);
+}
+
+#[test]
+fn test_long_include_paths_on_a_small_stack() {
+    std::thread::Builder::new()
+        .stack_size(256 * 1024)
+        .spawn(|| {
+            let code = format!("<?php include 'a'{};", " . '/a'".repeat(50_000));
+
+            assert_eq!(
+                paths("/app/index.php", &code)[0].as_ref().map(String::len),
+                Some("/app/a".len() + 2 * 50_000)
+            );
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+}
@@ -0,0 +1,213 @@
+use php_parser_rs::lexer::Lexer;
+use php_parser_rs::parser::error::ParseError;
+use php_parser_rs::parser::incremental::{Document, Reparse, TextEdit};
+
+static LEXER: Lexer = Lexer::new();
+
+const CODE: &str = r#"<?php
+
+namespace App;
+
+use Foo\Bar;
+
+function greet(string $name): string {
+    return "Hello, {$name}!";
+}
+
+final class Greeter extends Base implements Contract {
+    const GREETING = 'Hello';
+
+    /** The name. */
+    private ?string $name = null;
+
+    public function __construct(private readonly Bar $bar) {}
+
+    public function greet(): string {
+        return greet($this->name ?? 'world');
+    }
+}
+
+echo (new Greeter(new Bar()))->greet();
+"#;
+
+fn assert_same_as_fresh_parse(document: &Document) {
+    let fresh = LEXER
+        .tokenize(document.source())
+        .map_err(ParseError::from)
+        .and_then(php_parser_rs::parse);
+
+    assert_eq!(
+        document.program(),
+        fresh.as_ref(),
+        "incremental result differs for:\n{}",
+        String::from_utf8_lossy(document.source())
+    );
+    assert_eq!(
+        document.tokens().ok(),
+        LEXER.tokenize(document.source()).ok().as_deref()
+    );
+}
+
+fn edit(document: &mut Document, find: &str, replace: &str) -> Reparse {
+    let source = String::from_utf8(document.source().to_vec()).unwrap();
+    let start = source.find(find).unwrap();
+
+    let reparse = document.edit(TextEdit::new(start..start + find.len(), replace));
+    assert_same_as_fresh_parse(document);
+
+    reparse
+}
+
+#[test]
+fn test_edit_inside_a_method_only_reparses_the_method() {
+    let mut document = Document::new(CODE);
+
+    let reparse = edit(&mut document, "'world'", "'everyone'");
+
+    let source = String::from_utf8(document.source().to_vec()).unwrap();
+    let method = source.find("public function greet()").unwrap();
+    assert_eq!(
+        reparse,
+        Reparse::Incremental {
+            range: method..source.rfind("\n}\n").unwrap() + 1
+        }
+    );
+}
+
+#[test]
+fn test_edits_move_the_rest_of_the_tree() {
+    let mut document = Document::new(CODE);
+
+    // New lines and statements shift everything after them.
+    let reparse = edit(
+        &mut document,
+        "    return \"Hello",
+        "    $a = 1;\n\n    $b = [\n        2,\n    ];\n\n    return \"Hello",
+    );
+    assert!(matches!(reparse, Reparse::Incremental { .. }));
+
+    let reparse = edit(&mut document, "const GREETING", "public const GREETING");
+    assert!(matches!(reparse, Reparse::Incremental { .. }));
+
+    let reparse = edit(&mut document, "    /** The name. */\n", "");
+    assert!(matches!(reparse, Reparse::Incremental { .. }));
+
+    let reparse = edit(
+        &mut document,
+        "use Foo\\Bar;",
+        "use Foo\\Bar;\nuse Foo\\Baz;",
+    );
+    assert!(matches!(reparse, Reparse::Incremental { .. }));
+
+    // Further edits keep working on the updated tree.
+    let reparse = edit(&mut document, "$b = [", "$c = [");
+    assert!(matches!(reparse, Reparse::Incremental { .. }));
+    let reparse = edit(&mut document, "'Hello';", "'Hi';");
+    assert!(matches!(reparse, Reparse::Incremental { .. }));
+}
+
+#[test]
+fn test_edits_that_leak_out_of_a_statement_reparse_everything() {
+    let mut document = Document::new(CODE);
+
+    // The string and the comment would run on into the following statements.
+    assert_eq!(edit(&mut document, "'Hello';", "'Hello;"), Reparse::Full);
+    assert_eq!(edit(&mut document, "'Hello;", "'Hello';"), Reparse::Full);
+    assert_eq!(
+        edit(
+            &mut document,
+            "public function greet()",
+            "/* public function greet()"
+        ),
+        Reparse::Full
+    );
+    assert_eq!(
+        edit(&mut document, "    /* public", "    // public"),
+        Reparse::Full
+    );
+    assert_eq!(
+        edit(&mut document, "    // public", "    public"),
+        Reparse::Full
+    );
+
+    // A comment that ends before the next member doesn't.
+    assert!(matches!(
+        edit(&mut document, "const GREETING", "/* x */ const GREETING"),
+        Reparse::Incremental { .. }
+    ));
+    assert!(matches!(
+        edit(&mut document, "/* x */ const GREETING", "const GREETING"),
+        Reparse::Incremental { .. }
+    ));
+
+    // Closing the class early changes the structure of the whole file.
+    assert_eq!(
+        edit(&mut document, "private ?string", "} private ?string"),
+        Reparse::Full
+    );
+    assert!(document.program().is_err());
+
+    assert_eq!(
+        edit(&mut document, "} private ?string", "private ?string"),
+        Reparse::Full
+    );
+    assert!(document.program().is_ok());
+}
+
+#[test]
+fn test_edits_around_inline_html() {
+    let mut document = Document::new("<p><?php echo 1; ?></p>\n<?php\necho 2;\necho 3;\n");
+
+    assert_eq!(edit(&mut document, "echo 1", "echo 10"), Reparse::Full);
+    assert!(matches!(
+        edit(&mut document, "echo 3", "echo 30"),
+        Reparse::Incremental { .. }
+    ));
+    assert_eq!(edit(&mut document, "</p>", "</div>"), Reparse::Full);
+}
+
+// Every small edit anywhere in the file must give the same result as a fresh parse.
+#[test]
+fn test_single_byte_edits_everywhere() {
+    for offset in 0..=CODE.len() {
+        for (range, text) in [
+            (offset..offset, "x"),
+            (offset..offset, "\n"),
+            (offset..offset, "'"),
+            (offset..offset, "}"),
+            (offset..(offset + 1).min(CODE.len()), ""),
+        ] {
+            let mut document = Document::new(CODE);
+            document.edit(TextEdit::new(range, text));
+
+            assert_same_as_fresh_parse(&document);
+        }
+    }
+}
+
+// Typing and then deleting a statement one byte at a time, as an editor would.
+#[test]
+fn test_typing_into_a_document() {
+    let typed = "\n        if ($this->name === null) {\n            return 'nobody';\n        }\n";
+    let offset = CODE.find("        return greet(").unwrap() - 1;
+
+    let mut document = Document::new(CODE);
+    let mut incremental = 0;
+    for (index, byte) in typed.bytes().enumerate() {
+        let reparse = document.edit(TextEdit::new(offset + index..offset + index, vec![byte]));
+        if matches!(reparse, Reparse::Incremental { .. }) {
+            incremental += 1;
+        }
+
+        assert_same_as_fresh_parse(&document);
+    }
+
+    for index in (0..typed.len()).rev() {
+        document.edit(TextEdit::new(offset + index..offset + index + 1, ""));
+
+        assert_same_as_fresh_parse(&document);
+    }
+
+    assert_eq!(document.source(), CODE.as_bytes());
+    assert!(incremental > 0);
+}
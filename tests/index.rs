@@ -0,0 +1,155 @@
+use std::path::Path;
+
+use php_parser_rs::index::{declarations, Declaration, DeclarationIndex, DeclarationKind};
+use php_parser_rs::lexer::Lexer;
+use php_parser_rs::parse;
+
+static LEXER: Lexer = Lexer::new();
+
+fn declare(code: &str) -> Vec<Declaration> {
+    let tokens = LEXER.tokenize(code).unwrap();
+    let program = parse(tokens).unwrap();
+
+    declarations(&program)
+}
+
+fn names(declarations: &[Declaration]) -> Vec<(DeclarationKind, &str)> {
+    declarations
+        .iter()
+        .map(|declaration| (declaration.kind, declaration.name.as_str()))
+        .collect()
+}
+
+#[test]
+fn test_declarations_are_fully_qualified() {
+    let declarations = declare(
+        r#"<?php
+
+namespace App\Models;
+
+const VERSION = 1, NAME = 'app';
+
+interface HasName {}
+trait Named {}
+enum Status { case Active; }
+enum Level: int { case Low = 1; }
+
+final class User implements HasName {
+    use Named;
+
+    public function save() {
+        function helper() {}
+    }
+}
+
+if (!function_exists('App\Models\format')) {
+    function format() {}
+}
+
+define('App\DEBUG', true);
+\define(constant_name: 'GLOBAL_DEBUG', value: false);
+define($dynamic, 1);
+"#,
+    );
+
+    assert_eq!(
+        names(&declarations),
+        vec![
+            (DeclarationKind::Constant, "App\\Models\\VERSION"),
+            (DeclarationKind::Constant, "App\\Models\\NAME"),
+            (DeclarationKind::Interface, "App\\Models\\HasName"),
+            (DeclarationKind::Trait, "App\\Models\\Named"),
+            (DeclarationKind::Enum, "App\\Models\\Status"),
+            (DeclarationKind::Enum, "App\\Models\\Level"),
+            (DeclarationKind::Class, "App\\Models\\User"),
+            (DeclarationKind::Function, "App\\Models\\helper"),
+            (DeclarationKind::Function, "App\\Models\\format"),
+            (DeclarationKind::Constant, "App\\DEBUG"),
+            (DeclarationKind::Constant, "GLOBAL_DEBUG"),
+        ]
+    );
+
+    let user = &declarations[6];
+    assert_eq!(user.name_span, (12, 13));
+    assert_eq!(user.span.0, (12, 1));
+    assert_eq!(user.span.1 .0, 18);
+
+    assert_eq!(declarations[9].name_span, (24, 1));
+}
+
+#[test]
+fn test_braced_namespaces() {
+    let declarations = declare(
+        r#"<?php
+
+namespace Foo {
+    class Bar {}
+}
+
+namespace {
+    class Baz {}
+    function qux() {}
+}
+"#,
+    );
+
+    assert_eq!(
+        names(&declarations),
+        vec![
+            (DeclarationKind::Class, "Foo\\Bar"),
+            (DeclarationKind::Class, "Baz"),
+            (DeclarationKind::Function, "qux"),
+        ]
+    );
+}
+
+#[test]
+fn test_index_lookups_follow_php_name_rules() {
+    let mut index = DeclarationIndex::new();
+    index.update(
+        "src/User.php",
+        declare("<?php namespace App; class User {} const LIMIT = 1;"),
+    );
+    index.update("src/helpers.php", declare("<?php function app_path() {}"));
+
+    let found = index.class("\\app\\USER");
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].0, Path::new("src/User.php"));
+    assert_eq!(found[0].1.name, "App\\User");
+
+    assert_eq!(index.function("APP_PATH").len(), 1);
+    assert_eq!(index.constant("app\\LIMIT").len(), 1);
+    assert!(index.constant("App\\limit").is_empty());
+
+    // Names of different kinds don't clash.
+    assert!(index.function("App\\User").is_empty());
+}
+
+#[test]
+fn test_index_updates_per_file() {
+    let mut index = DeclarationIndex::new();
+    index.update("a.php", declare("<?php class Foo {}"));
+    index.update("b.php", declare("<?php interface Foo {} function foo() {}"));
+
+    let duplicates = index.duplicates();
+    assert_eq!(duplicates.len(), 1);
+    assert_eq!(
+        duplicates[0]
+            .iter()
+            .map(|(path, declaration)| (path.to_str().unwrap(), declaration.kind))
+            .collect::<Vec<_>>(),
+        vec![
+            ("a.php", DeclarationKind::Class),
+            ("b.php", DeclarationKind::Interface),
+        ]
+    );
+
+    index.update("b.php", declare("<?php function foo() {}"));
+    assert!(index.duplicates().is_empty());
+    assert_eq!(index.class("Foo").len(), 1);
+    assert_eq!(index.function("foo").len(), 1);
+
+    assert_eq!(index.remove(Path::new("a.php")).unwrap().len(), 1);
+    assert!(index.class("Foo").is_empty());
+    assert_eq!(index.files().count(), 1);
+}
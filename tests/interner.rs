@@ -0,0 +1,160 @@
+use std::sync::Arc;
+
+use php_parser_rs::lexer::interner::Interner;
+use php_parser_rs::lexer::token::TokenKind;
+use php_parser_rs::lexer::Lexer;
+use php_parser_rs::parse;
+use php_parser_rs::parser::ast::identifiers::Identifier;
+use php_parser_rs::parser::ast::identifiers::SimpleIdentifier;
+use php_parser_rs::parser::ast::{Expression, Statement};
+
+#[test]
+fn test_interning_is_idempotent() {
+    let interner = Interner::new();
+
+    let foo = interner.intern(b"foo");
+    let bar = interner.intern(b"bar");
+
+    assert_ne!(foo, bar);
+    assert_eq!(interner.intern(b"foo"), foo);
+    assert_eq!(interner.get(b"bar"), Some(bar));
+    assert_eq!(interner.get(b"baz"), None);
+    assert_eq!(interner.resolve(foo).as_deref(), Some(&b"foo"[..]));
+    assert_eq!(interner.len(), 2);
+}
+
+#[test]
+fn test_lexer_interns_names_and_keywords() {
+    let interner = Arc::new(Interner::new());
+    let lexer = Lexer::new().with_interner(interner.clone());
+
+    let tokens = lexer
+        .tokenize("<?php $this->foo(self::class, $this, 'foo');")
+        .unwrap();
+
+    let symbol = |kind: TokenKind| {
+        tokens
+            .iter()
+            .find(|token| token.kind == kind)
+            .and_then(|token| token.symbol)
+    };
+
+    let this = symbol(TokenKind::Variable("this".into())).unwrap();
+    assert_eq!(interner.resolve(this).as_deref(), Some(&b"this"[..]));
+    assert_eq!(
+        symbol(TokenKind::Identifier("foo".into())),
+        interner.get(b"foo")
+    );
+    assert_eq!(symbol(TokenKind::Self_), interner.get(b"self"));
+    assert_eq!(symbol(TokenKind::Class), interner.get(b"class"));
+
+    // Strings and punctuation are left alone.
+    assert_eq!(symbol(TokenKind::LiteralString("foo".into())), None);
+    assert_eq!(symbol(TokenKind::Arrow), None);
+
+    // Both occurrences of `$this` share the same symbol.
+    let variables = tokens
+        .iter()
+        .filter(|token| matches!(token.kind, TokenKind::Variable(_)))
+        .map(|token| token.symbol)
+        .collect::<Vec<_>>();
+    assert_eq!(variables, vec![Some(this), Some(this)]);
+}
+
+#[test]
+fn test_parser_keeps_symbols() {
+    let interner = Arc::new(Interner::new());
+    let lexer = Lexer::new().with_interner(interner.clone());
+
+    let program = parse(lexer.tokenize("<?php function foo() {} foo();").unwrap()).unwrap();
+
+    match &program[..] {
+        [Statement::Function(function), Statement::Expression {
+            expr: Expression::Call { target, .. },
+        }] => match target.as_ref() {
+            Expression::Identifier(Identifier::SimpleIdentifier(identifier)) => {
+                assert!(function.name.symbol.is_some());
+                assert_eq!(identifier.symbol, function.name.symbol);
+            }
+            _ => panic!("unexpected call target: {:?}", target),
+        },
+        _ => panic!("unexpected program: {:?}", program),
+    }
+}
+
+#[test]
+fn test_interned_and_plain_code_compare_the_same() {
+    let code = "<?php namespace App; class Foo { public function bar($baz) {} }";
+
+    let interned = Lexer::new().with_interner(Arc::new(Interner::new()));
+    let interned = parse(interned.tokenize(code).unwrap()).unwrap();
+    let other = Lexer::new().with_interner(Arc::new(Interner::new()));
+    let other = parse(other.tokenize(code).unwrap()).unwrap();
+    let plain = parse(Lexer::new().tokenize(code).unwrap()).unwrap();
+
+    assert_eq!(interned, plain);
+    assert_eq!(interned, other);
+    assert_eq!(format!("{:?}", interned), format!("{:?}", plain));
+}
+
+#[test]
+fn test_names_compare_by_symbol() {
+    let interner = Interner::new();
+    let other = Interner::new();
+
+    let foo = interner.intern(b"foo");
+    assert_ne!(foo, other.intern(b"foo"));
+    assert!(!foo.is_comparable_to(other.intern(b"foo")));
+    assert_eq!(other.resolve(foo), None);
+
+    let identifier = |name: &'static str, symbol| SimpleIdentifier {
+        span: (1, 1),
+        name: name.into(),
+        symbol,
+    };
+
+    // Names with symbols from the same interner are equal when their symbols are.
+    assert_eq!(identifier("foo", Some(foo)), identifier("bar", Some(foo)));
+    assert_ne!(
+        identifier("foo", Some(foo)),
+        identifier("foo", Some(interner.intern(b"bar")))
+    );
+
+    // Otherwise their bytes are compared.
+    assert_eq!(
+        identifier("foo", Some(foo)),
+        identifier("foo", Some(other.intern(b"foo")))
+    );
+    assert_eq!(identifier("foo", Some(foo)), identifier("foo", None));
+    assert_ne!(identifier("foo", Some(foo)), identifier("bar", None));
+}
+
+#[test]
+fn test_interner_is_shared_between_threads() {
+    let interner = Arc::new(Interner::new());
+
+    let symbols = std::thread::scope(|scope| {
+        let handles = (0..4)
+            .map(|_| {
+                let lexer = Lexer::new().with_interner(interner.clone());
+
+                scope.spawn(move || {
+                    let tokens = lexer.tokenize("<?php $a = new Foo\\Bar();").unwrap();
+
+                    tokens
+                        .into_iter()
+                        .filter_map(|token| token.symbol)
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect::<Vec<_>>()
+    });
+
+    assert!(symbols.windows(2).all(|pair| pair[0] == pair[1]));
+    assert_eq!(interner.len(), 3);
+}
@@ -0,0 +1,101 @@
+use php_parser_rs::lexer::token::TokenKind;
+use php_parser_rs::lexer::Lexer;
+use php_parser_rs::parser::error::ParseError;
+use php_parser_rs::{CancellationToken, ParserOptions};
+
+static LEXER: Lexer = Lexer::new();
+
+#[test]
+fn test_limits_are_off_by_default() {
+    let options = ParserOptions::default();
+
+    assert_eq!(options.max_input_size, None);
+    assert_eq!(options.max_tokens, None);
+    assert_eq!(options.max_nodes, None);
+    assert!(options.parse_source(&LEXER, "<?php echo 1;").is_ok());
+}
+
+#[test]
+fn test_input_size_limit() {
+    let options = ParserOptions::new().with_max_input_size(16);
+
+    assert!(options.parse_source(&LEXER, "<?php echo 1;").is_ok());
+
+    let error = options
+        .parse_source(&LEXER, "<?php echo 'hello world';")
+        .unwrap_err();
+    assert_eq!(error.code(), "E0054");
+    assert_eq!(
+        error.to_string(),
+        "Parse Error: Input is 25 bytes long, which is more than the limit of 16 bytes"
+    );
+}
+
+#[test]
+fn test_token_limit() {
+    let options = ParserOptions::new().with_max_tokens(4);
+
+    // `<?php`, `echo`, `1` and `;`.
+    assert!(options.parse_source(&LEXER, "<?php echo 1;").is_ok());
+
+    let error = options
+        .parse_source(&LEXER, "<?php echo 1; echo 2;")
+        .unwrap_err();
+    assert_eq!(
+        error,
+        ParseError::TooManyTokens {
+            limit: 4,
+            span: (1, 15)
+        }
+    );
+}
+
+#[test]
+fn test_node_limit() {
+    let code = "<?php echo 1 + 2;";
+
+    // The statement and both operands of the addition.
+    let options = ParserOptions::new().with_max_nodes(3);
+    assert!(options.parse(LEXER.tokenize(code).unwrap()).is_ok());
+
+    let options = ParserOptions::new().with_max_nodes(2);
+    let error = options.parse(LEXER.tokenize(code).unwrap()).unwrap_err();
+    assert!(matches!(error, ParseError::TooManyNodes { limit: 2, .. }));
+    assert_eq!(error.code(), "E0056");
+}
+
+#[test]
+fn test_cancelled_parse() {
+    let cancellation = CancellationToken::new();
+    let options = ParserOptions::new().with_cancellation(cancellation.clone());
+
+    assert!(options
+        .parse(LEXER.tokenize("<?php echo 1;").unwrap())
+        .is_ok());
+
+    cancellation.cancel();
+    let error = options
+        .parse(LEXER.tokenize("<?php echo 1;").unwrap())
+        .unwrap_err();
+    assert_eq!(error, ParseError::Cancelled { span: (1, 7) });
+    assert_eq!(error.code(), "E0057");
+}
+
+#[test]
+fn test_cancelling_inside_a_function_body() {
+    let cancellation = CancellationToken::new();
+    let options = ParserOptions::new().with_cancellation(cancellation.clone());
+
+    // Cancel as soon as the lexer reaches the body, the way another thread could
+    // while a large file is being parsed.
+    let tokens = LEXER
+        .stream("<?php function foo() { echo 1; echo 2; }")
+        .inspect(|token| {
+            if matches!(token, Ok(token) if token.kind == TokenKind::Echo) {
+                cancellation.cancel();
+            }
+        });
+
+    let error = options.parse_stream(tokens).unwrap_err();
+    assert_eq!(error, ParseError::Cancelled { span: (1, 24) });
+}
@@ -0,0 +1,356 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+use serde_json::{json, Value};
+
+const URI: &str = "file:///test.php";
+
+struct Client {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    id: u64,
+}
+
+impl Client {
+    fn start() -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_php-lsp"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        let stdin = child.stdin.take().unwrap();
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+
+        let mut client = Self {
+            child,
+            stdin,
+            stdout,
+            id: 0,
+        };
+
+        let result = client.request("initialize", json!({ "capabilities": {} }));
+        assert_eq!(result["capabilities"]["documentSymbolProvider"], true);
+        client.notify("initialized", json!({}));
+
+        client
+    }
+
+    fn send(&mut self, message: Value) {
+        let message = message.to_string();
+        write!(
+            self.stdin,
+            "Content-Length: {}\r\n\r\n{}",
+            message.len(),
+            message
+        )
+        .unwrap();
+        self.stdin.flush().unwrap();
+    }
+
+    fn receive(&mut self) -> Value {
+        let mut length = 0;
+        loop {
+            let mut header = String::new();
+            self.stdout.read_line(&mut header).unwrap();
+
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+
+            if let Some(value) = header.strip_prefix("Content-Length:") {
+                length = value.trim().parse().unwrap();
+            }
+        }
+
+        let mut body = vec![0; length];
+        self.stdout.read_exact(&mut body).unwrap();
+
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    fn request(&mut self, method: &str, params: Value) -> Value {
+        self.id += 1;
+        self.send(json!({ "jsonrpc": "2.0", "id": self.id, "method": method, "params": params }));
+
+        let response = self.receive();
+        assert_eq!(response["id"], self.id, "unexpected response {}", response);
+
+        response["result"].clone()
+    }
+
+    fn notify(&mut self, method: &str, params: Value) {
+        self.send(json!({ "jsonrpc": "2.0", "method": method, "params": params }));
+    }
+
+    fn open(&mut self, text: &str) -> Value {
+        self.notify(
+            "textDocument/didOpen",
+            json!({
+                "textDocument": { "uri": URI, "languageId": "php", "version": 1, "text": text },
+            }),
+        );
+
+        self.diagnostics()
+    }
+
+    fn diagnostics(&mut self) -> Value {
+        let notification = self.receive();
+        assert_eq!(notification["method"], "textDocument/publishDiagnostics");
+        assert_eq!(notification["params"]["uri"], URI);
+
+        notification["params"]["diagnostics"].clone()
+    }
+
+    fn shutdown(mut self) {
+        assert_eq!(self.request("shutdown", Value::Null), Value::Null);
+        self.notify("exit", Value::Null);
+
+        assert!(self.child.wait().unwrap().success());
+    }
+}
+
+fn document() -> Value {
+    json!({ "uri": URI })
+}
+
+#[test]
+fn test_diagnostics_follow_edits() {
+    let mut client = Client::start();
+
+    let diagnostics = client.open("<?php\n\necho 1\n");
+    assert_eq!(diagnostics.as_array().unwrap().len(), 1);
+    assert_eq!(diagnostics[0]["severity"], 1);
+    assert_eq!(
+        diagnostics[0]["range"]["start"],
+        json!({ "line": 3, "character": 0 })
+    );
+
+    // Insert the missing semicolon.
+    client.notify(
+        "textDocument/didChange",
+        json!({
+            "textDocument": { "uri": URI, "version": 2 },
+            "contentChanges": [{
+                "range": {
+                    "start": { "line": 2, "character": 6 },
+                    "end": { "line": 2, "character": 6 },
+                },
+                "text": ";",
+            }],
+        }),
+    );
+    assert_eq!(client.diagnostics(), json!([]));
+
+    // Replace the whole document.
+    client.notify(
+        "textDocument/didChange",
+        json!({
+            "textDocument": { "uri": URI, "version": 3 },
+            "contentChanges": [{ "text": "<?php\n\nfunction (" }],
+        }),
+    );
+    assert_eq!(client.diagnostics().as_array().unwrap().len(), 1);
+
+    client.notify(
+        "textDocument/didClose",
+        json!({ "textDocument": document() }),
+    );
+    assert_eq!(client.diagnostics(), json!([]));
+
+    client.shutdown();
+}
+
+#[test]
+fn test_document_symbols() {
+    let mut client = Client::start();
+    assert_eq!(
+        client.open("<?php\n\nclass Foo {\n    public $bar;\n}\n"),
+        json!([])
+    );
+
+    let symbols = client.request(
+        "textDocument/documentSymbol",
+        json!({ "textDocument": document() }),
+    );
+
+    assert_eq!(
+        symbols,
+        json!([{
+            "name": "Foo",
+            "kind": 5,
+            "range": {
+                "start": { "line": 2, "character": 0 },
+                "end": { "line": 4, "character": 1 },
+            },
+            "selectionRange": {
+                "start": { "line": 2, "character": 6 },
+                "end": { "line": 2, "character": 9 },
+            },
+            "children": [{
+                "name": "bar",
+                "kind": 7,
+                "range": {
+                    "start": { "line": 3, "character": 4 },
+                    "end": { "line": 3, "character": 15 },
+                },
+                "selectionRange": {
+                    "start": { "line": 3, "character": 11 },
+                    "end": { "line": 3, "character": 15 },
+                },
+                "children": [],
+            }],
+        }])
+    );
+
+    client.shutdown();
+}
+
+#[test]
+fn test_folding_and_selection_ranges() {
+    let mut client = Client::start();
+    client.open("<?php\n\n// One.\n// Two.\nfunction foo() {\n    return bar(1);\n}\n");
+
+    let folding = client.request(
+        "textDocument/foldingRange",
+        json!({ "textDocument": document() }),
+    );
+    assert_eq!(
+        folding,
+        json!([
+            { "startLine": 2, "endLine": 3, "kind": "comment" },
+            { "startLine": 4, "endLine": 5 },
+        ])
+    );
+
+    let selection = client.request(
+        "textDocument/selectionRange",
+        json!({
+            "textDocument": document(),
+            "positions": [{ "line": 5, "character": 12 }],
+        }),
+    );
+
+    // Walk out from the innermost range and check that each one contains the last.
+    let mut ranges = vec![];
+    let mut range = &selection[0];
+    while !range.is_null() {
+        let start = &range["range"]["start"];
+        let end = &range["range"]["end"];
+        ranges.push((
+            (
+                start["line"].as_u64().unwrap(),
+                start["character"].as_u64().unwrap(),
+            ),
+            (
+                end["line"].as_u64().unwrap(),
+                end["character"].as_u64().unwrap(),
+            ),
+        ));
+
+        range = &range["parent"];
+    }
+
+    assert!(ranges.len() > 1);
+    assert_eq!(ranges[0], ((5, 11), (5, 14)));
+    for pair in ranges.windows(2) {
+        assert!(pair[1].0 <= pair[0].0 && pair[0].1 <= pair[1].1);
+    }
+    assert!(ranges.contains(&((5, 11), (5, 17))));
+    assert!(ranges.contains(&((4, 0), (6, 1))));
+    assert_eq!(ranges[ranges.len() - 1], ((0, 0), (7, 0)));
+
+    client.shutdown();
+}
+
+#[test]
+fn test_semantic_tokens() {
+    let mut client = Client::start();
+    client.open("<?php\n$é = foo();\n");
+
+    let tokens = client.request(
+        "textDocument/semanticTokens/full",
+        json!({ "textDocument": document() }),
+    );
+
+    // `$é` is two UTF-16 code units long, and `foo` starts after `$é = `.
+    assert_eq!(tokens["data"], json!([1, 0, 2, 6, 0, 0, 5, 3, 2, 0]));
+
+    client.shutdown();
+}
+
+#[test]
+fn test_unterminated_doc_strings_are_reported() {
+    let mut client = Client::start();
+
+    let diagnostics = client.open("<?php $a = <<<EOT");
+    assert_eq!(diagnostics.as_array().unwrap().len(), 1);
+
+    let tokens = client.request(
+        "textDocument/semanticTokens/full",
+        json!({ "textDocument": document() }),
+    );
+    assert_eq!(tokens["data"], json!([]));
+
+    client.shutdown();
+}
+
+#[test]
+fn test_unknown_methods_are_rejected() {
+    let mut client = Client::start();
+
+    client.id += 1;
+    let id = client.id;
+    client
+        .send(json!({ "jsonrpc": "2.0", "id": id, "method": "textDocument/hover", "params": {} }));
+
+    let response = client.receive();
+    assert_eq!(response["id"], id);
+    assert_eq!(response["error"]["code"], -32601);
+
+    client.shutdown();
+}
+
+#[test]
+fn test_every_position_gets_a_selection_range() {
+    let mut client = Client::start();
+    client.open("");
+
+    let selection = client.request(
+        "textDocument/selectionRange",
+        json!({
+            "textDocument": document(),
+            "positions": [{ "line": 0, "character": 0 }],
+        }),
+    );
+
+    assert_eq!(
+        selection,
+        json!([{
+            "range": {
+                "start": { "line": 0, "character": 0 },
+                "end": { "line": 0, "character": 0 },
+            },
+        }])
+    );
+
+    client.shutdown();
+}
+
+#[test]
+fn test_messages_without_a_length_are_rejected() {
+    let mut client = Client::start();
+
+    write!(client.stdin, "Content-Type: application/json\r\n\r\n").unwrap();
+    client.stdin.flush().unwrap();
+
+    let response = client.receive();
+    assert_eq!(response["id"], Value::Null);
+    assert_eq!(response["error"]["code"], -32700);
+
+    // The server is still running.
+    client.open("<?php echo 1;");
+    client.shutdown();
+}
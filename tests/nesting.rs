@@ -0,0 +1,115 @@
+use std::thread;
+
+use php_parser_rs::lexer::Lexer;
+use php_parser_rs::parser::ast::owned::IntoOwned;
+use php_parser_rs::parser::error::ParseError;
+use php_parser_rs::{parse, ParserOptions};
+
+static LEXER: Lexer = Lexer::new();
+
+// Run `f` on a thread with a small stack, so that anything that recurses once per
+// level of nesting would overflow it.
+fn on_small_stack(f: impl FnOnce() + Send + 'static) {
+    thread::Builder::new()
+        .stack_size(256 * 1024)
+        .spawn(f)
+        .unwrap()
+        .join()
+        .unwrap();
+}
+
+fn nested_arrays(depth: usize) -> String {
+    format!("<?php $a = {}1{};", "[".repeat(depth), "]".repeat(depth))
+}
+
+#[test]
+fn test_nesting_within_the_limit_parses_on_a_small_stack() {
+    on_small_stack(|| {
+        let code = nested_arrays(ParserOptions::DEFAULT_MAX_DEPTH - 10);
+
+        assert!(parse(LEXER.tokenize(code.as_bytes()).unwrap()).is_ok());
+    });
+}
+
+#[test]
+fn test_nesting_past_the_limit_is_an_error() {
+    on_small_stack(|| {
+        let code = nested_arrays(10_000);
+
+        let error = parse(LEXER.tokenize(code.as_bytes()).unwrap()).unwrap_err();
+
+        assert!(matches!(
+            error,
+            ParseError::NestingTooDeep {
+                limit: ParserOptions::DEFAULT_MAX_DEPTH,
+                ..
+            }
+        ));
+        assert_eq!(error.code(), "E0053");
+    });
+}
+
+#[test]
+fn test_nesting_limit_is_configurable() {
+    let code = "<?php if ($a) { if ($b) { if ($c) { echo 1; } } }";
+
+    let options = ParserOptions::new().with_max_depth(3);
+    let error = options.parse(LEXER.tokenize(code).unwrap()).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Parse Error: Code is nested more than 3 levels deep on line 1 column 31"
+    );
+
+    let options = ParserOptions::new().with_max_depth(5);
+    assert!(options.parse(LEXER.tokenize(code).unwrap()).is_ok());
+}
+
+#[test]
+fn test_long_operator_chains_parse_and_drop_on_a_small_stack() {
+    on_small_stack(|| {
+        let code = format!("<?php $a = 'a'{};", " . 'a'".repeat(50_000));
+
+        let program = parse(LEXER.tokenize(code.as_bytes()).unwrap()).unwrap();
+        drop(program);
+    });
+}
+
+#[test]
+fn test_long_method_chains_parse_and_drop_on_a_small_stack() {
+    on_small_stack(|| {
+        let code = format!("<?php $a{};", "->b()".repeat(50_000));
+
+        let program = parse(LEXER.tokenize(code.as_bytes()).unwrap()).unwrap();
+        drop(program);
+    });
+}
+
+#[test]
+fn test_deeply_nested_arrays_drop_on_a_small_stack() {
+    let code = nested_arrays(100_000);
+    let tokens = LEXER.tokenize(code.as_bytes()).unwrap();
+    let program = ParserOptions::new()
+        .with_max_depth(1_000_000)
+        .parse(tokens)
+        .unwrap()
+        .into_owned();
+
+    on_small_stack(move || drop(program));
+}
+
+#[test]
+fn test_deeply_nested_calls_drop_on_a_small_stack() {
+    let code = format!(
+        "<?php $a = {}1{};",
+        "f(".repeat(100_000),
+        ")".repeat(100_000)
+    );
+    let tokens = LEXER.tokenize(code.as_bytes()).unwrap();
+    let program = ParserOptions::new()
+        .with_max_depth(1_000_000)
+        .parse(tokens)
+        .unwrap()
+        .into_owned();
+
+    on_small_stack(move || drop(program));
+}
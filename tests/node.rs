@@ -0,0 +1,98 @@
+use php_parser_rs::lexer::source::LineIndex;
+use php_parser_rs::lexer::Lexer;
+use php_parser_rs::parse;
+use php_parser_rs::parser::ast::node::{find_node_at, find_node_at_offset, Node};
+use php_parser_rs::parser::ast::{Expression, Statement};
+
+static LEXER: Lexer = Lexer::new();
+
+const CODE: &str = r#"<?php
+
+class Foo {
+    public function bar($baz) {
+        return $this->qux($baz, 1);
+    }
+}
+
+echo 1; echo $a;
+"#;
+
+fn kind(node: &Node) -> String {
+    let debug = format!("{:?}", node);
+
+    debug[..debug.find('(').unwrap()].to_string()
+}
+
+#[test]
+fn test_find_node_in_a_method() {
+    let program = parse(LEXER.tokenize(CODE).unwrap()).unwrap();
+
+    // The `$baz` argument to `$this->qux()`.
+    let found = find_node_at(&program, (5, 28)).unwrap();
+
+    assert!(matches!(
+        found.node,
+        Node::SimpleVariable(variable) if variable.name == b"baz"
+    ));
+    assert_eq!(
+        found.ancestors.iter().map(kind).collect::<Vec<_>>(),
+        vec![
+            "Statement",
+            "Class",
+            "ClassMember",
+            "Method",
+            "Statement",
+            "Expression",
+            "Arg",
+            "Expression",
+            "Variable",
+        ]
+    );
+    assert!(matches!(
+        found.ancestors[5],
+        Node::Expression(Expression::MethodCall { .. })
+    ));
+}
+
+#[test]
+fn test_find_node_among_siblings() {
+    let program = parse(LEXER.tokenize(CODE).unwrap()).unwrap();
+
+    let found = find_node_at(&program, (9, 15)).unwrap();
+    assert!(matches!(
+        found.node,
+        Node::SimpleVariable(variable) if variable.name == b"a"
+    ));
+    assert!(matches!(
+        found.ancestors[0],
+        Node::Statement(Statement::Echo { .. })
+    ));
+
+    // Literals have no span, so the closest node with one is returned instead.
+    let found = find_node_at(&program, (5, 33)).unwrap();
+    assert!(matches!(found.node, Node::Method(method) if method.name.name == b"bar"));
+}
+
+#[test]
+fn test_find_node_outside_of_any_node() {
+    let program = parse(LEXER.tokenize(CODE).unwrap()).unwrap();
+
+    assert_eq!(find_node_at(&program, (1, 1)), None);
+    assert_eq!(find_node_at(&program, (2, 1)), None);
+}
+
+#[test]
+fn test_find_node_at_a_byte_offset() {
+    let program = parse(LEXER.tokenize(CODE).unwrap()).unwrap();
+    let index = LineIndex::new(CODE.as_bytes());
+
+    let offset = CODE.find("$baz, 1").unwrap() + 2;
+    let found = find_node_at_offset(&program, &index, offset).unwrap();
+    assert_eq!(found, find_node_at(&program, (5, 29)).unwrap());
+    assert!(matches!(
+        found.node,
+        Node::SimpleVariable(variable) if variable.name == b"baz"
+    ));
+
+    assert_eq!(find_node_at_offset(&program, &index, 0), None);
+}
@@ -0,0 +1,191 @@
+mod common;
+
+use std::path::{Path, PathBuf};
+
+use php_parser_rs::parser::ast::Statement;
+use php_parser_rs::project::{FileError, Project};
+
+use common::{project, relative};
+
+fn relative_all(root: &Path, paths: Vec<PathBuf>) -> Vec<String> {
+    paths.iter().map(|path| relative(root, path)).collect()
+}
+
+#[test]
+fn test_discovery_with_include_and_exclude_patterns() {
+    let root = project(
+        "project",
+        "discovery",
+        &[
+            ("index.php", ""),
+            ("README.md", ""),
+            ("src/Foo.php", ""),
+            ("src/Foo/Bar.php", ""),
+            ("src/Foo/Bar.phtml", ""),
+            ("tests/FooTest.php", ""),
+            ("vendor/acme/lib/Baz.php", ""),
+        ],
+    );
+
+    assert_eq!(
+        relative_all(&root, Project::new(&root).files().unwrap()),
+        vec![
+            "index.php",
+            "src/Foo/Bar.php",
+            "src/Foo.php",
+            "tests/FooTest.php",
+            "vendor/acme/lib/Baz.php",
+        ]
+    );
+
+    let files = Project::new(&root)
+        .with_include("src/**/*.php")
+        .with_include("src/**/*.phtml")
+        .with_exclude("**/Foo.php")
+        .files()
+        .unwrap();
+    assert_eq!(
+        relative_all(&root, files),
+        vec!["src/Foo/Bar.php", "src/Foo/Bar.phtml"]
+    );
+
+    let files = Project::new(&root)
+        .with_exclude("vendor")
+        .with_exclude("t?sts")
+        .files()
+        .unwrap();
+    assert_eq!(
+        relative_all(&root, files),
+        vec!["index.php", "src/Foo/Bar.php", "src/Foo.php"]
+    );
+}
+
+#[test]
+fn test_files_are_parsed_in_parallel() {
+    let files = (0..20)
+        .map(|i| {
+            let code = if i % 7 == 3 {
+                "<?php echo 1".to_string()
+            } else {
+                format!("<?php function f{}() {{}}", i)
+            };
+
+            (format!("src/{:02}.php", i), code)
+        })
+        .collect::<Vec<_>>();
+    let files = files
+        .iter()
+        .map(|(path, code)| (path.as_str(), code.as_str()))
+        .collect::<Vec<_>>();
+    let root = project("project", "parallel", &files);
+
+    let result = Project::new(&root).with_threads(4).parse().unwrap();
+
+    assert_eq!(result.files.len(), 20);
+    assert!(!result.is_ok());
+    assert!(result.files.windows(2).all(|w| w[0].path < w[1].path));
+
+    let failed = result
+        .diagnostics()
+        .map(|(path, diagnostic)| {
+            let name = path.file_name().unwrap().to_string_lossy().into_owned();
+
+            (name, diagnostic.code)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        failed,
+        vec![
+            ("03.php".to_string(), "E0012"),
+            ("10.php".to_string(), "E0012"),
+            ("17.php".to_string(), "E0012"),
+        ]
+    );
+
+    let file = &result.files[0];
+    assert_eq!(file.source, b"<?php function f0() {}");
+    assert!(matches!(
+        file.result.as_ref().unwrap()[..],
+        [Statement::Function(_)]
+    ));
+
+    let single = Project::new(&root).with_threads(1).parse().unwrap();
+    assert!(single
+        .files
+        .iter()
+        .zip(&result.files)
+        .all(|(a, b)| a.path == b.path && a.result.is_ok() == b.result.is_ok()));
+}
+
+#[test]
+fn test_parser_options_apply_to_every_file() {
+    let root = project(
+        "project",
+        "options",
+        &[("a.php", "<?php echo 1;"), ("b.php", "<?php echo 1, 2, 3;")],
+    );
+
+    let result = Project::new(&root)
+        .with_options(php_parser_rs::ParserOptions::new().with_max_input_size(15))
+        .parse()
+        .unwrap();
+
+    let errors = result.errors().collect::<Vec<_>>();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].0.ends_with("b.php"));
+    assert!(matches!(errors[0].1, FileError::Parse(_)));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_symlinked_directories_are_not_followed() {
+    let root = project(
+        "project",
+        "symlinks",
+        &[("src/Foo.php", ""), ("lib/Bar.php", "")],
+    );
+    std::os::unix::fs::symlink(&root, root.join("src/loop")).unwrap();
+    std::os::unix::fs::symlink(root.join("lib"), root.join("linked")).unwrap();
+    std::os::unix::fs::symlink(root.join("lib/Bar.php"), root.join("src/Baz.php")).unwrap();
+
+    assert_eq!(
+        relative_all(&root, Project::new(&root).files().unwrap()),
+        vec!["lib/Bar.php", "src/Baz.php", "src/Foo.php"]
+    );
+}
+
+#[test]
+fn test_patterns_with_many_stars() {
+    let name = format!("{}.php", "a".repeat(100));
+    let root = project("project", "stars", &[(&name, ""), ("a/b/c/d/e.php", "")]);
+
+    let files = Project::new(&root)
+        .with_include("*a*a*a*a*a*a*a*a*a*a*a*a*b*")
+        .with_include("**/**/**/**/**/**/**/**/**/**/f.php")
+        .files()
+        .unwrap();
+    assert!(files.is_empty());
+
+    let files = Project::new(&root)
+        .with_include("*a*a*a*a*a*a*a*a*a*a*a*a*.php")
+        .with_include("**/**/**/**/**/**/**/**/**/**/e.php")
+        .files()
+        .unwrap();
+    assert_eq!(relative_all(&root, files), vec!["a/b/c/d/e.php", &name]);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_unreadable_files_are_reported() {
+    let root = project("project", "unreadable", &[("a.php", "<?php echo 1;")]);
+    std::os::unix::fs::symlink(root.join("missing.php"), root.join("b.php")).unwrap();
+
+    let result = Project::new(&root).parse().unwrap();
+    assert!(!result.is_ok());
+
+    let diagnostics = result
+        .diagnostics()
+        .map(|(path, diagnostic)| (relative(&root, path), diagnostic.code, diagnostic.span))
+        .collect::<Vec<_>>();
+    assert_eq!(diagnostics, vec![("b.php".to_string(), "E0065", (1, 1))]);
+}
@@ -0,0 +1,143 @@
+use php_parser_rs::lexer::Lexer;
+use php_parser_rs::parse;
+use php_parser_rs::ranges::{folding_ranges, selection_ranges, FoldingRange, FoldingRangeKind};
+
+static LEXER: Lexer = Lexer::new();
+
+fn folds(code: &str) -> Vec<(usize, usize, Option<FoldingRangeKind>)> {
+    let tokens = LEXER.tokenize(code).unwrap();
+    let program = parse(tokens.clone()).unwrap();
+
+    folding_ranges(code.as_bytes(), &tokens, &program)
+        .into_iter()
+        .map(
+            |FoldingRange {
+                 start_line,
+                 end_line,
+                 kind,
+             }| (start_line, end_line, kind),
+        )
+        .collect()
+}
+
+fn selections<'c>(code: &'c str, at: &str) -> Vec<&'c str> {
+    let tokens = LEXER.tokenize(code).unwrap();
+    let program = parse(tokens.clone()).unwrap();
+    let offset = code.find(at).unwrap();
+
+    selection_ranges(code.as_bytes(), &tokens, &program, offset)
+        .into_iter()
+        .map(|range| &code[range])
+        .collect()
+}
+
+#[test]
+fn test_blocks_are_folded() {
+    let ranges = folds(
+        r#"<?php
+
+class Foo {
+    public function bar($x) {
+        if ($x):
+            return [
+                1,
+                2,
+            ];
+        else:
+            return 3;
+        endif;
+    }
+}
+"#,
+    );
+
+    assert_eq!(
+        ranges,
+        vec![
+            (3, 13, None),
+            (4, 12, None),
+            (5, 9, None),
+            (6, 8, None),
+            (10, 11, None),
+        ]
+    );
+}
+
+#[test]
+fn test_imports_comments_heredocs_and_match_arms_are_folded() {
+    let ranges = folds(
+        r#"<?php
+
+use Foo\Bar;
+use Foo\Baz;
+
+// One.
+// Two.
+$x = 1; // Not part of the run.
+
+/**
+ * Docs.
+ */
+$y = match ($x) {
+    1 => foo(
+        $x,
+    ),
+    default => <<<EOT
+        text
+        EOT,
+};
+"#,
+    );
+
+    assert_eq!(
+        ranges,
+        vec![
+            (3, 4, Some(FoldingRangeKind::Imports)),
+            (6, 7, Some(FoldingRangeKind::Comment)),
+            (10, 12, Some(FoldingRangeKind::Comment)),
+            (13, 19, None),
+            (14, 16, None),
+            (17, 19, None),
+            (17, 18, None),
+        ]
+    );
+}
+
+#[test]
+fn test_selection_grows_from_argument_to_block() {
+    let code = r#"<?php
+
+function foo() {
+    $x = $this->bar(1, baz + 2);
+    return $x;
+}
+"#;
+
+    assert_eq!(
+        selections(code, "baz"),
+        vec![
+            "baz",
+            "baz + 2",
+            "1, baz + 2",
+            "(1, baz + 2)",
+            "$this->bar(1, baz + 2)",
+            "$x = $this->bar(1, baz + 2)",
+            "$x = $this->bar(1, baz + 2);",
+            "$x = $this->bar(1, baz + 2);\n    return $x;",
+            "{\n    $x = $this->bar(1, baz + 2);\n    return $x;\n}",
+            "function foo() {\n    $x = $this->bar(1, baz + 2);\n    return $x;\n}",
+            code,
+        ]
+    );
+}
+
+#[test]
+fn test_selection_of_control_flow() {
+    let code = "<?php\nif ($a) {\n    foo();\n} else {\n    bar();\n}\nbaz();\n";
+
+    let ranges = selections(code, "bar");
+    assert_eq!(ranges[0], "bar");
+    assert!(ranges.contains(&"{\n    bar();\n}"));
+    assert!(ranges.contains(&"if ($a) {\n    foo();\n} else {\n    bar();\n}"));
+    assert_eq!(ranges[ranges.len() - 1], code);
+}
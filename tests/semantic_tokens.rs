@@ -0,0 +1,196 @@
+use php_parser_rs::lexer::Lexer;
+use php_parser_rs::parse;
+use php_parser_rs::semantic_tokens::{encode, semantic_tokens, SemanticTokenKind, TOKEN_TYPES};
+
+static LEXER: Lexer = Lexer::new();
+
+fn classify(code: &str) -> Vec<(String, SemanticTokenKind)> {
+    let tokens = LEXER.tokenize(code).unwrap();
+    let program = parse(tokens.clone()).unwrap();
+
+    let lines = code.split('\n').collect::<Vec<_>>();
+    semantic_tokens(code.as_bytes(), &tokens, &program)
+        .into_iter()
+        .map(|token| {
+            let line = lines[token.span.0 - 1];
+            let start = token.span.1 - 1;
+
+            (line[start..start + token.length].to_string(), token.kind)
+        })
+        .collect()
+}
+
+fn kind_of(tokens: &[(String, SemanticTokenKind)], text: &str) -> Vec<SemanticTokenKind> {
+    tokens
+        .iter()
+        .filter(|(token, _)| token == text)
+        .map(|(_, kind)| *kind)
+        .collect()
+}
+
+#[test]
+fn test_soft_keywords_used_as_names() {
+    let tokens = classify(
+        r#"<?php
+
+enum Suit {
+    case Hearts;
+}
+
+class Factory {
+    public function enum() {}
+    public readonly int $from;
+}
+
+$factory->enum();
+"#,
+    );
+
+    assert_eq!(
+        kind_of(&tokens, "enum"),
+        vec![
+            SemanticTokenKind::Keyword,
+            SemanticTokenKind::Method,
+            SemanticTokenKind::Method,
+        ]
+    );
+    assert_eq!(
+        kind_of(&tokens, "readonly"),
+        vec![SemanticTokenKind::Keyword]
+    );
+    assert_eq!(
+        kind_of(&tokens, "Hearts"),
+        vec![SemanticTokenKind::Constant]
+    );
+    assert_eq!(kind_of(&tokens, "$from"), vec![SemanticTokenKind::Property]);
+    assert_eq!(kind_of(&tokens, "int"), vec![SemanticTokenKind::Type]);
+}
+
+#[test]
+fn test_names_are_classified_by_their_role() {
+    let tokens = classify(
+        r#"<?php
+
+#[Route('/')]
+function handle(Request $request, int $limit = LIMIT): Response {
+    $user = User::find($request->id, limit: $limit);
+
+    return new Response($user->name, Response::OK, strlen('x'), 1.5);
+}
+"#,
+    );
+
+    assert_eq!(
+        tokens,
+        vec![
+            ("Route".into(), SemanticTokenKind::Attribute),
+            ("'/'".into(), SemanticTokenKind::String),
+            ("function".into(), SemanticTokenKind::Keyword),
+            ("handle".into(), SemanticTokenKind::Function),
+            ("Request".into(), SemanticTokenKind::Class),
+            ("$request".into(), SemanticTokenKind::Parameter),
+            ("int".into(), SemanticTokenKind::Type),
+            ("$limit".into(), SemanticTokenKind::Parameter),
+            ("LIMIT".into(), SemanticTokenKind::Constant),
+            ("Response".into(), SemanticTokenKind::Class),
+            ("$user".into(), SemanticTokenKind::Variable),
+            ("User".into(), SemanticTokenKind::Class),
+            ("find".into(), SemanticTokenKind::Method),
+            ("$request".into(), SemanticTokenKind::Variable),
+            ("id".into(), SemanticTokenKind::Property),
+            ("limit".into(), SemanticTokenKind::Parameter),
+            ("$limit".into(), SemanticTokenKind::Variable),
+            ("return".into(), SemanticTokenKind::Keyword),
+            ("new".into(), SemanticTokenKind::Keyword),
+            ("Response".into(), SemanticTokenKind::Class),
+            ("$user".into(), SemanticTokenKind::Variable),
+            ("name".into(), SemanticTokenKind::Property),
+            ("Response".into(), SemanticTokenKind::Class),
+            ("OK".into(), SemanticTokenKind::Constant),
+            ("strlen".into(), SemanticTokenKind::Function),
+            ("'x'".into(), SemanticTokenKind::String),
+            ("1.5".into(), SemanticTokenKind::Number),
+        ]
+    );
+}
+
+#[test]
+fn test_docblocks_are_split_into_lines_and_tags() {
+    let tokens = classify(
+        r#"<?php
+
+/**
+ * Send mail to foo@example.com.
+ *
+ * @param string $to {@see Mailer}
+ */
+function send($to) {}
+"#,
+    );
+
+    assert_eq!(
+        tokens[..6],
+        vec![
+            ("/**".into(), SemanticTokenKind::Comment),
+            (
+                " * Send mail to foo@example.com.".into(),
+                SemanticTokenKind::Comment
+            ),
+            (" *".into(), SemanticTokenKind::Comment),
+            (" * ".into(), SemanticTokenKind::Comment),
+            ("@param".into(), SemanticTokenKind::DocblockTag),
+            (" string $to {".into(), SemanticTokenKind::Comment),
+        ]
+    );
+    assert_eq!(
+        tokens[6..9],
+        vec![
+            ("@see".into(), SemanticTokenKind::DocblockTag),
+            (" Mailer}".into(), SemanticTokenKind::Comment),
+            (" */".into(), SemanticTokenKind::Comment),
+        ]
+    );
+}
+
+#[test]
+fn test_lexical_classification_without_an_ast() {
+    let code = "<?php\n\nenum Foo { case Bar; }\n";
+    let tokens = LEXER.tokenize(code).unwrap();
+
+    let kinds = semantic_tokens(code.as_bytes(), &tokens, &[])
+        .into_iter()
+        .map(|token| token.kind)
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        kinds,
+        vec![SemanticTokenKind::Keyword, SemanticTokenKind::Keyword]
+    );
+}
+
+#[test]
+fn test_lsp_encoding() {
+    let code = "<?php\n$é = \"ü\"; // x\nfoo();\n";
+    let tokens = LEXER.tokenize(code).unwrap();
+    let program = parse(tokens.clone()).unwrap();
+
+    let data = encode(
+        code.as_bytes(),
+        &semantic_tokens(code.as_bytes(), &tokens, &program),
+    );
+
+    let variable = TOKEN_TYPES.iter().position(|t| *t == "variable").unwrap() as u32;
+    let string = TOKEN_TYPES.iter().position(|t| *t == "string").unwrap() as u32;
+    let comment = TOKEN_TYPES.iter().position(|t| *t == "comment").unwrap() as u32;
+    let function = TOKEN_TYPES.iter().position(|t| *t == "function").unwrap() as u32;
+
+    assert_eq!(
+        data,
+        vec![
+            1, 0, 2, variable, 0, //
+            0, 5, 3, string, 0, //
+            0, 5, 4, comment, 0, //
+            1, 0, 3, function, 0,
+        ]
+    );
+}
@@ -0,0 +1,63 @@
+use php_parser_rs::lexer::error::SyntaxError;
+use php_parser_rs::lexer::Lexer;
+use php_parser_rs::parser::ast::Statement;
+use php_parser_rs::parser::error::ParseError;
+use php_parser_rs::{parse, parse_stream};
+
+static LEXER: Lexer = Lexer::new();
+
+const CODE: &str = r#"<html><?php
+$a = "foo {$bar->baz} $qux[1]";
+echo <<<EOF
+    Hello, $name!
+    EOF;
+`ls $dir`;
+?>
+<p>text</p>
+<?php __halt_compiler(); raw data"#;
+
+#[test]
+fn test_stream_matches_tokenize() {
+    let streamed = LEXER.stream(CODE).collect::<Result<Vec<_>, _>>().unwrap();
+
+    assert_eq!(streamed, LEXER.tokenize(CODE).unwrap());
+}
+
+#[test]
+fn test_stream_stops_after_error() {
+    let mut stream = LEXER.stream("<?php $a; \"\\u{zz}\"; $b;");
+
+    assert!(stream.next().unwrap().is_ok());
+    assert!(stream.next().unwrap().is_ok());
+    assert!(stream.next().unwrap().is_ok());
+    assert_eq!(
+        stream.next(),
+        Some(Err(SyntaxError::InvalidUnicodeEscape { span: (1, 15) }))
+    );
+    assert_eq!(stream.next(), None);
+}
+
+#[test]
+fn test_parse_stream() {
+    let program = parse_stream(LEXER.stream(CODE)).unwrap();
+
+    assert_eq!(program, parse(LEXER.tokenize(CODE).unwrap()).unwrap());
+}
+
+#[test]
+fn test_parse_stream_reports_lexer_errors() {
+    let error = parse_stream(LEXER.stream("<?php $a = \"\\u{zz}\";")).unwrap_err();
+
+    assert_eq!(
+        error,
+        ParseError::SyntaxError(SyntaxError::InvalidUnicodeEscape { span: (1, 16) })
+    );
+}
+
+#[test]
+fn test_function_lookahead() {
+    let program = parse_stream(LEXER.stream("<?php function &foo() {} function &() {};")).unwrap();
+
+    assert!(matches!(program[0], Statement::Function(_)));
+    assert!(matches!(program[1], Statement::Expression { .. }));
+}
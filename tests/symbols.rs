@@ -0,0 +1,166 @@
+use php_parser_rs::lexer::Lexer;
+use php_parser_rs::parse;
+use php_parser_rs::symbols::{symbols, DocumentSymbol, SymbolKind, SymbolModifier};
+
+static LEXER: Lexer = Lexer::new();
+
+fn outline(code: &str) -> Vec<String> {
+    fn walk(symbols: &[DocumentSymbol], depth: usize, lines: &mut Vec<String>) {
+        for symbol in symbols {
+            let mut line = format!("{}{:?} {}", "  ".repeat(depth), symbol.kind, symbol.name);
+            for modifier in &symbol.modifiers {
+                line.push_str(&format!(" {:?}", modifier).to_lowercase());
+            }
+            lines.push(line);
+
+            walk(&symbol.children, depth + 1, lines);
+        }
+    }
+
+    let program = parse(LEXER.tokenize(code).unwrap()).unwrap();
+    let mut lines = vec![];
+    walk(&symbols(&program), 0, &mut lines);
+
+    lines
+}
+
+#[test]
+fn test_class_like_symbols() {
+    let code = r#"<?php
+
+namespace App;
+
+const VERSION = 1, NAME = 'app';
+
+function helper() {}
+
+abstract class Foo extends Bar implements Baz {
+    use Qux;
+
+    final public const A = 1, B = 2;
+    private static ?int $count = 0, $total;
+    var $legacy;
+
+    public function __construct(private readonly string $name, $plain) {}
+
+    abstract protected static function make(): static;
+}
+
+interface Baz {
+    const C = 1;
+    public function baz();
+}
+
+trait Qux {
+    protected $items = [];
+    function qux() {}
+}
+
+enum Suit {
+    case Hearts;
+    case Spades;
+    const Wild = self::Spades;
+}
+
+enum Status: string {
+    case Active = 'active';
+    public function label(): string {}
+}
+"#;
+
+    assert_eq!(
+        outline(code),
+        vec![
+            "Namespace App",
+            "  Constant VERSION",
+            "  Constant NAME",
+            "  Function helper",
+            "  Class Foo abstract",
+            "    Constant A final public",
+            "    Constant B final public",
+            "    Property count private static",
+            "    Property total private static",
+            "    Property legacy public",
+            "    Method __construct public",
+            "    Property name private readonly",
+            "    Method make abstract protected static",
+            "  Interface Baz",
+            "    Constant C",
+            "    Method baz public",
+            "  Trait Qux",
+            "    Property items protected",
+            "    Method qux",
+            "  Enum Suit",
+            "    EnumCase Hearts",
+            "    EnumCase Spades",
+            "    Constant Wild",
+            "  Enum Status",
+            "    EnumCase Active",
+            "    Method label public",
+        ]
+    );
+}
+
+#[test]
+fn test_braced_namespaces() {
+    let code = r#"<?php
+
+namespace Foo {
+    class A {}
+}
+
+namespace {
+    function b() {}
+}
+"#;
+
+    assert_eq!(
+        outline(code),
+        vec!["Namespace Foo", "  Class A", "Function b"]
+    );
+}
+
+#[test]
+fn test_conditional_declarations() {
+    let code = r#"<?php
+
+if (!function_exists('foo')) {
+    function foo() {
+        function inner() {}
+    }
+}
+
+$callback = function () {
+    class Hidden {}
+};
+"#;
+
+    assert_eq!(outline(code), vec!["Function foo"]);
+}
+
+#[test]
+fn test_symbol_spans() {
+    let code = r#"<?php
+
+#[Attribute]
+abstract class Foo {
+    public function bar() {
+        return 1;
+    }
+}
+"#;
+
+    let program = parse(LEXER.tokenize(code).unwrap()).unwrap();
+    let symbols = symbols(&program);
+
+    let class = &symbols[0];
+    assert_eq!(class.kind, SymbolKind::Class);
+    assert_eq!(class.modifiers, vec![SymbolModifier::Abstract]);
+    assert_eq!(class.span, ((3, 1), (8, 1)));
+    assert_eq!(class.name_span, (4, 16));
+
+    let method = &class.children[0];
+    assert_eq!(method.kind, SymbolKind::Method);
+    assert_eq!(method.span, ((5, 5), (7, 5)));
+    assert_eq!(method.name_span, (5, 21));
+}
@@ -1,15 +1,8 @@
 use std::env;
-use std::fs;
 use std::path::PathBuf;
 use std::process::Command;
-use std::thread;
 
-use php_parser_rs::lexer::Lexer;
-
-enum TestResult {
-    Success,
-    Error(String),
-}
+use php_parser_rs::project::Project;
 
 #[test]
 fn php_standard_library() {
@@ -301,113 +294,19 @@ fn test_repository(name: &str, repository: &str, ignore: &[&str]) {
         }
     }
 
-    let entries = read_directory(out_path.clone(), out_path, ignore);
-
-    let mut threads = vec![];
-    for (index, chunk) in entries.chunks(entries.len() / 4).enumerate() {
-        let chunk = chunk.to_vec();
-        let thread = thread::Builder::new()
-            .stack_size(16 * 1024 * 1024)
-            .name(format!("{name}:{index}"))
-            .spawn(move || {
-                let thread = thread::current();
-                let thread_name = thread.name().unwrap();
-
-                let mut results = vec![];
-                for (name, filename) in chunk {
-                    let code = std::fs::read(&filename).unwrap();
-
-                    match Lexer::new().tokenize(&code) {
-                        Ok(tokens) => match php_parser_rs::parse(tokens) {
-                            Ok(_) => {
-                                results.push(TestResult::Success);
-                            }
-                            Err(error) => {
-                                results.push(TestResult::Error(format!(
-                                    "❌ [{thread_name}][{name}]: {error} ({error:?})"
-                                )));
-                            }
-                        },
-                        Err(error) => {
-                            results.push(TestResult::Error(format!(
-                                "❌ [{thread_name}][{name}]: {error} ({error:?})"
-                            )));
-                        }
-                    }
-                }
-
-                results
-            });
-
-        threads.push(thread);
+    let mut project = Project::new(&out_path).with_exclude("vendor/symfony*");
+    for path in ignore {
+        project = project.with_exclude(path);
     }
 
-    let mut results = vec![];
-    for thread in threads {
-        let mut result = thread
-            .unwrap_or_else(|e| panic!("failed to spawn thread: {:#?}", e))
-            .join()
-            .unwrap_or_else(|e| panic!("failed to join thread: {:#?}", e));
+    let result = project.parse().unwrap();
+    for (path, error) in result.errors() {
+        let path = path.strip_prefix(&out_path).unwrap().display();
 
-        results.append(&mut result);
+        println!("❌ [{name}][{path}]: {error} ({error:?})");
     }
 
-    let mut fail = false;
-    results
-        .iter()
-        .map(|result| match result {
-            TestResult::Error(message) => {
-                fail = true;
-
-                println!("{}", message);
-            }
-            TestResult::Success => {}
-        })
-        .for_each(drop);
-
-    if fail {
+    if !result.is_ok() {
         panic!();
     }
 }
-
-fn read_directory(root: PathBuf, directory: PathBuf, ignore: &[&str]) -> Vec<(String, PathBuf)> {
-    let mut results = vec![];
-    let mut entries = fs::read_dir(&directory)
-        .unwrap()
-        .flatten()
-        .map(|entry| entry.path())
-        .collect::<Vec<PathBuf>>();
-
-    entries.sort();
-
-    for entry in entries {
-        if entry.is_dir() {
-            results.append(&mut read_directory(root.clone(), entry, ignore));
-
-            continue;
-        }
-
-        let path = &entry
-            .as_path()
-            .strip_prefix(&root)
-            .unwrap()
-            .to_str()
-            .unwrap();
-
-        if entry.is_file()
-            && entry.extension().unwrap_or_default() == "php"
-            && !ignore.contains(path)
-            && !path.starts_with("vendor/symfony")
-        {
-            let name_entry = entry.clone();
-            let fullanme_string = name_entry.to_string_lossy();
-            let name = fullanme_string
-                .strip_prefix(root.to_str().unwrap())
-                .unwrap();
-
-            results.push((name.to_string(), entry));
-        }
-    }
-
-    results
-}